@@ -0,0 +1,17 @@
+//! The DIMACS parser must reject malformed instances instead of panicking, and the graphs it reads
+//! must only have edges between their vertices.
+
+#![no_main]
+
+use gcp_heuristics::input::read_graph;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(Some(graph)) = read_graph(data) {
+        let n = graph.num_vertices();
+
+        for (u, neighbors) in graph.adj_list().iter().enumerate() {
+            assert!(neighbors.iter().all(|v| *v < n && *v != u));
+        }
+    }
+});
@@ -0,0 +1,19 @@
+//! The parsers of the named formats (edge lists, JSON and DOT) must reject malformed instances
+//! instead of panicking, and the graphs they read must have a label for each vertex.
+
+#![no_main]
+
+use gcp_heuristics::input::named::{read_dot, read_edge_list, read_json};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    for graph in [read_edge_list(data), read_json(data), read_dot(data)]
+        .into_iter()
+        .flatten()
+    {
+        let n = graph.num_vertices();
+
+        assert!(graph.labels().is_none_or(|labels| labels.len() == n));
+        assert!(graph.adj_list().iter().flatten().all(|v| *v < n));
+    }
+});
@@ -0,0 +1,164 @@
+use super::{genetic::coloring_upper_bound, relabel_colors, Solution};
+use crate::graph::adj_list::AdjList;
+use crate::progress::{self, Phase};
+use crate::rng::rng;
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
+#[cfg(not(feature = "deterministic"))]
+use rayon::prelude::*;
+use std::cmp::Reverse;
+
+/// Pheromone never evaporates below this, so every color stays possible for every vertex.
+const MIN_PHEROMONE: f64 = 0.01;
+
+#[derive(Debug)]
+pub struct AcoParams {
+    /// Colorings built in each cycle.
+    pub ants: usize,
+    /// Cycles of building colorings and updating the pheromone.
+    pub iterations: usize,
+    /// Weight of the pheromone when choosing a color.
+    pub alpha: f64,
+    /// Weight of the heuristic (preferring the smallest colors) when choosing a color.
+    pub beta: f64,
+    /// Fraction of the pheromone that evaporates after each cycle.
+    pub evaporation: f64,
+    /// Known lower bound on the number of colors: the search stops as soon as it's matched.
+    pub lower_bound: Option<usize>,
+}
+
+impl Default for AcoParams {
+    fn default() -> Self {
+        AcoParams {
+            ants: 10,
+            iterations: 100,
+            alpha: 1.0,
+            beta: 2.0,
+            evaporation: 0.1,
+            lower_bound: None,
+        }
+    }
+}
+
+/// Colors `graph` with Ant Colony Optimization, keeping a pheromone trail on each pair of a vertex
+/// and a color.
+///
+/// Each ant builds a coloring in DSATUR order (the vertex with the most colors among its neighbors
+/// first), choosing each color among the legal ones with a probability proportional to its
+/// pheromone to the power of `alpha`, times the inverse of the color to the power of `beta`. After
+/// each cycle, the pheromone evaporates, and the best ant of the cycle deposits more on its pairs,
+/// the closer it is to the best coloring so far.
+pub fn aco(graph: &AdjList, params: &AcoParams) -> Solution {
+    let n = graph.num_vertices();
+    let max_colors = coloring_upper_bound(graph);
+    let mut pheromone = vec![vec![1.0; max_colors + 1]; n];
+    let mut best: Option<Solution> = None;
+
+    for _ in 0..params.iterations.max(1) {
+        // The ants run in parallel, unless the results must be reproducible
+        #[cfg(not(feature = "deterministic"))]
+        let ants = (0..params.ants.max(1)).into_par_iter();
+        #[cfg(feature = "deterministic")]
+        let ants = 0..params.ants.max(1);
+
+        let cycle_best = ants
+            .map(|_| build_coloring(graph, &pheromone, params))
+            .min_by_key(|(num_colors, _)| *num_colors)
+            .unwrap();
+
+        progress::record_iteration();
+        progress::record_colors_from(Phase::Construction, cycle_best.0);
+
+        if best
+            .as_ref()
+            .is_none_or(|(num_colors, _)| cycle_best.0 < *num_colors)
+        {
+            best = Some(cycle_best.clone());
+        }
+
+        let best_colors = best.as_ref().unwrap().0;
+        if params
+            .lower_bound
+            .is_some_and(|lower_bound| best_colors <= lower_bound)
+            || progress::stop_requested()
+        {
+            break;
+        }
+
+        let deposit = 1.0 / (1 + cycle_best.0 - best_colors) as f64;
+        for (v, trail) in pheromone.iter_mut().enumerate() {
+            for amount in trail.iter_mut() {
+                *amount = (*amount * (1.0 - params.evaporation)).max(MIN_PHEROMONE);
+            }
+            trail[cycle_best.1[v]] += deposit;
+        }
+    }
+
+    let (num_colors, mut coloring) = best.unwrap();
+    relabel_colors(&mut coloring);
+
+    (num_colors, coloring)
+}
+
+/// Builds the coloring of a single ant, guided by `pheromone`.
+fn build_coloring(graph: &AdjList, pheromone: &[Vec<f64>], params: &AcoParams) -> Solution {
+    let n = graph.num_vertices();
+    let adj_list = graph.adj_list();
+    let max_colors = pheromone.first().map_or(0, |trail| trail.len() - 1);
+    let mut rng = rng();
+    let mut coloring = vec![0; n];
+    // `neighbor_colors[v][c]` is the number of neighbors of `v` colored with `c`
+    let mut neighbor_colors = vec![vec![0; max_colors + 1]; n];
+    let mut saturation = vec![0; n];
+    let mut num_colors = 0;
+
+    for _ in 0..n {
+        let vertex = (0..n)
+            .filter(|v| coloring[*v] == 0)
+            .max_by_key(|v| (saturation[*v], adj_list[*v].len(), Reverse(*v)))
+            .unwrap();
+
+        // A new color is always legal, since no vertex needs more than `max_colors`
+        let legal: Vec<usize> = (1..=(num_colors + 1).min(max_colors))
+            .filter(|color| neighbor_colors[vertex][*color] == 0)
+            .collect();
+        let weights = legal.iter().map(|color| {
+            pheromone[vertex][*color].powf(params.alpha) * (1.0 / *color as f64).powf(params.beta)
+        });
+        let color = match WeightedIndex::new(weights) {
+            Ok(distribution) => legal[distribution.sample(&mut rng)],
+            Err(_) => legal[0],
+        };
+
+        coloring[vertex] = color;
+        num_colors = num_colors.max(color);
+
+        for neighbor in adj_list[vertex].iter() {
+            if neighbor_colors[*neighbor][color] == 0 {
+                saturation[*neighbor] += 1;
+            }
+            neighbor_colors[*neighbor][color] += 1;
+        }
+    }
+
+    (num_colors, coloring)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::solve_fixtures_optimally;
+
+    #[test]
+    fn test_aco() {
+        solve_fixtures_optimally(|fixture| {
+            let params = AcoParams {
+                iterations: 50,
+                lower_bound: Some(fixture.chromatic_number),
+                ..Default::default()
+            };
+
+            aco(&fixture.graph, &params)
+        });
+    }
+}
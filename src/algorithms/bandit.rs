@@ -0,0 +1,182 @@
+use super::{solve_seeded, Solution};
+use crate::args::Algorithm;
+use crate::graph::adj_list::AdjList;
+use crate::progress;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Algorithms the time is split among, in the order they're first tried.
+pub const ARMS: [Algorithm; 4] = [
+    Algorithm::Grasp,
+    Algorithm::GraspPR,
+    Algorithm::Genetic,
+    Algorithm::GraspGA,
+];
+
+#[derive(Debug)]
+pub struct BanditParams {
+    /// Total time given to the algorithms.
+    pub budget: Duration,
+    /// Number of parts the budget is split into. Each algorithm run takes at most one.
+    pub epochs: usize,
+    pub lower_bound: Option<usize>,
+}
+
+impl Default for BanditParams {
+    fn default() -> Self {
+        BanditParams {
+            budget: Duration::from_secs(5),
+            epochs: 10,
+            lower_bound: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Arm {
+    pub pulls: usize,
+    pub total_reward: f64,
+}
+
+/// Picks the arm to pull with UCB1: an arm that was never pulled, or the one with the best mean
+/// reward plus its exploration bonus.
+pub fn select(arms: &[Arm]) -> usize {
+    if let Some(unpulled) = arms.iter().position(|arm| arm.pulls == 0) {
+        return unpulled;
+    }
+
+    let total_pulls: usize = arms.iter().map(|arm| arm.pulls).sum();
+    let score = |arm: &Arm| {
+        arm.total_reward / arm.pulls as f64
+            + (2.0 * (total_pulls as f64).ln() / arm.pulls as f64).sqrt()
+    };
+
+    (0..arms.len())
+        .max_by(|a, b| score(&arms[*a]).total_cmp(&score(&arms[*b])))
+        .unwrap()
+}
+
+/// Splits the time budget into epochs, running one of the [`ARMS`] (with its default parameters)
+/// in each, as chosen by a multi-armed bandit policy. Returns the best coloring found, along with
+/// the statistics of each arm.
+///
+/// Each run starts from the best coloring so far (if it can), and is stopped at the end of its
+/// epoch. Its reward is how fast it improved the best coloring: the fraction of the gap to the
+/// lower bound it closed, per epoch. The first run only builds the first coloring, so it isn't
+/// credited to its arm.
+pub fn bandit(graph: &AdjList, params: &BanditParams) -> (Solution, Vec<Arm>) {
+    let epoch = params.budget / params.epochs.max(1) as u32;
+    let start = Instant::now();
+    let mut arms = vec![Arm::default(); ARMS.len()];
+    let mut best: Option<Solution> = None;
+
+    while best.is_none() || start.elapsed() < params.budget {
+        if best.as_ref().is_some_and(|(num_colors, _)| {
+            params
+                .lower_bound
+                .is_some_and(|lower_bound| *num_colors <= lower_bound)
+        }) {
+            break;
+        }
+
+        let arm = select(&arms);
+        let limit = epoch.min(params.budget.saturating_sub(start.elapsed()));
+        let population = best.as_ref().map(|(_, coloring)| vec![coloring.clone()]);
+
+        let epoch_start = Instant::now();
+        let (solution, cancelled) = run_epoch(limit, || {
+            solve_seeded(graph, &ARMS[arm], params.lower_bound, population)
+        });
+        let elapsed = epoch_start.elapsed();
+
+        if let Some((best_colors, _)) = best {
+            let gap = best_colors
+                .saturating_sub(params.lower_bound.unwrap_or(1))
+                .max(1);
+            let gain = best_colors.saturating_sub(solution.0);
+            let speed = epoch.as_secs_f64() / elapsed.as_secs_f64().max(f64::EPSILON);
+
+            arms[arm].pulls += 1;
+            arms[arm].total_reward += (gain as f64 / gap as f64 * speed).min(1.0);
+        }
+
+        if best
+            .as_ref()
+            .is_none_or(|(best_colors, _)| solution.0 < *best_colors)
+        {
+            best = Some(solution);
+        }
+
+        if cancelled {
+            break;
+        }
+    }
+
+    (best.unwrap(), arms)
+}
+
+/// Runs `solve`, asking it to stop once `limit` is over. Returns its solution, and whether it was
+/// stopped by someone else (in which case the stop request is kept).
+fn run_epoch(limit: Duration, solve: impl FnOnce() -> Solution) -> (Solution, bool) {
+    let progress = progress::current();
+    let (sender, receiver) = mpsc::channel();
+    let timer = {
+        let progress = progress.clone();
+
+        thread::spawn(move || {
+            let timed_out = receiver.recv_timeout(limit) == Err(RecvTimeoutError::Timeout);
+            if timed_out {
+                progress.request_stop();
+            }
+            timed_out
+        })
+    };
+
+    let solution = solve();
+    // The timer is gone if it already timed out
+    let _ = sender.send(());
+
+    if timer.join().unwrap() {
+        progress.clear_stop();
+        (solution, false)
+    } else {
+        (solution, progress.stop_requested())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::solve_fixtures_optimally;
+
+    #[test]
+    fn test_select() {
+        let arm = |pulls, total_reward| Arm {
+            pulls,
+            total_reward,
+        };
+
+        assert_eq!(select(&[arm(1, 0.0), arm(0, 0.0)]), 1);
+        assert_eq!(select(&[arm(5, 1.0), arm(5, 4.0)]), 1);
+        // Rarely pulled arms get a larger bonus
+        assert_eq!(select(&[arm(100, 50.0), arm(2, 0.8)]), 1);
+    }
+
+    #[test]
+    fn test_bandit() {
+        solve_fixtures_optimally(|fixture| {
+            let params = BanditParams {
+                budget: Duration::from_secs(60),
+                epochs: 4,
+                lower_bound: Some(fixture.chromatic_number),
+            };
+            // The fixtures are colored optimally before their epochs end, so the other tests
+            // running at once are never asked to stop
+            let (solution, arms) = bandit(&fixture.graph, &params);
+
+            assert!(arms.iter().all(|arm| arm.total_reward <= arm.pulls as f64));
+            solution
+        });
+    }
+}
@@ -0,0 +1,255 @@
+#[cfg(feature = "sat")]
+use super::sat::decide_sat;
+use super::{
+    neighborhood::min_conflicts, partialcol::partialcol, relabel_colors, squash_colors,
+    tabucol::tabucol, Solution,
+};
+use crate::graph::adj_list::AdjList;
+use crate::graph::bounds::{degeneracy_ordering, greedy_coloring};
+use crate::progress::{self, Phase};
+
+/// Answer of a fixed-k solver to "can the graph be colored with `k` colors?".
+pub enum Decision {
+    /// Yes, with this coloring (which uses at most `k` colors).
+    Colorable(Vec<usize>),
+    /// No, and that's proven.
+    Uncolorable,
+    /// The solver gave up.
+    Unknown,
+}
+
+/// How [`search_k`] picks the next number of colors to try.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Search {
+    /// One less than the best coloring, until a decision fails.
+    #[default]
+    Decrement,
+    /// Halfway between the lower bound and the best coloring.
+    Binary,
+}
+
+/// Fixed-k solver wrapped by [`fixed_k`].
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Decider {
+    /// TabuCol, which removes the conflicts of a complete coloring.
+    #[default]
+    Tabucol,
+    /// PartialCol, which colors the vertices left out of a legal partial coloring.
+    PartialCol,
+    /// Min-conflicts, which moves conflicting vertices to their least conflicting color.
+    MinConflicts,
+    /// The SAT solver, the only one that can prove there's no coloring.
+    #[cfg(feature = "sat")]
+    Sat,
+}
+
+#[derive(Debug)]
+pub struct FixedKParams {
+    pub decider: Decider,
+    pub search: Search,
+    /// Maximum iterations of the heuristic deciders for each number of colors tried.
+    pub iterations: usize,
+    /// Maximum conflicts of the SAT solver for each number of colors tried. Unbounded if `None`.
+    #[cfg(feature = "sat")]
+    pub max_conflicts: Option<usize>,
+    /// Known lower bound on the number of colors: smaller numbers of colors aren't tried.
+    pub lower_bound: Option<usize>,
+}
+
+impl Default for FixedKParams {
+    fn default() -> Self {
+        FixedKParams {
+            decider: Decider::default(),
+            search: Search::default(),
+            iterations: 10000,
+            #[cfg(feature = "sat")]
+            max_conflicts: None,
+            lower_bound: None,
+        }
+    }
+}
+
+/// Minimizes the colors of `graph` with a solver of the decision problem "can the graph be colored
+/// with `k` colors?", starting from `initial`, a valid coloring with colors `1..=initial.0`.
+///
+/// `decide` is given the number of colors `k` to try and the best coloring so far (with more than
+/// `k` colors), which heuristics can start from. The numbers of colors are tried according to
+/// `search`, never below `lower_bound`: a failed decision (or an unproven one, in the binary
+/// search) rules out the smaller ones. The colorings found are credited to `phase`.
+///
+/// Returns the best coloring found along with a lower bound on the chromatic number, which is
+/// raised by the decisions proving a number of colors too small.
+pub fn search_k(
+    graph: &AdjList,
+    initial: Solution,
+    search: Search,
+    lower_bound: Option<usize>,
+    phase: Option<Phase>,
+    mut decide: impl FnMut(usize, &[usize]) -> Decision,
+) -> (Solution, usize) {
+    let n = graph.num_vertices();
+    let mut best = initial;
+    let mut proven = lower_bound.unwrap_or(0).max(usize::from(n > 0));
+    // Numbers of colors below this aren't tried, even if they aren't ruled out
+    let mut floor = proven;
+
+    while floor < best.0 && !progress::stop_requested() {
+        let k = match search {
+            Search::Decrement => best.0 - 1,
+            Search::Binary => floor + (best.0 - 1 - floor) / 2,
+        };
+
+        progress::record_iteration();
+
+        match decide(k, &best.1) {
+            Decision::Colorable(mut coloring) => {
+                relabel_colors(&mut coloring);
+                best = (coloring.iter().max().copied().unwrap_or(0), coloring);
+
+                match phase {
+                    Some(phase) => progress::record_colors_from(phase, best.0),
+                    None => progress::record_colors(best.0),
+                }
+            }
+            Decision::Uncolorable => {
+                proven = k + 1;
+                floor = k + 1;
+            }
+            Decision::Unknown => floor = k + 1,
+        }
+    }
+
+    (best, proven.min(n))
+}
+
+/// Colors `graph` by searching the number of colors (see [`search_k`]) from a greedy coloring (in
+/// smallest-last order), deciding each one with the fixed-k solver `decider`.
+///
+/// Returns the best coloring found along with a lower bound on the chromatic number, which only the
+/// SAT solver can raise above the known one.
+pub fn fixed_k(graph: &AdjList, params: &FixedKParams) -> (Solution, usize) {
+    let (_, ordering) = degeneracy_ordering(graph);
+    let mut coloring = greedy_coloring(graph, &ordering);
+    relabel_colors(&mut coloring);
+
+    let num_colors = coloring.iter().max().copied().unwrap_or(0);
+
+    progress::record_colors_from(Phase::Construction, num_colors);
+
+    let phase = match params.decider {
+        #[cfg(feature = "sat")]
+        Decider::Sat => None,
+        _ => Some(Phase::LocalSearch),
+    };
+
+    search_k(
+        graph,
+        (num_colors, coloring),
+        params.search,
+        params.lower_bound,
+        phase,
+        |k, best| match params.decider {
+            Decider::Tabucol => {
+                let mut candidate = best.to_vec();
+                squash_colors(graph, &mut candidate, k);
+
+                match tabucol(graph, &mut candidate, k, params.iterations) {
+                    0 => Decision::Colorable(candidate),
+                    _ => Decision::Unknown,
+                }
+            }
+            Decider::PartialCol => {
+                let mut candidate: Vec<usize> = best
+                    .iter()
+                    .map(|color| if *color > k { 0 } else { *color })
+                    .collect();
+
+                match partialcol(graph, &mut candidate, k, params.iterations) {
+                    0 => Decision::Colorable(candidate),
+                    _ => Decision::Unknown,
+                }
+            }
+            Decider::MinConflicts => {
+                let mut candidate = best.to_vec();
+                squash_colors(graph, &mut candidate, k);
+
+                match min_conflicts(graph, &mut candidate, k, params.iterations) {
+                    0 => Decision::Colorable(candidate),
+                    _ => Decision::Unknown,
+                }
+            }
+            #[cfg(feature = "sat")]
+            Decider::Sat => decide_sat(graph, k, params.max_conflicts),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::solve_fixtures;
+
+    #[test]
+    fn test_fixed_k() {
+        for (decider, search) in [
+            (Decider::Tabucol, Search::Decrement),
+            (Decider::PartialCol, Search::Binary),
+            (Decider::MinConflicts, Search::Decrement),
+            #[cfg(feature = "sat")]
+            (Decider::Sat, Search::Binary),
+        ] {
+            let params = FixedKParams {
+                decider,
+                search,
+                iterations: 1000,
+                ..Default::default()
+            };
+            let mut lower_bounds = Vec::new();
+            let fixtures = solve_fixtures(|fixture| {
+                let (solution, lower_bound) = fixed_k(&fixture.graph, &params);
+
+                lower_bounds.push(lower_bound);
+                solution
+            });
+
+            for ((fixture, num_colors), lower_bound) in fixtures.into_iter().zip(lower_bounds) {
+                assert_eq!(
+                    num_colors, fixture.chromatic_number,
+                    "{} {decider:?}",
+                    fixture.name
+                );
+                assert!(lower_bound <= fixture.chromatic_number, "{}", fixture.name);
+
+                // Only the SAT solver proves anything, and it always finishes on the fixtures
+                #[cfg(feature = "sat")]
+                if let Decider::Sat = decider {
+                    assert_eq!(lower_bound, fixture.chromatic_number, "{}", fixture.name);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_search_k() {
+        // A decider that colors with up to 3 colors and proves fewer impossible
+        let graph = crate::testing::cycle(3);
+        let initial = (3, vec![1, 2, 3]);
+        let decide = |k: usize, _: &[usize]| match k {
+            3.. => Decision::Colorable(vec![1, 2, 3]),
+            _ => Decision::Uncolorable,
+        };
+
+        for search in [Search::Decrement, Search::Binary] {
+            let (best, lower_bound) = search_k(&graph, initial.clone(), search, None, None, decide);
+
+            assert_eq!(best.0, 3);
+            assert_eq!(lower_bound, 3);
+        }
+
+        // Failing without a proof leaves the lower bound alone
+        let (_, lower_bound) = search_k(&graph, initial, Search::Binary, None, None, |_, _| {
+            Decision::Unknown
+        });
+        assert_eq!(lower_bound, 1);
+    }
+}
@@ -0,0 +1,102 @@
+use super::{match_colors, relabel_colors, Solution};
+use std::collections::HashSet;
+
+/// Number of vertices that must change classes to turn the partition of `first` into the one of
+/// `second`, pairing their classes greedily (see [`match_colors`]).
+pub fn partition_distance(first: &[usize], second: &[usize]) -> usize {
+    let matched = match_colors(first, second);
+
+    first
+        .iter()
+        .zip(matched.iter())
+        .filter(|(color, other)| color != other)
+        .count()
+}
+
+/// Picks up to `count` colorings among `candidates` that use at most `tolerance` more colors than
+/// the best one, and are at least `min_distance` apart from each other.
+///
+/// The first candidate (which should be the best one) is picked first. Then, the one farthest from
+/// all the picked ones is picked next (preferring earlier candidates on ties), until there are
+/// enough or none is far enough.
+pub fn diverse_pool(
+    candidates: Vec<Solution>,
+    count: usize,
+    tolerance: usize,
+    min_distance: usize,
+) -> Vec<Solution> {
+    let Some(best) = candidates.iter().map(|(num_colors, _)| *num_colors).min() else {
+        return Vec::new();
+    };
+
+    let mut seen = HashSet::new();
+    let candidates: Vec<Solution> = candidates
+        .into_iter()
+        .filter(|(num_colors, _)| *num_colors <= best + tolerance)
+        .map(|(num_colors, mut coloring)| {
+            relabel_colors(&mut coloring);
+            (num_colors, coloring)
+        })
+        .filter(|(_, coloring)| seen.insert(coloring.clone()))
+        .collect();
+
+    let mut pool: Vec<Solution> = Vec::with_capacity(count);
+    // Distance from each candidate to the closest picked coloring
+    let mut distances = vec![usize::MAX; candidates.len()];
+
+    while pool.len() < count {
+        let Some(next) = (0..candidates.len())
+            .filter(|i| distances[*i] >= min_distance.max(1))
+            .max_by_key(|i| (distances[*i], std::cmp::Reverse(*i)))
+        else {
+            break;
+        };
+
+        for (i, (_, coloring)) in candidates.iter().enumerate() {
+            distances[i] = distances[i].min(partition_distance(&candidates[next].1, coloring));
+        }
+        pool.push(candidates[next].clone());
+    }
+
+    pool
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_distance() {
+        assert_eq!(partition_distance(&[1, 1, 2, 3], &[3, 3, 1, 2]), 0);
+        assert_eq!(partition_distance(&[1, 1, 1, 2, 2], &[1, 1, 2, 2, 3]), 2);
+    }
+
+    #[test]
+    fn test_diverse_pool() {
+        let candidates = vec![
+            (2, vec![1, 2, 1, 2, 1, 2]),
+            // The same partition as the first one
+            (2, vec![2, 1, 2, 1, 2, 1]),
+            (2, vec![1, 2, 1, 2, 2, 1]),
+            (3, vec![1, 2, 3, 1, 2, 3]),
+            (4, vec![1, 2, 3, 4, 1, 2]),
+        ];
+
+        let pool = diverse_pool(candidates.clone(), 5, 1, 1);
+
+        assert_eq!(pool[0], (2, vec![1, 2, 1, 2, 1, 2]));
+        assert_eq!(pool.len(), 3);
+        assert!(pool.iter().all(|(num_colors, _)| *num_colors <= 3));
+
+        // The 3-coloring is farther from the best one than the other 2-coloring
+        let pool = diverse_pool(candidates.clone(), 2, 1, 1);
+
+        assert_eq!(pool[1].0, 3);
+
+        // Only the 3-coloring is far enough from the best one
+        let pool = diverse_pool(candidates, 5, 2, 3);
+
+        assert_eq!(pool.len(), 2);
+        assert!(diverse_pool(Vec::new(), 5, 0, 1).is_empty());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::graph::adj_list::AdjList;
+
+/// Extends `precoloring` (with colors in `1..=k`) to a valid coloring of `graph` with at most `k`
+/// colors, if there's one.
+///
+/// This is an exact backtracking search: the next vertex to color is always the uncolored one with
+/// the fewest colors left (as in DSATUR), so dead ends are found early. It's exponential in the
+/// worst case, so it's only suitable for small or heavily constrained instances.
+pub fn extend_coloring(
+    graph: &AdjList,
+    precoloring: &[Option<usize>],
+    k: usize,
+) -> Option<Vec<usize>> {
+    let mut coloring: Vec<usize> = precoloring.iter().map(|color| color.unwrap_or(0)).collect();
+
+    let is_precoloring_valid = graph
+        .edges()
+        .iter()
+        .all(|(u, v)| coloring[*u] == 0 || coloring[*u] != coloring[*v]);
+
+    if is_precoloring_valid && backtrack(graph, &mut coloring, k) {
+        Some(coloring)
+    } else {
+        None
+    }
+}
+
+/// Colors the uncolored vertices (with color 0) of `coloring`, returning whether it succeeded.
+fn backtrack(graph: &AdjList, coloring: &mut [usize], k: usize) -> bool {
+    // The uncolored vertex with the fewest available colors, along with them
+    let mut next: Option<(usize, Vec<usize>)> = None;
+
+    for v in (0..graph.num_vertices()).filter(|v| coloring[*v] == 0) {
+        let available = available_colors(graph, coloring, v, k);
+
+        if next
+            .as_ref()
+            .is_none_or(|(_, colors)| available.len() < colors.len())
+        {
+            let is_dead_end = available.is_empty();
+            next = Some((v, available));
+
+            if is_dead_end {
+                break;
+            }
+        }
+    }
+
+    let Some((v, available)) = next else {
+        return true;
+    };
+
+    for color in available {
+        coloring[v] = color;
+
+        if backtrack(graph, coloring, k) {
+            return true;
+        }
+    }
+
+    coloring[v] = 0;
+
+    false
+}
+
+fn available_colors(graph: &AdjList, coloring: &[usize], v: usize, k: usize) -> Vec<usize> {
+    let mut used = vec![false; k + 1];
+
+    for u in graph.adj_list()[v].iter() {
+        if coloring[*u] <= k {
+            used[coloring[*u]] = true;
+        }
+    }
+
+    (1..=k).filter(|color| !used[*color]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algorithms::is_coloring_valid, testing};
+
+    #[test]
+    fn test_extend_coloring() {
+        let graph = testing::myciel3();
+
+        assert!(extend_coloring(&graph, &[None; 11], 3).is_none());
+
+        let coloring = extend_coloring(&graph, &[None; 11], 4).unwrap();
+
+        assert!(is_coloring_valid(&graph, &coloring));
+        assert!(coloring.iter().all(|color| (1..=4).contains(color)));
+
+        // The precolored vertices keep their colors
+        let mut precoloring = [None; 6];
+        precoloring[0] = Some(2);
+        precoloring[3] = Some(1);
+
+        let coloring = extend_coloring(&testing::cycle(6), &precoloring, 2).unwrap();
+
+        assert_eq!(coloring, vec![2, 1, 2, 1, 2, 1]);
+
+        // Vertices at an even distance can't have different colors in a 2-coloring
+        precoloring[3] = None;
+        precoloring[2] = Some(1);
+
+        assert!(extend_coloring(&testing::cycle(6), &precoloring, 2).is_none());
+    }
+}
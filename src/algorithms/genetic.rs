@@ -1,7 +1,17 @@
-use super::{count_colors, is_valid_color_assignment, Solution};
+use super::{
+    count_colors, count_conflicts,
+    incumbent::Incumbent,
+    invariants, is_coloring_valid, is_valid_color_assignment,
+    kempe::{kempe_chain, swap_chain},
+    least_conflicting_color, relabel_colors, squash_colors, Solution,
+};
 use crate::graph::adj_list::AdjList;
+use crate::progress::{self, Phase};
+use crate::rng::rng;
 use rand::prelude::SliceRandom;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 // A coloring upper bound based on the largest neighborhood
 // Tighter upper bounds help during the randomized color
@@ -9,7 +19,7 @@ use rand::Rng;
 /// Determine an upper bound for the number of colors in a graph.
 ///
 /// This upper bound is calculated based on the `Brook's theorem` (i.e., the chromatic number is less or equal than the maximum vertex degree of the graph plus one).
-fn coloring_upper_bound(graph: &AdjList) -> usize {
+pub(super) fn coloring_upper_bound(graph: &AdjList) -> usize {
     let mut colors = 0;
 
     for i in 0..graph.num_vertices() {
@@ -28,15 +38,15 @@ fn coloring_upper_bound(graph: &AdjList) -> usize {
 /// A solution consists of a vector of size `n`, with `n` being the number of vertices of the graph,
 /// where the `ith position` receives a number between `1` and the `upper bound` for coloring.
 /// This number represents the `color of the ith vertex` in the current solution.
-fn generate_individual(graph: &AdjList, upper_bound: usize) -> Vec<usize> {
+pub(super) fn generate_individual(graph: &AdjList, upper_bound: usize) -> Vec<usize> {
     let n = graph.num_vertices();
     let mut individual = vec![1; n];
 
     for i in 0..n {
-        individual[i] = rand::thread_rng().gen_range(1..=upper_bound);
+        individual[i] = rng().gen_range(1..=upper_bound);
 
         while !is_valid_color_assignment(graph, &individual, i) {
-            individual[i] = rand::thread_rng().gen_range(1..=upper_bound);
+            individual[i] = rng().gen_range(1..=upper_bound);
         }
     }
 
@@ -45,19 +55,22 @@ fn generate_individual(graph: &AdjList, upper_bound: usize) -> Vec<usize> {
 
 /// Traverses the solution vector, changing the color of each vertex to a random color
 /// with a probability given by the `mutation_probability` parameter.
-fn mutate(
+///
+/// Vertices left in conflict by the crossover (see [`RepairStrategy::LeaveConflicted`]) are
+/// always recolored, so the resulting individual is valid.
+pub(super) fn mutate(
     graph: &AdjList,
     individual: &mut [usize],
     upper_bound: usize,
     mutation_probability: f64,
 ) {
     let n = graph.num_vertices();
-    let mut rng = rand::thread_rng();
+    let mut rng = rng();
 
     for i in 0..n {
         let rand = rng.gen_range(0.0..1.0);
 
-        if rand <= mutation_probability {
+        if rand <= mutation_probability || !is_valid_color_assignment(graph, individual, i) {
             individual[i] = rng.gen_range(1..=upper_bound);
 
             while !is_valid_color_assignment(graph, individual, i) {
@@ -70,7 +83,7 @@ fn mutate(
 /// Selects a fraction (`selected_population_ratio` parameter) of the fittest individuals
 /// in the population and, from them, randomly selects two individuals who will be the parents
 /// of an offspring.
-fn select(
+pub(super) fn select(
     population: &Vec<Solution>,
     population_size: usize,
     selected_population_ratio: f64,
@@ -80,40 +93,192 @@ fn select(
     let (_, mut colors): (Vec<usize>, Vec<Vec<usize>>) =
         population.to_owned().clone().into_iter().unzip();
 
-    colors.truncate(limit + 1);
+    // At least two individuals are needed to pick the parents
+    colors.truncate((limit + 1).max(2));
 
-    let p: Vec<Vec<usize>> = colors
-        .choose_multiple(&mut rand::thread_rng(), 2)
-        .cloned()
-        .collect();
+    let p: Vec<Vec<usize>> = colors.choose_multiple(&mut rng(), 2).cloned().collect();
 
     (p[0].clone(), p[1].clone())
 }
 
+/// How the conflicts introduced by the crossover are removed from an offspring.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum RepairStrategy {
+    /// Assign the first legal color, starting from 1.
+    #[default]
+    FirstLegal,
+    /// Assign a random legal color among the ones already used by the offspring.
+    RandomLegal,
+    /// Assign the least used legal color among the ones already used by the offspring.
+    LeastUsed,
+    /// Don't repair the offspring, leaving the conflicts to be fixed by the mutation.
+    LeaveConflicted,
+}
+
+/// How an offspring is recombined from its parents.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Crossover {
+    /// Copy the first parent up to a random position, and the second parent from there on.
+    #[default]
+    OnePoint,
+    /// Start from the first parent, and bring color classes of the second parent into it with
+    /// Kempe chain swaps, which never add conflicts.
+    Kempe,
+}
+
 /// Given two parents `p1` and `p2`, returns an offspring generated from the recombination
-/// of `p1` and `p2`.
+/// of `p1` and `p2`, with the `strategy` crossover.
 ///
-/// The crossover strategy implemented is the one-point crossover (i.e., chooses
-/// a random position of the vector and makes the offspring equal to the first parent up to
-/// that position and equal to the second parent from that position onwards.)
-fn crossover(graph: &AdjList, p1: Vec<usize>, p2: Vec<usize>) -> Vec<usize> {
-    let n = graph.num_vertices();
-    let mut rng = rand::thread_rng();
-    let mut offspring = vec![1; n];
-    let pos = rng.gen_range(0..n);
+/// The conflicting vertices are then recolored according to `repair_strategy`.
+pub(super) fn crossover(
+    graph: &AdjList,
+    p1: Vec<usize>,
+    p2: Vec<usize>,
+    strategy: Crossover,
+    repair_strategy: RepairStrategy,
+) -> Vec<usize> {
+    let mut offspring = recombine(graph, &p1, &p2, strategy);
 
-    offspring[..(pos + 1)].copy_from_slice(&p1[..(pos + 1)]);
+    repair(graph, &mut offspring, repair_strategy, None);
 
-    offspring[(pos + 1)..].copy_from_slice(&p2[(pos + 1)..]);
+    offspring
+}
 
-    for i in 0..n {
-        let mut start_color = 1;
+/// Recolors the vertices of `offspring` that conflict with a neighbor, following `strategy`.
+///
+/// The strategies that pick among the colors already in use fall back to the first legal color
+/// when none of them is legal for a vertex. With a `max_color` (as in the fixed `k` search), only
+/// the colors up to it are picked, and a vertex without a legal one is left conflicted.
+fn repair(
+    graph: &AdjList,
+    offspring: &mut [usize],
+    strategy: RepairStrategy,
+    max_color: Option<usize>,
+) {
+    if let RepairStrategy::LeaveConflicted = strategy {
+        return;
+    }
 
-        while !is_valid_color_assignment(graph, &offspring, i) {
-            offspring[i] = start_color;
-            start_color += 1;
+    let mut usage: HashMap<usize, usize> = HashMap::new();
+
+    for color in offspring.iter() {
+        *usage.entry(*color).or_default() += 1;
+    }
+
+    for i in 0..offspring.len() {
+        if is_valid_color_assignment(graph, offspring, i) {
+            continue;
         }
+
+        let original_color = offspring[i];
+        let neighbor_colors: HashSet<usize> = graph.adj_list()[i]
+            .iter()
+            .map(|neighbor| offspring[*neighbor])
+            .collect();
+        let max_color = max_color.unwrap_or(usize::MAX);
+        let mut legal: Vec<(usize, usize)> = usage
+            .iter()
+            .filter(|(color, count)| {
+                **count > 0 && **color <= max_color && !neighbor_colors.contains(color)
+            })
+            .map(|(color, count)| (*color, *count))
+            .collect();
+
+        // Iterating over a HashMap yields an arbitrary order
+        legal.sort();
+
+        let color = match strategy {
+            RepairStrategy::RandomLegal => legal.choose(&mut rng()).map(|(color, _)| *color),
+            RepairStrategy::LeastUsed => legal
+                .iter()
+                .min_by_key(|(color, count)| (*count, *color))
+                .map(|(color, _)| *color),
+            _ => None,
+        };
+
+        if let Some(color) =
+            color.or_else(|| (1..=max_color).find(|color| !neighbor_colors.contains(color)))
+        {
+            offspring[i] = color;
+        }
+
+        *usage.entry(original_color).or_default() -= 1;
+        *usage.entry(offspring[i]).or_default() += 1;
     }
+}
+
+/// Recombines `p1` and `p2` with the `strategy` crossover, without fixing conflicts.
+pub(super) fn recombine(
+    graph: &AdjList,
+    p1: &[usize],
+    p2: &[usize],
+    strategy: Crossover,
+) -> Vec<usize> {
+    match strategy {
+        Crossover::OnePoint => one_point_crossover(graph.num_vertices(), p1, p2),
+        Crossover::Kempe => kempe_crossover(graph, p1, p2),
+    }
+}
+
+/// Brings half of the color classes of `p2` (picked at random) into a copy of `p1`.
+///
+/// For each class, the vertices of the class are moved to the color (in the offspring) of one
+/// of them, each by swapping its Kempe chain with that color. Chains that would move vertices of
+/// the class already moved are skipped. The offspring has as many conflicts as `p1` (none, if
+/// it's valid), and never more colors.
+fn kempe_crossover(graph: &AdjList, p1: &[usize], p2: &[usize]) -> Vec<usize> {
+    let mut rng = rng();
+    let mut offspring = p1.to_vec();
+    let mut classes: HashMap<usize, Vec<usize>> = HashMap::new();
+
+    for (v, color) in p2.iter().enumerate() {
+        classes.entry(*color).or_default().push(v);
+    }
+
+    let mut classes: Vec<(usize, Vec<usize>)> = classes.into_iter().collect();
+    // Iterating over a HashMap yields an arbitrary order
+    classes.sort();
+    classes.shuffle(&mut rng);
+
+    let num_classes = classes.len().div_ceil(2);
+    let mut in_class = vec![false; offspring.len()];
+
+    for (_, class) in classes.into_iter().take(num_classes) {
+        let target = offspring[*class.choose(&mut rng).unwrap()];
+
+        class.iter().for_each(|v| in_class[*v] = true);
+
+        for v in class.iter() {
+            let color = offspring[*v];
+            if color == target {
+                continue;
+            }
+
+            let chain = kempe_chain(graph, &offspring, *v, target);
+            if chain
+                .iter()
+                .any(|u| in_class[*u] && offspring[*u] == target)
+            {
+                continue;
+            }
+
+            swap_chain(&mut offspring, &chain, color, target);
+        }
+
+        class.iter().for_each(|v| in_class[*v] = false);
+    }
+
+    offspring
+}
+
+/// Recombines `p1` and `p2` without fixing the conflicts that may arise at the cut point.
+fn one_point_crossover(n: usize, p1: &[usize], p2: &[usize]) -> Vec<usize> {
+    let mut offspring = vec![1; n];
+    let pos = rng().gen_range(0..n);
+
+    offspring[..(pos + 1)].copy_from_slice(&p1[..(pos + 1)]);
+
+    offspring[(pos + 1)..].copy_from_slice(&p2[(pos + 1)..]);
 
     offspring
 }
@@ -122,60 +287,348 @@ fn crossover(graph: &AdjList, p1: Vec<usize>, p2: Vec<usize>) -> Vec<usize> {
 ///
 /// This function is called after a `sort`, so the remaining elements after the `truncate` are the
 /// fittest individuals in a population with the original size.
-fn replace(population: &mut Vec<Solution>, population_size: usize) {
+pub(super) fn replace(population: &mut Vec<Solution>, population_size: usize) {
     population.truncate(population_size);
 }
 
+/// Objective minimized by the Genetic Algorithm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GeneticMode {
+    /// Evolve valid colorings, minimizing the number of distinct colors.
+    #[default]
+    MinColors,
+    /// Evolve colorings with a fixed number of colors `k`, minimizing the number of conflicting
+    /// edges. Whenever a conflict-free coloring is found, `k` is decreased.
+    FixedK,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneticParams {
+    pub generations: usize,
+    pub population_size: usize,
+    pub offsprings_per_generation: usize,
+    pub mutation_probability: f64,
+    pub selected_population_ratio: f64,
+    pub mode: GeneticMode,
+    pub repair_strategy: RepairStrategy,
+    pub crossover: Crossover,
+    /// Known lower bound on the number of colors: the search stops as soon as it's matched.
+    pub lower_bound: Option<usize>,
+}
+
+impl Default for GeneticParams {
+    fn default() -> Self {
+        GeneticParams {
+            generations: 80000,
+            population_size: 100,
+            offsprings_per_generation: 2,
+            mutation_probability: 0.01,
+            selected_population_ratio: 0.2,
+            mode: GeneticMode::default(),
+            repair_strategy: RepairStrategy::default(),
+            crossover: Crossover::default(),
+            lower_bound: None,
+        }
+    }
+}
+
+/// A member of a [`Population`]. The meaning of `fitness` depends on the [`GeneticMode`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Individual {
+    pub fitness: usize,
+    pub coloring: Vec<usize>,
+}
+
+/// The population of a Genetic Algorithm run, as exported by `--dump-population`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Population {
+    pub mode: GeneticMode,
+    pub individuals: Vec<Individual>,
+}
+
+/// Runs the Genetic Algorithm, returning the best solution along with the final population.
+///
+/// If `initial_population` is provided, its colorings are used (after being repaired) as the
+/// first individuals of the population, which is completed with random individuals if needed.
 pub fn genetic(
     graph: &AdjList,
-    generations: usize,
-    population_size: usize,
-    offsprings_per_generation: usize,
-    mutation_probability: f64,
-    selected_population_ratio: f64,
-) -> Solution {
-    let mut best = graph.num_vertices();
-    let mut colors = (1..=best).collect();
-    let mut population = Vec::<Solution>::new();
+    params: &GeneticParams,
+    initial_population: Option<Vec<Vec<usize>>>,
+) -> (Solution, Population) {
+    genetic_shared(
+        graph,
+        params,
+        initial_population,
+        &Incumbent::new(params.lower_bound),
+    )
+}
+
+/// Like [`genetic`], but sharing `incumbent` with the other searches running at once: the search
+/// stops once the incumbent is optimal and, in the fixed-k mode, `k` drops to the target of the
+/// incumbent as soon as another search beats it.
+pub fn genetic_shared(
+    graph: &AdjList,
+    params: &GeneticParams,
+    initial_population: Option<Vec<Vec<usize>>>,
+    incumbent: &Incumbent,
+) -> (Solution, Population) {
+    let population = generate_population(graph, params, initial_population);
+
+    let (solution, population) = match params.mode {
+        GeneticMode::MinColors => genetic_min_colors(graph, params, population, incumbent),
+        GeneticMode::FixedK => genetic_fixed_k(graph, params, population, incumbent),
+    };
+
+    let individuals = population
+        .into_iter()
+        .map(|(fitness, coloring)| Individual { fitness, coloring })
+        .collect();
+
+    (
+        solution,
+        Population {
+            mode: params.mode,
+            individuals,
+        },
+    )
+}
+
+/// Generates a sorted population of `population_size` valid individuals, starting from
+/// `initial_population` (if any).
+pub(super) fn generate_population(
+    graph: &AdjList,
+    params: &GeneticParams,
+    initial_population: Option<Vec<Vec<usize>>>,
+) -> Vec<Solution> {
     let upper_bound = coloring_upper_bound(graph);
+    let mut population: Vec<Solution> = initial_population
+        .unwrap_or_default()
+        .into_iter()
+        .map(|mut individual| {
+            repair(graph, &mut individual, RepairStrategy::FirstLegal, None);
+            (count_colors(&individual), individual)
+        })
+        .collect();
 
-    for _ in 0..population_size {
+    while population.len() < params.population_size {
         let individual = generate_individual(graph, upper_bound);
         population.push((count_colors(&individual), individual));
     }
 
     population.sort();
 
-    for _ in 0..generations {
-        for _ in 0..offsprings_per_generation {
-            let (p1, p2) = select(&population, population_size, selected_population_ratio);
+    replace(&mut population, params.population_size);
+
+    population
+}
 
-            let mut offspring = crossover(graph, p1, p2);
+fn genetic_min_colors(
+    graph: &AdjList,
+    params: &GeneticParams,
+    mut population: Vec<Solution>,
+    incumbent: &Incumbent,
+) -> (Solution, Vec<Solution>) {
+    let (mut best, mut colors) = population[0].clone();
+    let upper_bound = coloring_upper_bound(graph);
 
-            mutate(graph, &mut offspring, upper_bound, mutation_probability);
+    progress::record_colors_from(Phase::Construction, best);
+    incumbent.offer(best);
 
-            population.push((count_colors(&offspring), offspring));
+    for _ in 0..params.generations {
+        if incumbent.is_optimal() || progress::stop_requested() {
+            break;
+        }
+
+        for _ in 0..params.offsprings_per_generation {
+            let (p1, p2) = select(
+                &population,
+                params.population_size,
+                params.selected_population_ratio,
+            );
+
+            let mut offspring = crossover(graph, p1, p2, params.crossover, params.repair_strategy);
+
+            // The offspring may still have conflicts, if they're left for the mutation to fix
+            let crossed_colors = count_colors(&offspring);
+            if crossed_colors < best && is_coloring_valid(graph, &offspring) {
+                progress::record_colors_from(Phase::Crossover, crossed_colors);
+            }
+
+            mutate(
+                graph,
+                &mut offspring,
+                upper_bound,
+                params.mutation_probability,
+            );
+
+            let num_colors = count_colors(&offspring);
+            progress::record_colors_from(Phase::Mutation, num_colors);
+
+            population.push((num_colors, offspring));
         }
 
         population.sort();
 
-        replace(&mut population, population_size);
+        replace(&mut population, params.population_size);
+
+        invariants::check_population(graph, &population, params.mode);
 
         let current_best = population[0].clone();
 
+        progress::record_iteration();
+        progress::record_colors(current_best.0);
+        incumbent.offer(current_best.0);
+
         if current_best.0 < best {
             best = current_best.0;
             colors = current_best.1.clone()
         }
     }
 
-    (best, colors)
+    ((best, colors), population)
+}
+
+/// Mutation for the fixed `k` search: each conflicting vertex is moved to its least conflicting
+/// color with a probability given by the `mutation_probability` parameter.
+fn mutate_conflicts(
+    graph: &AdjList,
+    individual: &mut [usize],
+    k: usize,
+    mutation_probability: f64,
+) {
+    let mut rng = rng();
+
+    for i in 0..graph.num_vertices() {
+        if is_valid_color_assignment(graph, individual, i) {
+            continue;
+        }
+
+        if rng.gen_range(0.0..1.0) <= mutation_probability {
+            individual[i] = least_conflicting_color(graph, individual, i, k);
+        }
+    }
+}
+
+/// Searches for conflict-free colorings with a fixed number of colors `k`.
+///
+/// The search starts with `k` equal to the number of colors of the best individual of a randomly
+/// generated (valid) population minus one. The fitness of an individual is its number of
+/// conflicting edges. When an individual with no conflicts is found, it becomes the new best
+/// solution, `k` is decreased and the whole population is squashed into the new number of colors.
+/// The same happens when another search sharing `incumbent` finds a coloring with at most `k`
+/// colors, so `k` never stays above the target of the incumbent. The search ends once `k` would be
+/// zero, as a coloring with a single color can't be improved.
+fn genetic_fixed_k(
+    graph: &AdjList,
+    params: &GeneticParams,
+    mut population: Vec<Solution>,
+    incumbent: &Incumbent,
+) -> (Solution, Vec<Solution>) {
+    let (mut best, mut colors) = population[0].clone();
+
+    progress::record_colors_from(Phase::Construction, best);
+    incumbent.offer(best);
+
+    // There are no colors to squash the population into
+    if best <= 1 {
+        return ((best, colors), population);
+    }
+
+    let mut k = incumbent.target().unwrap_or(best - 1);
+    if k > 0 {
+        squash_population(graph, &mut population, k);
+    }
+
+    for _ in 0..params.generations {
+        if k == 0 || incumbent.is_optimal() || progress::stop_requested() {
+            break;
+        }
+
+        if incumbent.prunes(k) {
+            k = incumbent.target().unwrap_or(k);
+            if k == 0 {
+                break;
+            }
+
+            squash_population(graph, &mut population, k);
+
+            invariants::check_population(graph, &population, params.mode);
+            continue;
+        }
+
+        for _ in 0..params.offsprings_per_generation {
+            let (p1, p2) = select(
+                &population,
+                params.population_size,
+                params.selected_population_ratio,
+            );
+
+            let mut offspring = recombine(graph, &p1, &p2, params.crossover);
+            repair(graph, &mut offspring, params.repair_strategy, Some(k));
+            let crossed_conflicts = count_conflicts(graph, &offspring);
+
+            mutate_conflicts(graph, &mut offspring, k, params.mutation_probability);
+
+            let conflicts = count_conflicts(graph, &offspring);
+            if conflicts == 0 {
+                let phase = match crossed_conflicts {
+                    0 => Phase::Crossover,
+                    _ => Phase::Mutation,
+                };
+
+                progress::record_colors_from(phase, count_colors(&offspring));
+            }
+
+            population.push((conflicts, offspring));
+        }
+
+        population.sort();
+
+        replace(&mut population, params.population_size);
+
+        invariants::check_population(graph, &population, params.mode);
+
+        progress::record_iteration();
+        progress::record_conflicts(population[0].0);
+
+        if population[0].0 == 0 {
+            best = count_colors(&population[0].1);
+            colors = population[0].1.clone();
+
+            progress::record_colors(best);
+            incumbent.offer(best);
+
+            k = incumbent.target().unwrap_or(best - 1);
+            if k == 0 {
+                break;
+            }
+
+            squash_population(graph, &mut population, k);
+
+            invariants::check_population(graph, &population, params.mode);
+        }
+    }
+
+    ((best, colors), population)
+}
+
+/// Squashes every individual of `population` into `k` colors, updating their conflicts and
+/// sorting them again.
+pub(super) fn squash_population(graph: &AdjList, population: &mut [Solution], k: usize) {
+    for (conflicts, individual) in population.iter_mut() {
+        relabel_colors(individual);
+        squash_colors(graph, individual, k);
+        *conflicts = count_conflicts(graph, individual);
+    }
+
+    population.sort();
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{algorithms::is_coloring_valid, input};
+    use crate::{algorithms::is_coloring_valid, testing};
 
     #[test]
     fn test_coloring_upper_bound() {
@@ -187,46 +640,40 @@ mod tests {
 
         assert_eq!(coloring_upper_bound(&g1), 4);
 
-        if let Ok(Some(g2)) = input::read_graph_from_file("data/myc/myciel3.col") {
-            assert_eq!(coloring_upper_bound(&g2), 6);
-        } else {
-            panic!("The file containing the test graph is missing")
-        }
+        let g2 = testing::myciel3();
+
+        assert_eq!(coloring_upper_bound(&g2), 6);
     }
 
     #[test]
     fn test_generate_individual() {
-        if let Ok(Some(graph)) = input::read_graph_from_file("data/myc/myciel3.col") {
-            let upper_bound = coloring_upper_bound(&graph);
+        let graph = testing::myciel3();
 
-            assert_eq!(upper_bound, 6);
+        let upper_bound = coloring_upper_bound(&graph);
 
-            let individual = generate_individual(&graph, upper_bound);
+        assert_eq!(upper_bound, 6);
 
-            assert!(is_coloring_valid(&graph, &individual));
-        } else {
-            panic!("The file containing the test graph is missing")
-        }
+        let individual = generate_individual(&graph, upper_bound);
+
+        assert!(is_coloring_valid(&graph, &individual));
     }
 
     #[test]
     fn test_mutate() {
-        if let Ok(Some(graph)) = input::read_graph_from_file("data/myc/myciel3.col") {
-            let upper_bound = coloring_upper_bound(&graph);
+        let graph = testing::myciel3();
 
-            assert_eq!(upper_bound, 6);
+        let upper_bound = coloring_upper_bound(&graph);
 
-            let mut individual = generate_individual(&graph, upper_bound);
+        assert_eq!(upper_bound, 6);
 
-            assert!(is_coloring_valid(&graph, &individual));
+        let mut individual = generate_individual(&graph, upper_bound);
 
-            // A little higher mutation probability just to ensure that some vertices actually change
-            mutate(&graph, &mut individual, upper_bound, 0.2);
+        assert!(is_coloring_valid(&graph, &individual));
 
-            assert!(is_coloring_valid(&graph, &individual));
-        } else {
-            panic!("The file containing the test graph is missing")
-        }
+        // A little higher mutation probability just to ensure that some vertices actually change
+        mutate(&graph, &mut individual, upper_bound, 0.2);
+
+        assert!(is_coloring_valid(&graph, &individual));
     }
 
     #[test]
@@ -255,25 +702,107 @@ mod tests {
 
     #[test]
     fn test_crossover() {
-        if let Ok(Some(graph)) = input::read_graph_from_file("data/myc/myciel3.col") {
-            let mut population = Vec::new();
-            let upper_bound = coloring_upper_bound(&graph);
+        let graph = testing::myciel3();
 
-            for _ in 0..6 {
-                let individual = generate_individual(&graph, upper_bound);
-                population.push((count_colors(&individual), individual));
-            }
+        let mut population = Vec::new();
+        let upper_bound = coloring_upper_bound(&graph);
 
-            population.sort();
+        for _ in 0..6 {
+            let individual = generate_individual(&graph, upper_bound);
+            population.push((count_colors(&individual), individual));
+        }
+
+        population.sort();
+
+        let (p1, p2) = select(&population, population.len(), 0.2);
+
+        for strategy in [Crossover::OnePoint, Crossover::Kempe] {
+            let offspring = crossover(
+                &graph,
+                p1.clone(),
+                p2.clone(),
+                strategy,
+                RepairStrategy::FirstLegal,
+            );
+
+            assert!(is_coloring_valid(&graph, &offspring));
+        }
+    }
 
-            let (p1, p2) = select(&population, population.len(), 0.2);
+    #[test]
+    fn test_kempe_crossover() {
+        let graph = testing::myciel4();
+        let upper_bound = coloring_upper_bound(&graph);
 
-            let offspring = crossover(&graph, p1, p2);
+        for _ in 0..20 {
+            let p1 = generate_individual(&graph, upper_bound);
+            let p2 = generate_individual(&graph, upper_bound);
+            let offspring = kempe_crossover(&graph, &p1, &p2);
 
+            // Valid by construction, without being repaired
             assert!(is_coloring_valid(&graph, &offspring));
-        } else {
-            panic!("The file containing the test graph is missing")
+            assert!(count_colors(&offspring) <= count_colors(&p1));
         }
+
+        // The single class of the second parent of an edgeless graph is brought in whole
+        let graph = AdjList::new(4);
+        assert_eq!(
+            count_colors(&kempe_crossover(&graph, &[1, 2, 3, 4], &[1; 4])),
+            1
+        );
+    }
+
+    #[test]
+    fn test_repair() {
+        // Only vertices 0 and 1 are adjacent, and they share a color.
+        // Colors 2 and 3 are both legal for vertex 0, but 3 is used less often
+        let mut graph = AdjList::new(5);
+        graph.add_edge(0, 1);
+
+        let individual = vec![1, 1, 2, 2, 3];
+
+        let mut first_legal = individual.clone();
+        repair(&graph, &mut first_legal, RepairStrategy::FirstLegal, None);
+        assert_eq!(first_legal, vec![2, 1, 2, 2, 3]);
+
+        let mut least_used = individual.clone();
+        repair(&graph, &mut least_used, RepairStrategy::LeastUsed, None);
+        assert_eq!(least_used, vec![3, 1, 2, 2, 3]);
+
+        let mut random_legal = individual.clone();
+        repair(&graph, &mut random_legal, RepairStrategy::RandomLegal, None);
+        assert!(is_coloring_valid(&graph, &random_legal));
+        assert!(random_legal[0] == 2 || random_legal[0] == 3);
+
+        let mut leave_conflicted = individual.clone();
+        repair(
+            &graph,
+            &mut leave_conflicted,
+            RepairStrategy::LeaveConflicted,
+            None,
+        );
+        assert_eq!(leave_conflicted, individual);
+
+        // Only color 1 is allowed, so the conflict stays
+        let mut bounded = individual.clone();
+        repair(&graph, &mut bounded, RepairStrategy::FirstLegal, Some(1));
+        assert_eq!(bounded, individual);
+
+        // Color 3 is used less often, but it's beyond the bound
+        let mut bounded = individual.clone();
+        repair(&graph, &mut bounded, RepairStrategy::LeastUsed, Some(2));
+        assert_eq!(bounded, vec![2, 1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn test_mutate_fixes_conflicts() {
+        let graph = AdjList::complete(4);
+        let upper_bound = coloring_upper_bound(&graph);
+        let mut individual = vec![1, 1, 2, 2];
+
+        mutate(&graph, &mut individual, upper_bound, 0.0);
+
+        assert!(is_coloring_valid(&graph, &individual));
     }
 
     #[test]
@@ -308,14 +837,105 @@ mod tests {
 
     #[test]
     fn test_genetic() {
-        if let Ok(Some(graph)) = input::read_graph_from_file("data/myc/myciel3.col") {
-            let (best, colors) = genetic(&graph, 10000, 100, 2, 0.01, 0.2);
+        let graph = testing::myciel3();
+
+        let params = GeneticParams {
+            generations: 10000,
+            population_size: 100,
+            offsprings_per_generation: 2,
+            mutation_probability: 0.01,
+            selected_population_ratio: 0.2,
+            mode: GeneticMode::MinColors,
+            repair_strategy: RepairStrategy::FirstLegal,
+            crossover: Crossover::OnePoint,
+            lower_bound: None,
+        };
+        let ((best, colors), _) = genetic(&graph, &params, None);
+
+        assert!(best <= coloring_upper_bound(&graph));
+
+        assert!(is_coloring_valid(&graph, &colors));
+    }
 
-            assert!(best <= coloring_upper_bound(&graph));
+    #[test]
+    fn test_genetic_from_initial_population() {
+        let graph = AdjList::complete(4);
+        let params = GeneticParams {
+            generations: 10,
+            population_size: 3,
+            offsprings_per_generation: 2,
+            mutation_probability: 0.01,
+            selected_population_ratio: 0.2,
+            mode: GeneticMode::MinColors,
+            repair_strategy: RepairStrategy::FirstLegal,
+            crossover: Crossover::OnePoint,
+            lower_bound: None,
+        };
+
+        // The invalid individual must be repaired and the missing ones generated
+        let initial_population = vec![vec![1, 1, 2, 2]];
+        let ((best, colors), population) = genetic(&graph, &params, Some(initial_population));
+
+        assert_eq!(best, 4);
+        assert!(is_coloring_valid(&graph, &colors));
+        assert_eq!(population.mode, GeneticMode::MinColors);
+        assert_eq!(population.individuals.len(), 3);
+
+        for individual in population.individuals {
+            assert!(is_coloring_valid(&graph, &individual.coloring));
+            assert_eq!(individual.fitness, count_colors(&individual.coloring));
+        }
+    }
 
+    #[test]
+    fn test_genetic_stops_at_lower_bound() {
+        // Every valid coloring of K5 is optimal, so no generation should run
+        let graph = AdjList::complete(5);
+
+        for mode in [GeneticMode::MinColors, GeneticMode::FixedK] {
+            let params = GeneticParams {
+                generations: usize::MAX,
+                population_size: 10,
+                offsprings_per_generation: 2,
+                mutation_probability: 0.01,
+                selected_population_ratio: 0.2,
+                mode,
+                repair_strategy: RepairStrategy::FirstLegal,
+                crossover: Crossover::OnePoint,
+                lower_bound: Some(5),
+            };
+            let ((best, colors), _) = genetic(&graph, &params, None);
+
+            assert_eq!(best, 5);
             assert!(is_coloring_valid(&graph, &colors));
-        } else {
-            panic!("The file containing the test graph is missing")
         }
     }
+
+    #[test]
+    fn test_genetic_fixed_k() {
+        let graph = testing::myciel3();
+
+        let params = GeneticParams {
+            generations: 2000,
+            population_size: 50,
+            offsprings_per_generation: 2,
+            mutation_probability: 0.2,
+            selected_population_ratio: 0.2,
+            mode: GeneticMode::FixedK,
+            repair_strategy: RepairStrategy::FirstLegal,
+            crossover: Crossover::OnePoint,
+            lower_bound: None,
+        };
+        let ((best, colors), _) = genetic(&graph, &params, None);
+
+        assert_eq!(best, count_colors(&colors));
+
+        assert!(is_coloring_valid(&graph, &colors));
+
+        // A single color leaves no smaller `k` to squash the population into
+        let ((best, colors), _) = genetic(&AdjList::new(5), &params, None);
+
+        assert_eq!(best, 1);
+        assert_eq!(colors, vec![1; 5]);
+    }
 }
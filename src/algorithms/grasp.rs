@@ -1,8 +1,305 @@
-use super::Solution;
+use super::{
+    incumbent::Incumbent,
+    invariants,
+    neighborhood::{
+        best_kempe_move, best_recolor, count_forbidden_per_vertex, get_coloring_from_class_list,
+        get_forbidden_vertices, redistribute,
+    },
+    relabel_colors,
+    tabucol::tabucol,
+    Solution,
+};
 use crate::graph::adj_list::AdjList;
+use crate::progress::{self, Phase};
+use crate::rng::{derive_seed, rng, with_seed};
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
 use rand::seq::SliceRandom;
+use rand::Rng;
+#[cfg(not(feature = "deterministic"))]
 use rayon::prelude::*;
-use std::collections::{BinaryHeap, HashSet};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// RCL sizes tried by Reactive GRASP with a cardinality-based RCL.
+const REACTIVE_SIZES: [usize; 8] = [1, 2, 3, 4, 5, 7, 10, 15];
+/// RCL thresholds tried by Reactive GRASP with a value-based RCL.
+const REACTIVE_ALPHAS: [f64; 11] = [0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
+/// Exponent amplifying the differences between the RCL parameters in Reactive GRASP.
+const REACTIVE_DELTA: i32 = 10;
+
+/// How the restricted candidate list (RCL) is built during the construction phase.
+#[derive(Debug, Clone, Copy)]
+pub enum CandidateList {
+    /// Take the `n` vertices with the largest degrees (cardinality-based RCL).
+    Cardinality(usize),
+    /// Take all vertices whose degree is at least `max - alpha * (max - min)` (value-based RCL).
+    Threshold(f64),
+}
+
+/// How the long-term frequency memory biases the choice of a vertex from the candidate list.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum MemoryBias {
+    /// Favor vertices that frequently ended up in large color classes.
+    Intensify,
+    /// Favor vertices that rarely ended up in large color classes.
+    Diversify,
+}
+
+/// Method used to remove the conflicts created by merging color classes in the improvement phase.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Improvement {
+    /// Recolor conflicting vertices while it reduces the conflicts.
+    #[default]
+    Local,
+    /// Run a bounded TabuCol search with the merged number of colors.
+    Tabu,
+    /// Run the local search, then the tabu search on the conflicts it couldn't remove, which
+    /// spends the tabu iterations only where the cheaper search plateaus.
+    LocalThenTabu,
+}
+
+/// Heuristic used to build the initial solution of each GRASP iteration.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Construction {
+    /// Grow one color class at a time as a large independent set (see [`assign_color`]).
+    #[default]
+    IndependentSet,
+    /// Randomized DSATUR, coloring one vertex at a time.
+    Dsatur,
+}
+
+/// How the improvement phase removes a color class before trying to fix the resulting conflicts.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Merge {
+    /// Merge the two smallest classes.
+    #[default]
+    TwoSmallest,
+    /// Move each vertex of the smallest class to the class where it has the fewest neighbors.
+    SmallestIntoBestFit,
+    /// Move each vertex of a random class to the class where it has the fewest neighbors.
+    DissolveRandom,
+    /// Merge the two classes with the fewest edges between them.
+    FewestConflicts,
+}
+
+#[derive(Debug)]
+pub struct GraspParams {
+    pub grasp_iterations: i32,
+    pub color_iterations: i32,
+    pub candidate_list: CandidateList,
+    pub construction: Construction,
+    pub memory: Option<MemoryBias>,
+    pub merge: Merge,
+    pub improvement: Improvement,
+    /// Maximum number of iterations of each tabu search, if `improvement` is `Tabu` or
+    /// `LocalThenTabu`.
+    pub tabu_iterations: usize,
+    /// The local search stops after `no_improvement_factor` times the initial number of forbidden
+    /// edges consecutive iterations without improvement.
+    pub no_improvement_factor: usize,
+    /// Maximum number of iterations of each local search, if any.
+    pub local_search_iterations: Option<usize>,
+    /// Whether the RCL parameter adapts over the iterations (Reactive GRASP), instead of being fixed
+    /// by `candidate_list` (whose kind is kept, see [`ReactiveRcl`]).
+    pub reactive: bool,
+    /// Whether the local search also tries Kempe-chain interchanges when recoloring a vertex
+    /// doesn't help.
+    pub kempe_moves: bool,
+    /// Number of consecutive failed merges tolerated before the improvement phase stops.
+    pub merge_retries: usize,
+    /// Number of times the coloring of each iteration is perturbed (see [`perturb`]) and improved
+    /// again once the improvement phase stalls (GRASP-ILS). The perturbed coloring is kept if it
+    /// doesn't end up with more colors.
+    pub perturbation_restarts: usize,
+    /// Maximum number of merges (failed or not) tried by each improvement phase, if any.
+    pub improvement_merges: Option<usize>,
+    /// Maximum duration of each improvement phase, if any. Checked between merges, so a single
+    /// search can run past it.
+    pub improvement_time: Option<Duration>,
+    /// Seed each iteration derives its own random generator from (with its index), so the results
+    /// don't depend on how the iterations are scheduled on the threads. Unless the iterations share
+    /// what they learn (with `memory` or `reactive`), or stop early once `lower_bound` or a time
+    /// limit is reached. Random if `None`.
+    pub seed: Option<u64>,
+    /// Maximum duration of each iteration, if any. Past it, the construction stops retrying
+    /// classes, and the improvement phase and perturbations stop, so the iteration returns the
+    /// best coloring it has. Checked between steps, so it's a soft limit.
+    pub iteration_time: Option<Duration>,
+    /// Known lower bound on the number of colors: no more iterations start once it's matched.
+    pub lower_bound: Option<usize>,
+    /// Maximum number of vertices of each class, if any. The constructions close a class once
+    /// it's full, and the improvement phase and perturbations only keep class lists within it.
+    pub max_class_size: Option<usize>,
+}
+
+impl Default for GraspParams {
+    fn default() -> Self {
+        GraspParams {
+            grasp_iterations: 25,
+            color_iterations: 25,
+            candidate_list: CandidateList::Cardinality(3),
+            construction: Construction::IndependentSet,
+            memory: None,
+            reactive: false,
+            merge: Merge::TwoSmallest,
+            improvement: Improvement::Local,
+            tabu_iterations: 1000,
+            no_improvement_factor: 2,
+            local_search_iterations: None,
+            kempe_moves: false,
+            merge_retries: 0,
+            perturbation_restarts: 0,
+            improvement_merges: None,
+            improvement_time: None,
+            seed: None,
+            iteration_time: None,
+            lower_bound: None,
+            max_class_size: None,
+        }
+    }
+}
+
+/// Long-term memory shared by the GRASP iterations.
+///
+/// Counts, for each vertex, how many of the solutions found so far placed it in a large color
+/// class, i.e., a class with at least as many vertices as the average class.
+struct FrequencyMemory {
+    bias: MemoryBias,
+    frequencies: Vec<AtomicUsize>,
+}
+
+impl FrequencyMemory {
+    fn new(num_vertices: usize, bias: MemoryBias) -> Self {
+        FrequencyMemory {
+            bias,
+            frequencies: (0..num_vertices).map(|_| AtomicUsize::new(0)).collect(),
+        }
+    }
+
+    /// Records the vertices in the large classes among the first `num_classes` of `class_list`.
+    fn record(&self, class_list: &[Vec<usize>], num_classes: usize) {
+        if num_classes == 0 {
+            return;
+        }
+
+        let classes = &class_list[..num_classes];
+        let num_vertices: usize = classes.iter().map(|class| class.len()).sum();
+
+        for class in classes {
+            if class.len() * num_classes >= num_vertices {
+                for vertex in class {
+                    self.frequencies[*vertex].fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Selection weights for `candidates`, according to the bias.
+    fn weights(&self, candidates: &[usize]) -> Vec<usize> {
+        let frequencies: Vec<usize> = candidates
+            .iter()
+            .map(|vertex| self.frequencies[*vertex].load(Ordering::Relaxed))
+            .collect();
+
+        match self.bias {
+            MemoryBias::Intensify => frequencies.iter().map(|f| f + 1).collect(),
+            MemoryBias::Diversify => {
+                let max = frequencies.iter().max().copied().unwrap_or(0);
+                frequencies.iter().map(|f| max - f + 1).collect()
+            }
+        }
+    }
+
+    /// Randomly picks a vertex from `candidates`, weighted according to the bias.
+    fn choose(&self, candidates: &[usize]) -> Option<usize> {
+        let distribution = WeightedIndex::new(self.weights(candidates)).ok()?;
+
+        Some(candidates[distribution.sample(&mut rng())])
+    }
+}
+
+/// The adaptive choice of the RCL parameter of Reactive GRASP (Prais and Ribeiro, 2000).
+///
+/// Each iteration draws a parameter from a fixed set of values with probability proportional to
+/// `(best / average)^REACTIVE_DELTA`, where `best` is the fewest colors found by any iteration and
+/// `average` is the average number of colors of the iterations that used that value. Values not
+/// used yet get the highest weight, so every one is tried early on.
+struct ReactiveRcl {
+    values: Vec<CandidateList>,
+    /// The best number of colors found, and the sum and count of the number of colors found by
+    /// each value.
+    stats: Mutex<(usize, Vec<(usize, usize)>)>,
+}
+
+impl ReactiveRcl {
+    /// The values tried are of the same kind as `candidate_list`.
+    fn new(candidate_list: CandidateList) -> Self {
+        let values: Vec<CandidateList> = match candidate_list {
+            CandidateList::Cardinality(_) => REACTIVE_SIZES
+                .into_iter()
+                .map(CandidateList::Cardinality)
+                .collect(),
+            CandidateList::Threshold(_) => REACTIVE_ALPHAS
+                .into_iter()
+                .map(CandidateList::Threshold)
+                .collect(),
+        };
+
+        ReactiveRcl {
+            stats: Mutex::new((usize::MAX, vec![(0, 0); values.len()])),
+            values,
+        }
+    }
+
+    /// Selection weights for the values, from the results so far.
+    fn weights(&self) -> Vec<f64> {
+        let (best, stats) = &*self.stats.lock().unwrap();
+
+        stats
+            .iter()
+            .map(|(sum, count)| match count {
+                0 => 1.0,
+                _ => (*best as f64 * *count as f64 / *sum as f64).powi(REACTIVE_DELTA),
+            })
+            .collect()
+    }
+
+    /// Randomly picks a value, returning its index along with it.
+    fn choose(&self) -> (usize, CandidateList) {
+        let index = WeightedIndex::new(self.weights())
+            .map_or(0, |distribution| distribution.sample(&mut rng()));
+
+        (index, self.values[index])
+    }
+
+    /// Records that an iteration with the value of `index` found a coloring with `num_colors`.
+    fn record(&self, index: usize, num_colors: usize) {
+        let (best, stats) = &mut *self.stats.lock().unwrap();
+
+        *best = (*best).min(num_colors);
+        stats[index].0 += num_colors;
+        stats[index].1 += 1;
+    }
+}
+
+/// Given a `graph`, gets the degrees of the vertices in `subset`, counting the neighbors within
+/// `list` (or within `subset` itself, i.e., the induced subgraph, if `list` is not provided).
+fn get_degrees(graph: &AdjList, subset: &[usize], list: Option<&[usize]>) -> Vec<(usize, usize)> {
+    let list = if let Some(list) = list { list } else { subset };
+    let vertex_set: Vec<usize> = (0..graph.num_vertices()).collect();
+    let mut degrees: Vec<(usize, usize)> = vertex_set
+        .iter()
+        .map(|vertex| (*vertex, graph.get_degree_in_list(*vertex, list)))
+        .collect();
+
+    degrees.retain(|x| subset.contains(&x.0));
+
+    degrees
+}
 
 /// Given a `graph`, gets (at most) `n` indexes of the higher degree vertices in the subgraph induced by
 /// `subset`. If `list` is provided, don't use the induced subgraph.
@@ -13,108 +310,386 @@ fn get_n_largest_degree(
     subset: &[usize],
     list: Option<&[usize]>,
 ) -> Vec<usize> {
-    let list = if let Some(list) = list { list } else { subset };
-    let vertex_set: Vec<usize> = (0..graph.num_vertices()).collect();
-    let mut degrees: Vec<(usize, usize)> = vertex_set
-        .iter()
-        .map(|vertex| (*vertex, graph.get_degree_in_list(*vertex, list)))
-        .collect();
-
-    degrees.retain(|x| subset.contains(&x.0));
+    let mut degrees = get_degrees(graph, subset, list);
 
-    degrees.sort_by(|lhs, rhs| rhs.1.cmp(&lhs.1));
+    degrees.sort_by_key(|(_, degree)| Reverse(*degree));
 
     degrees.iter().take(n).map(|(index, _)| *index).collect()
 }
 
-/// Counts the number of edges in subgraph induced by `graph` and `list`.
-fn count_remaining_edges(graph: &AdjList, list: &[usize]) -> usize {
-    let mut count = 0;
+/// Same as [`get_n_largest_degree`], but instead of a fixed number of vertices, gets every vertex
+/// whose degree is within `alpha` (a fraction of the range between the smallest and largest
+/// degrees) of the largest degree.
+fn get_largest_degree_within_alpha(
+    alpha: f64,
+    graph: &AdjList,
+    subset: &[usize],
+    list: Option<&[usize]>,
+) -> Vec<usize> {
+    let degrees = get_degrees(graph, subset, list);
+    let max = degrees.iter().map(|(_, degree)| *degree).max().unwrap_or(0);
+    let min = degrees.iter().map(|(_, degree)| *degree).min().unwrap_or(0);
+    let threshold = max as f64 - alpha * (max - min) as f64;
 
-    for i in 0..list.len() {
-        for j in i + 1..list.len() {
-            if graph.adj_list()[i].contains(&j) {
-                count += 1;
-            }
+    degrees
+        .iter()
+        .filter(|(_, degree)| *degree as f64 >= threshold)
+        .map(|(index, _)| *index)
+        .collect()
+}
+
+/// Builds the restricted candidate list from `subset`, as described by `candidate_list`.
+fn get_candidate_list(
+    candidate_list: CandidateList,
+    graph: &AdjList,
+    subset: &[usize],
+    list: Option<&[usize]>,
+) -> Vec<usize> {
+    match candidate_list {
+        CandidateList::Cardinality(n) => get_n_largest_degree(n, graph, subset, list),
+        CandidateList::Threshold(alpha) => {
+            get_largest_degree_within_alpha(alpha, graph, subset, list)
         }
     }
+}
+
+/// Counts the number of edges of the subgraph of `graph` induced by the vertices in `list`.
+fn count_remaining_edges(graph: &AdjList, list: &[usize]) -> usize {
+    let mut in_list = vec![false; graph.num_vertices()];
+    for vertex in list {
+        in_list[*vertex] = true;
+    }
+
+    list.iter()
+        .map(|vertex| count_neighbors_in(graph, *vertex, &in_list))
+        .sum::<usize>()
+        / 2
+}
 
-    count
+/// Counts the neighbors of `vertex` in `graph` that are members of the set given by `in_set`.
+fn count_neighbors_in(graph: &AdjList, vertex: usize, in_set: &[bool]) -> usize {
+    graph.adj_list()[vertex]
+        .iter()
+        .filter(|neighbor| in_set[**neighbor])
+        .count()
 }
 
 /// Runs a single GRASP execution with the given parameters.
-pub fn grasp_wrapper(
+pub fn grasp_wrapper(graph: &AdjList, params: &GraspParams) -> Solution {
+    grasp(graph, params, 1).into_iter().next().unwrap()
+}
+
+/// Runs GRASP, returning the (at most) `num_solutions` best distinct colorings found by its
+/// iterations. The first iteration always runs, so there's at least one coloring (as long as
+/// `num_solutions` and the number of iterations aren't zero), even if a stop was requested.
+///
+/// The colorings are relabeled (see [`relabel_colors`]), so colorings of the same partition are
+/// the same, and kept once. They're sorted by number of colors, ties broken by comparing the
+/// colorings themselves, so the best one comes first and the order doesn't depend on which
+/// iteration finished first.
+pub fn grasp(graph: &AdjList, params: &GraspParams, num_solutions: usize) -> Vec<Solution> {
+    grasp_shared(
+        graph,
+        params,
+        num_solutions,
+        &Incumbent::new(params.lower_bound),
+    )
+}
+
+/// Like [`grasp`], but sharing `incumbent` with the other searches running at once: no iteration
+/// but the first starts, and no improvement phase keeps merging classes, once the incumbent is
+/// optimal.
+pub fn grasp_shared(
+    graph: &AdjList,
+    params: &GraspParams,
+    num_solutions: usize,
+    incumbent: &Incumbent,
+) -> Vec<Solution> {
+    let max_colors = graph.num_vertices();
+    let memory = params
+        .memory
+        .map(|bias| FrequencyMemory::new(max_colors, bias));
+    let reactive = params
+        .reactive
+        .then(|| ReactiveRcl::new(params.candidate_list));
+
+    // The iterations run in parallel, unless the results must be reproducible
+    #[cfg(not(feature = "deterministic"))]
+    let iterations = (0..params.grasp_iterations).into_par_iter();
+    #[cfg(feature = "deterministic")]
+    let iterations = 0..params.grasp_iterations;
+
+    let all: Vec<Solution> = iterations
+        .filter_map(|iteration| match params.seed {
+            Some(seed) => with_seed(derive_seed(seed, iteration as u64), || {
+                grasp_iteration(
+                    graph,
+                    params,
+                    iteration,
+                    incumbent,
+                    memory.as_ref(),
+                    reactive.as_ref(),
+                )
+            }),
+            None => grasp_iteration(
+                graph,
+                params,
+                iteration,
+                incumbent,
+                memory.as_ref(),
+                reactive.as_ref(),
+            ),
+        })
+        .collect();
+
+    let mut solutions: Vec<Solution> = all
+        .into_iter()
+        .map(|(num_colors, mut coloring)| {
+            // The same partition may be found with different color labels
+            relabel_colors(&mut coloring);
+            (num_colors, coloring)
+        })
+        .collect();
+
+    solutions.sort();
+    solutions.dedup();
+    solutions.truncate(num_solutions);
+
+    solutions
+}
+
+/// Runs the `iteration`-th GRASP iteration (construction and improvement), returning its
+/// coloring, or `None` if it shouldn't start because `incumbent` is optimal or a stop was
+/// requested. The first iteration always runs, so GRASP has a coloring to return.
+fn grasp_iteration(
     graph: &AdjList,
-    grasp_iterations: i32,
-    color_iterations: i32,
-    color_list_size: usize,
-) -> Solution {
-    let mut solutions = grasp(
+    params: &GraspParams,
+    iteration: i32,
+    incumbent: &Incumbent,
+    memory: Option<&FrequencyMemory>,
+    reactive: Option<&ReactiveRcl>,
+) -> Option<Solution> {
+    if iteration > 0 && (incumbent.is_optimal() || progress::stop_requested()) {
+        return None;
+    }
+
+    let deadline = params.iteration_time.map(|time| Instant::now() + time);
+    let (value, candidate_list) = match reactive {
+        Some(reactive) => {
+            let (index, candidate_list) = reactive.choose();
+            (Some(index), candidate_list)
+        }
+        None => (None, params.candidate_list),
+    };
+
+    let (mut num_color_classes, mut class_list) = match params.construction {
+        Construction::IndependentSet => {
+            independent_set_construction(graph, params, candidate_list, memory, deadline)
+        }
+        Construction::Dsatur => {
+            dsatur_construction(graph, candidate_list, memory, params.max_class_size)
+        }
+    };
+
+    invariants::check_class_list(graph, &class_list, num_color_classes);
+
+    progress::record_colors_from(Phase::Construction, num_color_classes);
+
+    improve_phase(
         graph,
-        grasp_iterations,
-        color_iterations,
-        color_list_size,
-        1,
+        params,
+        &mut num_color_classes,
+        &mut class_list,
+        incumbent,
+        deadline,
     );
-    let (num_colors, coloring) = solutions.pop().unwrap();
-    (num_colors, coloring)
+
+    for _ in 0..params.perturbation_restarts {
+        if num_color_classes <= 1
+            || incumbent.is_optimal()
+            || progress::stop_requested()
+            || deadline.is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            break;
+        }
+
+        let (mut num_perturbed, mut perturbed) = perturb(graph, &class_list[..num_color_classes]);
+
+        invariants::check_class_list(graph, &perturbed, num_perturbed);
+
+        improve_phase(
+            graph,
+            params,
+            &mut num_perturbed,
+            &mut perturbed,
+            incumbent,
+            deadline,
+        );
+
+        if num_perturbed <= num_color_classes && fits(&perturbed, params.max_class_size) {
+            num_color_classes = num_perturbed;
+            class_list = perturbed;
+        }
+    }
+
+    invariants::check_class_list(graph, &class_list, num_color_classes);
+
+    if let Some(memory) = memory {
+        memory.record(&class_list, num_color_classes);
+    }
+    if let (Some(reactive), Some(index)) = (reactive, value) {
+        reactive.record(index, num_color_classes);
+    }
+
+    progress::record_iteration();
+    progress::record_colors_from(Phase::LocalSearch, num_color_classes);
+    incumbent.offer(num_color_classes);
+
+    let coloring = get_coloring_from_class_list(graph.num_vertices(), &class_list);
+    Some((num_color_classes, coloring))
 }
 
-pub fn grasp(
+/// Builds a class list by repeatedly extracting (the best of `color_iterations` attempts of)
+/// an independent set from the uncolored vertices, with [`assign_color`] and `candidate_list`.
+///
+/// Past `deadline` (if any), each class is extracted only once.
+///
+/// Returns the number of classes and the class list (with one slot per vertex).
+fn independent_set_construction(
     graph: &AdjList,
-    grasp_iterations: i32,
-    color_iterations: i32,
-    color_list_size: usize,
-    num_solutions: usize,
-) -> BinaryHeap<Solution> {
+    params: &GraspParams,
+    candidate_list: CandidateList,
+    memory: Option<&FrequencyMemory>,
+    deadline: Option<Instant>,
+) -> (usize, Vec<Vec<usize>>) {
     let max_colors = graph.num_vertices();
-    let mut solutions = BinaryHeap::with_capacity(num_solutions);
-
-    let all: Vec<Solution> = (0..grasp_iterations)
-        .into_par_iter()
-        .map(|_| {
-            let mut num_color_classes = 0;
-            let mut vertex_set: Vec<usize> = (0..max_colors).collect();
-            let mut class_list: Vec<Vec<usize>> = Vec::new();
-
-            class_list.resize(max_colors, Vec::new());
-
-            while !vertex_set.is_empty() {
-                let mut min_num_edges_remaining = usize::MAX;
-
-                num_color_classes += 1;
-
-                for _ in 0..color_iterations {
-                    assign_color(
-                        &vertex_set,
-                        color_list_size,
-                        graph,
-                        &mut min_num_edges_remaining,
-                        &mut class_list,
-                        num_color_classes,
-                    );
-                }
+    let mut num_color_classes = 0;
+    let mut vertex_set: Vec<usize> = (0..max_colors).collect();
+    let mut in_vertex_set = vec![true; max_colors];
+    let mut num_edges = count_remaining_edges(graph, &vertex_set);
+    let mut class_list: Vec<Vec<usize>> = Vec::new();
+
+    class_list.resize(max_colors, Vec::new());
 
-                vertex_set.retain(|vertex| !class_list[num_color_classes - 1].contains(vertex));
+    while !vertex_set.is_empty() {
+        let mut min_num_edges_remaining = usize::MAX;
+
+        num_color_classes += 1;
+
+        for attempt in 0..params.color_iterations {
+            if attempt > 0 && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break;
             }
 
-            improve_phase(graph, &mut num_color_classes, &mut class_list);
+            assign_color(
+                &vertex_set,
+                &in_vertex_set,
+                num_edges,
+                candidate_list,
+                graph,
+                &mut min_num_edges_remaining,
+                &mut class_list,
+                num_color_classes,
+                memory,
+                params.max_class_size,
+            );
+        }
 
-            let coloring = get_coloring_from_class_list(max_colors, &class_list);
-            (num_color_classes, coloring)
-        })
-        .collect();
+        // The class is independent, so each of its edges to the rest is counted once
+        for vertex in class_list[num_color_classes - 1].iter() {
+            in_vertex_set[*vertex] = false;
+            num_edges -= count_neighbors_in(graph, *vertex, &in_vertex_set);
+        }
+        vertex_set.retain(|vertex| in_vertex_set[*vertex]);
+    }
+
+    (num_color_classes, class_list)
+}
+
+/// Builds a class list with a randomized DSATUR: at each step, a vertex is chosen from the
+/// candidate list (as described by `candidate_list`) of the uncolored vertices with the highest
+/// saturation (i.e., number of distinct colors in their neighborhood, with ties broken by degree)
+/// and receives the smallest color not used by its neighbors (whose class has fewer than
+/// `max_class_size` vertices, if given).
+///
+/// Returns the number of classes and the class list (with one slot per vertex).
+fn dsatur_construction(
+    graph: &AdjList,
+    candidate_list: CandidateList,
+    memory: Option<&FrequencyMemory>,
+    max_class_size: Option<usize>,
+) -> (usize, Vec<Vec<usize>>) {
+    let n = graph.num_vertices();
+    let adj_list = graph.adj_list();
+    let mut coloring = vec![0; n];
+    let mut class_sizes = vec![0; n + 1];
+    let mut neighbor_colors: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+    let mut uncolored: Vec<usize> = (0..n).collect();
+    let mut num_color_classes = 0;
+
+    while !uncolored.is_empty() {
+        let mut scores: Vec<(usize, (usize, usize))> = uncolored
+            .iter()
+            .map(|vertex| {
+                (
+                    *vertex,
+                    (neighbor_colors[*vertex].len(), adj_list[*vertex].len()),
+                )
+            })
+            .collect();
+
+        scores.sort_by_key(|(_, score)| Reverse(*score));
+
+        let candidates: Vec<usize> = match candidate_list {
+            CandidateList::Cardinality(size) => scores
+                .iter()
+                .take(size)
+                .map(|(vertex, _)| *vertex)
+                .collect(),
+            CandidateList::Threshold(alpha) => {
+                // Sorted in decreasing order
+                let max = scores[0].1 .0;
+                let min = scores[scores.len() - 1].1 .0;
+                let threshold = max as f64 - alpha * (max - min) as f64;
+
+                scores
+                    .iter()
+                    .filter(|(_, (saturation, _))| *saturation as f64 >= threshold)
+                    .map(|(vertex, _)| *vertex)
+                    .collect()
+            }
+        };
 
-    for solution in all {
-        if solutions.len() < num_solutions {
-            solutions.push(solution);
-        } else if solution.0 < solutions.peek().unwrap().0 {
-            solutions.pop();
-            solutions.push(solution);
+        let vertex = match memory {
+            Some(memory) => memory.choose(&candidates),
+            None => candidates.choose(&mut rng()).copied(),
+        }
+        .expect("CSize must be at least 1");
+
+        let color = (1..)
+            .find(|color| {
+                !neighbor_colors[vertex].contains(color)
+                    && max_class_size.is_none_or(|max| class_sizes[*color] < max)
+            })
+            .unwrap();
+
+        coloring[vertex] = color;
+        class_sizes[color] += 1;
+        num_color_classes = num_color_classes.max(color);
+        uncolored.retain(|v| *v != vertex);
+
+        for neighbor in adj_list[vertex].iter() {
+            neighbor_colors[*neighbor].insert(color);
         }
     }
 
-    solutions
+    let mut class_list: Vec<Vec<usize>> = vec![Vec::new(); n];
+
+    for (vertex, color) in coloring.iter().enumerate() {
+        class_list[color - 1].push(vertex);
+    }
+
+    (num_color_classes, class_list)
 }
 
 /// Tries to assign a color class `num_color_classes` to `class_list`
@@ -124,43 +699,63 @@ pub fn grasp(
 /// been colored. It tries to cover the remaining graph entirely (or until no candidates remain).
 ///
 /// Refer to the article for more information about the heuristic.
+///
+/// If a `memory` is provided, the vertex is chosen from the candidate list according to its bias,
+/// instead of uniformly.
+///
+/// The class is closed once it has `max_class_size` vertices, if given.
+///
+/// The class is kept if it leaves fewer edges among the uncolored vertices than
+/// `min_num_edges_remaining`. Starting from the `num_edges` edges among `vertex_set` (whose
+/// members are marked by `in_vertex_set`), the count drops by the neighbors each vertex leaves
+/// behind as it joins the class.
+#[allow(clippy::too_many_arguments)]
 fn assign_color(
     vertex_set: &[usize],
-    color_list_size: usize,
+    in_vertex_set: &[bool],
+    num_edges: usize,
+    candidate_list: CandidateList,
     graph: &AdjList,
     min_num_edges_remaining: &mut usize,
     class_list: &mut [Vec<usize>],
     num_color_classes: usize,
+    memory: Option<&FrequencyMemory>,
+    max_class_size: Option<usize>,
 ) {
     let mut admissible_uncolored: Vec<usize> = vertex_set.to_vec();
     let mut inadmissible_uncolored: Vec<usize> = Vec::new();
+    let mut remaining_edges = num_edges;
     let mut current_color_class: Vec<usize> = Vec::new();
 
-    while !admissible_uncolored.is_empty() {
-        let candidate_list = if inadmissible_uncolored.is_empty() {
-            get_n_largest_degree(color_list_size, graph, &admissible_uncolored, None)
+    while !admissible_uncolored.is_empty()
+        && max_class_size.is_none_or(|max| current_color_class.len() < max)
+    {
+        let candidates = if inadmissible_uncolored.is_empty() {
+            get_candidate_list(candidate_list, graph, &admissible_uncolored, None)
         } else {
-            get_n_largest_degree(
-                color_list_size,
+            get_candidate_list(
+                candidate_list,
                 graph,
                 &admissible_uncolored,
                 Some(&inadmissible_uncolored),
             )
         };
-        let vertex = candidate_list.choose(&mut rand::thread_rng());
+        let vertex = match memory {
+            Some(memory) => memory.choose(&candidates),
+            None => candidates.choose(&mut rng()).copied(),
+        };
 
         if let Some(vertex) = vertex {
-            current_color_class.push(*vertex);
-            let neighbors = graph.adj_list()[*vertex].clone();
-            admissible_uncolored.retain(|node| node != vertex && !neighbors.contains(node));
+            // None of its neighbors is in the class, so they all stay uncolored
+            remaining_edges -= count_neighbors_in(graph, vertex, in_vertex_set);
+            current_color_class.push(vertex);
+            let neighbors = graph.adj_list()[vertex].clone();
+            admissible_uncolored.retain(|node| *node != vertex && !neighbors.contains(node));
             inadmissible_uncolored = [inadmissible_uncolored, neighbors].concat();
         } else {
             panic!("CSize must be at least 1")
         }
     }
-    let mut remaining_vertices = vertex_set.to_vec();
-    remaining_vertices.retain(|vertex| !current_color_class.contains(vertex));
-    let remaining_edges = count_remaining_edges(graph, &remaining_vertices);
 
     if remaining_edges < *min_num_edges_remaining {
         class_list[num_color_classes - 1] = current_color_class;
@@ -168,54 +763,162 @@ fn assign_color(
     }
 }
 
-/// Tries to improve the coloring from `class_list` by
-///
-/// 1. Merging the smallest class colors
-/// 2. Applying a local search for the resulting class list
+/// Merges two of the (non-empty) `classes` into one, according to `strategy`.
 ///
-/// The process repeats until a forbidden coloring is found
-fn improve_phase(graph: &AdjList, num_classes: &mut usize, class_list: &mut Vec<Vec<usize>>) {
-    let mut num_forbidden = 0;
+/// The merged class is the first in the returned class list, which has one class fewer.
+fn merge_classes(graph: &AdjList, classes: &[Vec<usize>], strategy: Merge) -> Vec<Vec<usize>> {
+    let mut new_classes: Vec<Vec<usize>> = classes
+        .iter()
+        .filter(|class| !class.is_empty())
+        .cloned()
+        .collect();
 
-    while num_forbidden == 0 {
-        let mut lenghts: Vec<(usize, usize)> = class_list
-            .iter()
-            .enumerate()
-            .map(|(index, class)| (index, class.len()))
-            .take(*num_classes)
-            .collect();
+    if new_classes.len() < 2 {
+        return new_classes;
+    }
 
-        lenghts.sort_by(|lhs, rhs| rhs.1.cmp(&lhs.1));
+    let (first, second) = match strategy {
+        Merge::TwoSmallest => {
+            let mut lenghts: Vec<(usize, usize)> = new_classes
+                .iter()
+                .enumerate()
+                .map(|(index, class)| (index, class.len()))
+                .collect();
 
-        let smallest_lengths: Vec<usize> = lenghts
-            .iter()
-            .rev()
-            .take(2)
-            .map(|(index, _)| *index)
-            .collect();
+            lenghts.sort_by_key(|(_, length)| Reverse(*length));
 
-        let mut combined_class: Vec<usize> = vec![];
+            let smallest_lengths: Vec<usize> = lenghts
+                .iter()
+                .rev()
+                .take(2)
+                .map(|(index, _)| *index)
+                .collect();
 
-        for index in smallest_lengths.iter() {
-            combined_class.append(&mut class_list[*index].clone());
+            (smallest_lengths[0], smallest_lengths[1])
         }
+        Merge::FewestConflicts => {
+            let k = new_classes.len();
+            let coloring = get_coloring_from_class_list(graph.num_vertices(), &new_classes);
+            let mut conflicts = vec![vec![0; k]; k];
+
+            for (u, neighbors) in graph.adj_list().iter().enumerate() {
+                for v in neighbors.iter() {
+                    if coloring[u] != 0 && coloring[*v] != 0 {
+                        conflicts[coloring[u] - 1][coloring[*v] - 1] += 1;
+                    }
+                }
+            }
 
-        let mut new_classes: Vec<Vec<usize>> = vec![];
+            // Break ties by the size of the merged class
+            (0..k)
+                .flat_map(|i| (i + 1..k).map(move |j| (i, j)))
+                .min_by_key(|(i, j)| {
+                    (
+                        conflicts[*i][*j],
+                        new_classes[*i].len() + new_classes[*j].len(),
+                    )
+                })
+                .unwrap()
+        }
+        Merge::SmallestIntoBestFit | Merge::DissolveRandom => {
+            let index = if let Merge::SmallestIntoBestFit = strategy {
+                (0..new_classes.len())
+                    .min_by_key(|index| new_classes[*index].len())
+                    .unwrap()
+            } else {
+                rng().gen_range(0..new_classes.len())
+            };
+            let dissolved = new_classes.remove(index);
+
+            redistribute(graph, &mut new_classes, dissolved);
+
+            return new_classes;
+        }
+    };
 
-        new_classes.push(combined_class);
+    let mut combined_class = new_classes[first].clone();
+    combined_class.extend_from_slice(&new_classes[second]);
 
-        for (index, class) in class_list.iter().enumerate() {
-            if index == smallest_lengths[0] || index == smallest_lengths[1] || class.is_empty() {
-                continue;
-            }
-            new_classes.push(class.clone());
+    let mut merged = vec![combined_class];
+
+    for (index, class) in new_classes.into_iter().enumerate() {
+        if index != first && index != second {
+            merged.push(class);
         }
+    }
 
-        num_forbidden = local_search(graph, &mut new_classes);
+    merged
+}
 
-        if num_forbidden == 0 {
+/// Tries to improve the coloring from `class_list` by
+///
+/// 1. Merging two classes (by default, the smallest ones; see [`Merge`])
+/// 2. Applying a local search (or a tabu search, see [`Improvement`]) for the resulting class list
+///
+/// The process repeats until a forbidden coloring is found more than `merge_retries` times in a
+/// row (or a single class remains, or `incumbent` is optimal, or the budget of
+/// `improvement_merges` merges or `improvement_time` runs out, or `deadline` passes, or a stop is
+/// requested)
+fn improve_phase(
+    graph: &AdjList,
+    params: &GraspParams,
+    num_classes: &mut usize,
+    class_list: &mut Vec<Vec<usize>>,
+    incumbent: &Incumbent,
+    deadline: Option<Instant>,
+) {
+    let deadline = params
+        .improvement_time
+        .map(|time| Instant::now() + time)
+        .into_iter()
+        .chain(deadline)
+        .min();
+    let mut failed_merges = 0;
+    let mut merges = 0;
+
+    while failed_merges <= params.merge_retries
+        && *num_classes > 1
+        && params
+            .improvement_merges
+            .is_none_or(|max_merges| merges < max_merges)
+        && deadline.is_none_or(|deadline| Instant::now() < deadline)
+        && !incumbent.is_optimal()
+        && !progress::stop_requested()
+    {
+        merges += 1;
+
+        let mut new_classes = merge_classes(graph, &class_list[..*num_classes], params.merge);
+
+        let num_forbidden = match params.improvement {
+            Improvement::Local => local_search(
+                graph,
+                &mut new_classes,
+                params.no_improvement_factor,
+                params.local_search_iterations,
+                params.kempe_moves,
+            ),
+            Improvement::Tabu => tabu_search(graph, &mut new_classes, params.tabu_iterations),
+            Improvement::LocalThenTabu => match local_search(
+                graph,
+                &mut new_classes,
+                params.no_improvement_factor,
+                params.local_search_iterations,
+                params.kempe_moves,
+            ) {
+                0 => 0,
+                _ => tabu_search(graph, &mut new_classes, params.tabu_iterations),
+            },
+        };
+
+        // A merge that overfills a class counts as failed, like one that leaves conflicts
+        if num_forbidden == 0 && fits(&new_classes, params.max_class_size) {
+            // Moving vertices around may have emptied some classes
+            new_classes.retain(|class| !class.is_empty());
             *num_classes = new_classes.len();
             *class_list = new_classes;
+            failed_merges = 0;
+        } else {
+            failed_merges += 1;
         }
     }
 
@@ -223,66 +926,104 @@ fn improve_phase(graph: &AdjList, num_classes: &mut usize, class_list: &mut Vec<
     class_list.resize(num_vertices, Vec::new());
 }
 
-/// Counts the number of forbidden edges in `graph` according to `class_list`.
+/// Whether every class of `class_list` has at most `max_class_size` vertices, if given.
+fn fits(class_list: &[Vec<usize>], max_class_size: Option<usize>) -> bool {
+    max_class_size.is_none_or(|max| class_list.iter().all(|class| class.len() <= max))
+}
+
+/// Moves the coloring of `classes` (a valid one, with at least two classes) somewhere else, for
+/// the improvement phase to start over from: a random class is dissolved into the others (see
+/// [`redistribute`]), and its vertices left in conflict each go to the first class (in random
+/// order) where they have no neighbors, or to a new one.
 ///
-/// Saves the corresponding vertices in a set.
-fn get_forbidden_vertices(graph: &AdjList, class_list: &[Vec<usize>]) -> (usize, HashSet<usize>) {
-    let num_vertices = graph.num_vertices();
-    let adj_list = graph.adj_list();
-    let coloring = get_coloring_from_class_list(num_vertices, class_list);
-    let mut count = 0;
-    let mut forbidden = HashSet::new();
-    for (i, v) in adj_list.iter().enumerate() {
-        for j in v.iter() {
-            if coloring[i] == coloring[*j] {
-                count += 1;
-                forbidden.insert(i);
-                forbidden.insert(*j);
-            }
+/// Returns the number of classes and the class list (with one slot per vertex) of the perturbed
+/// coloring, which is valid.
+fn perturb(graph: &AdjList, classes: &[Vec<usize>]) -> (usize, Vec<Vec<usize>>) {
+    let n = graph.num_vertices();
+    let mut rng = rng();
+    let mut class_list = classes.to_vec();
+    let dissolved = class_list.remove(rng.gen_range(0..class_list.len()));
+
+    redistribute(graph, &mut class_list, dissolved.clone());
+
+    let coloring = get_coloring_from_class_list(n, &class_list);
+    let mut conflicting: Vec<usize> = dissolved
+        .into_iter()
+        .filter(|vertex| count_forbidden_per_vertex(graph, &coloring, *vertex) > 0)
+        .collect();
+
+    // The dissolved vertices aren't adjacent to each other, so only they can be in conflict
+    for vertex in conflicting.iter() {
+        class_list[coloring[*vertex] - 1].retain(|x| x != vertex);
+    }
+
+    conflicting.shuffle(&mut rng);
+    class_list.shuffle(&mut rng);
+
+    for vertex in conflicting {
+        let neighbors = &graph.adj_list()[vertex];
+
+        match class_list
+            .iter_mut()
+            .find(|class| class.iter().all(|x| !neighbors.contains(x)))
+        {
+            Some(class) => class.push(vertex),
+            None => class_list.push(vec![vertex]),
         }
     }
-    (count / 2, forbidden)
+
+    class_list.retain(|class| !class.is_empty());
+
+    let num_classes = class_list.len();
+    class_list.resize(n, Vec::new());
+
+    (num_classes, class_list)
 }
 
 /// Applies a local search for `class_list` according to `graph`.
 ///
 /// The local search works by selecting an illegal vertex and trying every possible color swap for
-/// said vertex to reduce the number of forbidden vertices in the graph.
+/// said vertex to reduce the number of forbidden vertices in the graph. If none helps and `kempe`
+/// is set, it tries swapping each other color with the vertex's over its Kempe chain instead (see
+/// [`best_kempe_move`]), which can escape where single vertices are stuck on dense graphs.
 /// If we can improve, we update the `class_list`.
 ///
 /// Repeats the process while they are forbidden vertices
-/// or the number of iterations that haven't improved `class_list` reaches a threshold.
+/// or the number of iterations that haven't improved `class_list` reaches a threshold
+/// (`no_improvement_factor` times the initial number of forbidden edges, but at least 1),
+/// or the total number of iterations reaches `max_iterations` (if provided).
 ///
 /// Returns the number of edges that are still forbidden.
-fn local_search(graph: &AdjList, class_list: &mut Vec<Vec<usize>>) -> usize {
+fn local_search(
+    graph: &AdjList,
+    class_list: &mut [Vec<usize>],
+    no_improvement_factor: usize,
+    max_iterations: Option<usize>,
+    kempe: bool,
+) -> usize {
     let (mut forbidden_count, mut forbidden_set) = get_forbidden_vertices(graph, class_list);
-    let no_improvement_ceil = 2 * forbidden_count;
+    // Otherwise, a factor of 0 would give up before trying anything
+    let no_improvement_ceil = (no_improvement_factor * forbidden_count).max(1);
+    let max_iterations = max_iterations.unwrap_or(usize::MAX);
     let mut forbidden_vertices: Vec<usize> = forbidden_set.into_iter().collect();
     // We use this variable to control how many iterations we can go by without improvement
     let mut no_improvement = 0;
+    let mut iterations = 0;
+
+    while forbidden_count > 0 && no_improvement < no_improvement_ceil && iterations < max_iterations
+    {
+        iterations += 1;
 
-    while forbidden_count > 0 && no_improvement < no_improvement_ceil {
         // Randomly choose an illegal vertex (i.e., one that is colored with the same color as an adjacent vertex).
 
         // Since forbidden_count > 0 we can unwrap
-        let vertex = forbidden_vertices.choose(&mut rand::thread_rng()).unwrap();
-        let mut coloring = get_coloring_from_class_list(graph.num_vertices(), class_list);
-        let mut best_count = count_forbidden_per_vertex(graph, &coloring, *vertex);
-        let original_count = best_count;
-        let mut best_color = coloring[*vertex];
-        let original_color = best_color;
+        let vertex = forbidden_vertices.choose(&mut rng()).unwrap();
+        let coloring = get_coloring_from_class_list(graph.num_vertices(), class_list);
+        let original_count = count_forbidden_per_vertex(graph, &coloring, *vertex);
+        let original_color = coloring[*vertex];
 
         // Make all possible attempts to switch v to a different color to improve the current value of f(s).
-
-        // Colors are 1-indexed
-        for i in 1..class_list.len() + 1 {
-            coloring[*vertex] = i;
-            let new_count = count_forbidden_per_vertex(graph, &coloring, *vertex);
-            if new_count < best_count {
-                best_count = new_count;
-                best_color = i;
-            }
-        }
+        let (best_color, best_count) = best_recolor(graph, &coloring, *vertex, class_list.len());
 
         if best_count < original_count {
             no_improvement = 0;
@@ -297,6 +1038,31 @@ fn local_search(graph: &AdjList, class_list: &mut Vec<Vec<usize>>) -> usize {
 
             (forbidden_count, forbidden_set) = get_forbidden_vertices(graph, class_list);
             forbidden_vertices = forbidden_set.into_iter().collect();
+
+            invariants::check_class_list_conflicts(graph, class_list, forbidden_count);
+        } else if let Some((other, chain, _)) = kempe
+            .then(|| best_kempe_move(graph, &coloring, *vertex, class_list.len()))
+            .flatten()
+        {
+            no_improvement = 0;
+
+            // Swapping the classes of the chain's vertices
+            let in_chain: HashSet<&usize> = chain.iter().collect();
+            class_list[original_color - 1].retain(|x| !in_chain.contains(x));
+            class_list[other - 1].retain(|x| !in_chain.contains(x));
+            for x in chain.iter() {
+                let index = if coloring[*x] == original_color {
+                    other
+                } else {
+                    original_color
+                };
+                class_list[index - 1].push(*x);
+            }
+
+            (forbidden_count, forbidden_set) = get_forbidden_vertices(graph, class_list);
+            forbidden_vertices = forbidden_set.into_iter().collect();
+
+            invariants::check_class_list_conflicts(graph, class_list, forbidden_count);
         } else {
             no_improvement += 1;
         }
@@ -305,71 +1071,72 @@ fn local_search(graph: &AdjList, class_list: &mut Vec<Vec<usize>>) -> usize {
     forbidden_count
 }
 
-/// Turn a "Class List" into a traditional coloring. A class list assigns each index in a vector to
-/// a vector of vertices, which represent a given color.
-fn get_coloring_from_class_list(num_vertices: usize, class_list: &[Vec<usize>]) -> Vec<usize> {
-    let mut coloring: Vec<usize> = vec![0; num_vertices];
+/// Applies a bounded tabu search (TabuCol) for `class_list` according to `graph`, keeping the
+/// number of classes fixed.
+///
+/// Returns the number of edges that are still forbidden.
+fn tabu_search(graph: &AdjList, class_list: &mut [Vec<usize>], max_iterations: usize) -> usize {
+    let mut coloring = get_coloring_from_class_list(graph.num_vertices(), class_list);
+    let forbidden_count = tabucol(graph, &mut coloring, class_list.len(), max_iterations);
 
-    for (i, class) in class_list.iter().enumerate() {
-        for vertex in class {
-            assert_eq!(coloring[*vertex], 0);
+    for class in class_list.iter_mut() {
+        class.clear();
+    }
 
-            coloring[*vertex] = i + 1;
-        }
+    for (vertex, color) in coloring.iter().enumerate() {
+        class_list[color - 1].push(vertex);
     }
 
-    coloring
-}
+    invariants::check_class_list_conflicts(graph, class_list, forbidden_count);
 
-/// Counts the number of forbidden edges from `vertex` in `graph` according to `coloring`.
-fn count_forbidden_per_vertex(graph: &AdjList, coloring: &[usize], vertex: usize) -> usize {
-    graph.adj_list()[vertex]
-        .iter()
-        .filter(|x| coloring[**x] == coloring[vertex])
-        .count()
+    forbidden_count
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{algorithms::is_coloring_valid, input};
+    use crate::graph::bounds::greedy_coloring;
+    use crate::progress::Progress;
+    use crate::{
+        algorithms::{count_colors, is_coloring_valid},
+        testing::{self, arbitrary_coloring, arbitrary_graph, NUM_CASES},
+    };
+    use proptest::prelude::*;
+    use std::sync::Arc;
 
     #[test]
     fn test_get_n_largest_degree() {
-        if let Ok(Some(graph)) = input::read_graph_from_file("data/myc/myciel3.col") {
-            // Use a subset to filter,
-            // i.e., use an induced subgraph
-            let set_subset = vec![10, 3, 4, 5];
-            let largest_degrees = get_n_largest_degree(3, &graph, &set_subset, None);
+        let graph = testing::myciel3();
 
-            assert_eq!(largest_degrees, vec![3, 5, 4]);
+        // Use a subset to filter,
+        // i.e., use an induced subgraph
+        let set_subset = vec![10, 3, 4, 5];
+        let largest_degrees = get_n_largest_degree(3, &graph, &set_subset, None);
 
-            // "Don't" use the subset to filter
-            // Since the parameter isn't optional, this effect is emulated by setting the subset to
-            // all vertices
-            let set_entire_graph: Vec<usize> = (0..graph.num_vertices()).collect();
-            let largest_degrees = get_n_largest_degree(5, &graph, &set_entire_graph, None);
+        assert_eq!(largest_degrees, vec![3, 5, 4]);
 
-            assert_eq!(largest_degrees, vec![10, 0, 1, 2, 3]);
+        // "Don't" use the subset to filter
+        // Since the parameter isn't optional, this effect is emulated by setting the subset to
+        // all vertices
+        let set_entire_graph: Vec<usize> = (0..graph.num_vertices()).collect();
+        let largest_degrees = get_n_largest_degree(5, &graph, &set_entire_graph, None);
 
-            // We don't care if the number of elements we're actually taking is smaller than the
-            // number we requested, due to a limitation in the subset length
-            let n_larger_than_subset = set_subset.len() + 1;
-            let largest_degrees =
-                get_n_largest_degree(n_larger_than_subset, &graph, &set_subset, None);
+        assert_eq!(largest_degrees, vec![10, 0, 1, 2, 3]);
 
-            assert_eq!(largest_degrees.len(), set_subset.len());
+        // We don't care if the number of elements we're actually taking is smaller than the
+        // number we requested, due to a limitation in the subset length
+        let n_larger_than_subset = set_subset.len() + 1;
+        let largest_degrees = get_n_largest_degree(n_larger_than_subset, &graph, &set_subset, None);
 
-            // We also don't care if we request too many elements overall
-            // i.e., more elements than the number of vertices in the graph
-            let too_many_elements = set_entire_graph.len() + 1;
-            let largest_degrees =
-                get_n_largest_degree(too_many_elements, &graph, &set_entire_graph, None);
+        assert_eq!(largest_degrees.len(), set_subset.len());
 
-            assert_eq!(largest_degrees.len(), set_entire_graph.len());
-        } else {
-            panic!("The file containing the test graph is missing")
-        }
+        // We also don't care if we request too many elements overall
+        // i.e., more elements than the number of vertices in the graph
+        let too_many_elements = set_entire_graph.len() + 1;
+        let largest_degrees =
+            get_n_largest_degree(too_many_elements, &graph, &set_entire_graph, None);
+
+        assert_eq!(largest_degrees.len(), set_entire_graph.len());
 
         let mut graph = AdjList::complete(4);
 
@@ -394,26 +1161,305 @@ mod tests {
     }
 
     #[test]
-    fn test_count_remaining_edges() {
-        if let Ok(Some(graph)) = input::read_graph_from_file("data/myc/myciel3.col") {
-            let list = vec![0, 1, 2];
-            let num_edges = count_remaining_edges(&graph, &list);
+    fn test_get_largest_degree_within_alpha() {
+        // A star centered at 0 plus the edge 1---2:
+        // degrees are 3 (vertex 0), 2 (vertices 1 and 2) and 1 (vertex 3)
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(0, 3);
+        graph.add_edge(1, 2);
+        let subset = vec![0, 1, 2, 3];
 
-            assert_eq!(num_edges, 2);
-        } else {
-            panic!("The file containing the test graph is missing")
+        // Purely greedy
+        let candidates = get_largest_degree_within_alpha(0.0, &graph, &subset, None);
+        assert_eq!(candidates, vec![0]);
+
+        // Threshold of 3 - 0.5 * (3 - 1) = 2
+        let candidates = get_largest_degree_within_alpha(0.5, &graph, &subset, None);
+        assert_eq!(candidates, vec![0, 1, 2]);
+
+        // Purely random
+        let candidates = get_largest_degree_within_alpha(1.0, &graph, &subset, None);
+        assert_eq!(candidates, subset);
+    }
+
+    #[test]
+    fn test_dsatur_construction() {
+        let graph = testing::myciel4();
+
+        for candidate_list in [CandidateList::Cardinality(3), CandidateList::Threshold(0.5)] {
+            let (num_classes, class_list) = dsatur_construction(&graph, candidate_list, None, None);
+            let coloring = get_coloring_from_class_list(graph.num_vertices(), &class_list);
+
+            assert!(is_coloring_valid(&graph, &coloring));
+            assert_eq!(num_classes, count_colors(&coloring));
         }
+
+        // A purely greedy DSATUR is exact for bipartite graphs
+        let mut graph = AdjList::new(6);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 5);
+        graph.add_edge(5, 0);
+
+        let (num_classes, _) =
+            dsatur_construction(&graph, CandidateList::Cardinality(1), None, None);
+
+        assert_eq!(num_classes, 2);
+    }
+
+    #[test]
+    fn test_count_remaining_edges() {
+        let graph = testing::myciel3();
+
+        let list = vec![0, 1, 2];
+        let num_edges = count_remaining_edges(&graph, &list);
+
+        assert_eq!(num_edges, 2);
+
+        // The edges among the vertices themselves, not their positions in the list
+        assert_eq!(count_remaining_edges(&graph, &[5, 0, 2]), 0);
+        assert_eq!(count_remaining_edges(&graph, &[10, 5, 6, 7, 8, 9]), 5);
     }
 
     #[test]
     fn test_grasp_wrapper() {
         // Asserts GRASP provides a solution
-        if let Ok(Some(graph)) = input::read_graph_from_file("data/myc/myciel5.col") {
-            let (_, coloring) = grasp_wrapper(&graph, 10, 5, 5);
+        let graph = testing::myciel5();
 
-            assert!(is_coloring_valid(&graph, &coloring));
-        } else {
-            panic!("The file containing the test graph is missing")
+        let params = GraspParams {
+            grasp_iterations: 10,
+            color_iterations: 5,
+            candidate_list: CandidateList::Cardinality(5),
+            ..Default::default()
+        };
+        let (_, coloring) = grasp_wrapper(&graph, &params);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        let params = GraspParams {
+            candidate_list: CandidateList::Threshold(0.3),
+            ..params
+        };
+        let (_, coloring) = grasp_wrapper(&graph, &params);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        let params = GraspParams {
+            memory: Some(MemoryBias::Intensify),
+            ..params
+        };
+        let (_, coloring) = grasp_wrapper(&graph, &params);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        let params = GraspParams {
+            reactive: true,
+            ..params
+        };
+        let (_, coloring) = grasp_wrapper(&graph, &params);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        let params = GraspParams {
+            improvement: Improvement::Tabu,
+            tabu_iterations: 1000,
+            ..params
+        };
+        let (_, coloring) = grasp_wrapper(&graph, &params);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        let params = GraspParams {
+            construction: Construction::Dsatur,
+            ..params
+        };
+        let (_, coloring) = grasp_wrapper(&graph, &params);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        let params = GraspParams {
+            construction: Construction::IndependentSet,
+            improvement: Improvement::Local,
+            no_improvement_factor: 4,
+            local_search_iterations: Some(100),
+            merge_retries: 2,
+            ..params
+        };
+        let (_, coloring) = grasp_wrapper(&graph, &params);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        let params = GraspParams {
+            perturbation_restarts: 3,
+            ..params
+        };
+        let (_, coloring) = grasp_wrapper(&graph, &params);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        // Out of time right away, each iteration still returns its constructed coloring
+        let params = GraspParams {
+            iteration_time: Some(Duration::ZERO),
+            ..params
+        };
+        let (_, coloring) = grasp_wrapper(&graph, &params);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+    }
+
+    #[test]
+    fn test_grasp_max_class_size() {
+        // Without the cap, the 7 vertices of C7 fit in 3 classes of up to 3 vertices
+        let mut graph = AdjList::new(7);
+        for v in 0..7 {
+            graph.add_edge(v, (v + 1) % 7);
+        }
+
+        for construction in [Construction::IndependentSet, Construction::Dsatur] {
+            for improvement in [Improvement::Local, Improvement::Tabu] {
+                let params = GraspParams {
+                    grasp_iterations: 5,
+                    color_iterations: 5,
+                    construction,
+                    improvement,
+                    merge_retries: 2,
+                    perturbation_restarts: 2,
+                    max_class_size: Some(2),
+                    ..Default::default()
+                };
+                let (num_colors, coloring) = grasp_wrapper(&graph, &params);
+
+                assert!(is_coloring_valid(&graph, &coloring));
+                assert_eq!(num_colors, 4);
+                assert!((1..=4).all(|color| coloring.iter().filter(|c| **c == color).count() <= 2));
+            }
+        }
+    }
+
+    #[test]
+    fn test_seeded_grasp() {
+        // A seed gives the same colorings however the iterations are scheduled
+        let graph = testing::myciel4();
+
+        let params = GraspParams {
+            grasp_iterations: 8,
+            color_iterations: 5,
+            seed: Some(5),
+            ..Default::default()
+        };
+
+        assert_eq!(grasp(&graph, &params, 8), grasp(&graph, &params, 8));
+    }
+
+    #[test]
+    fn test_grasp_cancelled_before_start() {
+        // The first iteration still runs, so there's a coloring to return
+        let graph = testing::myciel5();
+        let progress = Arc::new(Progress::for_job(None));
+        progress.request_stop();
+
+        let solutions = progress::run_job(&progress, || grasp(&graph, &GraspParams::default(), 5));
+
+        assert_eq!(solutions.len(), 1);
+        assert!(is_coloring_valid(&graph, &solutions[0].1));
+
+        let (_, coloring) =
+            progress::run_job(&progress, || grasp_wrapper(&graph, &GraspParams::default()));
+
+        assert!(is_coloring_valid(&graph, &coloring));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(NUM_CASES))]
+
+        #[test]
+        fn test_perturb(graph in arbitrary_graph()) {
+            let ordering: Vec<usize> = (0..graph.num_vertices()).collect();
+            let class_list = get_class_list_from_coloring(&greedy_coloring(&graph, &ordering));
+
+            prop_assume!(class_list.len() >= 2);
+
+            let (num_classes, perturbed) = perturb(&graph, &class_list);
+            let coloring = get_coloring_from_class_list(graph.num_vertices(), &perturbed);
+
+            prop_assert!(is_coloring_valid(&graph, &coloring));
+            prop_assert_eq!(count_colors(&coloring), num_classes);
+        }
+    }
+
+    #[test]
+    fn test_frequency_memory() {
+        let class_list = vec![vec![0, 1, 2], vec![3], vec![4, 5], vec![]];
+
+        let intensify = FrequencyMemory::new(6, MemoryBias::Intensify);
+        // Average class size is 2, so only the first and the third classes are large
+        intensify.record(&class_list, 3);
+        intensify.record(&class_list[..1], 1);
+
+        assert_eq!(intensify.weights(&[0, 3, 4]), vec![3, 1, 2]);
+
+        let diversify = FrequencyMemory::new(6, MemoryBias::Diversify);
+        diversify.record(&class_list, 3);
+        diversify.record(&class_list[..1], 1);
+
+        assert_eq!(diversify.weights(&[0, 3, 4]), vec![1, 3, 2]);
+
+        assert!([0, 3, 4].contains(&intensify.choose(&[0, 3, 4]).unwrap()));
+        assert_eq!(intensify.choose(&[]), None);
+    }
+
+    #[test]
+    fn test_reactive_rcl() {
+        let reactive = ReactiveRcl::new(CandidateList::Cardinality(3));
+
+        // Every value starts with the same weight
+        assert!(reactive.weights().iter().all(|weight| *weight == 1.0));
+
+        reactive.record(0, 10);
+        reactive.record(1, 5);
+        reactive.record(1, 7);
+        let weights = reactive.weights();
+
+        // Averaging 6 colors against the best 5, and 10 against it
+        assert!((weights[1] - (5.0_f64 / 6.0).powi(REACTIVE_DELTA)).abs() < 1e-12);
+        assert!((weights[0] - 0.5_f64.powi(REACTIVE_DELTA)).abs() < 1e-12);
+        assert_eq!(weights[2], 1.0);
+
+        let (index, candidate_list) = reactive.choose();
+        assert!(
+            matches!(candidate_list, CandidateList::Cardinality(size) if size == REACTIVE_SIZES[index])
+        );
+
+        let reactive = ReactiveRcl::new(CandidateList::Threshold(0.5));
+        assert!(matches!(reactive.choose().1, CandidateList::Threshold(_)));
+    }
+
+    #[test]
+    fn test_grasp_deduplicates_solutions() {
+        // The complete graph has a single optimal partition, no matter the labels
+        let graph = AdjList::complete(4);
+        let params = GraspParams {
+            grasp_iterations: 10,
+            ..Default::default()
+        };
+
+        let solutions = grasp(&graph, &params, 5);
+
+        assert_eq!(solutions, vec![(4, vec![1, 2, 3, 4])]);
+
+        // The best distinct colorings come first
+        let graph = testing::myciel4();
+        let solutions = grasp(&graph, &params, 5);
+
+        assert!(!solutions.is_empty() && solutions.len() <= 5);
+        assert!(solutions.windows(2).all(|pair| pair[0] < pair[1]));
+        for (num_colors, coloring) in solutions.iter() {
+            assert!(is_coloring_valid(&graph, coloring));
+            assert_eq!(count_colors(coloring), *num_colors);
         }
     }
 
@@ -426,30 +1472,126 @@ mod tests {
         graph.add_edge(2, 4);
         graph.add_edge(2, 5);
 
+        for (improvement, merge) in [
+            (Improvement::Local, Merge::TwoSmallest),
+            (Improvement::Tabu, Merge::TwoSmallest),
+            (Improvement::LocalThenTabu, Merge::TwoSmallest),
+            (Improvement::Local, Merge::SmallestIntoBestFit),
+            (Improvement::Local, Merge::DissolveRandom),
+            (Improvement::Local, Merge::FewestConflicts),
+        ] {
+            let params = GraspParams {
+                merge,
+                improvement,
+                tabu_iterations: 100,
+                ..Default::default()
+            };
+            let mut num_classes = 4;
+            let mut class_list = vec![vec![1], vec![2], vec![4, 5], vec![0, 3]];
+
+            improve_phase(
+                &graph,
+                &params,
+                &mut num_classes,
+                &mut class_list,
+                &Incumbent::new(None),
+                None,
+            );
+
+            assert!(num_classes <= 4);
+
+            // Since the algorithm is randomized, we can't compare to an expected result
+            // But it should still be valid nonetheless
+            let coloring = get_coloring_from_class_list(6, &class_list);
+
+            assert!(is_coloring_valid(&graph, &coloring));
+        }
+
+        // Without any merges allowed, the coloring stays put
+        let params = GraspParams {
+            improvement_merges: Some(0),
+            ..Default::default()
+        };
+        let mut num_classes = 4;
+        let mut class_list = vec![vec![1], vec![2], vec![4, 5], vec![0, 3]];
+
+        improve_phase(
+            &graph,
+            &params,
+            &mut num_classes,
+            &mut class_list,
+            &Incumbent::new(None),
+            None,
+        );
+
+        assert_eq!(num_classes, 4);
+
+        // Another search already found an optimal coloring, so there's nothing left to improve
+        let incumbent = Incumbent::new(Some(2));
+        incumbent.offer(2);
         let mut num_classes = 4;
         let mut class_list = vec![vec![1], vec![2], vec![4, 5], vec![0, 3]];
 
-        improve_phase(&graph, &mut num_classes, &mut class_list);
+        improve_phase(
+            &graph,
+            &GraspParams::default(),
+            &mut num_classes,
+            &mut class_list,
+            &incumbent,
+            None,
+        );
+
+        assert_eq!(num_classes, 4);
+    }
 
-        assert!(num_classes <= 4);
+    #[test]
+    fn test_tabu_search() {
+        // Basically a linked list colored as 1---2---2---3
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        let mut color_classes = vec![vec![0], vec![1, 2], vec![3]];
+
+        let num_forbidden = tabu_search(&graph, &mut color_classes, 100);
+
+        assert_eq!(num_forbidden, 0);
 
-        // Since the algorithm is randomized, we can't compare to an expected result
-        // But it should still be valid nonetheless
-        let coloring = get_coloring_from_class_list(6, &class_list);
+        let coloring = get_coloring_from_class_list(4, &color_classes);
 
         assert!(is_coloring_valid(&graph, &coloring));
     }
 
     #[test]
-    fn test_get_forbidden_vertices() {
-        // The complete graph
-        let graph = AdjList::complete(5);
-        let color_classes = vec![vec![0], vec![1], vec![2, 3, 4]];
+    fn test_merge_classes() {
+        // A path 0---1---2---3 plus the isolated vertex 4
+        let mut graph = AdjList::new(5);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        let class_list = vec![vec![0, 2], vec![1], vec![3], vec![4], vec![]];
+
+        let merged = merge_classes(&graph, &class_list, Merge::TwoSmallest);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].len(), 2);
 
-        let (count, forbidden) = get_forbidden_vertices(&graph, &color_classes);
+        // Classes [1] and [3] are the only pair of size 1 without an edge between them
+        let merged = merge_classes(&graph, &class_list, Merge::FewestConflicts);
+        assert_eq!(merged.len(), 3);
+        assert!(merged[0] == vec![1, 3] || merged[0] == vec![1, 4] || merged[0] == vec![3, 4]);
+
+        for merge in [Merge::SmallestIntoBestFit, Merge::DissolveRandom] {
+            let merged = merge_classes(&graph, &class_list, merge);
+            let mut vertices: Vec<usize> = merged.concat();
+            vertices.sort();
+
+            assert_eq!(merged.len(), 3);
+            assert_eq!(vertices, vec![0, 1, 2, 3, 4]);
+        }
 
-        assert_eq!(forbidden, HashSet::from([2, 3, 4]));
-        assert_eq!(count, 3)
+        // A single class can't be merged
+        let merged = merge_classes(&graph, &[vec![0, 1, 2, 3, 4]], Merge::TwoSmallest);
+        assert_eq!(merged.len(), 1);
     }
 
     #[test]
@@ -461,26 +1603,79 @@ mod tests {
         graph.add_edge(2, 3);
         let mut color_classes = vec![vec![0], vec![1, 2], vec![3]];
 
-        let num_forbidden = local_search(&graph, &mut color_classes);
+        let num_forbidden = local_search(&graph, &mut color_classes, 2, None, false);
 
         assert_eq!(num_forbidden, 0);
+
+        // Without any iterations, nothing can be fixed
+        let mut color_classes = vec![vec![0], vec![1, 2], vec![3]];
+        let num_forbidden = local_search(&graph, &mut color_classes, 2, Some(0), false);
+
+        assert_eq!(num_forbidden, 1);
+
+        // The tree 1 - 0 - 2 plus 1 - 3, where 0 and 1 conflict: recoloring either one just moves
+        // the conflict, but swapping the chain of 0 and 2 removes it
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 2);
+        graph.add_edge(1, 3);
+
+        let mut color_classes = vec![vec![0, 1], vec![2, 3]];
+        assert_eq!(local_search(&graph, &mut color_classes, 2, None, false), 1);
+
+        let mut color_classes = vec![vec![0, 1], vec![2, 3]];
+        assert_eq!(local_search(&graph, &mut color_classes, 2, None, true), 0);
     }
 
-    #[test]
-    fn test_get_coloring_from_class_list() {
-        let class_list = vec![vec![0], vec![1, 2], vec![3]];
-        let coloring = get_coloring_from_class_list(4, &class_list);
+    /// Builds the class list of `coloring`, with as many classes as the largest color.
+    fn get_class_list_from_coloring(coloring: &[usize]) -> Vec<Vec<usize>> {
+        let mut class_list = vec![Vec::new(); coloring.iter().max().copied().unwrap_or(0)];
 
-        assert_eq!(coloring, [1, 2, 2, 3])
+        for (vertex, color) in coloring.iter().enumerate() {
+            class_list[color - 1].push(vertex);
+        }
+
+        class_list
     }
 
-    #[test]
-    fn test_count_forbidden_per_vertex() {
-        let graph = AdjList::complete(5);
-        let coloring = [1, 1, 1, 1, 1];
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(NUM_CASES))]
 
-        let num_forbidden = count_forbidden_per_vertex(&graph, &coloring, 1);
+        #[test]
+        fn test_class_list_round_trip(
+            coloring in (1..=30usize).prop_flat_map(arbitrary_coloring)
+        ) {
+            let class_list = get_class_list_from_coloring(&coloring);
 
-        assert_eq!(num_forbidden, 4);
+            prop_assert_eq!(
+                get_coloring_from_class_list(coloring.len(), &class_list),
+                coloring
+            );
+        }
+
+        #[test]
+        fn test_improvements_never_increase_conflicts(
+            (graph, coloring) in arbitrary_graph().prop_flat_map(|graph| {
+                let num_vertices = graph.num_vertices();
+                (Just(graph), arbitrary_coloring(num_vertices))
+            })
+        ) {
+            let (initial_conflicts, _) =
+                get_forbidden_vertices(&graph, &get_class_list_from_coloring(&coloring));
+
+            for kempe in [false, true] {
+                let mut class_list = get_class_list_from_coloring(&coloring);
+                let conflicts = local_search(&graph, &mut class_list, 2, None, kempe);
+
+                prop_assert!(conflicts <= initial_conflicts);
+                prop_assert_eq!(get_forbidden_vertices(&graph, &class_list).0, conflicts);
+            }
+
+            let mut class_list = get_class_list_from_coloring(&coloring);
+            let conflicts = tabu_search(&graph, &mut class_list, 100);
+
+            prop_assert!(conflicts <= initial_conflicts);
+            prop_assert_eq!(get_forbidden_vertices(&graph, &class_list).0, conflicts);
+        }
     }
 }
@@ -1,41 +1,397 @@
-use super::{count_colors, grasp::grasp, is_coloring_valid, Solution};
+use super::{
+    count_colors, count_conflicts,
+    diverse::partition_distance,
+    grasp::{grasp, GraspParams},
+    match_colors, relabel_colors,
+    tabucol::tabucol,
+    Solution,
+};
 use crate::graph::adj_list::AdjList;
+use crate::progress::{self, Phase};
+use std::cmp::Reverse;
+use std::collections::HashMap;
 
-pub fn grasp_path_relinking(graph: &AdjList, num_solutions_grasp: usize) -> Solution {
-    let mut solutions = grasp(graph, 25, 25, 3, num_solutions_grasp).into_sorted_vec();
-    solutions.reverse();
-    let mut best_solution = solutions.pop().unwrap().clone();
+/// Which end of a relinking path is the starting solution.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Direction {
+    /// Walk from each GRASP solution towards the best solution.
+    #[default]
+    Forward,
+    /// Walk from the best solution towards each GRASP solution.
+    Backward,
+}
 
-    while let Some(solution) = solutions.pop() {
-        // Always follow the current best coloring, instead of using the starting one
-        let original_best_coloring = best_solution.1.clone();
-        let mut difference = simmetric_difference(&original_best_coloring, &solution.1);
-        let mut new_coloring = solution.1.clone();
+#[derive(Debug)]
+pub struct PathRelinkingParams {
+    /// Number of GRASP solutions used in the relinking.
+    pub num_solutions: usize,
+    pub direction: Direction,
+    /// Maximum iterations of the tabu search applied to promising intermediate colorings
+    /// (0 disables it).
+    pub local_search_iterations: usize,
+    /// Fraction of the symmetric difference that is actually walked (1.0 walks the whole path).
+    pub truncation: f64,
+    /// Colorings closer than this (see [`partition_distance`]) to one already in the elite pool
+    /// aren't admitted to it, unless they're better than all of its colorings.
+    pub min_distance: usize,
+    /// Whether to relink every pair of an elite pool until it stops changing (see
+    /// [`evolutionary_path_relinking`]), instead of the best solution with each of the others.
+    pub evolutionary: bool,
+}
+
+impl Default for PathRelinkingParams {
+    fn default() -> Self {
+        PathRelinkingParams {
+            num_solutions: 10,
+            direction: Direction::Forward,
+            local_search_iterations: 0,
+            truncation: 1.0,
+            min_distance: 1,
+            evolutionary: false,
+        }
+    }
+}
+
+/// Elite colorings relinked by GRASP+PR: at most `capacity` of them, sorted from best to worst, and
+/// kept at least `min_distance` apart from each other (see [`ElitePool::admit`]).
+#[derive(Debug)]
+pub struct ElitePool {
+    capacity: usize,
+    min_distance: usize,
+    solutions: Vec<Solution>,
+}
+
+impl ElitePool {
+    pub fn new(capacity: usize, min_distance: usize) -> Self {
+        ElitePool {
+            capacity: capacity.max(1),
+            min_distance,
+            solutions: Vec::with_capacity(capacity),
+        }
+    }
 
-        while let Some(vertex) = difference.pop() {
-            new_coloring[vertex] = original_best_coloring[vertex];
+    /// The colorings of the pool, sorted from best to worst.
+    pub fn solutions(&self) -> &[Solution] {
+        &self.solutions
+    }
+
+    /// Offers `solution` to the pool, returning whether it was admitted.
+    ///
+    /// A coloring better than all of the pool is always admitted. Otherwise, it must be at least
+    /// `min_distance` away from each coloring of the pool, and, if the pool is full, better than its
+    /// worst coloring. When the pool is full, the coloring replaces the one most similar to it among
+    /// those that aren't better (the worst one, on ties).
+    pub fn admit(&mut self, (num_colors, mut coloring): Solution) -> bool {
+        relabel_colors(&mut coloring);
 
-            let num_colors = count_colors(&new_coloring);
+        let distances: Vec<usize> = self
+            .solutions
+            .iter()
+            .map(|(_, other)| partition_distance(other, &coloring))
+            .collect();
+        let is_best = self
+            .solutions
+            .first()
+            .is_none_or(|(best, _)| num_colors < *best);
 
-            // Avoid having to check if the coloring is valid (since it's more expensive)
-            // if the number of colors hasn't improved
-            if num_colors < best_solution.0 {
-                continue;
+        if !is_best {
+            let is_worse = self.solutions.len() >= self.capacity
+                && self
+                    .solutions
+                    .last()
+                    .is_some_and(|(worst, _)| num_colors >= *worst);
+
+            if is_worse
+                || distances
+                    .iter()
+                    .any(|distance| *distance < self.min_distance)
+            {
+                return false;
             }
+        }
+
+        if self.solutions.len() >= self.capacity {
+            let replaced = (0..self.solutions.len())
+                .filter(|index| self.solutions[*index].0 >= num_colors)
+                .min_by_key(|index| (distances[*index], Reverse(*index)))
+                .unwrap();
+
+            self.solutions.remove(replaced);
+        }
+
+        let position = self
+            .solutions
+            .partition_point(|(other, _)| *other <= num_colors);
+        self.solutions.insert(position, (num_colors, coloring));
+
+        true
+    }
+}
+
+/// Relinks the best GRASP colorings: the GRASP iterations (at least `num_solutions` of them) fill an
+/// [`ElitePool`], whose colorings are relinked with [`path_relinking`] or, if `evolutionary`,
+/// [`evolutionary_path_relinking`].
+pub fn grasp_path_relinking(
+    graph: &AdjList,
+    grasp_params: &GraspParams,
+    params: &PathRelinkingParams,
+) -> Solution {
+    let num_candidates = params
+        .num_solutions
+        .max(grasp_params.grasp_iterations.max(0) as usize);
+    let mut pool = ElitePool::new(params.num_solutions, params.min_distance);
+
+    for solution in grasp(graph, grasp_params, num_candidates) {
+        pool.admit(solution);
+    }
+
+    let solutions = pool.solutions;
+
+    match params.evolutionary {
+        true => evolutionary_path_relinking(graph, solutions, grasp_params.lower_bound, params),
+        false => path_relinking(graph, solutions, grasp_params.lower_bound, params),
+    }
+}
+
+/// Relinks the best of `solutions` (sorted from best to worst, and not empty) with each of the
+/// others, in order, stopping once `lower_bound` (if any) is reached.
+pub fn path_relinking(
+    graph: &AdjList,
+    solutions: Vec<Solution>,
+    lower_bound: Option<usize>,
+    params: &PathRelinkingParams,
+) -> Solution {
+    let mut solutions = solutions.into_iter();
+    let mut best_solution = solutions.next().unwrap();
+
+    for solution in solutions {
+        if lower_bound.is_some_and(|lower_bound| best_solution.0 <= lower_bound)
+            || progress::stop_requested()
+        {
+            break;
+        }
+
+        // Always follow the current best coloring, instead of using the starting one
+        let (initial_coloring, guiding_coloring) = match params.direction {
+            Direction::Forward => (solution.1, best_solution.1.clone()),
+            Direction::Backward => (best_solution.1.clone(), solution.1),
+        };
+        // Otherwise, the same classes with different labels would be seen as a difference
+        let initial_coloring = match_colors(&guiding_coloring, &initial_coloring);
+
+        relink(
+            graph,
+            initial_coloring,
+            &guiding_coloring,
+            params,
+            &mut best_solution,
+        );
+
+        progress::record_iteration();
+        progress::record_colors(best_solution.0);
+    }
+
+    best_solution
+}
+
+/// Evolutionary path relinking (Resende and Werneck, 2004): `solutions` (sorted from best to worst,
+/// and not empty) fill an [`ElitePool`] of at most `num_solutions` colorings, and each generation
+/// relinks every pair of the pool, in `direction` (forward walks from the worse coloring of the
+/// pair towards the better one). The best coloring found along each path that beats the worse
+/// one is offered to the pool, and the generations go on until none is admitted, or `lower_bound`
+/// (if any) is reached.
+///
+/// Returns the best coloring of the pool.
+pub fn evolutionary_path_relinking(
+    graph: &AdjList,
+    solutions: Vec<Solution>,
+    lower_bound: Option<usize>,
+    params: &PathRelinkingParams,
+) -> Solution {
+    let mut pool = ElitePool::new(params.num_solutions, params.min_distance);
+
+    for solution in solutions {
+        pool.admit(solution);
+    }
+
+    loop {
+        let elite = pool.solutions();
+
+        if lower_bound.is_some_and(|lower_bound| elite[0].0 <= lower_bound)
+            || progress::stop_requested()
+        {
+            break;
+        }
+
+        let mut offspring = Vec::new();
 
-            if !is_coloring_valid(graph, &new_coloring) {
-                continue;
+        for i in 0..elite.len() {
+            for j in i + 1..elite.len() {
+                let (better, worse) = (&elite[i], &elite[j]);
+                let (initial_coloring, guiding_coloring) = match params.direction {
+                    Direction::Forward => (&worse.1, &better.1),
+                    Direction::Backward => (&better.1, &worse.1),
+                };
+                let initial_coloring = match_colors(guiding_coloring, initial_coloring);
+                let mut found = worse.clone();
+
+                relink(
+                    graph,
+                    initial_coloring,
+                    guiding_coloring,
+                    params,
+                    &mut found,
+                );
+
+                progress::record_iteration();
+
+                if found.0 < worse.0 {
+                    offspring.push(found);
+                }
             }
+        }
+
+        let mut admitted = false;
+        for solution in offspring {
+            admitted |= pool.admit(solution);
+        }
+
+        progress::record_colors(pool.solutions()[0].0);
+
+        if !admitted {
+            break;
+        }
+    }
+
+    pool.solutions.swap_remove(0)
+}
+
+/// Walks from `initial_coloring` to `guiding_coloring`, one vertex at a time, updating
+/// `best_solution` whenever a valid coloring with fewer colors is found along the way.
+///
+/// At each step, every vertex that still differs from `guiding_coloring` is evaluated, and the one
+/// whose move leads to the fewest conflicts (then, the fewest colors) is moved.
+///
+/// Intermediate colorings that have conflicts but use fewer colors than `best_solution` are
+/// promising: a tabu search of at most `local_search_iterations` tries to fix them (without
+/// affecting the walk itself).
+///
+/// Only the first `truncation` fraction of the steps is taken, since most of the gains happen close
+/// to the initial coloring.
+fn relink(
+    graph: &AdjList,
+    initial_coloring: Vec<usize>,
+    guiding_coloring: &[usize],
+    params: &PathRelinkingParams,
+    best_solution: &mut Solution,
+) {
+    let mut difference = simmetric_difference(guiding_coloring, &initial_coloring);
+    let num_steps = (params.truncation * difference.len() as f64).ceil() as usize;
+    let truncated = num_steps < difference.len();
+    let mut new_coloring = initial_coloring;
+    let mut conflicts = count_conflicts(graph, &new_coloring);
+    let mut usage: HashMap<usize, usize> = HashMap::new();
+
+    for color in new_coloring.iter() {
+        *usage.entry(*color).or_default() += 1;
+    }
+
+    for _ in 0..num_steps {
+        let (index, (new_conflicts, num_colors)) = difference
+            .iter()
+            .enumerate()
+            .map(|(index, vertex)| {
+                let score = evaluate_move(
+                    graph,
+                    &new_coloring,
+                    &usage,
+                    conflicts,
+                    *vertex,
+                    guiding_coloring[*vertex],
+                );
+                (index, score)
+            })
+            .min_by_key(|(_, score)| *score)
+            .unwrap();
 
+        let vertex = difference.swap_remove(index);
+        let previous_color = new_coloring[vertex];
+
+        new_coloring[vertex] = guiding_coloring[vertex];
+        conflicts = new_conflicts;
+
+        let previous_usage = usage.get_mut(&previous_color).unwrap();
+        *previous_usage -= 1;
+        if *previous_usage == 0 {
+            usage.remove(&previous_color);
+        }
+        *usage.entry(new_coloring[vertex]).or_default() += 1;
+
+        debug_assert_eq!(num_colors, usage.len());
+
+        if conflicts == 0 && num_colors < best_solution.0 {
             best_solution.0 = num_colors;
             best_solution.1 = new_coloring.clone();
+
+            progress::record_colors_from(Phase::PathRelinking, num_colors);
+        } else if params.local_search_iterations > 0 && num_colors < best_solution.0 {
+            let mut candidate = new_coloring.clone();
+
+            relabel_colors(&mut candidate);
+
+            if tabucol(
+                graph,
+                &mut candidate,
+                num_colors,
+                params.local_search_iterations,
+            ) == 0
+            {
+                best_solution.0 = count_colors(&candidate);
+                best_solution.1 = candidate;
+
+                progress::record_colors_from(Phase::PathRelinking, best_solution.0);
+            }
         }
+    }
 
-        // At the end we should have turned `new_coloring` into the `original_best_coloring`
-        assert_eq!(new_coloring, original_best_coloring);
+    // At the end we should have turned `new_coloring` into the `guiding_coloring`
+    if !truncated {
+        assert_eq!(new_coloring, guiding_coloring);
     }
+}
 
-    best_solution
+/// Evaluates recoloring `vertex` with `color` in `coloring`, which has `conflicts` conflicting
+/// edges and uses the colors in `usage` (mapped to their number of vertices).
+///
+/// Returns the number of conflicting edges and the number of colors after the move.
+fn evaluate_move(
+    graph: &AdjList,
+    coloring: &[usize],
+    usage: &HashMap<usize, usize>,
+    conflicts: usize,
+    vertex: usize,
+    color: usize,
+) -> (usize, usize) {
+    let previous_color = coloring[vertex];
+    let neighbors = &graph.adj_list()[vertex];
+    let lost = neighbors
+        .iter()
+        .filter(|neighbor| coloring[**neighbor] == previous_color)
+        .count();
+    let gained = neighbors
+        .iter()
+        .filter(|neighbor| coloring[**neighbor] == color)
+        .count();
+
+    let mut num_colors = usage.len();
+    if usage.get(&previous_color) == Some(&1) {
+        num_colors -= 1;
+    }
+    if !usage.contains_key(&color) {
+        num_colors += 1;
+    }
+
+    (conflicts + gained - lost, num_colors)
 }
 
 /// Calculates the indexes where `lhs` and `rhs` differ, given that they have the same length.
@@ -59,7 +415,7 @@ fn simmetric_difference(lhs: &[usize], rhs: &[usize]) -> Vec<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{algorithms::is_coloring_valid, input};
+    use crate::{algorithms::is_coloring_valid, testing};
 
     #[test]
     fn test_simmetric_difference() {
@@ -69,15 +425,212 @@ mod tests {
         assert_eq!(simmetric_difference(&lhs, &rhs), vec![1, 2]);
     }
 
+    #[test]
+    fn test_evaluate_move() {
+        let graph = AdjList::complete(3);
+        let coloring = vec![1, 1, 2];
+        let usage = HashMap::from([(1, 2), (2, 1)]);
+
+        // Fixes the only conflict, but introduces a new color
+        assert_eq!(evaluate_move(&graph, &coloring, &usage, 1, 0, 3), (0, 3));
+        // Trades a conflict for another, removing color 2
+        assert_eq!(evaluate_move(&graph, &coloring, &usage, 1, 2, 1), (3, 1));
+    }
+
+    #[test]
+    fn test_relink() {
+        // A path 0---1---2, colored with 3 colors and with 2 colors
+        let mut graph = AdjList::new(3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+
+        let mut best_solution = (3, vec![1, 2, 3]);
+
+        relink(
+            &graph,
+            vec![1, 2, 3],
+            &[2, 1, 2],
+            &PathRelinkingParams::default(),
+            &mut best_solution,
+        );
+
+        // The best intermediate coloring is [1, 2, 1] (or the guiding coloring itself)
+        assert_eq!(best_solution.0, 2);
+        assert!(is_coloring_valid(&graph, &best_solution.1));
+    }
+
+    #[test]
+    fn test_truncated_relink() {
+        // A path 0---1---2---3---4, walking from 5 colors towards 2
+        let mut graph = AdjList::new(5);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+
+        let params = PathRelinkingParams {
+            truncation: 0.0,
+            ..Default::default()
+        };
+        let mut best_solution = (5, vec![1, 2, 3, 4, 5]);
+
+        relink(
+            &graph,
+            vec![1, 2, 3, 4, 5],
+            &[1, 2, 1, 2, 1],
+            &params,
+            &mut best_solution,
+        );
+
+        // No steps were taken
+        assert_eq!(best_solution.0, 5);
+
+        let params = PathRelinkingParams {
+            truncation: 0.4,
+            ..Default::default()
+        };
+
+        relink(
+            &graph,
+            vec![1, 2, 3, 4, 5],
+            &[1, 2, 1, 2, 1],
+            &params,
+            &mut best_solution,
+        );
+
+        // Two of the three differing vertices are moved, each one removing a color
+        assert_eq!(best_solution.0, 3);
+        assert!(is_coloring_valid(&graph, &best_solution.1));
+    }
+
+    #[test]
+    fn test_relink_with_local_search() {
+        // A cycle 0---1---2---3---0: walking from [1, 2, 3, 4] to [1, 1, 2, 2] never finds a
+        // valid coloring with 2 colors, but the local search can fix the intermediate ones
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 0);
+
+        let mut best_solution = (4, vec![1, 2, 3, 4]);
+
+        relink(
+            &graph,
+            vec![1, 2, 3, 4],
+            &[1, 1, 2, 2],
+            &PathRelinkingParams::default(),
+            &mut best_solution,
+        );
+
+        assert_eq!(best_solution.0, 3);
+
+        let params = PathRelinkingParams {
+            local_search_iterations: 100,
+            ..Default::default()
+        };
+        let mut best_solution = (4, vec![1, 2, 3, 4]);
+
+        relink(
+            &graph,
+            vec![1, 2, 3, 4],
+            &[1, 1, 2, 2],
+            &params,
+            &mut best_solution,
+        );
+
+        assert_eq!(best_solution.0, 2);
+        assert!(is_coloring_valid(&graph, &best_solution.1));
+    }
+
     #[test]
     fn test_grasp_path_relinking() {
         // Asserts GRASP + PR provides a solution
-        if let Ok(Some(graph)) = input::read_graph_from_file("data/myc/myciel5.col") {
-            let (_, coloring) = grasp_path_relinking(&graph, 5);
+        let graph = testing::myciel5();
+
+        for direction in [Direction::Forward, Direction::Backward] {
+            let params = PathRelinkingParams {
+                num_solutions: 5,
+                direction,
+                local_search_iterations: 100,
+                truncation: 0.5,
+                min_distance: 10,
+                evolutionary: false,
+            };
+            let (_, coloring) = grasp_path_relinking(&graph, &GraspParams::default(), &params);
+
+            assert!(is_coloring_valid(&graph, &coloring));
+
+            let params = PathRelinkingParams {
+                evolutionary: true,
+                ..params
+            };
+            let (_, coloring) = grasp_path_relinking(&graph, &GraspParams::default(), &params);
 
             assert!(is_coloring_valid(&graph, &coloring));
-        } else {
-            panic!("The file containing the test graph is missing")
         }
     }
+
+    #[test]
+    fn test_elite_pool() {
+        let mut pool = ElitePool::new(2, 1);
+
+        assert!(pool.admit((3, vec![3, 1, 2])));
+        // The same partition with other labels
+        assert!(!pool.admit((3, vec![1, 2, 3])));
+        assert!(pool.admit((2, vec![1, 2, 1])));
+        assert_eq!(pool.solutions(), [(2, vec![1, 2, 1]), (3, vec![1, 2, 3])]);
+
+        // Once full, only colorings better than the worst one get in
+        assert!(!pool.admit((3, vec![1, 2, 3])));
+        assert!(pool.admit((2, vec![1, 2, 2])));
+        assert_eq!(pool.solutions(), [(2, vec![1, 2, 1]), (2, vec![1, 2, 2])]);
+
+        // Colorings too close to the pool are turned down, unless they're the best
+        let mut pool = ElitePool::new(3, 2);
+
+        assert!(pool.admit((3, vec![1, 2, 3, 3, 3])));
+        assert!(!pool.admit((3, vec![1, 2, 3, 3, 1])));
+        assert!(pool.admit((3, vec![1, 2, 3, 1, 1])));
+        assert!(pool.admit((2, vec![1, 2, 2, 2, 2])));
+        assert_eq!(pool.solutions()[0].0, 2);
+
+        // The best coloring replaces the most similar one of the full pool
+        assert!(!pool.admit((4, vec![1, 2, 3, 4, 4])));
+        assert!(pool.admit((1, vec![1, 1, 1, 1, 1])));
+        assert_eq!(
+            pool.solutions(),
+            [
+                (1, vec![1, 1, 1, 1, 1]),
+                (3, vec![1, 2, 3, 3, 3]),
+                (3, vec![1, 2, 3, 1, 1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_evolutionary_path_relinking() {
+        // A path 0---1---2---3: the only path between the two colorings leads nowhere better
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+
+        let params = PathRelinkingParams {
+            num_solutions: 3,
+            evolutionary: true,
+            ..Default::default()
+        };
+        let solutions = vec![(3, vec![1, 2, 3, 1]), (4, vec![1, 2, 3, 4])];
+        let (num_colors, coloring) = evolutionary_path_relinking(&graph, solutions, None, &params);
+
+        assert_eq!(num_colors, 3);
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        let solutions = vec![(2, vec![1, 2, 1, 2]), (4, vec![1, 2, 3, 4])];
+        let (num_colors, coloring) = evolutionary_path_relinking(&graph, solutions, None, &params);
+
+        assert_eq!(num_colors, 2);
+        assert!(is_coloring_valid(&graph, &coloring));
+    }
 }
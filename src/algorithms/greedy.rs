@@ -0,0 +1,266 @@
+use super::Solution;
+use crate::graph::adj_list::AdjList;
+use crate::graph::bounds::{degeneracy_ordering, greedy_coloring};
+use crate::progress::{self, Phase};
+use crate::rng::rng;
+use rand::seq::SliceRandom;
+#[cfg(not(feature = "deterministic"))]
+use rayon::prelude::*;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+/// Order in which the greedy coloring goes through the vertices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VertexOrder {
+    /// A uniformly random order.
+    Random,
+    /// Decreasing degree, as in Welsh–Powell.
+    LargestFirst,
+    /// The vertex with the most distinct colors among its neighbors first, as in DSATUR.
+    Saturation,
+    /// Repeatedly remove a vertex of maximum degree, placing it before the ones already removed.
+    LargestLast,
+    /// Repeatedly remove a vertex of minimum degree, placing it before the ones already removed.
+    SmallestLast,
+}
+
+impl VertexOrder {
+    /// The orders that don't depend on chance.
+    pub const DETERMINISTIC: [VertexOrder; 4] = [
+        VertexOrder::LargestFirst,
+        VertexOrder::Saturation,
+        VertexOrder::LargestLast,
+        VertexOrder::SmallestLast,
+    ];
+
+    /// The name of the order, as printed when it wins.
+    pub fn name(&self) -> &'static str {
+        match self {
+            VertexOrder::Random => "random",
+            VertexOrder::LargestFirst => "largest-first",
+            VertexOrder::Saturation => "saturation",
+            VertexOrder::LargestLast => "largest-last",
+            VertexOrder::SmallestLast => "smallest-last",
+        }
+    }
+
+    /// The vertices of `graph` in this order.
+    pub fn ordering(&self, graph: &AdjList) -> Vec<usize> {
+        match self {
+            VertexOrder::Random => {
+                let mut ordering: Vec<usize> = (0..graph.num_vertices()).collect();
+                ordering.shuffle(&mut rng());
+                ordering
+            }
+            VertexOrder::LargestFirst => largest_first_ordering(graph),
+            VertexOrder::Saturation => saturation_ordering(graph),
+            VertexOrder::LargestLast => largest_last_ordering(graph),
+            VertexOrder::SmallestLast => degeneracy_ordering(graph).1,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct GreedyPortfolioParams {
+    /// Random orders tried, besides each of the [`VertexOrder::DETERMINISTIC`] ones.
+    pub random_orderings: usize,
+}
+
+impl Default for GreedyPortfolioParams {
+    fn default() -> Self {
+        GreedyPortfolioParams {
+            random_orderings: 100,
+        }
+    }
+}
+
+/// The vertices of `graph` by decreasing degree, ties broken by index.
+pub fn largest_first_ordering(graph: &AdjList) -> Vec<usize> {
+    let mut ordering: Vec<usize> = (0..graph.num_vertices()).collect();
+    ordering.sort_by_key(|v| Reverse(graph.adj_list()[*v].len()));
+
+    ordering
+}
+
+/// Colors `graph` with the Welsh–Powell algorithm: going through the vertices by decreasing degree,
+/// each color is given to every vertex left with no neighbor colored with it, before moving on to
+/// the next color.
+///
+/// This is the same as coloring the vertices greedily in that order, each one with the smallest
+/// color not used by its neighbors, which is how it's computed.
+pub fn welsh_powell(graph: &AdjList) -> Solution {
+    let coloring = greedy_coloring(graph, &largest_first_ordering(graph));
+    let num_colors = coloring.iter().max().copied().unwrap_or(0);
+
+    progress::record_iteration();
+    progress::record_colors_from(Phase::Construction, num_colors);
+
+    (num_colors, coloring)
+}
+
+/// Colors `graph` greedily in every [`VertexOrder::DETERMINISTIC`] order, and in
+/// `random_orderings` random ones, in parallel. Returns the best coloring along with the order that
+/// found it (the first one listed, on ties).
+pub fn greedy_portfolio(
+    graph: &AdjList,
+    params: &GreedyPortfolioParams,
+) -> (Solution, VertexOrder) {
+    let orders: Vec<VertexOrder> = VertexOrder::DETERMINISTIC
+        .into_iter()
+        .chain((0..params.random_orderings).map(|_| VertexOrder::Random))
+        .collect();
+
+    // The orders run in parallel, unless the results must be reproducible
+    #[cfg(not(feature = "deterministic"))]
+    let orders = orders.into_par_iter();
+    #[cfg(feature = "deterministic")]
+    let orders = orders.into_iter();
+
+    orders
+        .map(|order| {
+            let coloring = greedy_coloring(graph, &order.ordering(graph));
+            let num_colors = coloring.iter().max().copied().unwrap_or(0);
+
+            progress::record_iteration();
+            progress::record_colors_from(Phase::Construction, num_colors);
+
+            ((num_colors, coloring), order)
+        })
+        .min_by_key(|((num_colors, _), _)| *num_colors)
+        .unwrap()
+}
+
+/// The order in which DSATUR colors `graph`: the uncolored vertex with the most distinct colors
+/// among its neighbors first (then the one with the most neighbors, then the first one), colored
+/// with the smallest color not used by its neighbors.
+fn saturation_ordering(graph: &AdjList) -> Vec<usize> {
+    let n = graph.num_vertices();
+    let adj_list = graph.adj_list();
+    let mut colored = vec![false; n];
+    let mut neighbor_colors: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+    let mut ordering = Vec::with_capacity(n);
+
+    for _ in 0..n {
+        let vertex = (0..n)
+            .filter(|v| !colored[*v])
+            .max_by_key(|v| (neighbor_colors[*v].len(), adj_list[*v].len(), Reverse(*v)))
+            .unwrap();
+        let color = (1..)
+            .find(|color| !neighbor_colors[vertex].contains(color))
+            .unwrap();
+
+        colored[vertex] = true;
+        ordering.push(vertex);
+
+        for neighbor in adj_list[vertex].iter() {
+            neighbor_colors[*neighbor].insert(color);
+        }
+    }
+
+    ordering
+}
+
+/// Repeatedly removes a vertex of maximum degree (in what's left of `graph`), placing it before the
+/// ones already removed.
+fn largest_last_ordering(graph: &AdjList) -> Vec<usize> {
+    let n = graph.num_vertices();
+    let mut degrees: Vec<usize> = graph
+        .adj_list()
+        .iter()
+        .map(|neighbors| neighbors.len())
+        .collect();
+    let mut removed = vec![false; n];
+    let mut ordering = Vec::with_capacity(n);
+
+    for _ in 0..n {
+        let vertex = (0..n)
+            .filter(|v| !removed[*v])
+            .max_by_key(|v| (degrees[*v], Reverse(*v)))
+            .unwrap();
+
+        removed[vertex] = true;
+        ordering.push(vertex);
+
+        for neighbor in graph.adj_list()[vertex].iter() {
+            degrees[*neighbor] -= 1;
+        }
+    }
+
+    ordering.reverse();
+
+    ordering
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::solve_fixtures;
+
+    #[test]
+    fn test_welsh_powell() {
+        for (fixture, num_colors) in solve_fixtures(|fixture| welsh_powell(&fixture.graph)) {
+            // The bound of Welsh and Powell: the i-th vertex by degree takes at most the color
+            // min(i, degree + 1)
+            let ordering = largest_first_ordering(&fixture.graph);
+            let bound = ordering
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (i + 1).min(fixture.graph.adj_list()[*v].len() + 1))
+                .max()
+                .unwrap();
+
+            assert!(num_colors <= bound, "{}", fixture.name);
+        }
+
+        // The star with 4 leaves: the center goes first, and all the leaves share a color
+        let mut graph = AdjList::new(5);
+        for leaf in 0..4 {
+            graph.add_edge(leaf, 4);
+        }
+
+        assert_eq!(largest_first_ordering(&graph)[0], 4);
+        assert_eq!(welsh_powell(&graph), (2, vec![2, 2, 2, 2, 1]));
+    }
+
+    #[test]
+    fn test_greedy_portfolio() {
+        let params = GreedyPortfolioParams {
+            random_orderings: 10,
+        };
+        let fixtures = solve_fixtures(|fixture| {
+            let (solution, order) = greedy_portfolio(&fixture.graph, &params);
+
+            // The winning order reproduces the coloring, unless it's random
+            if order != VertexOrder::Random {
+                assert_eq!(
+                    greedy_coloring(&fixture.graph, &order.ordering(&fixture.graph)),
+                    solution.1
+                );
+            }
+
+            solution
+        });
+
+        // The largest first order is among the ones tried
+        for (fixture, num_colors) in fixtures {
+            assert!(
+                num_colors <= welsh_powell(&fixture.graph).0,
+                "{}",
+                fixture.name
+            );
+        }
+
+        // Every order goes through every vertex once
+        let graph = crate::testing::petersen();
+        for order in VertexOrder::DETERMINISTIC {
+            let mut ordering = order.ordering(&graph);
+            ordering.sort();
+            assert_eq!(
+                ordering,
+                (0..10).collect::<Vec<usize>>(),
+                "{}",
+                order.name()
+            );
+        }
+    }
+}
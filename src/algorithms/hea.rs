@@ -0,0 +1,261 @@
+use super::{count_conflicts, least_conflicting_color, relabel_colors, tabucol::tabucol, Solution};
+use crate::graph::adj_list::AdjList;
+use crate::graph::bounds::{degeneracy_ordering, greedy_coloring};
+use crate::progress::{self, Phase};
+use crate::rng::rng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// An individual of the HEA: `k` color classes partitioning the vertices, which may have
+/// conflicts.
+type Partition = Vec<Vec<usize>>;
+
+#[derive(Debug)]
+pub struct HeaParams {
+    pub population_size: usize,
+    pub generations: usize,
+    /// Maximum iterations of the tabu search applied to each individual.
+    pub tabu_iterations: usize,
+    /// Known lower bound on the number of colors: the search stops as soon as it's matched.
+    pub lower_bound: Option<usize>,
+}
+
+impl Default for HeaParams {
+    fn default() -> Self {
+        HeaParams {
+            population_size: 10,
+            generations: 1000,
+            tabu_iterations: 2000,
+            lower_bound: None,
+        }
+    }
+}
+
+/// Colors `graph` with the Hybrid Evolutionary Algorithm (Galinier and Hao, 1999), which searches
+/// for a conflict-free partition of the vertices into a fixed number of classes `k`.
+///
+/// Individuals are partitions, combined with the Greedy Partition Crossover (GPX), which builds the
+/// offspring class by class from the largest class left in each parent, in turns. Each offspring is
+/// refined with a tabu search (TabuCol) and replaces the worst of its parents. The search starts
+/// with one class less than a greedy coloring (in smallest-last order) and, whenever an offspring
+/// has no conflicts, the whole population loses a class.
+pub fn hea(graph: &AdjList, params: &HeaParams) -> Solution {
+    let n = graph.num_vertices();
+    let (_, ordering) = degeneracy_ordering(graph);
+    let mut best_coloring = greedy_coloring(graph, &ordering);
+    relabel_colors(&mut best_coloring);
+
+    let mut best = best_coloring.iter().max().copied().unwrap_or(0);
+    let population_size = params.population_size.max(2);
+    let reached = |best: usize| {
+        best <= 1
+            || params
+                .lower_bound
+                .is_some_and(|lower_bound| best <= lower_bound)
+    };
+
+    progress::record_colors_from(Phase::Construction, best);
+
+    if reached(best) {
+        return (best, best_coloring);
+    }
+
+    let mut k = best - 1;
+    let mut population: Vec<(usize, Partition)> = (0..population_size)
+        .map(|_| refine(graph, random_partition(n, k), params.tabu_iterations))
+        .collect();
+
+    for _ in 0..params.generations {
+        if reached(best) || progress::stop_requested() {
+            break;
+        }
+
+        if let Some((_, partition)) = population.iter().find(|(conflicts, _)| *conflicts == 0) {
+            best_coloring = to_coloring(partition, n);
+            relabel_colors(&mut best_coloring);
+            best = best_coloring.iter().max().copied().unwrap_or(0);
+
+            progress::record_colors(best);
+
+            if reached(best) {
+                break;
+            }
+
+            k = best - 1;
+            population = population
+                .into_iter()
+                .map(|(_, partition)| {
+                    refine(
+                        graph,
+                        remove_classes(graph, partition, k),
+                        params.tabu_iterations,
+                    )
+                })
+                .collect();
+            continue;
+        }
+
+        let parents: Vec<usize> = (0..population.len()).collect();
+        let parents: Vec<usize> = parents.choose_multiple(&mut rng(), 2).copied().collect();
+        let child = gpx(&population[parents[0]].1, &population[parents[1]].1, n);
+        let crossed_conflicts = count_conflicts(graph, &to_coloring(&child, n));
+        let (conflicts, child) = refine(graph, child, params.tabu_iterations);
+
+        if conflicts == 0 {
+            let phase = match crossed_conflicts {
+                0 => Phase::Crossover,
+                _ => Phase::LocalSearch,
+            };
+            let num_colors = child.iter().filter(|class| !class.is_empty()).count();
+
+            progress::record_colors_from(phase, num_colors);
+        }
+
+        progress::record_iteration();
+        progress::record_conflicts(conflicts);
+
+        let worst = *parents
+            .iter()
+            .max_by_key(|parent| population[**parent].0)
+            .unwrap();
+        population[worst] = (conflicts, child);
+    }
+
+    (best, best_coloring)
+}
+
+/// The Greedy Partition Crossover: the offspring gets as many classes as the parents, each one the
+/// largest class left in a parent (alternately), whose vertices are then removed from both parents.
+/// The vertices left at the end are put in random classes.
+fn gpx(first: &Partition, second: &Partition, n: usize) -> Partition {
+    let k = first.len();
+    let mut rng = rng();
+    let mut parents = [first.clone(), second.clone()];
+    let mut placed = vec![false; n];
+    let mut offspring = Vec::with_capacity(k);
+
+    for turn in 0..k {
+        let parent = &mut parents[turn % 2];
+        let largest = (0..parent.len())
+            .max_by_key(|class| parent[*class].len())
+            .unwrap();
+        let class = std::mem::take(&mut parent[largest]);
+
+        for v in class.iter() {
+            placed[*v] = true;
+        }
+        for parent in parents.iter_mut() {
+            for other in parent.iter_mut() {
+                other.retain(|v| !placed[*v]);
+            }
+        }
+
+        offspring.push(class);
+    }
+
+    for v in (0..n).filter(|v| !placed[*v]) {
+        offspring[rng.gen_range(0..k)].push(v);
+    }
+
+    offspring
+}
+
+/// Puts each vertex in one of `k` random classes.
+fn random_partition(n: usize, k: usize) -> Partition {
+    let mut rng = rng();
+    let mut partition = vec![Vec::new(); k];
+
+    for v in 0..n {
+        partition[rng.gen_range(0..k)].push(v);
+    }
+
+    partition
+}
+
+/// Removes the smallest classes of `partition` until `k` are left, moving each of their vertices
+/// to the class with the fewest of its neighbors.
+fn remove_classes(graph: &AdjList, mut partition: Partition, k: usize) -> Partition {
+    let mut removed = Vec::new();
+
+    while partition.len() > k {
+        let smallest = (0..partition.len())
+            .min_by_key(|class| partition[*class].len())
+            .unwrap();
+        removed.append(&mut partition.swap_remove(smallest));
+    }
+
+    let mut coloring = to_coloring(&partition, graph.num_vertices());
+
+    for v in removed {
+        let color = least_conflicting_color(graph, &coloring, v, k);
+        coloring[v] = color;
+        partition[color - 1].push(v);
+    }
+
+    partition
+}
+
+/// Runs the tabu search on `partition`, returning the improved partition along with its number of
+/// conflicting edges.
+fn refine(graph: &AdjList, partition: Partition, max_iterations: usize) -> (usize, Partition) {
+    let n = graph.num_vertices();
+    let k = partition.len();
+    let mut coloring = to_coloring(&partition, n);
+    let conflicts = tabucol(graph, &mut coloring, k, max_iterations);
+
+    let mut partition = vec![Vec::new(); k];
+    for (v, color) in coloring.iter().enumerate() {
+        partition[color - 1].push(v);
+    }
+
+    (conflicts, partition)
+}
+
+/// The coloring where the vertices of the `i`-th class (from 0) of `partition` have color `i + 1`.
+fn to_coloring(partition: &Partition, n: usize) -> Vec<usize> {
+    let mut coloring = vec![0; n];
+
+    for (class, vertices) in partition.iter().enumerate() {
+        for v in vertices {
+            coloring[*v] = class + 1;
+        }
+    }
+
+    coloring
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::solve_fixtures_optimally;
+
+    #[test]
+    fn test_hea() {
+        solve_fixtures_optimally(|fixture| {
+            let params = HeaParams {
+                generations: 50,
+                tabu_iterations: 200,
+                lower_bound: Some(fixture.chromatic_number),
+                ..Default::default()
+            };
+
+            hea(&fixture.graph, &params)
+        });
+    }
+
+    #[test]
+    fn test_gpx() {
+        let first = vec![vec![0, 1, 2], vec![3, 4], vec![5]];
+        let second = vec![vec![0, 3], vec![1, 4, 5], vec![2]];
+        let offspring = gpx(&first, &second, 6);
+
+        // The largest class of the first parent, then the largest one left in the second
+        assert_eq!(offspring.len(), 3);
+        assert_eq!(offspring[0], vec![0, 1, 2]);
+        assert_eq!(offspring[1], vec![4, 5]);
+
+        let mut vertices: Vec<usize> = offspring.concat();
+        vertices.sort();
+        assert_eq!(vertices, (0..6).collect::<Vec<usize>>());
+    }
+}
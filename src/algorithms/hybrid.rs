@@ -0,0 +1,85 @@
+use super::{
+    genetic::{genetic, GeneticParams},
+    grasp::{grasp, GraspParams},
+    grasp_pr::{path_relinking, PathRelinkingParams},
+    relabel_colors, Solution,
+};
+use crate::graph::adj_list::AdjList;
+
+/// Runs GRASP, then the Genetic Algorithm starting from the best GRASP colorings (after the ones of
+/// `initial_population`, if any), so it doesn't start from random colorings alone.
+///
+/// If `pr_params` is provided, the best coloring of the Genetic Algorithm is then relinked with
+/// the best GRASP colorings, as in GRASP+PR.
+pub fn grasp_ga(
+    graph: &AdjList,
+    grasp_params: &GraspParams,
+    genetic_params: &GeneticParams,
+    pr_params: Option<&PathRelinkingParams>,
+    initial_population: Option<Vec<Vec<usize>>>,
+) -> Solution {
+    let elite = grasp(graph, grasp_params, genetic_params.population_size);
+    let best = &elite[0];
+
+    if genetic_params
+        .lower_bound
+        .is_some_and(|lower_bound| best.0 <= lower_bound)
+    {
+        return best.clone();
+    }
+
+    let population = initial_population
+        .unwrap_or_default()
+        .into_iter()
+        .chain(elite.iter().map(|(_, coloring)| coloring.clone()))
+        .collect();
+    let ((num_colors, mut coloring), _) = genetic(graph, genetic_params, Some(population));
+    relabel_colors(&mut coloring);
+
+    let Some(pr_params) = pr_params else {
+        return (num_colors, coloring);
+    };
+
+    let mut solutions: Vec<Solution> = elite
+        .into_iter()
+        .filter(|(_, other)| *other != coloring)
+        .take(pr_params.num_solutions.saturating_sub(1))
+        .collect();
+    solutions.push((num_colors, coloring));
+    solutions.sort();
+
+    path_relinking(graph, solutions, genetic_params.lower_bound, pr_params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::solve_fixtures_optimally;
+
+    #[test]
+    fn test_grasp_ga() {
+        for pr_params in [None, Some(&PathRelinkingParams::default())] {
+            solve_fixtures_optimally(|fixture| {
+                let grasp_params = GraspParams {
+                    grasp_iterations: 5,
+                    lower_bound: Some(fixture.chromatic_number),
+                    ..Default::default()
+                };
+                let genetic_params = GeneticParams {
+                    generations: 200,
+                    population_size: 20,
+                    lower_bound: Some(fixture.chromatic_number),
+                    ..Default::default()
+                };
+
+                grasp_ga(
+                    &fixture.graph,
+                    &grasp_params,
+                    &genetic_params,
+                    pr_params,
+                    None,
+                )
+            });
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use super::{relabel_colors, Solution};
+use crate::graph::adj_list::AdjList;
+use crate::graph::bounds::{degeneracy_ordering, greedy_coloring};
+use crate::progress::{self, Phase};
+use good_lp::{
+    constraint, default_solver, variable, variables, Expression, Solution as _, SolutionStatus,
+    SolverModel, Variable, WithInitialSolution, WithTimeLimit,
+};
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+pub struct IlpParams {
+    /// Time the solver is given to prove its coloring optimal. Unbounded if `None`.
+    pub time_limit: Option<Duration>,
+    /// Known lower bound on the number of colors, reported when the solver doesn't prove its
+    /// coloring optimal.
+    pub lower_bound: Option<usize>,
+}
+
+/// Colors `graph` by solving the assignment formulation of the coloring ILP with the default
+/// solver of good_lp: `x[v][c]` is whether vertex `v` takes color `c`, and `w[c]` whether color
+/// `c` is used, which is minimized.
+///
+/// The colors are those of a greedy coloring (in smallest-last order), which also starts the
+/// solver. Symmetric solutions are cut by using the colors in order, and by only letting the `i`-th
+/// vertex take the first `i + 1` colors (which the relabeled greedy coloring already does).
+///
+/// The solver can't be stopped early, except by `time_limit`. Returns the best coloring found
+/// along with a lower bound on the chromatic number, which is its number of colors if the solver
+/// proved it optimal, so the gap is left to the known lower bound otherwise.
+pub fn ilp(graph: &AdjList, params: &IlpParams) -> (Solution, usize) {
+    let n = graph.num_vertices();
+    let (_, ordering) = degeneracy_ordering(graph);
+    let mut initial = greedy_coloring(graph, &ordering);
+    relabel_colors(&mut initial);
+
+    let k = initial.iter().max().copied().unwrap_or(0);
+    let known = params.lower_bound.unwrap_or(0).max(usize::from(n > 0));
+
+    progress::record_colors_from(Phase::Construction, k);
+
+    if k <= known {
+        return ((k, initial), k);
+    }
+
+    let mut vars = variables!();
+    let x: Vec<Vec<Variable>> = (0..n)
+        .map(|v| vars.add_vector(variable().binary(), k.min(v + 1)))
+        .collect();
+    let w: Vec<Variable> = vars.add_vector(variable().binary(), k);
+
+    let objective: Expression = w.iter().sum();
+    let mut model = vars.minimise(objective).using(default_solver);
+
+    for colors in x.iter() {
+        model.add_constraint(constraint!(colors.iter().sum::<Expression>() == 1));
+    }
+    for (u, v) in graph.edges() {
+        for c in 0..x[u].len().min(x[v].len()) {
+            model.add_constraint(constraint!(x[u][c] + x[v][c] <= w[c]));
+        }
+    }
+    // The colors no neighbor may take aren't tied to their use by the edges
+    for v in 0..n {
+        let tied = graph.adj_list()[v]
+            .iter()
+            .map(|u| x[*u].len())
+            .max()
+            .unwrap_or(0);
+
+        for c in tied.min(x[v].len())..x[v].len() {
+            model.add_constraint(constraint!(x[v][c] <= w[c]));
+        }
+    }
+    for c in 1..k {
+        model.add_constraint(constraint!(w[c] <= w[c - 1]));
+    }
+
+    let start = x
+        .iter()
+        .enumerate()
+        .flat_map(|(v, colors)| {
+            let color = initial[v];
+            colors
+                .iter()
+                .enumerate()
+                .map(move |(c, var)| (*var, f64::from(u8::from(c + 1 == color))))
+        })
+        .chain(w.iter().map(|var| (*var, 1.0)))
+        .collect::<Vec<_>>();
+    let mut model = model.with_initial_solution(start);
+
+    if let Some(time_limit) = params.time_limit {
+        model = model.with_time_limit(time_limit.as_secs_f64());
+    }
+
+    let Ok(solution) = model.solve() else {
+        return ((k, initial), known);
+    };
+
+    let mut coloring: Vec<usize> = x
+        .iter()
+        .map(|colors| {
+            colors
+                .iter()
+                .position(|var| solution.value(*var) > 0.5)
+                .map_or(0, |c| c + 1)
+        })
+        .collect();
+
+    // Never worse than the coloring the solver started from
+    if coloring.contains(&0) {
+        return ((k, initial), known);
+    }
+
+    relabel_colors(&mut coloring);
+    let num_colors = coloring.iter().max().copied().unwrap_or(0);
+
+    if num_colors > k {
+        return ((k, initial), known);
+    }
+
+    progress::record_colors(num_colors);
+
+    let proven = match solution.status() {
+        SolutionStatus::Optimal => num_colors,
+        SolutionStatus::TimeLimit | SolutionStatus::GapLimit => known,
+    };
+
+    ((num_colors, coloring), proven)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{count_colors, is_coloring_valid};
+    use crate::testing::{self, fixtures};
+
+    #[test]
+    fn test_ilp() {
+        for fixture in fixtures()
+            .into_iter()
+            .filter(|fixture| fixture.graph.num_vertices() <= 11)
+        {
+            let ((num_colors, coloring), lower_bound) = ilp(&fixture.graph, &IlpParams::default());
+
+            assert!(
+                is_coloring_valid(&fixture.graph, &coloring),
+                "{}",
+                fixture.name
+            );
+            assert_eq!(count_colors(&coloring), num_colors);
+            assert_eq!(num_colors, fixture.chromatic_number, "{}", fixture.name);
+            assert_eq!(lower_bound, fixture.chromatic_number, "{}", fixture.name);
+        }
+
+        // Isolated vertices still count their color
+        let mut graph = AdjList::new(3);
+        graph.add_edge(0, 1);
+        let ((num_colors, coloring), lower_bound) = ilp(&graph, &IlpParams::default());
+
+        assert_eq!((num_colors, lower_bound), (2, 2));
+        assert!(coloring[2] <= 2);
+
+        // Out of time, the gap is left to the known lower bound
+        let params = IlpParams {
+            time_limit: Some(Duration::ZERO),
+            lower_bound: Some(2),
+        };
+        let ((num_colors, coloring), lower_bound) = ilp(&testing::myciel4(), &params);
+
+        assert!(is_coloring_valid(&testing::myciel4(), &coloring));
+        assert!(num_colors >= 5);
+        assert_eq!(lower_bound, 2);
+    }
+}
@@ -0,0 +1,98 @@
+//! The best number of colors found by any of the searches running at once, shared without locks.
+//!
+//! Searches sharing an [`Incumbent`] follow the same contract: they [`offer`](Incumbent::offer)
+//! every valid coloring they find, never start (or keep) searching for colorings with `k` colors
+//! once [`prunes`](Incumbent::prunes) says any of them wouldn't be an improvement, and aim for
+//! [`target`](Incumbent::target) colors when they can pick their number of colors. So a thread
+//! that finds a better coloring tightens the targets of all the others at once.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Stands for a number of colors that wasn't found yet.
+const NONE: usize = usize::MAX;
+
+#[derive(Debug)]
+pub struct Incumbent {
+    best: AtomicUsize,
+    lower_bound: Option<usize>,
+}
+
+impl Incumbent {
+    pub fn new(lower_bound: Option<usize>) -> Self {
+        Incumbent {
+            best: AtomicUsize::new(NONE),
+            lower_bound,
+        }
+    }
+
+    /// The fewest colors of a valid coloring found so far, if any.
+    pub fn best(&self) -> Option<usize> {
+        let best = self.best.load(Ordering::Relaxed);
+
+        (best != NONE).then_some(best)
+    }
+
+    /// Records a valid coloring with `num_colors` colors. Returns whether it's the best so far.
+    pub fn offer(&self, num_colors: usize) -> bool {
+        num_colors < self.best.fetch_min(num_colors, Ordering::Relaxed)
+    }
+
+    /// The most colors a coloring may use to beat the best one, if there's one to beat.
+    pub fn target(&self) -> Option<usize> {
+        self.best().map(|best| best.saturating_sub(1))
+    }
+
+    /// Whether the best coloring matches the lower bound, so no search can improve it.
+    pub fn is_optimal(&self) -> bool {
+        self.lower_bound
+            .zip(self.best())
+            .is_some_and(|(lower_bound, best)| best <= lower_bound)
+    }
+
+    /// Whether searching for colorings with `k` colors is wasted work: there's already one with
+    /// at most `k` colors, or the best one is optimal.
+    pub fn prunes(&self, k: usize) -> bool {
+        self.is_optimal() || self.best().is_some_and(|best| best <= k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_incumbent() {
+        let incumbent = Incumbent::new(Some(3));
+
+        assert_eq!((incumbent.best(), incumbent.target()), (None, None));
+        assert!(!incumbent.prunes(10));
+
+        assert!(incumbent.offer(6));
+        assert!(!incumbent.offer(7));
+        assert_eq!((incumbent.best(), incumbent.target()), (Some(6), Some(5)));
+        assert!(incumbent.prunes(6) && !incumbent.prunes(5));
+
+        assert!(incumbent.offer(3));
+        assert!(incumbent.is_optimal() && incumbent.prunes(2));
+    }
+
+    #[test]
+    fn test_shared_incumbent() {
+        let incumbent = Incumbent::new(None);
+
+        thread::scope(|scope| {
+            for thread in 0..8 {
+                let incumbent = &incumbent;
+                scope.spawn(move || {
+                    for num_colors in (thread * 10..thread * 10 + 50).rev() {
+                        incumbent.offer(num_colors);
+                    }
+                });
+            }
+        });
+
+        assert_eq!(incumbent.best(), Some(0));
+        assert!(!incumbent.is_optimal());
+    }
+}
@@ -0,0 +1,159 @@
+//! Checks of the internal state of the algorithms, for catching bookkeeping bugs.
+//!
+//! The checks only run with the `invariants` feature: without it, each `check_*` function is a
+//! no-op. With it, a violated invariant panics, describing the corrupted state.
+
+use super::{count_colors, count_conflicts, genetic::GeneticMode, Solution};
+use crate::graph::adj_list::AdjList;
+
+const ENABLED: bool = cfg!(feature = "invariants");
+
+/// Checks that `class_list` partitions the vertices of `graph`, with its first `num_classes`
+/// classes non-empty and the remaining ones empty.
+pub fn check_class_list(graph: &AdjList, class_list: &[Vec<usize>], num_classes: usize) {
+    if ENABLED {
+        if let Err(err) = class_list_error(graph, class_list, num_classes) {
+            panic!("invariant violated: {err}");
+        }
+    }
+}
+
+/// Checks that `conflicts` is the number of conflicting edges of the coloring given by
+/// `class_list`.
+pub fn check_class_list_conflicts(graph: &AdjList, class_list: &[Vec<usize>], conflicts: usize) {
+    if ENABLED {
+        let mut coloring = vec![0; graph.num_vertices()];
+
+        for (i, class) in class_list.iter().enumerate() {
+            for vertex in class {
+                coloring[*vertex] = i + 1;
+            }
+        }
+
+        check_conflicts(graph, &coloring, conflicts);
+    }
+}
+
+/// Checks that `conflicts` is the number of conflicting edges of `coloring`.
+pub fn check_conflicts(graph: &AdjList, coloring: &[usize], conflicts: usize) {
+    if ENABLED {
+        let actual = count_conflicts(graph, coloring);
+
+        assert_eq!(
+            actual, conflicts,
+            "invariant violated: the coloring has {actual} conflicts, but {conflicts} are recorded"
+        );
+    }
+}
+
+/// Checks that the fitness of each individual of `population` matches its coloring, according to
+/// the `mode`, and that the colorings of the [`GeneticMode::MinColors`] mode are valid.
+pub fn check_population(graph: &AdjList, population: &[Solution], mode: GeneticMode) {
+    if ENABLED {
+        if let Err(err) = population_error(graph, population, mode) {
+            panic!("invariant violated: {err}");
+        }
+    }
+}
+
+fn class_list_error(
+    graph: &AdjList,
+    class_list: &[Vec<usize>],
+    num_classes: usize,
+) -> Result<(), String> {
+    let mut classes = vec![None; graph.num_vertices()];
+
+    for (i, class) in class_list.iter().enumerate() {
+        if (i < num_classes) == class.is_empty() {
+            return Err(format!(
+                "class {i} is {}, but there are {num_classes} classes",
+                if class.is_empty() {
+                    "empty"
+                } else {
+                    "not empty"
+                }
+            ));
+        }
+
+        for vertex in class {
+            match classes.get(*vertex) {
+                None => {
+                    return Err(format!(
+                        "class {i} has vertex {vertex}, which doesn't exist"
+                    ))
+                }
+                Some(Some(other)) => {
+                    return Err(format!("vertex {vertex} is in classes {other} and {i}"))
+                }
+                Some(None) => classes[*vertex] = Some(i),
+            }
+        }
+    }
+
+    match classes.iter().position(Option::is_none) {
+        Some(vertex) => Err(format!("vertex {vertex} isn't in any class")),
+        None => Ok(()),
+    }
+}
+
+fn population_error(
+    graph: &AdjList,
+    population: &[Solution],
+    mode: GeneticMode,
+) -> Result<(), String> {
+    for (i, (fitness, coloring)) in population.iter().enumerate() {
+        let actual = match mode {
+            GeneticMode::MinColors => count_colors(coloring),
+            GeneticMode::FixedK => count_conflicts(graph, coloring),
+        };
+
+        if actual != *fitness {
+            return Err(format!(
+                "individual {i} has fitness {fitness}, but its coloring gives {actual}"
+            ));
+        }
+
+        if matches!(mode, GeneticMode::MinColors) && count_conflicts(graph, coloring) > 0 {
+            return Err(format!("individual {i} isn't a valid coloring"));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_list_error() {
+        let graph = AdjList::complete(3);
+
+        assert!(class_list_error(&graph, &[vec![0, 2], vec![1], vec![]], 2).is_ok());
+        // Vertex 2 is missing
+        assert!(class_list_error(&graph, &[vec![0], vec![1], vec![]], 2).is_err());
+        // Vertex 1 is repeated
+        assert!(class_list_error(&graph, &[vec![0, 1], vec![1, 2]], 2).is_err());
+        // The number of classes is wrong
+        assert!(class_list_error(&graph, &[vec![0, 2], vec![1], vec![]], 3).is_err());
+        // Vertex 3 doesn't exist
+        assert!(class_list_error(&graph, &[vec![0, 3], vec![1, 2]], 2).is_err());
+    }
+
+    #[test]
+    fn test_population_error() {
+        let graph = AdjList::complete(3);
+
+        assert!(population_error(&graph, &[(3, vec![1, 2, 3])], GeneticMode::MinColors).is_ok());
+        assert!(population_error(&graph, &[(2, vec![1, 2, 3])], GeneticMode::MinColors).is_err());
+        assert!(population_error(&graph, &[(2, vec![1, 2, 2])], GeneticMode::MinColors).is_err());
+        assert!(population_error(&graph, &[(1, vec![1, 2, 2])], GeneticMode::FixedK).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "invariants")]
+    #[should_panic(expected = "invariant violated")]
+    fn test_check_conflicts() {
+        check_conflicts(&AdjList::complete(3), &[1, 1, 2], 0);
+    }
+}
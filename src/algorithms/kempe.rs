@@ -0,0 +1,131 @@
+use crate::graph::adj_list::AdjList;
+use std::collections::HashSet;
+
+/// The Kempe chain of `vertex` and the color `other`: the vertices reachable from `vertex` through
+/// vertices colored with either its color or `other`.
+///
+/// Swapping the two colors over a chain (see [`swap_chain`]) never changes the number of
+/// conflicts, so it turns valid colorings into valid colorings.
+pub fn kempe_chain(graph: &AdjList, coloring: &[usize], vertex: usize, other: usize) -> Vec<usize> {
+    let color = coloring[vertex];
+    let mut in_chain = vec![false; coloring.len()];
+    let mut chain = vec![vertex];
+    let mut stack = vec![vertex];
+
+    in_chain[vertex] = true;
+
+    while let Some(v) = stack.pop() {
+        for u in graph.adj_list()[v].iter() {
+            if !in_chain[*u] && (coloring[*u] == color || coloring[*u] == other) {
+                in_chain[*u] = true;
+                chain.push(*u);
+                stack.push(*u);
+            }
+        }
+    }
+
+    chain
+}
+
+/// Like [`kempe_chain`], but only through edges whose ends have different colors, so the
+/// conflicting edges don't extend the chain.
+///
+/// Swapping the two colors over this chain never adds conflicts, and removes those between the
+/// chain and the rest of the graph, which makes it a move for colorings with conflicts.
+pub fn bichromatic_chain(
+    graph: &AdjList,
+    coloring: &[usize],
+    vertex: usize,
+    other: usize,
+) -> Vec<usize> {
+    let color = coloring[vertex];
+    let mut in_chain = HashSet::from([vertex]);
+    let mut chain = vec![vertex];
+    let mut stack = vec![vertex];
+
+    while let Some(v) = stack.pop() {
+        for u in graph.adj_list()[v].iter() {
+            if coloring[*u] != coloring[v]
+                && (coloring[*u] == color || coloring[*u] == other)
+                && in_chain.insert(*u)
+            {
+                chain.push(*u);
+                stack.push(*u);
+            }
+        }
+    }
+
+    chain
+}
+
+/// Swaps the colors `a` and `b` of the vertices of `chain`.
+pub fn swap_chain(coloring: &mut [usize], chain: &[usize], a: usize, b: usize) {
+    for v in chain {
+        if coloring[*v] == a {
+            coloring[*v] = b;
+        } else if coloring[*v] == b {
+            coloring[*v] = a;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::is_coloring_valid;
+    use crate::testing;
+
+    #[test]
+    fn test_kempe_chain() {
+        // The path 0 - 1 - 2 - 3 - 4, where 3 breaks the chain of the colors 1 and 2
+        let mut graph = AdjList::new(5);
+        for v in 0..4 {
+            graph.add_edge(v, v + 1);
+        }
+        let mut coloring = vec![1, 2, 1, 3, 1];
+
+        let mut chain = kempe_chain(&graph, &coloring, 0, 2);
+        chain.sort();
+        assert_eq!(chain, [0, 1, 2]);
+
+        swap_chain(&mut coloring, &chain, 1, 2);
+        assert_eq!(coloring, [2, 1, 2, 3, 1]);
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        // A color no neighbor has makes a chain of a single vertex
+        assert_eq!(kempe_chain(&graph, &coloring, 4, 2), [4]);
+    }
+
+    #[test]
+    fn test_bichromatic_chain() {
+        // The path 0 - 1 - 2, where 0 and 1 conflict
+        let mut graph = AdjList::new(3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        let mut coloring = vec![1, 1, 2];
+
+        let mut chain = bichromatic_chain(&graph, &coloring, 1, 2);
+        chain.sort();
+        assert_eq!(chain, [1, 2]);
+
+        swap_chain(&mut coloring, &chain, 1, 2);
+        assert!(is_coloring_valid(&graph, &coloring));
+    }
+
+    #[test]
+    fn test_swap_chain_keeps_colorings_valid() {
+        let graph = testing::petersen();
+        let mut coloring = vec![1, 2, 1, 2, 3, 2, 3, 3, 1, 1];
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        for v in 0..graph.num_vertices() {
+            for other in 1..=3 {
+                let color = coloring[v];
+                let chain = kempe_chain(&graph, &coloring, v, other);
+
+                swap_chain(&mut coloring, &chain, color, other);
+                assert!(is_coloring_valid(&graph, &coloring));
+            }
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use super::{count_conflicts, relabel_colors, Solution};
+use crate::graph::adj_list::AdjList;
+use crate::graph::bounds::{degeneracy_ordering, greedy_coloring};
+use crate::progress::{self, Phase};
+use crate::rng::rng;
+use rand::seq::SliceRandom;
+use std::cmp::Reverse;
+
+/// How LNS chooses the vertices to uncolor.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Destroy {
+    /// The vertices with the most conflicts, ties broken randomly.
+    #[default]
+    Conflicts,
+    /// Vertices chosen uniformly at random.
+    Random,
+}
+
+/// Which repaired colorings replace the current one in LNS.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Acceptance {
+    /// Only those with fewer conflicts.
+    #[default]
+    Better,
+    /// Those with at most as many conflicts, so the search can drift across plateaus.
+    NotWorse,
+}
+
+#[derive(Debug)]
+pub struct LnsParams {
+    /// Rounds of destroying and repairing the coloring.
+    pub iterations: usize,
+    /// Fraction of the vertices uncolored in each round (at least one).
+    pub destroy_fraction: f64,
+    pub destroy: Destroy,
+    pub acceptance: Acceptance,
+    /// Known lower bound on the number of colors: the search stops as soon as it's matched.
+    pub lower_bound: Option<usize>,
+}
+
+impl Default for LnsParams {
+    fn default() -> Self {
+        LnsParams {
+            iterations: 10000,
+            destroy_fraction: 0.1,
+            destroy: Destroy::default(),
+            acceptance: Acceptance::default(),
+            lower_bound: None,
+        }
+    }
+}
+
+/// Colors `graph` with Large Neighborhood Search, which searches for a conflict-free coloring with
+/// a fixed number of colors `k`.
+///
+/// The search starts with one color less than a greedy coloring (in smallest-last order), whose
+/// last class is uncolored and repaired. Each round uncolors a fraction of the vertices of the
+/// current coloring (see [`Destroy`]) and reinserts them in DSATUR order: the vertex with the most
+/// colors among its neighbors first, in the color where it has the fewest conflicts. The repaired
+/// coloring replaces the current one according to the [`Acceptance`] criterion. Whenever a coloring
+/// has no conflicts, `k` is decreased.
+pub fn lns(graph: &AdjList, params: &LnsParams) -> Solution {
+    let n = graph.num_vertices();
+    let (_, ordering) = degeneracy_ordering(graph);
+    let mut best_coloring = greedy_coloring(graph, &ordering);
+    relabel_colors(&mut best_coloring);
+
+    let mut best = best_coloring.iter().max().copied().unwrap_or(0);
+    let reached = |best: usize| {
+        best <= 1
+            || params
+                .lower_bound
+                .is_some_and(|lower_bound| best <= lower_bound)
+    };
+
+    progress::record_colors_from(Phase::Construction, best);
+
+    if reached(best) {
+        return (best, best_coloring);
+    }
+
+    let destroyed = ((params.destroy_fraction * n as f64).round() as usize).clamp(1, n);
+    let mut k = best - 1;
+    let mut current = drop_last_color(graph, &best_coloring, k);
+    let mut conflicts = count_conflicts(graph, &current);
+    let mut iterations = 0;
+
+    loop {
+        if conflicts == 0 {
+            best_coloring = current.clone();
+            relabel_colors(&mut best_coloring);
+            best = best_coloring.iter().max().copied().unwrap_or(0);
+
+            progress::record_colors_from(Phase::LocalSearch, best);
+
+            if reached(best) {
+                break;
+            }
+
+            k = best - 1;
+            current = drop_last_color(graph, &best_coloring, k);
+            conflicts = count_conflicts(graph, &current);
+            continue;
+        }
+
+        if iterations == params.iterations || progress::stop_requested() {
+            break;
+        }
+
+        iterations += 1;
+
+        let mut candidate = current.clone();
+        destroy(graph, &mut candidate, destroyed, params.destroy);
+        repair(graph, &mut candidate, k);
+        let candidate_conflicts = count_conflicts(graph, &candidate);
+
+        progress::record_iteration();
+        progress::record_conflicts(candidate_conflicts);
+
+        let accepted = match params.acceptance {
+            Acceptance::Better => candidate_conflicts < conflicts,
+            Acceptance::NotWorse => candidate_conflicts <= conflicts,
+        };
+
+        if accepted {
+            current = candidate;
+            conflicts = candidate_conflicts;
+        }
+    }
+
+    (best, best_coloring)
+}
+
+/// The coloring with `k` colors where the vertices of `coloring` (which has `k + 1` colors) with
+/// the last color are reinserted with [`repair`].
+fn drop_last_color(graph: &AdjList, coloring: &[usize], k: usize) -> Vec<usize> {
+    let mut dropped: Vec<usize> = coloring
+        .iter()
+        .map(|color| if *color > k { 0 } else { *color })
+        .collect();
+
+    repair(graph, &mut dropped, k);
+
+    dropped
+}
+
+/// Uncolors (sets to 0) `count` vertices of `coloring`, chosen by `strategy`.
+fn destroy(graph: &AdjList, coloring: &mut [usize], count: usize, strategy: Destroy) {
+    let mut vertices: Vec<usize> = (0..coloring.len()).collect();
+    vertices.shuffle(&mut rng());
+
+    if let Destroy::Conflicts = strategy {
+        // The sort is stable, so the shuffle breaks the ties
+        vertices.sort_by_key(|v| {
+            Reverse(
+                graph.adj_list()[*v]
+                    .iter()
+                    .filter(|u| coloring[**u] == coloring[*v])
+                    .count(),
+            )
+        });
+    }
+
+    for v in vertices.into_iter().take(count) {
+        coloring[v] = 0;
+    }
+}
+
+/// Colors the uncolored vertices (with color 0) of `coloring` with colors in `1..=k`, in DSATUR
+/// order: the vertex with the most distinct colors among its neighbors first (then the one with the
+/// most neighbors), in the color where it has the fewest neighbors (ties broken randomly).
+fn repair(graph: &AdjList, coloring: &mut [usize], k: usize) {
+    let adj_list = graph.adj_list();
+    let mut rng = rng();
+    let mut uncolored: Vec<usize> = (0..coloring.len()).filter(|v| coloring[*v] == 0).collect();
+    let mut position = vec![usize::MAX; coloring.len()];
+    for (i, v) in uncolored.iter().enumerate() {
+        position[*v] = i;
+    }
+
+    // `neighbor_colors[position[v]][c]` is the number of neighbors of `v` colored with `c`
+    let mut neighbor_colors = vec![vec![0; k + 1]; uncolored.len()];
+    let mut saturation = vec![0; uncolored.len()];
+    for (i, v) in uncolored.iter().enumerate() {
+        for u in adj_list[*v].iter() {
+            if coloring[*u] != 0 {
+                if neighbor_colors[i][coloring[*u]] == 0 {
+                    saturation[i] += 1;
+                }
+                neighbor_colors[i][coloring[*u]] += 1;
+            }
+        }
+    }
+
+    while !uncolored.is_empty() {
+        let index = (0..uncolored.len())
+            .max_by_key(|i| {
+                let v = uncolored[*i];
+                (saturation[position[v]], adj_list[v].len())
+            })
+            .unwrap();
+        let vertex = uncolored.swap_remove(index);
+        let counts = &neighbor_colors[position[vertex]];
+
+        let fewest = (1..=k).map(|color| counts[color]).min().unwrap();
+        let best_fits: Vec<usize> = (1..=k).filter(|color| counts[*color] == fewest).collect();
+        let color = *best_fits.choose(&mut rng).unwrap();
+
+        coloring[vertex] = color;
+
+        for u in adj_list[vertex].iter() {
+            if coloring[*u] == 0 {
+                let i = position[*u];
+                if neighbor_colors[i][color] == 0 {
+                    saturation[i] += 1;
+                }
+                neighbor_colors[i][color] += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::count_colors;
+    use crate::testing::{solve_fixtures, solve_fixtures_optimally, Fixture};
+
+    fn solve(fixture: &Fixture, destroy: Destroy, acceptance: Acceptance) -> Solution {
+        let params = LnsParams {
+            iterations: 500,
+            destroy,
+            acceptance,
+            lower_bound: Some(fixture.chromatic_number),
+            ..Default::default()
+        };
+
+        lns(&fixture.graph, &params)
+    }
+
+    #[test]
+    fn test_lns() {
+        // Drifting across plateaus, the search colors every fixture optimally
+        solve_fixtures_optimally(|fixture| solve(fixture, Destroy::Random, Acceptance::NotWorse));
+
+        // Only taking better colorings, it may get stuck (as on queen5_5), but never above the
+        // greedy coloring it starts from
+        let fixtures =
+            solve_fixtures(|fixture| solve(fixture, Destroy::Conflicts, Acceptance::Better));
+        for (fixture, num_colors) in fixtures {
+            let (_, ordering) = degeneracy_ordering(&fixture.graph);
+            let greedy = count_colors(&greedy_coloring(&fixture.graph, &ordering));
+
+            assert!(num_colors <= greedy, "{}", fixture.name);
+        }
+    }
+
+    #[test]
+    fn test_repair() {
+        // The path 0 - 1 - 2 - 3, with its inner vertices uncolored
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+
+        let mut coloring = vec![1, 0, 0, 2];
+        repair(&graph, &mut coloring, 2);
+
+        assert_eq!(coloring, vec![1, 2, 1, 2]);
+    }
+}
@@ -0,0 +1,137 @@
+use super::{
+    count_colors, count_conflicts,
+    genetic::{
+        generate_population, recombine, replace, select, squash_population, GeneticMode,
+        GeneticParams,
+    },
+    incumbent::Incumbent,
+    invariants, relabel_colors,
+    tabucol::tabucol,
+    Solution,
+};
+use crate::graph::adj_list::AdjList;
+use crate::progress::{self, Phase};
+
+#[derive(Debug)]
+pub struct MemeticParams {
+    pub generations: usize,
+    /// Maximum iterations of the tabu search applied to each offspring.
+    pub tabu_iterations: usize,
+}
+
+impl Default for MemeticParams {
+    fn default() -> Self {
+        MemeticParams {
+            generations: 1000,
+            tabu_iterations: 1000,
+        }
+    }
+}
+
+/// Runs a memetic algorithm: the Genetic Algorithm searching for conflict-free colorings with a
+/// fixed number of colors `k`, where every offspring is improved by a tabu search (TabuCol) of at
+/// most `tabu_iterations` before joining the population, instead of being mutated.
+///
+/// The population, the selection and the crossover follow `genetic_params` (its mode and repair
+/// strategy aside, since the offspring keep their conflicts for the tabu search). As in the fixed-k
+/// mode of the Genetic Algorithm, whenever an offspring has no conflicts, `k` is decreased and the
+/// whole population is squashed into the new number of colors.
+pub fn memetic(
+    graph: &AdjList,
+    genetic_params: &GeneticParams,
+    params: &MemeticParams,
+    initial_population: Option<Vec<Vec<usize>>>,
+) -> Solution {
+    let incumbent = Incumbent::new(genetic_params.lower_bound);
+    let mut population = generate_population(graph, genetic_params, initial_population);
+    let (mut best, mut colors) = population[0].clone();
+
+    progress::record_colors_from(Phase::Construction, best);
+    incumbent.offer(best);
+
+    // Graphs without edges can't do better
+    if best <= 1 {
+        return (best, colors);
+    }
+
+    let mut k = best - 1;
+    squash_population(graph, &mut population, k);
+
+    for _ in 0..params.generations {
+        if k == 0 || incumbent.is_optimal() || progress::stop_requested() {
+            break;
+        }
+
+        for _ in 0..genetic_params.offsprings_per_generation {
+            let (p1, p2) = select(
+                &population,
+                genetic_params.population_size,
+                genetic_params.selected_population_ratio,
+            );
+
+            let mut offspring = recombine(graph, &p1, &p2, genetic_params.crossover);
+            let crossed_conflicts = count_conflicts(graph, &offspring);
+            let conflicts = tabucol(graph, &mut offspring, k, params.tabu_iterations);
+
+            if conflicts == 0 {
+                let phase = match crossed_conflicts {
+                    0 => Phase::Crossover,
+                    _ => Phase::LocalSearch,
+                };
+
+                progress::record_colors_from(phase, count_colors(&offspring));
+            }
+
+            population.push((conflicts, offspring));
+        }
+
+        population.sort();
+
+        replace(&mut population, genetic_params.population_size);
+
+        invariants::check_population(graph, &population, GeneticMode::FixedK);
+
+        progress::record_iteration();
+        progress::record_conflicts(population[0].0);
+
+        if population[0].0 == 0 {
+            best = count_colors(&population[0].1);
+            colors = population[0].1.clone();
+
+            progress::record_colors(best);
+            incumbent.offer(best);
+
+            k = best - 1;
+            if k > 0 {
+                squash_population(graph, &mut population, k);
+            }
+        }
+    }
+
+    relabel_colors(&mut colors);
+
+    (best, colors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::solve_fixtures_optimally;
+
+    #[test]
+    fn test_memetic() {
+        solve_fixtures_optimally(|fixture| {
+            let genetic_params = GeneticParams {
+                population_size: 10,
+                lower_bound: Some(fixture.chromatic_number),
+                ..Default::default()
+            };
+            let params = MemeticParams {
+                generations: 50,
+                tabu_iterations: 100,
+            };
+
+            memetic(&fixture.graph, &genetic_params, &params, None)
+        });
+    }
+}
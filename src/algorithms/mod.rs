@@ -1,11 +1,225 @@
+use crate::args::Algorithm;
 use crate::graph::adj_list::AdjList;
-use std::collections::HashSet;
+use crate::rng::rng;
+use rand::seq::SliceRandom;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 
+pub mod aco;
+pub mod bandit;
+pub mod decision;
+pub mod diverse;
+pub mod exact;
 pub mod genetic;
 pub mod grasp;
 pub mod grasp_pr;
+pub mod greedy;
+pub mod hea;
+pub mod hybrid;
+#[cfg(feature = "ilp")]
+pub mod ilp;
+pub mod incumbent;
+mod invariants;
+pub mod kempe;
+pub mod lns;
+pub mod memetic;
+pub mod multilevel;
+mod neighborhood;
+pub mod pareto;
+pub mod partialcol;
+pub mod partition;
+pub mod portfolio;
+pub mod recolor;
+#[cfg(feature = "sat")]
+pub mod sat;
+pub mod tabucol;
+pub mod vns;
+pub mod xrlf;
+pub mod zykov;
 
-type Solution = (usize, Vec<usize>);
+pub type Solution = (usize, Vec<usize>);
+
+/// Runs `algorithm` on `graph` with its default parameters, stopping once `lower_bound` (if any)
+/// is reached.
+pub fn solve_with_defaults(
+    graph: &AdjList,
+    algorithm: &Algorithm,
+    lower_bound: Option<usize>,
+) -> Solution {
+    solve_seeded(graph, algorithm, lower_bound, None)
+}
+
+/// Like [`solve_with_defaults`], but starting the Genetic Algorithm (on its own, after GRASP or
+/// as part of the memetic algorithm) from the colorings of `population` (the other algorithms
+/// build their own colorings, so they ignore it).
+pub fn solve_seeded(
+    graph: &AdjList,
+    algorithm: &Algorithm,
+    lower_bound: Option<usize>,
+    population: Option<Vec<Vec<usize>>>,
+) -> Solution {
+    let grasp_params = grasp::GraspParams {
+        lower_bound,
+        ..Default::default()
+    };
+
+    match algorithm {
+        Algorithm::Genetic => {
+            let params = genetic::GeneticParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            genetic::genetic(graph, &params, population).0
+        }
+        Algorithm::Grasp => grasp::grasp_wrapper(graph, &grasp_params),
+        Algorithm::GraspPR => grasp_pr::grasp_path_relinking(
+            graph,
+            &grasp_params,
+            &grasp_pr::PathRelinkingParams::default(),
+        ),
+        Algorithm::GraspGA => hybrid::grasp_ga(
+            graph,
+            &grasp_params,
+            &genetic::GeneticParams {
+                lower_bound,
+                ..Default::default()
+            },
+            None,
+            population,
+        ),
+        Algorithm::Bandit => {
+            let params = bandit::BanditParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            bandit::bandit(graph, &params).0
+        }
+        Algorithm::Xrlf => {
+            let params = xrlf::XrlfParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            xrlf::xrlf(graph, &params)
+        }
+        Algorithm::Portfolio => portfolio::portfolio(
+            graph,
+            &grasp_params,
+            &genetic::GeneticParams {
+                lower_bound,
+                ..Default::default()
+            },
+        ),
+        Algorithm::Tabu => {
+            let params = tabucol::TabuParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            tabucol::tabu(graph, &params)
+        }
+        #[cfg(feature = "sat")]
+        Algorithm::Sat => {
+            let params = sat::SatParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            sat::sat(graph, &params).0
+        }
+        Algorithm::Aco => {
+            let params = aco::AcoParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            aco::aco(graph, &params)
+        }
+        Algorithm::Memetic => memetic::memetic(
+            graph,
+            &genetic::GeneticParams {
+                lower_bound,
+                ..Default::default()
+            },
+            &memetic::MemeticParams::default(),
+            population,
+        ),
+        Algorithm::Hea => {
+            let params = hea::HeaParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            hea::hea(graph, &params)
+        }
+        Algorithm::Vns => {
+            let params = vns::VnsParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            vns::vns(graph, &params)
+        }
+        Algorithm::Lns => {
+            let params = lns::LnsParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            lns::lns(graph, &params)
+        }
+        Algorithm::PartialCol => {
+            let params = partialcol::PartialColParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            partialcol::partial_col(graph, &params)
+        }
+        Algorithm::Greedy => greedy::welsh_powell(graph),
+        Algorithm::GreedyPortfolio => {
+            greedy::greedy_portfolio(graph, &greedy::GreedyPortfolioParams::default()).0
+        }
+        Algorithm::Zykov => {
+            // Unbounded, the search can take hours on graphs whose cliques are far from the
+            // chromatic number, such as the Mycielski ones
+            let params = zykov::ZykovParams {
+                max_nodes: Some(100_000),
+                lower_bound,
+            };
+
+            zykov::zykov(graph, &params).0
+        }
+        Algorithm::Multilevel => {
+            let params = multilevel::MultilevelParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            multilevel::multilevel(graph, &params)
+        }
+        Algorithm::FixedK => {
+            let params = decision::FixedKParams {
+                lower_bound,
+                ..Default::default()
+            };
+
+            decision::fixed_k(graph, &params).0
+        }
+        #[cfg(feature = "ilp")]
+        Algorithm::Ilp => {
+            // Unbounded, the solver can take hours on graphs far from their lower bounds
+            let params = ilp::IlpParams {
+                time_limit: Some(std::time::Duration::from_secs(10)),
+                lower_bound,
+            };
+
+            ilp::ilp(graph, &params).0
+        }
+    }
+}
 
 /// Checks if the current color assignment of a node and his neighborhood is valid.
 fn is_valid_color_assignment(graph: &AdjList, solution: &[usize], node: usize) -> bool {
@@ -15,20 +229,191 @@ fn is_valid_color_assignment(graph: &AdjList, solution: &[usize], node: usize) -
 }
 
 /// Counts the number of colors used in a GCP solution.
-fn count_colors(solution: &[usize]) -> usize {
+pub fn count_colors(solution: &[usize]) -> usize {
     let colors: HashSet<&usize> = solution.iter().collect();
 
     colors.len()
 }
 
+/// Counts the number of edges in `graph` whose endpoints share a color in `coloring`.
+fn count_conflicts(graph: &AdjList, coloring: &[usize]) -> usize {
+    graph
+        .adj_list()
+        .iter()
+        .enumerate()
+        .map(|(u, neighbors)| {
+            neighbors
+                .iter()
+                .filter(|v| u < **v && coloring[u] == coloring[**v])
+                .count()
+        })
+        .sum()
+}
+
+/// Counts the neighbors of `vertex` with each color in `0..=max_color` of `coloring`, indexed by
+/// color. Neighbors with larger colors can't conflict with any of these, so they aren't counted.
+fn neighbor_color_counts(
+    graph: &AdjList,
+    coloring: &[usize],
+    vertex: usize,
+    max_color: usize,
+) -> Vec<usize> {
+    let mut counts = vec![0; max_color + 1];
+
+    for neighbor in graph.adj_list()[vertex].iter() {
+        if let Some(count) = counts.get_mut(coloring[*neighbor]) {
+            *count += 1;
+        }
+    }
+
+    counts
+}
+
+/// Returns the color in `1..=k` with the fewest neighbors of `vertex` in `coloring`.
+///
+/// Ties are broken randomly.
+fn least_conflicting_color(graph: &AdjList, coloring: &[usize], vertex: usize, k: usize) -> usize {
+    let counts = neighbor_color_counts(graph, coloring, vertex, k);
+    let fewest = counts[1..].iter().min().copied().unwrap_or(0);
+    let candidates: Vec<usize> = (1..=k).filter(|color| counts[*color] == fewest).collect();
+
+    *candidates.choose(&mut rng()).unwrap()
+}
+
+/// Moves each vertex of `coloring` with a color above `k`, in order, to its least conflicting color
+/// in `1..=k`.
+fn squash_colors(graph: &AdjList, coloring: &mut [usize], k: usize) {
+    for vertex in 0..coloring.len() {
+        if coloring[vertex] > k {
+            coloring[vertex] = least_conflicting_color(graph, coloring, vertex, k);
+        }
+    }
+}
+
+/// Relabels the colors of `coloring` to `1..=c`, in order of first appearance.
+///
+/// Each class ends up labeled by the rank of its smallest vertex, so this is the canonical form of
+/// the partition: colorings with the same classes are equal once relabeled.
+pub fn relabel_colors(coloring: &mut [usize]) {
+    let mut relabel = HashMap::new();
+
+    for color in coloring.iter_mut() {
+        let next_label = relabel.len() + 1;
+        *color = *relabel.entry(*color).or_insert(next_label);
+    }
+}
+
+/// The classes of `coloring` in canonical form: each one sorted, in order of their smallest
+/// vertex (see [`relabel_colors`]).
+pub fn canonical_classes(coloring: &[usize]) -> Vec<Vec<usize>> {
+    let mut canonical = coloring.to_vec();
+    relabel_colors(&mut canonical);
+
+    let mut classes = vec![Vec::new(); count_colors(&canonical)];
+    for (v, color) in canonical.iter().enumerate() {
+        classes[color - 1].push(v);
+    }
+
+    classes
+}
+
+/// Relabels the classes of `coloring` so they match the classes of `reference`, which allows
+/// comparing the two partitions regardless of their color labels.
+///
+/// The matching is greedy: pairs of classes are matched in decreasing order of overlap (i.e., the
+/// number of vertices they share). Classes of `coloring` left unmatched get new labels, larger
+/// than any label of `reference`.
+pub fn match_colors(reference: &[usize], coloring: &[usize]) -> Vec<usize> {
+    let mut overlaps: HashMap<(usize, usize), usize> = HashMap::new();
+
+    for (reference_color, color) in reference.iter().zip(coloring.iter()) {
+        *overlaps.entry((*color, *reference_color)).or_default() += 1;
+    }
+
+    let mut overlaps: Vec<((usize, usize), usize)> = overlaps.into_iter().collect();
+
+    // Largest overlaps first, with ties broken by the labels for determinism
+    overlaps.sort_by_key(|(pair, overlap)| (Reverse(*overlap), *pair));
+
+    let mut relabel: HashMap<usize, usize> = HashMap::new();
+    let mut used: HashSet<usize> = HashSet::new();
+
+    for ((color, reference_color), _) in overlaps {
+        if relabel.contains_key(&color) || used.contains(&reference_color) {
+            continue;
+        }
+
+        relabel.insert(color, reference_color);
+        used.insert(reference_color);
+    }
+
+    let mut next_label = reference.iter().max().copied().unwrap_or(0);
+
+    coloring
+        .iter()
+        .map(|color| {
+            *relabel.entry(*color).or_insert_with(|| {
+                next_label += 1;
+                next_label
+            })
+        })
+        .collect()
+}
+
+/// Moves vertices out of the classes of `coloring` with more than `max_size` vertices, so every
+/// class has at most `max_size` vertices and the coloring stays valid.
+///
+/// Each excess vertex goes to the first class with room and none of its neighbors, or to a new
+/// class if there's none. Returns the number of colors of the resulting coloring.
+pub fn cap_class_sizes(graph: &AdjList, coloring: &mut [usize], max_size: usize) -> usize {
+    assert!(
+        max_size > 0,
+        "classes must have room for at least one vertex"
+    );
+
+    relabel_colors(coloring);
+
+    let mut sizes = vec![0; count_colors(coloring) + 1];
+    for color in coloring.iter() {
+        sizes[*color] += 1;
+    }
+
+    for v in 0..coloring.len() {
+        if sizes[coloring[v]] <= max_size {
+            continue;
+        }
+
+        let mut is_blocked = vec![false; sizes.len()];
+        for u in graph.adj_list()[v].iter() {
+            is_blocked[coloring[*u]] = true;
+        }
+
+        let target = (1..sizes.len())
+            .find(|color| !is_blocked[*color] && sizes[*color] < max_size)
+            .unwrap_or_else(|| {
+                sizes.push(0);
+                sizes.len() - 1
+            });
+
+        sizes[coloring[v]] -= 1;
+        sizes[target] += 1;
+        coloring[v] = target;
+    }
+
+    sizes.len() - 1
+}
+
 /// Checks if `coloring` is valid for `graph`.
-fn is_coloring_valid(graph: &AdjList, coloring: &[usize]) -> bool {
+pub fn is_coloring_valid(graph: &AdjList, coloring: &[usize]) -> bool {
     (0..graph.num_vertices()).all(|x| is_valid_color_assignment(graph, coloring, x))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::{arbitrary_graph, NUM_CASES};
+    use clap::ValueEnum;
+    use proptest::prelude::*;
 
     #[test]
     fn test_count_colors() {
@@ -50,6 +435,66 @@ mod tests {
         assert!(!is_valid_color_assignment(&graph, &[1, 2, 2, 1], 2));
     }
 
+    #[test]
+    fn test_count_conflicts() {
+        let graph = AdjList::complete(4);
+
+        assert_eq!(count_conflicts(&graph, &[1, 2, 3, 4]), 0);
+        assert_eq!(count_conflicts(&graph, &[1, 1, 2, 2]), 2);
+        assert_eq!(count_conflicts(&graph, &[1, 1, 1, 1]), 6);
+    }
+
+    #[test]
+    fn test_cap_class_sizes() {
+        // An empty graph fits in a single class, unless the classes are capped
+        let graph = AdjList::new(5);
+        let mut coloring = vec![1; 5];
+
+        assert_eq!(cap_class_sizes(&graph, &mut coloring, 2), 3);
+        assert!((1..=3).all(|c| coloring.iter().filter(|x| **x == c).count() <= 2));
+
+        let graph = crate::testing::myciel4();
+        let mut coloring = grasp::grasp_wrapper(&graph, &Default::default()).1;
+        let num_colors = cap_class_sizes(&graph, &mut coloring, 4);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+        assert_eq!(count_colors(&coloring), num_colors);
+        assert!((1..=num_colors).all(|c| coloring.iter().filter(|x| **x == c).count() <= 4));
+    }
+
+    #[test]
+    fn test_relabel_colors() {
+        let mut coloring = vec![7, 3, 7, 9, 3];
+
+        relabel_colors(&mut coloring);
+
+        assert_eq!(coloring, vec![1, 2, 1, 3, 2]);
+    }
+
+    #[test]
+    fn test_canonical_classes() {
+        let classes = canonical_classes(&[7, 3, 7, 9, 3]);
+
+        assert_eq!(classes, vec![vec![0, 2], vec![1, 4], vec![3]]);
+        // The same partition, with other labels
+        assert_eq!(canonical_classes(&[2, 5, 2, 1, 5]), classes);
+        assert_ne!(canonical_classes(&[2, 5, 2, 5, 1]), classes);
+        assert!(canonical_classes(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_match_colors() {
+        // The same partition with permuted labels
+        assert_eq!(match_colors(&[1, 1, 2, 3], &[3, 3, 1, 2]), vec![1, 1, 2, 3]);
+
+        // Class {0, 1, 2} overlaps the most with class 1 of the reference.
+        // The remaining class only overlaps with 2, which is still available
+        assert_eq!(match_colors(&[1, 1, 2, 2], &[5, 5, 5, 7]), vec![1, 1, 1, 2]);
+
+        // More classes than the reference
+        assert_eq!(match_colors(&[1, 1, 1], &[1, 2, 3]), vec![1, 2, 3]);
+    }
+
     #[test]
     fn test_is_coloring_valid() {
         let mut graph = AdjList::new(4);
@@ -61,4 +506,188 @@ mod tests {
         assert!(is_coloring_valid(&graph, &[1, 2, 3, 1]));
         assert!(!is_coloring_valid(&graph, &[1, 2, 2, 1]));
     }
+
+    #[test]
+    fn test_squash_colors() {
+        // A path 0---1---2---3 colored with 4 colors
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+
+        let mut coloring = vec![1, 2, 3, 4];
+
+        squash_colors(&graph, &mut coloring, 2);
+
+        assert_eq!(coloring, [1, 2, 1, 2]);
+
+        // Ties between the least conflicting colors are broken randomly
+        let mut coloring = vec![1, 1, 3];
+
+        squash_colors(&AdjList::new(3), &mut coloring, 2);
+
+        assert!((1..=2).contains(&coloring[2]));
+    }
+
+    /// Runs `algorithm` on `graph` with parameters small enough for the property-based tests, or
+    /// with its defaults if they're already cheap.
+    fn solve_quickly(graph: &AdjList, algorithm: &Algorithm) -> Solution {
+        let grasp_params = grasp::GraspParams {
+            grasp_iterations: 5,
+            color_iterations: 5,
+            ..Default::default()
+        };
+        let genetic_params = genetic::GeneticParams {
+            generations: 200,
+            population_size: 10,
+            ..Default::default()
+        };
+
+        match algorithm {
+            Algorithm::Genetic => genetic::genetic(graph, &genetic_params, None).0,
+            Algorithm::Grasp => grasp::grasp_wrapper(graph, &grasp_params),
+            Algorithm::GraspPR => grasp_pr::grasp_path_relinking(
+                graph,
+                &grasp_params,
+                &grasp_pr::PathRelinkingParams {
+                    num_solutions: 3,
+                    ..Default::default()
+                },
+            ),
+            Algorithm::GraspGA => {
+                hybrid::grasp_ga(graph, &grasp_params, &genetic_params, None, None)
+            }
+            Algorithm::Bandit => {
+                let params = bandit::BanditParams {
+                    budget: std::time::Duration::from_millis(50),
+                    ..Default::default()
+                };
+
+                bandit::bandit(graph, &params).0
+            }
+            Algorithm::Xrlf => {
+                let params = xrlf::XrlfParams {
+                    exact_limit: 8,
+                    ..Default::default()
+                };
+
+                xrlf::xrlf(graph, &params)
+            }
+            Algorithm::Portfolio => portfolio::portfolio(graph, &grasp_params, &genetic_params),
+            #[cfg(feature = "sat")]
+            Algorithm::Sat => {
+                let params = sat::SatParams {
+                    max_conflicts: Some(1000),
+                    lower_bound: None,
+                };
+
+                sat::sat(graph, &params).0
+            }
+            Algorithm::Memetic => {
+                let params = memetic::MemeticParams {
+                    generations: 20,
+                    tabu_iterations: 100,
+                };
+
+                memetic::memetic(graph, &genetic_params, &params, None)
+            }
+            Algorithm::Hea => {
+                let params = hea::HeaParams {
+                    generations: 20,
+                    tabu_iterations: 100,
+                    ..Default::default()
+                };
+
+                hea::hea(graph, &params)
+            }
+            Algorithm::Zykov => {
+                let params = zykov::ZykovParams {
+                    max_nodes: Some(1000),
+                    lower_bound: None,
+                };
+
+                zykov::zykov(graph, &params).0
+            }
+            Algorithm::Vns => {
+                let params = vns::VnsParams {
+                    iterations: 1000,
+                    ..Default::default()
+                };
+
+                vns::vns(graph, &params)
+            }
+            Algorithm::Lns => {
+                let params = lns::LnsParams {
+                    iterations: 1000,
+                    ..Default::default()
+                };
+
+                lns::lns(graph, &params)
+            }
+            Algorithm::Multilevel => {
+                let params = multilevel::MultilevelParams {
+                    coarsest_size: 10,
+                    refine_iterations: 100,
+                    ..Default::default()
+                };
+
+                multilevel::multilevel(graph, &params)
+            }
+            #[cfg(feature = "ilp")]
+            Algorithm::Ilp => {
+                let params = ilp::IlpParams {
+                    time_limit: Some(std::time::Duration::from_secs(1)),
+                    lower_bound: None,
+                };
+
+                ilp::ilp(graph, &params).0
+            }
+            _ => solve_with_defaults(graph, algorithm, None),
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(NUM_CASES))]
+
+        #[test]
+        fn test_algorithms_return_valid_colorings(graph in arbitrary_graph()) {
+            let max_degree = graph.adj_list().iter().map(Vec::len).max().unwrap();
+
+            for algorithm in Algorithm::value_variants() {
+                let (num_colors, coloring) = solve_quickly(&graph, algorithm);
+
+                prop_assert!(is_coloring_valid(&graph, &coloring), "{:?}", algorithm);
+                prop_assert_eq!(count_colors(&coloring), num_colors, "{:?}", algorithm);
+                prop_assert!(num_colors <= max_degree + 1, "{:?}", algorithm);
+            }
+        }
+    }
+
+    #[test]
+    fn test_algorithms_on_fixtures() {
+        use super::{genetic::*, grasp::*, grasp_pr::*};
+        use crate::testing::solve_fixtures;
+
+        let genetic_params = GeneticParams {
+            generations: 2000,
+            population_size: 20,
+            ..Default::default()
+        };
+
+        let grasp = solve_fixtures(|fixture| grasp_wrapper(&fixture.graph, &Default::default()));
+        let grasp_pr = solve_fixtures(|fixture| {
+            grasp_path_relinking(
+                &fixture.graph,
+                &GraspParams::default(),
+                &PathRelinkingParams::default(),
+            )
+        });
+        let ga = solve_fixtures(|fixture| genetic(&fixture.graph, &genetic_params, None).0);
+
+        for (fixture, num_colors) in grasp.into_iter().chain(grasp_pr).chain(ga) {
+            let max_degree = fixture.graph.adj_list().iter().map(Vec::len).max().unwrap();
+
+            assert!(num_colors <= max_degree + 1, "{}", fixture.name);
+        }
+    }
 }
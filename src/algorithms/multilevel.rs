@@ -0,0 +1,212 @@
+use super::{
+    relabel_colors, solve_with_defaults,
+    tabucol::{tabu_from, TabuParams},
+    Solution,
+};
+use crate::args::Algorithm;
+use crate::graph::adj_list::AdjList;
+use crate::progress;
+use crate::rng::rng;
+use rand::seq::SliceRandom;
+
+/// Coarsening stops once a level keeps more than this fraction of the vertices of the previous one.
+const MIN_SHRINK: f64 = 0.95;
+
+#[derive(Debug)]
+pub struct MultilevelParams {
+    /// Algorithm (with its default parameters) coloring the coarsest graph. The multilevel
+    /// algorithm itself stands for the default algorithm.
+    pub algorithm: Algorithm,
+    /// Coarsening stops once the graph has at most this many vertices.
+    pub coarsest_size: usize,
+    /// Maximum iterations of the tabu search refining each level, for each number of colors tried.
+    pub refine_iterations: usize,
+    /// Known lower bound on the number of colors: the search stops as soon as it's matched.
+    pub lower_bound: Option<usize>,
+}
+
+impl Default for MultilevelParams {
+    fn default() -> Self {
+        MultilevelParams {
+            algorithm: Algorithm::default(),
+            coarsest_size: 500,
+            refine_iterations: 1000,
+            lower_bound: None,
+        }
+    }
+}
+
+/// Colors `graph` with a multilevel scheme, for instances too large for the algorithms as a whole.
+///
+/// The graph is coarsened level by level, each level merging pairs of non-adjacent vertices (see
+/// [`coarsen`]), until it's small enough or stops shrinking. The coarsest graph is colored by
+/// `algorithm`, and the coloring is projected back one level at a time: each vertex takes the
+/// color of the vertex it was merged into, which keeps the coloring valid. At each level, the
+/// tabu search of [`tabu_from`] then tries to remove colors.
+pub fn multilevel(graph: &AdjList, params: &MultilevelParams) -> Solution {
+    // Each level is a coarse graph along with the vertex of it each vertex of the finer one went to
+    let mut levels: Vec<(AdjList, Vec<usize>)> = Vec::new();
+
+    loop {
+        let finer = levels.last().map_or(graph, |(coarse, _)| coarse);
+
+        if finer.num_vertices() <= params.coarsest_size || progress::stop_requested() {
+            break;
+        }
+
+        let (coarse, map) = coarsen(finer);
+
+        if coarse.num_vertices() as f64 > MIN_SHRINK * finer.num_vertices() as f64 {
+            break;
+        }
+
+        levels.push((coarse, map));
+    }
+
+    let algorithm = match params.algorithm {
+        Algorithm::Multilevel => Algorithm::default(),
+        ref algorithm => algorithm.clone(),
+    };
+    let coarsest = levels.last().map_or(graph, |(coarse, _)| coarse);
+    let mut best = solve_with_defaults(coarsest, &algorithm, params.lower_bound);
+    let tabu_params = TabuParams {
+        iterations: params.refine_iterations,
+        lower_bound: params.lower_bound,
+    };
+
+    for level in (0..levels.len()).rev() {
+        let finer = match level {
+            0 => graph,
+            _ => &levels[level - 1].0,
+        };
+        let coloring = levels[level].1.iter().map(|v| best.1[*v]).collect();
+
+        best = tabu_from(finer, (best.0, coloring), &tabu_params);
+    }
+
+    relabel_colors(&mut best.1);
+
+    best
+}
+
+/// Merges pairs of non-adjacent vertices of `graph`, going through the vertices in a random order
+/// and pairing each one left with the vertex left at distance 2 that shares the most neighbors
+/// with it (vertices with many common neighbors tend to share a color in good colorings).
+///
+/// Returns the coarse graph and, for each vertex, the coarse vertex it went to. Any coloring of the
+/// coarse graph gives a valid coloring of `graph` through it.
+fn coarsen(graph: &AdjList) -> (AdjList, Vec<usize>) {
+    let n = graph.num_vertices();
+    let adj_list = graph.adj_list();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.shuffle(&mut rng());
+
+    let mut mate: Vec<Option<usize>> = vec![None; n];
+    let mut is_neighbor = vec![false; n];
+    let mut common = vec![0; n];
+
+    for u in order {
+        if mate[u].is_some() {
+            continue;
+        }
+
+        for w in adj_list[u].iter() {
+            is_neighbor[*w] = true;
+        }
+
+        let mut candidates = Vec::new();
+        for w in adj_list[u].iter() {
+            for x in adj_list[*w].iter() {
+                if *x != u && !is_neighbor[*x] && mate[*x].is_none() {
+                    if common[*x] == 0 {
+                        candidates.push(*x);
+                    }
+                    common[*x] += 1;
+                }
+            }
+        }
+
+        let partner = candidates.iter().copied().max_by_key(|x| common[*x]);
+
+        for x in candidates {
+            common[x] = 0;
+        }
+        for w in adj_list[u].iter() {
+            is_neighbor[*w] = false;
+        }
+
+        // Vertices without a partner stay on their own
+        let partner = partner.unwrap_or(u);
+        mate[u] = Some(partner);
+        mate[partner] = Some(u);
+    }
+
+    let mut map = vec![0; n];
+    let mut num_coarse = 0;
+    for v in 0..n {
+        let partner = mate[v].unwrap();
+
+        if partner >= v {
+            map[v] = num_coarse;
+            map[partner] = num_coarse;
+            num_coarse += 1;
+        }
+    }
+
+    let mut edges: Vec<(usize, usize)> = graph
+        .edges()
+        .into_iter()
+        .map(|(a, b)| (map[a].min(map[b]), map[a].max(map[b])))
+        .collect();
+    edges.sort();
+    edges.dedup();
+
+    let mut coarse = AdjList::new(num_coarse);
+    for (a, b) in edges {
+        coarse.add_edge(a, b);
+    }
+
+    (coarse, map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::solve_fixtures_optimally;
+    use crate::testing::{arbitrary_graph, NUM_CASES};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_multilevel() {
+        solve_fixtures_optimally(|fixture| {
+            let params = MultilevelParams {
+                algorithm: Algorithm::Tabu,
+                coarsest_size: 4,
+                refine_iterations: 200,
+                lower_bound: Some(fixture.chromatic_number),
+            };
+
+            multilevel(&fixture.graph, &params)
+        });
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(NUM_CASES))]
+
+        #[test]
+        fn test_coarsen(graph in arbitrary_graph()) {
+            let (coarse, map) = coarsen(&graph);
+
+            prop_assert!(coarse.num_vertices() <= graph.num_vertices());
+            // Merged vertices aren't adjacent, and every edge survives
+            for (u, v) in graph.edges() {
+                prop_assert_ne!(map[u], map[v]);
+                prop_assert!(coarse.adj_list()[map[u]].contains(&map[v]));
+            }
+            // At most two vertices go to each coarse vertex
+            for c in 0..coarse.num_vertices() {
+                prop_assert!((1..=2).contains(&map.iter().filter(|v| **v == c).count()));
+            }
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! Moves shared by the local searches: recoloring a single vertex, dissolving a color class into
+//! the others, and converting between colorings and class lists. Also the min-conflicts heuristic,
+//! built on the same recoloring move.
+
+use super::kempe::bichromatic_chain;
+use super::neighbor_color_counts;
+use crate::graph::adj_list::AdjList;
+use crate::rng::rng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+/// Probability of the min-conflicts heuristic moving a vertex to a random color.
+const RANDOM_WALK: f64 = 0.1;
+
+/// Turn a "Class List" into a traditional coloring. A class list assigns each index in a vector to
+/// a vector of vertices, which represent a given color.
+pub(super) fn get_coloring_from_class_list(
+    num_vertices: usize,
+    class_list: &[Vec<usize>],
+) -> Vec<usize> {
+    let mut coloring: Vec<usize> = vec![0; num_vertices];
+
+    for (i, class) in class_list.iter().enumerate() {
+        for vertex in class {
+            assert_eq!(coloring[*vertex], 0);
+
+            coloring[*vertex] = i + 1;
+        }
+    }
+
+    coloring
+}
+
+/// Counts the number of forbidden edges in `graph` according to `class_list`.
+///
+/// Saves the corresponding vertices in a set.
+pub(super) fn get_forbidden_vertices(
+    graph: &AdjList,
+    class_list: &[Vec<usize>],
+) -> (usize, HashSet<usize>) {
+    let num_vertices = graph.num_vertices();
+    let adj_list = graph.adj_list();
+    let coloring = get_coloring_from_class_list(num_vertices, class_list);
+    let mut count = 0;
+    let mut forbidden = HashSet::new();
+    for (i, v) in adj_list.iter().enumerate() {
+        for j in v.iter() {
+            if coloring[i] == coloring[*j] {
+                count += 1;
+                forbidden.insert(i);
+                forbidden.insert(*j);
+            }
+        }
+    }
+    (count / 2, forbidden)
+}
+
+/// Counts the number of forbidden edges from `vertex` in `graph` according to `coloring`.
+pub(super) fn count_forbidden_per_vertex(
+    graph: &AdjList,
+    coloring: &[usize],
+    vertex: usize,
+) -> usize {
+    graph.adj_list()[vertex]
+        .iter()
+        .filter(|x| coloring[**x] == coloring[vertex])
+        .count()
+}
+
+/// Moves each vertex in `vertices` to the class of `class_list` where it has the fewest
+/// neighbors (ties broken randomly).
+pub(super) fn redistribute(graph: &AdjList, class_list: &mut [Vec<usize>], vertices: Vec<usize>) {
+    let mut coloring = get_coloring_from_class_list(graph.num_vertices(), class_list);
+
+    for vertex in vertices {
+        let mut conflicts = vec![0; class_list.len()];
+
+        for neighbor in graph.adj_list()[vertex].iter() {
+            if coloring[*neighbor] != 0 {
+                conflicts[coloring[*neighbor] - 1] += 1;
+            }
+        }
+
+        let fewest = conflicts.iter().min().copied().unwrap_or(0);
+        let best_fits: Vec<usize> = (0..class_list.len())
+            .filter(|index| conflicts[*index] == fewest)
+            .collect();
+        let index = *best_fits.choose(&mut rng()).unwrap();
+
+        class_list[index].push(vertex);
+        coloring[vertex] = index + 1;
+    }
+}
+
+/// The color in `1..=k` of `coloring` with the fewest neighbors of `vertex` (its current one, unless
+/// another has strictly fewer), along with that number of neighbors.
+pub(super) fn best_recolor(
+    graph: &AdjList,
+    coloring: &[usize],
+    vertex: usize,
+    k: usize,
+) -> (usize, usize) {
+    let neighbors = neighbor_color_counts(graph, coloring, vertex, k.max(coloring[vertex]));
+
+    let mut best = coloring[vertex];
+    for color in 1..=k {
+        if neighbors[color] < neighbors[best] {
+            best = color;
+        }
+    }
+
+    (best, neighbors[best])
+}
+
+/// The Kempe-chain interchange of `vertex` (see [`bichromatic_chain`]) with a color in `1..=k`
+/// that removes the most conflicts of `coloring`: the other color, the chain and the number of
+/// conflicts removed. `None` if no interchange removes any.
+pub(super) fn best_kempe_move(
+    graph: &AdjList,
+    coloring: &[usize],
+    vertex: usize,
+    k: usize,
+) -> Option<(usize, Vec<usize>, usize)> {
+    let mut best: Option<(usize, Vec<usize>, usize)> = None;
+
+    for other in (1..=k).filter(|other| *other != coloring[vertex]) {
+        let chain = bichromatic_chain(graph, coloring, vertex, other);
+        let in_chain: HashSet<&usize> = chain.iter().collect();
+        // Only the conflicts between the chain and the rest of the graph go away
+        let removed = chain
+            .iter()
+            .map(|v| {
+                graph.adj_list()[*v]
+                    .iter()
+                    .filter(|u| coloring[**u] == coloring[*v] && !in_chain.contains(u))
+                    .count()
+            })
+            .sum();
+
+        if removed > best.as_ref().map_or(0, |(_, _, removed)| *removed) {
+            best = Some((other, chain, removed));
+        }
+    }
+
+    best
+}
+
+/// Recolors conflicting vertices of `coloring` with their best color in `1..=k` (see
+/// [`best_recolor`]), while that reduces the conflicts. Returns the number of conflicting edges
+/// left.
+pub(super) fn recolor_descent(graph: &AdjList, coloring: &mut [usize], k: usize) -> usize {
+    let mut improved = true;
+
+    while improved {
+        improved = false;
+
+        let mut vertices: Vec<usize> = (0..coloring.len())
+            .filter(|v| count_forbidden_per_vertex(graph, coloring, *v) > 0)
+            .collect();
+        vertices.shuffle(&mut rng());
+
+        for vertex in vertices {
+            let current = count_forbidden_per_vertex(graph, coloring, vertex);
+            let (color, conflicts) = best_recolor(graph, coloring, vertex, k);
+
+            if conflicts < current {
+                coloring[vertex] = color;
+                improved = true;
+            }
+        }
+    }
+
+    (0..coloring.len())
+        .map(|v| count_forbidden_per_vertex(graph, coloring, v))
+        .sum::<usize>()
+        / 2
+}
+
+/// Tries to remove the conflicts of `coloring` (which uses the colors `1..=k`) with the
+/// min-conflicts heuristic (Minton et al., 1992), for at most `max_iterations` iterations.
+///
+/// At each iteration, a random conflicting vertex moves to a random color with probability
+/// `RANDOM_WALK`, and to its least conflicting color otherwise (ties broken randomly), even if
+/// that's the one it has.
+///
+/// `coloring` is updated to the best coloring found. Returns its number of conflicting edges.
+pub(super) fn min_conflicts(
+    graph: &AdjList,
+    coloring: &mut [usize],
+    k: usize,
+    max_iterations: usize,
+) -> usize {
+    let n = graph.num_vertices();
+    let adj_list = graph.adj_list();
+    let mut rng = rng();
+
+    // `gamma[v][c]` is the number of neighbors of `v` colored with `c`
+    let mut gamma = vec![vec![0; k + 1]; n];
+    for (v, neighbors) in adj_list.iter().enumerate() {
+        for u in neighbors {
+            gamma[v][coloring[*u]] += 1;
+        }
+    }
+
+    let mut conflicts: usize = (0..n).map(|v| gamma[v][coloring[v]]).sum::<usize>() / 2;
+    let mut best_conflicts = conflicts;
+    let mut best_coloring = coloring.to_vec();
+
+    for _ in 0..max_iterations {
+        if conflicts == 0 {
+            break;
+        }
+
+        let conflicting: Vec<usize> = (0..n).filter(|v| gamma[*v][coloring[*v]] > 0).collect();
+        let v = *conflicting.choose(&mut rng).unwrap();
+        let previous = coloring[v];
+
+        let color = match rng.gen_bool(RANDOM_WALK) {
+            true => rng.gen_range(1..=k),
+            false => {
+                let fewest = (1..=k).map(|c| gamma[v][c]).min().unwrap();
+                let colors: Vec<usize> = (1..=k).filter(|c| gamma[v][*c] == fewest).collect();
+                *colors.choose(&mut rng).unwrap()
+            }
+        };
+
+        conflicts = conflicts + gamma[v][color] - gamma[v][previous];
+        coloring[v] = color;
+
+        for u in adj_list[v].iter() {
+            gamma[*u][previous] -= 1;
+            gamma[*u][color] += 1;
+        }
+
+        if conflicts < best_conflicts {
+            best_conflicts = conflicts;
+            best_coloring.copy_from_slice(coloring);
+        }
+    }
+
+    coloring.copy_from_slice(&best_coloring);
+
+    best_conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redistribute() {
+        let graph = AdjList::complete(4);
+        let mut class_list = vec![vec![0, 1], vec![2]];
+
+        redistribute(&graph, &mut class_list, vec![3]);
+
+        assert_eq!(class_list, vec![vec![0, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn test_get_forbidden_vertices() {
+        // The complete graph
+        let graph = AdjList::complete(5);
+        let color_classes = vec![vec![0], vec![1], vec![2, 3, 4]];
+
+        let (count, forbidden) = get_forbidden_vertices(&graph, &color_classes);
+
+        assert_eq!(forbidden, HashSet::from([2, 3, 4]));
+        assert_eq!(count, 3)
+    }
+
+    #[test]
+    fn test_get_coloring_from_class_list() {
+        let class_list = vec![vec![0], vec![1, 2], vec![3]];
+        let coloring = get_coloring_from_class_list(4, &class_list);
+
+        assert_eq!(coloring, [1, 2, 2, 3])
+    }
+
+    #[test]
+    fn test_count_forbidden_per_vertex() {
+        let graph = AdjList::complete(5);
+        let coloring = [1, 1, 1, 1, 1];
+
+        let num_forbidden = count_forbidden_per_vertex(&graph, &coloring, 1);
+
+        assert_eq!(num_forbidden, 4);
+    }
+
+    #[test]
+    fn test_best_recolor() {
+        // The path 0 - 1 - 2, with every vertex colored the same
+        let mut graph = AdjList::new(3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        let coloring = [1, 1, 1];
+
+        assert_eq!(best_recolor(&graph, &coloring, 1, 2), (2, 0));
+        // Vertices keep their color on ties
+        assert_eq!(best_recolor(&graph, &[1, 2, 1], 0, 1), (1, 0));
+
+        let mut coloring = coloring.to_vec();
+        assert_eq!(recolor_descent(&graph, &mut coloring, 2), 0);
+        assert_eq!(coloring[0], coloring[2]);
+        assert_ne!(coloring[0], coloring[1]);
+    }
+
+    #[test]
+    fn test_min_conflicts() {
+        let graph = crate::testing::cycle(4);
+        let mut coloring = vec![1, 1, 1, 1];
+
+        assert_eq!(min_conflicts(&graph, &mut coloring, 2, 1000), 0);
+        assert!(crate::algorithms::is_coloring_valid(&graph, &coloring));
+        assert!(coloring.iter().all(|color| (1..=2).contains(color)));
+
+        // Without any iterations, the coloring stays put
+        let mut coloring = vec![1, 1, 1, 1];
+        assert_eq!(min_conflicts(&graph, &mut coloring, 2, 0), 4);
+        assert_eq!(coloring, [1, 1, 1, 1]);
+    }
+}
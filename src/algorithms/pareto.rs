@@ -0,0 +1,303 @@
+use super::{
+    count_colors,
+    genetic::{coloring_upper_bound, crossover, generate_individual, mutate, GeneticParams},
+    relabel_colors,
+};
+use crate::graph::adj_list::AdjList;
+use crate::progress;
+use crate::rng::rng;
+use rand::Rng;
+use std::collections::{HashMap, HashSet};
+
+/// Objective optimized along with the number of colors.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Objective {
+    /// Difference between the sizes of the largest and the smallest color classes.
+    Imbalance,
+    /// Sum of the (1-indexed) colors of the vertices, with the largest classes taking the
+    /// smallest colors.
+    SumOfColors,
+}
+
+/// A coloring along with its number of colors and its value for the other objective.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Member {
+    pub num_colors: usize,
+    pub objective: usize,
+    pub coloring: Vec<usize>,
+}
+
+impl Member {
+    /// Evaluates `coloring`, relabeling its colors: by first appearance, or by decreasing size of
+    /// their classes for [`Objective::SumOfColors`] (which minimizes the sum for the partition).
+    pub fn new(mut coloring: Vec<usize>, objective: Objective) -> Self {
+        relabel_colors(&mut coloring);
+
+        let num_colors = count_colors(&coloring);
+        let mut sizes = vec![0; num_colors];
+        for color in coloring.iter() {
+            sizes[color - 1] += 1;
+        }
+
+        let objective = match objective {
+            Objective::Imbalance => {
+                sizes.iter().max().unwrap_or(&0) - sizes.iter().min().unwrap_or(&0)
+            }
+            Objective::SumOfColors => {
+                let mut colors: Vec<usize> = (1..=num_colors).collect();
+                colors.sort_by_key(|color| std::cmp::Reverse(sizes[color - 1]));
+
+                let labels: HashMap<usize, usize> = colors
+                    .into_iter()
+                    .enumerate()
+                    .map(|(label, color)| (color, label + 1))
+                    .collect();
+                coloring.iter_mut().for_each(|color| *color = labels[color]);
+
+                coloring.iter().sum()
+            }
+        };
+
+        Member {
+            num_colors,
+            objective,
+            coloring,
+        }
+    }
+
+    fn dominates(&self, other: &Member) -> bool {
+        self.num_colors <= other.num_colors
+            && self.objective <= other.objective
+            && (self.num_colors < other.num_colors || self.objective < other.objective)
+    }
+}
+
+/// Sorts `members` into non-dominated fronts (as in NSGA-II), returning the rank (the index of the
+/// front, 0 being the best) and the crowding distance within its front of each member.
+pub fn rank(members: &[Member]) -> (Vec<usize>, Vec<f64>) {
+    let n = members.len();
+    let mut dominated_by = vec![0; n];
+    let mut dominates: Vec<Vec<usize>> = vec![Vec::new(); n];
+
+    for i in 0..n {
+        for j in 0..n {
+            if members[i].dominates(&members[j]) {
+                dominates[i].push(j);
+                dominated_by[j] += 1;
+            }
+        }
+    }
+
+    let mut ranks = vec![0; n];
+    let mut crowding = vec![0.0; n];
+    let mut front: Vec<usize> = (0..n).filter(|i| dominated_by[*i] == 0).collect();
+    let mut current = 0;
+
+    while !front.is_empty() {
+        crowding_distances(members, &front, &mut crowding);
+
+        let mut next = Vec::new();
+        for i in front {
+            ranks[i] = current;
+
+            for j in dominates[i].iter() {
+                dominated_by[*j] -= 1;
+                if dominated_by[*j] == 0 {
+                    next.push(*j);
+                }
+            }
+        }
+
+        front = next;
+        current += 1;
+    }
+
+    (ranks, crowding)
+}
+
+/// The crowding distance of each member of `front`: how far its neighbors in the front are, over
+/// both objectives. The members at the ends of the front are always kept.
+fn crowding_distances(members: &[Member], front: &[usize], crowding: &mut [f64]) {
+    let objectives: [fn(&Member) -> usize; 2] = [|m| m.num_colors, |m| m.objective];
+
+    for objective in objectives {
+        let mut sorted = front.to_vec();
+        sorted.sort_by_key(|i| objective(&members[*i]));
+
+        let (first, last) = (sorted[0], sorted[sorted.len() - 1]);
+        let range = (objective(&members[last]) - objective(&members[first])).max(1) as f64;
+
+        crowding[first] = f64::INFINITY;
+        crowding[last] = f64::INFINITY;
+
+        for window in sorted.windows(3) {
+            let gap = objective(&members[window[2]]) - objective(&members[window[0]]);
+            crowding[window[1]] += gap as f64 / range;
+        }
+    }
+}
+
+/// Keeps the best `size` members: whole fronts, in order, then the least crowded members of the
+/// front that doesn't fit.
+///
+/// Members with the same values as a better one are only kept if there's room left, otherwise
+/// copies of the same point of the front take over the population.
+fn survivors(members: Vec<Member>, size: usize) -> Vec<Member> {
+    let (ranks, crowding) = rank(&members);
+    let mut order: Vec<usize> = (0..members.len()).collect();
+
+    order.sort_by(|a, b| {
+        ranks[*a]
+            .cmp(&ranks[*b])
+            .then(crowding[*b].total_cmp(&crowding[*a]))
+    });
+
+    let mut seen = HashSet::new();
+    let (mut order, copies): (Vec<usize>, Vec<usize>) = order
+        .into_iter()
+        .partition(|i| seen.insert((members[*i].num_colors, members[*i].objective)));
+    order.extend(copies);
+    order.truncate(size);
+
+    let mut members: Vec<Option<Member>> = members.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .map(|i| members[i].take().unwrap())
+        .collect()
+}
+
+/// Picks the better of two random members, by rank and then by crowding distance.
+fn tournament<'a>(members: &'a [Member], ranks: &[usize], crowding: &[f64]) -> &'a Member {
+    let a = rng().gen_range(0..members.len());
+    let b = rng().gen_range(0..members.len());
+
+    if (ranks[a], -crowding[a]) <= (ranks[b], -crowding[b]) {
+        &members[a]
+    } else {
+        &members[b]
+    }
+}
+
+/// Runs NSGA-II over valid colorings, minimizing both the number of colors and `objective`, with
+/// the operators of the Genetic Algorithm (in its min-colors mode). Returns the final Pareto front,
+/// with a coloring for each pair of values, by increasing number of colors.
+pub fn nsga2(graph: &AdjList, params: &GeneticParams, objective: Objective) -> Vec<Member> {
+    let upper_bound = coloring_upper_bound(graph);
+    let size = params.population_size.max(2);
+    let mut population: Vec<Member> = (0..size)
+        .map(|_| Member::new(generate_individual(graph, upper_bound), objective))
+        .collect();
+    let (mut ranks, mut crowding) = rank(&population);
+
+    for _ in 0..params.generations {
+        if progress::stop_requested() {
+            break;
+        }
+
+        let mut offsprings = Vec::with_capacity(params.offsprings_per_generation);
+
+        for _ in 0..params.offsprings_per_generation {
+            let p1 = tournament(&population, &ranks, &crowding).coloring.clone();
+            let p2 = tournament(&population, &ranks, &crowding).coloring.clone();
+
+            let mut offspring = crossover(graph, p1, p2, params.crossover, params.repair_strategy);
+            mutate(
+                graph,
+                &mut offspring,
+                upper_bound,
+                params.mutation_probability,
+            );
+
+            offsprings.push(Member::new(offspring, objective));
+        }
+
+        population.append(&mut offsprings);
+        population = survivors(population, size);
+        (ranks, crowding) = rank(&population);
+
+        progress::record_iteration();
+        if let Some(best) = population.iter().map(|member| member.num_colors).min() {
+            progress::record_colors(best);
+        }
+    }
+
+    let mut front: Vec<Member> = population
+        .into_iter()
+        .zip(ranks)
+        .filter(|(_, rank)| *rank == 0)
+        .map(|(member, _)| member)
+        .collect();
+
+    front.sort_by_key(|member| (member.num_colors, member.objective));
+    front.dedup_by_key(|member| (member.num_colors, member.objective));
+
+    front
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::is_coloring_valid;
+    use crate::testing;
+
+    #[test]
+    fn test_member() {
+        let member = Member::new(vec![3, 1, 1, 2, 1], Objective::Imbalance);
+
+        assert_eq!(member.coloring, vec![1, 2, 2, 3, 2]);
+        assert_eq!((member.num_colors, member.objective), (3, 2));
+
+        // The largest class takes the color 1
+        let member = Member::new(vec![3, 1, 1, 2, 1], Objective::SumOfColors);
+
+        assert_eq!(member.coloring[1..3], [1, 1]);
+        assert_eq!((member.num_colors, member.objective), (3, 8));
+    }
+
+    #[test]
+    fn test_rank() {
+        let member = |num_colors, objective| Member {
+            num_colors,
+            objective,
+            coloring: Vec::new(),
+        };
+        let members = [
+            member(3, 5),
+            member(4, 2),
+            member(4, 6),
+            member(5, 0),
+            member(3, 4),
+        ];
+        let (ranks, crowding) = rank(&members);
+
+        assert_eq!(ranks, vec![1, 0, 2, 0, 0]);
+        assert!(crowding[4].is_infinite() && crowding[3].is_infinite());
+        assert!(crowding[1].is_finite());
+    }
+
+    #[test]
+    fn test_nsga2() {
+        let graph = testing::myciel4();
+        let params = GeneticParams {
+            generations: 300,
+            population_size: 30,
+            offsprings_per_generation: 10,
+            ..Default::default()
+        };
+
+        for objective in [Objective::Imbalance, Objective::SumOfColors] {
+            let front = nsga2(&graph, &params, objective);
+
+            assert!(!front.is_empty());
+            for member in front.iter() {
+                assert!(is_coloring_valid(&graph, &member.coloring));
+                assert_eq!(Member::new(member.coloring.clone(), objective), *member);
+            }
+            // More colors are only worth it for a better value of the other objective
+            assert!(front
+                .windows(2)
+                .all(|pair| pair[0].num_colors < pair[1].num_colors
+                    && pair[0].objective > pair[1].objective));
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use super::{relabel_colors, Solution};
+use crate::graph::adj_list::AdjList;
+use crate::graph::bounds::{degeneracy_ordering, greedy_coloring};
+use crate::progress::{self, Phase};
+use crate::rng::rng;
+use rand::Rng;
+
+/// Tabu tenure is `A + lambda * uncolored vertices`, with `A` drawn from `0..TENURE_RANDOM`.
+const TENURE_RANDOM: usize = 10;
+const TENURE_LAMBDA: f64 = 0.6;
+
+#[derive(Debug)]
+pub struct PartialColParams {
+    /// Maximum iterations of the search for each number of colors tried.
+    pub iterations: usize,
+    /// Known lower bound on the number of colors: the search stops as soon as it's matched.
+    pub lower_bound: Option<usize>,
+}
+
+impl Default for PartialColParams {
+    fn default() -> Self {
+        PartialColParams {
+            iterations: 10000,
+            lower_bound: None,
+        }
+    }
+}
+
+/// Colors `graph` with PartialCol (Blöchliger and Zufferey, 2008), searching for a complete
+/// coloring with a fixed number of colors `k` at a time.
+///
+/// The search starts from a greedy coloring (in smallest-last order). Whenever a coloring with `k`
+/// colors is found, the vertices of its last color are uncolored, and [`partialcol`] tries to
+/// color them with `k - 1` colors. The best coloring is returned once a search fails.
+pub fn partial_col(graph: &AdjList, params: &PartialColParams) -> Solution {
+    let (_, ordering) = degeneracy_ordering(graph);
+    let mut coloring = greedy_coloring(graph, &ordering);
+    relabel_colors(&mut coloring);
+
+    let mut best = (coloring.iter().max().copied().unwrap_or(0), coloring);
+
+    progress::record_colors_from(Phase::Construction, best.0);
+
+    while best.0 > 1
+        && params
+            .lower_bound
+            .is_none_or(|lower_bound| best.0 > lower_bound)
+        && !progress::stop_requested()
+    {
+        let k = best.0 - 1;
+        let mut candidate: Vec<usize> = best
+            .1
+            .iter()
+            .map(|color| if *color > k { 0 } else { *color })
+            .collect();
+
+        let uncolored = partialcol(graph, &mut candidate, k, params.iterations);
+
+        progress::record_iteration();
+
+        if uncolored > 0 {
+            break;
+        }
+
+        relabel_colors(&mut candidate);
+        best = (candidate.iter().max().copied().unwrap_or(0), candidate);
+
+        progress::record_colors_from(Phase::LocalSearch, best.0);
+    }
+
+    best
+}
+
+/// Tries to color the uncolored vertices (with color 0) of the legal partial coloring `coloring`
+/// (which uses the colors `1..=k`) with the PartialCol metaheuristic, for at most `max_iterations`
+/// iterations.
+///
+/// Unlike [`super::tabucol::tabucol`], the coloring never has conflicts: each move colors an
+/// uncolored vertex and uncolors its neighbors with the same color instead. At each iteration, the
+/// best non-tabu move (i.e., the one uncoloring the fewest neighbors) is applied, even if it
+/// worsens the coloring. The uncolored neighbors can't go back to the color they just left for a
+/// number of iterations (the tabu tenure), unless doing so leads to the best coloring found so far
+/// (aspiration criterion).
+///
+/// `coloring` is updated to the best partial coloring found. Returns its number of uncolored
+/// vertices.
+pub fn partialcol(
+    graph: &AdjList,
+    coloring: &mut [usize],
+    k: usize,
+    max_iterations: usize,
+) -> usize {
+    let n = graph.num_vertices();
+    let adj_list = graph.adj_list();
+    let mut rng = rng();
+
+    // `gamma[v][c]` is the number of neighbors of `v` colored with `c`
+    let mut gamma = vec![vec![0; k + 1]; n];
+    // `tabu[v][c]` is the first iteration in which `v` can be colored with `c` again
+    let mut tabu = vec![vec![0; k + 1]; n];
+
+    for (v, neighbors) in adj_list.iter().enumerate() {
+        for u in neighbors {
+            gamma[v][coloring[*u]] += 1;
+        }
+    }
+
+    let mut uncolored: Vec<usize> = (0..n).filter(|v| coloring[*v] == 0).collect();
+    let mut best_uncolored = uncolored.len();
+    let mut best_coloring = coloring.to_vec();
+
+    for iteration in 0..max_iterations {
+        if uncolored.is_empty() {
+            break;
+        }
+
+        let mut best_delta = isize::MAX;
+        let mut best_moves: Vec<(usize, usize)> = Vec::new();
+
+        for (index, v) in uncolored.iter().enumerate() {
+            for c in 1..=k {
+                // Coloring `v` uncolors its neighbors with `c`
+                let delta = gamma[*v][c] as isize - 1;
+                let is_tabu = tabu[*v][c] > iteration;
+                let aspiration = (uncolored.len() as isize + delta) < best_uncolored as isize;
+
+                if is_tabu && !aspiration {
+                    continue;
+                }
+
+                if delta < best_delta {
+                    best_delta = delta;
+                    best_moves.clear();
+                }
+                if delta == best_delta {
+                    best_moves.push((index, c));
+                }
+            }
+        }
+
+        if best_moves.is_empty() {
+            continue;
+        }
+
+        let (index, c) = best_moves[rng.gen_range(0..best_moves.len())];
+        let v = uncolored.swap_remove(index);
+
+        set_color(graph, coloring, &mut gamma, v, c);
+
+        let tenure =
+            rng.gen_range(0..TENURE_RANDOM) + (TENURE_LAMBDA * uncolored.len() as f64) as usize;
+
+        for u in adj_list[v].iter() {
+            if coloring[*u] == c {
+                set_color(graph, coloring, &mut gamma, *u, 0);
+                uncolored.push(*u);
+                tabu[*u][c] = iteration + tenure + 1;
+            }
+        }
+
+        if uncolored.len() < best_uncolored {
+            best_uncolored = uncolored.len();
+            best_coloring.copy_from_slice(coloring);
+        }
+    }
+
+    coloring.copy_from_slice(&best_coloring);
+
+    best_uncolored
+}
+
+/// Changes the color of `vertex` to `color` (0 uncolors it), keeping `gamma` up to date.
+fn set_color(
+    graph: &AdjList,
+    coloring: &mut [usize],
+    gamma: &mut [Vec<usize>],
+    vertex: usize,
+    color: usize,
+) {
+    let previous = coloring[vertex];
+
+    coloring[vertex] = color;
+
+    for u in graph.adj_list()[vertex].iter() {
+        gamma[*u][previous] -= 1;
+        gamma[*u][color] += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        algorithms::{count_colors, is_coloring_valid},
+        testing,
+    };
+
+    #[test]
+    fn test_partialcol() {
+        // A cycle with 4 vertices, with every vertex uncolored
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 0);
+
+        let mut coloring = vec![0, 0, 0, 0];
+        let uncolored = partialcol(&graph, &mut coloring, 2, 100);
+
+        assert_eq!(uncolored, 0);
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        // There's no way to color the complete graph with fewer colors than vertices, but the
+        // search should still return a legal partial coloring with a single uncolored vertex
+        let graph = AdjList::complete(4);
+        let mut coloring = vec![0, 0, 0, 0];
+        let uncolored = partialcol(&graph, &mut coloring, 3, 100);
+
+        assert_eq!(uncolored, 1);
+        assert_eq!(coloring.iter().filter(|color| **color == 0).count(), 1);
+        assert_eq!(count_colors(&coloring), 4);
+    }
+
+    #[test]
+    fn test_partialcol_myciel() {
+        let graph = testing::myciel4();
+
+        // The chromatic number of myciel4 is 5
+        let mut coloring = vec![0; graph.num_vertices()];
+        let uncolored = partialcol(&graph, &mut coloring, 5, 10000);
+
+        assert_eq!(uncolored, 0);
+        assert!(is_coloring_valid(&graph, &coloring));
+    }
+
+    #[test]
+    fn test_partial_col() {
+        testing::solve_fixtures_optimally(|fixture| {
+            let params = PartialColParams {
+                iterations: 1000,
+                lower_bound: Some(fixture.chromatic_number),
+            };
+
+            partial_col(&fixture.graph, &params)
+        });
+    }
+}
@@ -0,0 +1,260 @@
+use super::{relabel_colors, tabucol::tabucol_within, Solution};
+use crate::graph::adj_list::AdjList;
+use crate::progress;
+use std::collections::VecDeque;
+use std::thread;
+
+#[derive(Debug)]
+pub struct PartitionParams {
+    /// Number of parts the graph is split into, each colored on its own thread.
+    pub num_parts: usize,
+    /// Iterations of the local search (TabuCol, around the boundaries of the parts) removing the
+    /// conflicts between the parts.
+    pub merge_iterations: usize,
+}
+
+impl Default for PartitionParams {
+    fn default() -> Self {
+        PartitionParams {
+            num_parts: 4,
+            merge_iterations: 10000,
+        }
+    }
+}
+
+/// Splits the vertices of `graph` into at most `num_parts` parts of about the same size, growing
+/// each part with a breadth-first search so most of its vertices are close to each other (and
+/// few edges are left between the parts).
+pub fn bfs_partition(graph: &AdjList, num_parts: usize) -> Vec<Vec<usize>> {
+    let n = graph.num_vertices();
+    let part_size = n.div_ceil(num_parts.max(1)).max(1);
+    let mut is_assigned = vec![false; n];
+    let mut parts: Vec<Vec<usize>> = vec![Vec::new()];
+
+    for start in 0..n {
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(v) = queue.pop_front() {
+            if is_assigned[v] {
+                continue;
+            }
+            if parts.last().unwrap().len() == part_size {
+                parts.push(Vec::new());
+            }
+
+            is_assigned[v] = true;
+            parts.last_mut().unwrap().push(v);
+            queue.extend(graph.adj_list()[v].iter().filter(|u| !is_assigned[**u]));
+        }
+    }
+
+    parts.retain(|part| !part.is_empty());
+
+    parts
+}
+
+/// The subgraph of `graph` induced by `vertices`, with `vertices[i]` as its vertex `i`.
+pub fn induced_subgraph(graph: &AdjList, vertices: &[usize]) -> AdjList {
+    let mut index = vec![usize::MAX; graph.num_vertices()];
+    for (i, v) in vertices.iter().enumerate() {
+        index[*v] = i;
+    }
+
+    let mut subgraph = AdjList::new(vertices.len());
+    for (i, v) in vertices.iter().enumerate() {
+        for u in graph.adj_list()[*v].iter() {
+            if index[*u] != usize::MAX && i < index[*u] {
+                subgraph.add_edge(i, index[*u]);
+            }
+        }
+    }
+
+    subgraph
+}
+
+/// The vertices around the boundaries of `parts`: the ones with neighbors in other parts, and
+/// their neighbors. Every edge between two parts has both ends among them.
+pub fn boundary_region(graph: &AdjList, parts: &[Vec<usize>]) -> Vec<usize> {
+    let n = graph.num_vertices();
+    let mut part_of = vec![0; n];
+    for (i, part) in parts.iter().enumerate() {
+        for v in part {
+            part_of[*v] = i;
+        }
+    }
+
+    let mut in_region = vec![false; n];
+    for v in 0..n {
+        let neighbors = &graph.adj_list()[v];
+
+        if neighbors.iter().any(|u| part_of[*u] != part_of[v]) {
+            in_region[v] = true;
+            for u in neighbors {
+                in_region[*u] = true;
+            }
+        }
+    }
+
+    (0..n).filter(|v| in_region[*v]).collect()
+}
+
+/// Colors huge graphs by divide and conquer: `graph` is split into parts (see [`bfs_partition`]),
+/// each part is colored by `solve` on its own thread, and the colorings of the parts are merged.
+///
+/// The parts share the colors `1..=k`, `k` being the most colors a part needed, so only the edges
+/// between the parts may conflict. TabuCol then tries to remove these conflicts with `k` colors,
+/// recoloring only the vertices around the boundaries (see [`boundary_region`]); the vertices
+/// still in conflict take the smallest color none of their neighbors have.
+pub fn partition_and_merge(
+    graph: &AdjList,
+    params: &PartitionParams,
+    solve: impl Fn(&AdjList) -> Solution + Sync,
+) -> Solution {
+    let parts = bfs_partition(graph, params.num_parts);
+
+    let progress = progress::current();
+
+    let colorings: Vec<Vec<usize>> = thread::scope(|scope| {
+        let handles: Vec<_> = parts
+            .iter()
+            .map(|part| {
+                let (solve, progress) = (&solve, &progress);
+
+                scope.spawn(move || {
+                    let (_, mut coloring) = progress.run(|| solve(&induced_subgraph(graph, part)));
+                    relabel_colors(&mut coloring);
+                    coloring
+                })
+            })
+            .collect();
+
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let mut coloring = vec![0; graph.num_vertices()];
+    for (part, part_coloring) in parts.iter().zip(colorings) {
+        for (v, color) in part.iter().zip(part_coloring) {
+            coloring[*v] = color;
+        }
+    }
+
+    let k = coloring.iter().max().copied().unwrap_or(0);
+    let region = boundary_region(graph, &parts);
+    if tabucol_within(graph, &mut coloring, k, &region, params.merge_iterations) > 0 {
+        resolve_conflicts(graph, &mut coloring);
+    }
+
+    relabel_colors(&mut coloring);
+
+    (super::count_colors(&coloring), coloring)
+}
+
+/// Moves each vertex in conflict to the smallest color none of its neighbors have, which never
+/// creates new conflicts, so the coloring ends up valid.
+fn resolve_conflicts(graph: &AdjList, coloring: &mut [usize]) {
+    for v in 0..coloring.len() {
+        let neighbors = &graph.adj_list()[v];
+
+        if neighbors.iter().all(|u| coloring[*u] != coloring[v]) {
+            continue;
+        }
+
+        let mut is_blocked = vec![false; neighbors.len() + 2];
+        for u in neighbors {
+            if coloring[*u] < is_blocked.len() {
+                is_blocked[coloring[*u]] = true;
+            }
+        }
+
+        coloring[v] = (1..is_blocked.len()).find(|c| !is_blocked[*c]).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::grasp::{grasp_wrapper, GraspParams};
+    use crate::algorithms::is_coloring_valid;
+    use crate::testing::{self, solve_fixtures, solve_fixtures_optimally};
+
+    #[test]
+    fn test_bfs_partition() {
+        let graph = testing::cycle(10);
+        let parts = bfs_partition(&graph, 3);
+
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts.iter().map(Vec::len).collect::<Vec<_>>(), [4, 4, 2]);
+
+        let mut vertices: Vec<usize> = parts.concat();
+        vertices.sort();
+        assert_eq!(vertices, (0..10).collect::<Vec<_>>());
+
+        // The first part grows around the vertex 0, as a path of the cycle
+        let subgraph = induced_subgraph(&graph, &parts[0]);
+        assert_eq!(parts[0], [0, 1, 9, 2]);
+        assert_eq!(subgraph.edges(), [(0, 1), (0, 2), (1, 3)]);
+
+        assert_eq!(bfs_partition(&AdjList::new(2), 5).len(), 2);
+    }
+
+    #[test]
+    fn test_boundary_region() {
+        // A path 0 - ... - 7 split in the middle
+        let mut graph = AdjList::new(8);
+        for v in 0..7 {
+            graph.add_edge(v, v + 1);
+        }
+
+        let parts = vec![(0..4).collect(), (4..8).collect()];
+        assert_eq!(boundary_region(&graph, &parts), [2, 3, 4, 5]);
+
+        assert!(boundary_region(&graph, &[(0..8).collect()]).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_conflicts() {
+        let graph = AdjList::complete(4);
+        let mut coloring = vec![1, 1, 2, 2];
+
+        resolve_conflicts(&graph, &mut coloring);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+    }
+
+    #[test]
+    fn test_partition_and_merge() {
+        let params = PartitionParams {
+            num_parts: 3,
+            merge_iterations: 1000,
+        };
+
+        // The merge may need more colors than the parts, but the vertices left in conflict take the
+        // smallest color none of their neighbors have
+        let fixtures = solve_fixtures(|fixture| {
+            partition_and_merge(&fixture.graph, &params, |graph| {
+                grasp_wrapper(graph, &Default::default())
+            })
+        });
+        for (fixture, num_colors) in fixtures {
+            let max_degree = fixture.graph.adj_list().iter().map(Vec::len).max().unwrap();
+
+            assert!(num_colors <= max_degree + 1, "{}", fixture.name);
+        }
+
+        // A single part is colored as a whole, with nothing to merge
+        let params = PartitionParams {
+            num_parts: 1,
+            ..params
+        };
+        solve_fixtures_optimally(|fixture| {
+            let grasp_params = GraspParams {
+                lower_bound: Some(fixture.chromatic_number),
+                ..Default::default()
+            };
+
+            partition_and_merge(&fixture.graph, &params, |graph| {
+                grasp_wrapper(graph, &grasp_params)
+            })
+        });
+    }
+}
@@ -0,0 +1,66 @@
+use super::{
+    genetic::{genetic_shared, GeneticMode, GeneticParams},
+    grasp::{grasp_shared, GraspParams},
+    incumbent::Incumbent,
+    Solution,
+};
+use crate::graph::adj_list::AdjList;
+use crate::progress;
+use std::thread;
+
+/// Runs GRASP and the Genetic Algorithm (in its fixed-k mode) at once, sharing an [`Incumbent`]:
+/// every coloring GRASP finds lowers the `k` the Genetic Algorithm is after, and both stop as soon
+/// as either matches the lower bound. Returns the best coloring of the two.
+pub fn portfolio(
+    graph: &AdjList,
+    grasp_params: &GraspParams,
+    genetic_params: &GeneticParams,
+) -> Solution {
+    let incumbent = Incumbent::new(grasp_params.lower_bound.or(genetic_params.lower_bound));
+    let genetic_params = GeneticParams {
+        mode: GeneticMode::FixedK,
+        ..genetic_params.clone()
+    };
+
+    let progress = progress::current();
+
+    thread::scope(|scope| {
+        let genetic = scope
+            .spawn(|| progress.run(|| genetic_shared(graph, &genetic_params, None, &incumbent).0));
+        // GRASP finds nothing if it runs no iterations
+        let grasp = grasp_shared(graph, grasp_params, 1, &incumbent)
+            .into_iter()
+            .next();
+        let genetic = genetic.join().unwrap();
+
+        match grasp {
+            Some(grasp) => grasp.min(genetic),
+            None => genetic,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::solve_fixtures_optimally;
+
+    #[test]
+    fn test_portfolio() {
+        solve_fixtures_optimally(|fixture| {
+            let grasp_params = GraspParams {
+                grasp_iterations: 5,
+                lower_bound: Some(fixture.chromatic_number),
+                ..Default::default()
+            };
+            let genetic_params = GeneticParams {
+                generations: 2000,
+                population_size: 20,
+                lower_bound: Some(fixture.chromatic_number),
+                ..Default::default()
+            };
+
+            portfolio(&fixture.graph, &grasp_params, &genetic_params)
+        });
+    }
+}
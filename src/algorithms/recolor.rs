@@ -0,0 +1,296 @@
+use super::count_colors;
+use crate::graph::adj_list::AdjList;
+use crate::rng::rng;
+use rand::seq::SliceRandom;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug)]
+pub struct RecolorParams {
+    /// Distance from the endpoints of a new conflicting edge within which vertices may be
+    /// recolored to remove the conflict.
+    pub radius: usize,
+    /// Maximum number of moves of the local search removing a conflict.
+    pub max_iterations: usize,
+    /// Number of moves after which a vertex may take the color it left again.
+    pub tabu_tenure: usize,
+}
+
+impl Default for RecolorParams {
+    fn default() -> Self {
+        RecolorParams {
+            radius: 2,
+            max_iterations: 1000,
+            tabu_tenure: 7,
+        }
+    }
+}
+
+/// A graph along with a valid coloring, kept valid as edges are added and removed, without solving
+/// from scratch.
+///
+/// The colors of the vertices far from the changed edges never change, so the coloring stays
+/// stable over time (although some colors may end up unused).
+pub struct Recolorer {
+    graph: AdjList,
+    coloring: Vec<usize>,
+    params: RecolorParams,
+}
+
+impl Recolorer {
+    pub fn new(graph: AdjList, coloring: Vec<usize>, params: RecolorParams) -> Self {
+        assert_eq!(
+            graph.num_vertices(),
+            coloring.len(),
+            "the coloring must have a color for each vertex"
+        );
+        assert!(
+            super::is_coloring_valid(&graph, &coloring),
+            "the coloring must be valid"
+        );
+
+        Recolorer {
+            graph,
+            coloring,
+            params,
+        }
+    }
+
+    pub fn graph(&self) -> &AdjList {
+        &self.graph
+    }
+
+    pub fn coloring(&self) -> &[usize] {
+        &self.coloring
+    }
+
+    pub fn num_colors(&self) -> usize {
+        count_colors(&self.coloring)
+    }
+
+    /// Adds an isolated vertex, with the color 1, returning its index.
+    pub fn add_vertex(&mut self) -> usize {
+        self.coloring.push(1);
+
+        self.graph.add_vertex()
+    }
+
+    /// Adds the edge `(u, v)` (if it's new), repairing the coloring if `u` and `v` share a color.
+    ///
+    /// One of them takes a color none of its neighbors have, if there's one. Otherwise, a local
+    /// search recolors the vertices around them, with the colors in use. If it fails, `v` takes a
+    /// new color.
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        if u == v || self.graph.adj_list()[u].contains(&v) {
+            return;
+        }
+
+        self.graph.add_edge(u, v);
+
+        if self.coloring[u] != self.coloring[v] {
+            return;
+        }
+
+        let max_color = self.max_color();
+
+        for w in [u, v] {
+            if let Some(color) = self.free_color(w, max_color) {
+                self.coloring[w] = color;
+                return;
+            }
+        }
+
+        if !self.local_search(&[u, v], max_color) {
+            self.coloring[v] = max_color + 1;
+        }
+    }
+
+    /// Removes the edge `(u, v)`, if any. The coloring stays valid, but `u` and `v` leave the
+    /// largest color if they can, so the number of colors may go down.
+    pub fn remove_edge(&mut self, u: usize, v: usize) {
+        if !self.graph.adj_list()[u].contains(&v) {
+            return;
+        }
+
+        self.graph.sub_edge(u, v);
+
+        let max_color = self.max_color();
+
+        for w in [u, v] {
+            if self.coloring[w] != max_color {
+                continue;
+            }
+            if let Some(color) = self.free_color(w, max_color - 1) {
+                self.coloring[w] = color;
+            }
+        }
+    }
+
+    fn max_color(&self) -> usize {
+        self.coloring.iter().max().copied().unwrap_or(0)
+    }
+
+    /// The smallest color in `1..=max_color` none of the neighbors of `vertex` have.
+    fn free_color(&self, vertex: usize, max_color: usize) -> Option<usize> {
+        let mut is_blocked = vec![false; max_color + 1];
+
+        for u in self.graph.adj_list()[vertex].iter() {
+            if self.coloring[*u] <= max_color {
+                is_blocked[self.coloring[*u]] = true;
+            }
+        }
+
+        (1..=max_color).find(|color| !is_blocked[*color])
+    }
+
+    /// The vertices at distance at most `radius` from any of `sources`.
+    fn region(&self, sources: &[usize]) -> Vec<usize> {
+        let mut region: Vec<usize> = sources.to_vec();
+        let mut seen: HashSet<usize> = sources.iter().copied().collect();
+        let mut frontier = region.clone();
+
+        for _ in 0..self.params.radius {
+            let mut next = Vec::new();
+
+            for v in frontier {
+                for u in self.graph.adj_list()[v].iter() {
+                    if seen.insert(*u) {
+                        next.push(*u);
+                    }
+                }
+            }
+
+            region.extend(next.iter().copied());
+            frontier = next;
+        }
+
+        region
+    }
+
+    /// Removes the conflicts around `sources` by moving the vertices of their region among the
+    /// colors `1..=max_color` (min-conflicts, with a short tabu list so it doesn't cycle). Returns
+    /// whether it succeeded; the coloring is left untouched otherwise.
+    fn local_search(&mut self, sources: &[usize], max_color: usize) -> bool {
+        let region = self.region(sources);
+        let backup: Vec<usize> = region.iter().map(|v| self.coloring[*v]).collect();
+        // `tabu[(v, c)]` is the first move in which `v` can be colored with `c` again
+        let mut tabu: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut rng = rng();
+
+        for iteration in 0..self.params.max_iterations {
+            let conflicting: Vec<usize> = region
+                .iter()
+                .copied()
+                .filter(|v| self.conflicts(*v, self.coloring[*v]) > 0)
+                .collect();
+
+            let Some(vertex) = conflicting.choose(&mut rng).copied() else {
+                return true;
+            };
+
+            let current = self.coloring[vertex];
+            let candidates: Vec<(usize, usize)> = (1..=max_color)
+                .filter(|color| {
+                    *color != current
+                        && tabu
+                            .get(&(vertex, *color))
+                            .is_none_or(|end| *end <= iteration)
+                })
+                .map(|color| (self.conflicts(vertex, color), color))
+                .collect();
+
+            let Some(min_conflicts) = candidates.iter().map(|(conflicts, _)| *conflicts).min()
+            else {
+                continue;
+            };
+            let best: Vec<usize> = candidates
+                .into_iter()
+                .filter(|(conflicts, _)| *conflicts == min_conflicts)
+                .map(|(_, color)| color)
+                .collect();
+
+            self.coloring[vertex] = *best.choose(&mut rng).unwrap();
+            tabu.insert((vertex, current), iteration + self.params.tabu_tenure + 1);
+        }
+
+        for (v, color) in region.iter().zip(backup) {
+            self.coloring[*v] = color;
+        }
+
+        false
+    }
+
+    /// Number of neighbors of `vertex` colored with `color`.
+    fn conflicts(&self, vertex: usize, color: usize) -> usize {
+        self.graph.adj_list()[vertex]
+            .iter()
+            .filter(|u| self.coloring[**u] == color)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{grasp::grasp_wrapper, is_coloring_valid};
+    use crate::testing;
+
+    #[test]
+    fn test_add_edge() {
+        // A path 0 - 1 - 2, colored with 2 colors
+        let mut graph = AdjList::new(3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+
+        let mut recolorer = Recolorer::new(graph, vec![1, 2, 1], RecolorParams::default());
+
+        // Closing the triangle needs a new color
+        recolorer.add_edge(0, 2);
+        assert!(is_coloring_valid(recolorer.graph(), recolorer.coloring()));
+        assert_eq!(recolorer.num_colors(), 3);
+
+        // Adding it again changes nothing
+        recolorer.add_edge(2, 0);
+        assert_eq!(recolorer.graph().adj_list()[0].len(), 2);
+
+        recolorer.remove_edge(0, 2);
+        assert!(is_coloring_valid(recolorer.graph(), recolorer.coloring()));
+        assert_eq!(recolorer.num_colors(), 2);
+    }
+
+    #[test]
+    fn test_local_search() {
+        // The paths 0 - 1 - 2 and 3 - 4 - 5, colored with 2 colors
+        let mut graph = AdjList::new(6);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(3, 4);
+        graph.add_edge(4, 5);
+
+        let mut recolorer = Recolorer::new(graph, vec![1, 2, 1, 1, 2, 1], RecolorParams::default());
+
+        // Neither 0 nor 3 has a free color, but the graph stays bipartite, so one of the paths
+        // is recolored instead of taking a new color
+        recolorer.add_edge(0, 3);
+        assert!(is_coloring_valid(recolorer.graph(), recolorer.coloring()));
+        assert_eq!(recolorer.num_colors(), 2);
+    }
+
+    #[test]
+    fn test_recolorer() {
+        let graph = testing::myciel5();
+        let coloring = grasp_wrapper(&graph, &Default::default()).1;
+        let mut recolorer = Recolorer::new(graph, coloring, RecolorParams::default());
+        let edges = recolorer.graph().edges();
+
+        for (u, v) in edges.iter().step_by(3) {
+            recolorer.remove_edge(*u, *v);
+            assert!(is_coloring_valid(recolorer.graph(), recolorer.coloring()));
+        }
+        for (u, v) in edges.iter().step_by(3) {
+            recolorer.add_edge(*u, *v);
+            assert!(is_coloring_valid(recolorer.graph(), recolorer.coloring()));
+        }
+
+        assert_eq!(recolorer.graph().edges(), edges);
+    }
+}
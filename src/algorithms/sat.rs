@@ -0,0 +1,78 @@
+use super::{
+    decision::{search_k, Decision, Search},
+    relabel_colors, Solution,
+};
+use crate::graph::adj_list::AdjList;
+use crate::graph::bounds::{degeneracy_ordering, greedy_coloring};
+use crate::progress;
+use crate::sat::{
+    decode, encode,
+    solver::{solve, Outcome},
+};
+
+#[derive(Debug, Default)]
+pub struct SatParams {
+    /// Maximum conflicts of the SAT solver for each number of colors tried. Unbounded if `None`.
+    pub max_conflicts: Option<usize>,
+    /// Known lower bound on the number of colors: smaller numbers of colors aren't tried.
+    pub lower_bound: Option<usize>,
+}
+
+/// Colors `graph` exactly, by binary searching the number of colors `k` between the lower bound and
+/// a greedy coloring (in smallest-last order), deciding whether each `k` is feasible with the SAT
+/// solver (see [`search_k`]).
+///
+/// Returns the best coloring found along with a lower bound on the chromatic number, which is the
+/// number of colors of the coloring unless the solver gave up on some `k`.
+pub fn sat(graph: &AdjList, params: &SatParams) -> (Solution, usize) {
+    let (_, ordering) = degeneracy_ordering(graph);
+    let mut coloring = greedy_coloring(graph, &ordering);
+    relabel_colors(&mut coloring);
+
+    let num_colors = coloring.iter().max().copied().unwrap_or(0);
+
+    progress::record_colors(num_colors);
+
+    search_k(
+        graph,
+        (num_colors, coloring),
+        Search::Binary,
+        params.lower_bound,
+        None,
+        |k, _| decide_sat(graph, k, params.max_conflicts),
+    )
+}
+
+/// Decides whether `graph` can be colored with `k` colors with the SAT solver, giving up after
+/// `max_conflicts` conflicts.
+pub(super) fn decide_sat(graph: &AdjList, k: usize, max_conflicts: Option<usize>) -> Decision {
+    match solve(&encode(graph, k), max_conflicts) {
+        Outcome::Satisfiable(model) => Decision::Colorable(decode(&model, graph.num_vertices(), k)),
+        Outcome::Unsatisfiable => Decision::Uncolorable,
+        Outcome::Unknown => Decision::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::solve_fixtures_optimally;
+
+    #[test]
+    fn test_sat() {
+        solve_fixtures_optimally(|fixture| {
+            let (solution, lower_bound) = sat(&fixture.graph, &SatParams::default());
+
+            assert_eq!(lower_bound, fixture.chromatic_number, "{}", fixture.name);
+            solution
+        });
+
+        // Without any conflicts allowed, the search may not prove anything
+        let params = SatParams {
+            max_conflicts: Some(0),
+            lower_bound: None,
+        };
+        let ((num_colors, _), lower_bound) = sat(&crate::testing::myciel4(), &params);
+        assert!(lower_bound <= 5 && 5 <= num_colors);
+    }
+}
@@ -0,0 +1,285 @@
+use super::{
+    decision::{search_k, Decision, Search},
+    invariants, relabel_colors, squash_colors, Solution,
+};
+use crate::graph::adj_list::AdjList;
+use crate::graph::bounds::{degeneracy_ordering, greedy_coloring};
+use crate::progress::{self, Phase};
+use crate::rng::rng;
+use rand::Rng;
+
+/// Tabu tenure is `A + lambda * conflicting vertices`, with `A` drawn from `0..TENURE_RANDOM`.
+const TENURE_RANDOM: usize = 10;
+const TENURE_LAMBDA: f64 = 0.6;
+
+#[derive(Debug)]
+pub struct TabuParams {
+    /// Maximum iterations of the search for each number of colors tried.
+    pub iterations: usize,
+    /// Known lower bound on the number of colors: the search stops as soon as it's matched.
+    pub lower_bound: Option<usize>,
+}
+
+impl Default for TabuParams {
+    fn default() -> Self {
+        TabuParams {
+            iterations: 10000,
+            lower_bound: None,
+        }
+    }
+}
+
+/// Colors `graph` with TabuCol (Hertz and de Werra, 1987), searching for a conflict-free coloring
+/// with a fixed number of colors `k` at a time.
+///
+/// The search starts from a greedy coloring (in smallest-last order). Whenever a coloring with `k`
+/// colors is found, the vertices of its last color are moved to their least conflicting color among
+/// the others, and [`tabucol`] tries to remove the conflicts with `k - 1` colors. The best coloring
+/// is returned once a search fails.
+pub fn tabu(graph: &AdjList, params: &TabuParams) -> Solution {
+    let (_, ordering) = degeneracy_ordering(graph);
+    let mut coloring = greedy_coloring(graph, &ordering);
+    relabel_colors(&mut coloring);
+
+    let num_colors = coloring.iter().max().copied().unwrap_or(0);
+
+    progress::record_colors_from(Phase::Construction, num_colors);
+
+    tabu_from(graph, (num_colors, coloring), params)
+}
+
+/// Removes colors from `best`, a valid coloring with colors `1..=best.0`, as [`tabu`] does from its
+/// greedy coloring.
+pub fn tabu_from(graph: &AdjList, best: Solution, params: &TabuParams) -> Solution {
+    let (best, _) = search_k(
+        graph,
+        best,
+        Search::Decrement,
+        params.lower_bound,
+        Some(Phase::LocalSearch),
+        |k, best| {
+            let mut candidate = best.to_vec();
+            squash_colors(graph, &mut candidate, k);
+
+            match tabucol(graph, &mut candidate, k, params.iterations) {
+                0 => Decision::Colorable(candidate),
+                _ => Decision::Unknown,
+            }
+        },
+    );
+
+    best
+}
+
+/// Tries to remove the conflicts of `coloring` (which uses the colors `1..=k`) with the TabuCol
+/// metaheuristic, for at most `max_iterations` iterations.
+///
+/// At each iteration, the best non-tabu move (i.e., recoloring a conflicting vertex) is applied,
+/// even if it worsens the coloring. A vertex can't go back to the color it just left for a number
+/// of iterations (the tabu tenure), unless doing so leads to the best coloring found so far
+/// (aspiration criterion).
+///
+/// `coloring` is updated to the best coloring found. Returns its number of conflicting edges.
+pub fn tabucol(graph: &AdjList, coloring: &mut [usize], k: usize, max_iterations: usize) -> usize {
+    let vertices: Vec<usize> = (0..graph.num_vertices()).collect();
+
+    tabucol_within(graph, coloring, k, &vertices, max_iterations)
+}
+
+/// Like [`tabucol`], but only recoloring `vertices`, whose neighbors outside of it keep their
+/// colors. Every conflict of `coloring` must involve one of `vertices`.
+///
+/// The search only keeps track of `vertices`, so its cost doesn't grow with the rest of `graph`.
+pub fn tabucol_within(
+    graph: &AdjList,
+    coloring: &mut [usize],
+    k: usize,
+    vertices: &[usize],
+    max_iterations: usize,
+) -> usize {
+    let adj_list = graph.adj_list();
+    let mut rng = rng();
+
+    // `index[v]` is the position of `v` in `vertices`, if it may be recolored
+    let mut index = vec![None; graph.num_vertices()];
+    for (i, v) in vertices.iter().enumerate() {
+        index[*v] = Some(i);
+    }
+
+    // `gamma[i][c]` is the number of neighbors of `vertices[i]` colored with `c`
+    let mut gamma = vec![vec![0; k + 1]; vertices.len()];
+    // `tabu[i][c]` is the first iteration in which `vertices[i]` can be colored with `c` again
+    let mut tabu = vec![vec![0; k + 1]; vertices.len()];
+    let mut conflicts: usize = 0;
+
+    for (i, v) in vertices.iter().enumerate() {
+        for u in adj_list[*v].iter() {
+            gamma[i][coloring[*u]] += 1;
+
+            // Edges between two of `vertices` are seen from both ends, but counted once
+            if coloring[*u] == coloring[*v] && index[*u].is_none_or(|j| i < j) {
+                conflicts += 1;
+            }
+        }
+    }
+
+    let mut best_conflicts = conflicts;
+    let mut best_colors: Vec<usize> = vertices.iter().map(|v| coloring[*v]).collect();
+
+    for iteration in 0..max_iterations {
+        if conflicts == 0 {
+            break;
+        }
+
+        let conflicting: Vec<usize> = (0..vertices.len())
+            .filter(|i| gamma[*i][coloring[vertices[*i]]] > 0)
+            .collect();
+        let mut best_delta = isize::MAX;
+        let mut best_moves: Vec<(usize, usize)> = Vec::new();
+
+        for i in conflicting.iter() {
+            let current = coloring[vertices[*i]];
+
+            for c in 1..=k {
+                if c == current {
+                    continue;
+                }
+
+                let delta = gamma[*i][c] as isize - gamma[*i][current] as isize;
+                let is_tabu = tabu[*i][c] > iteration;
+                let aspiration = (conflicts as isize + delta) < best_conflicts as isize;
+
+                if is_tabu && !aspiration {
+                    continue;
+                }
+
+                if delta < best_delta {
+                    best_delta = delta;
+                    best_moves.clear();
+                }
+                if delta == best_delta {
+                    best_moves.push((*i, c));
+                }
+            }
+        }
+
+        if best_moves.is_empty() {
+            continue;
+        }
+
+        let (i, c) = best_moves[rng.gen_range(0..best_moves.len())];
+        let v = vertices[i];
+        let previous = coloring[v];
+
+        coloring[v] = c;
+        conflicts = (conflicts as isize + best_delta) as usize;
+
+        for u in adj_list[v].iter() {
+            if let Some(j) = index[*u] {
+                gamma[j][previous] -= 1;
+                gamma[j][c] += 1;
+            }
+        }
+
+        let tenure =
+            rng.gen_range(0..TENURE_RANDOM) + (TENURE_LAMBDA * conflicting.len() as f64) as usize;
+        tabu[i][previous] = iteration + tenure + 1;
+
+        invariants::check_conflicts(graph, coloring, conflicts);
+
+        if conflicts < best_conflicts {
+            best_conflicts = conflicts;
+            for (color, v) in best_colors.iter_mut().zip(vertices) {
+                *color = coloring[*v];
+            }
+        }
+    }
+
+    for (v, color) in vertices.iter().zip(best_colors) {
+        coloring[*v] = color;
+    }
+
+    best_conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{algorithms::is_coloring_valid, testing};
+
+    #[test]
+    fn test_tabucol() {
+        // A cycle with 4 vertices, with every vertex colored the same
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 0);
+
+        let mut coloring = vec![1, 1, 1, 1];
+        let conflicts = tabucol(&graph, &mut coloring, 2, 100);
+
+        assert_eq!(conflicts, 0);
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        // There's no way to color the complete graph with fewer colors than vertices,
+        // but the search should still return the best coloring it finds
+        let graph = AdjList::complete(4);
+        let mut coloring = vec![1, 1, 1, 1];
+        let conflicts = tabucol(&graph, &mut coloring, 3, 100);
+
+        assert_eq!(conflicts, 1);
+        assert!(coloring.iter().all(|color| (1..=3).contains(color)));
+    }
+
+    #[test]
+    fn test_tabucol_within() {
+        // A path 0 - 1 - 2 - 3 where only 1 and 2 may be recolored, in conflict with each other
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+
+        let mut coloring = vec![1, 2, 2, 1];
+        let conflicts = tabucol_within(&graph, &mut coloring, 3, &[1, 2], 100);
+
+        assert_eq!(conflicts, 0);
+        assert!(is_coloring_valid(&graph, &coloring));
+        assert_eq!((coloring[0], coloring[3]), (1, 1));
+
+        // With 2 colors, the fixed ends leave both inner vertices only the color 2
+        let mut coloring = vec![1, 2, 2, 1];
+        let conflicts = tabucol_within(&graph, &mut coloring, 2, &[1, 2], 100);
+
+        assert_eq!(conflicts, 1);
+        assert_eq!(coloring, [1, 2, 2, 1]);
+    }
+
+    #[test]
+    fn test_tabucol_myciel() {
+        let graph = testing::myciel4();
+
+        // The chromatic number of myciel4 is 5
+        let mut coloring = vec![1; graph.num_vertices()];
+        let conflicts = tabucol(&graph, &mut coloring, 5, 10000);
+
+        assert_eq!(conflicts, 0);
+        assert!(is_coloring_valid(&graph, &coloring));
+    }
+
+    #[test]
+    fn test_tabu() {
+        testing::solve_fixtures_optimally(|fixture| {
+            let params = TabuParams {
+                iterations: 1000,
+                lower_bound: Some(fixture.chromatic_number),
+            };
+
+            tabu(&fixture.graph, &params)
+        });
+
+        // Without a lower bound, the search goes on until it fails
+        let params = TabuParams::default();
+        assert_eq!(tabu(&testing::myciel4(), &params).0, 5);
+    }
+}
@@ -0,0 +1,203 @@
+use super::{
+    kempe::{kempe_chain, swap_chain},
+    neighborhood::{get_coloring_from_class_list, recolor_descent, redistribute},
+    relabel_colors, Solution,
+};
+use crate::graph::adj_list::AdjList;
+use crate::graph::bounds::{degeneracy_ordering, greedy_coloring};
+use crate::progress::{self, Phase};
+use crate::rng::rng;
+use rand::Rng;
+
+/// Number of shaking neighborhoods: recoloring a vertex, swapping a Kempe chain and dissolving a
+/// class.
+const NEIGHBORHOODS: usize = 3;
+
+#[derive(Debug)]
+pub struct VnsParams {
+    /// Shakes (each followed by the local search) over the whole run.
+    pub iterations: usize,
+    /// Known lower bound on the number of colors: the search stops as soon as it's matched.
+    pub lower_bound: Option<usize>,
+}
+
+impl Default for VnsParams {
+    fn default() -> Self {
+        VnsParams {
+            iterations: 10000,
+            lower_bound: None,
+        }
+    }
+}
+
+/// Colors `graph` with Variable Neighborhood Search, which searches for a conflict-free coloring
+/// with a fixed number of colors `k`.
+///
+/// The search starts with one color less than a greedy coloring (in smallest-last order), dissolving
+/// its smallest class. Each iteration shakes the current coloring in one of the neighborhoods, in
+/// increasing size: recoloring a random vertex, swapping a random Kempe chain, or dissolving a
+/// random class into the others. The shaken coloring is then improved by recoloring its
+/// conflicting vertices while that helps. If it has fewer conflicts than the current one, it's
+/// accepted and the shaking goes back to the first neighborhood; otherwise, it moves to the next
+/// one, cycling back after the last. Whenever a coloring has no conflicts, `k` is decreased.
+pub fn vns(graph: &AdjList, params: &VnsParams) -> Solution {
+    let (_, ordering) = degeneracy_ordering(graph);
+    let mut best_coloring = greedy_coloring(graph, &ordering);
+    relabel_colors(&mut best_coloring);
+
+    let mut best = best_coloring.iter().max().copied().unwrap_or(0);
+    let reached = |best: usize| {
+        best <= 1
+            || params
+                .lower_bound
+                .is_some_and(|lower_bound| best <= lower_bound)
+    };
+
+    progress::record_colors_from(Phase::Construction, best);
+
+    if reached(best) {
+        return (best, best_coloring);
+    }
+
+    let mut k = best - 1;
+    let mut current = dissolve(
+        graph,
+        &best_coloring,
+        best,
+        smallest_class(&best_coloring, best),
+    );
+    let mut conflicts = recolor_descent(graph, &mut current, k);
+    let mut neighborhood = 1;
+    let mut iterations = 0;
+
+    loop {
+        if conflicts == 0 {
+            best_coloring = current.clone();
+            relabel_colors(&mut best_coloring);
+            best = best_coloring.iter().max().copied().unwrap_or(0);
+
+            progress::record_colors_from(Phase::LocalSearch, best);
+
+            if reached(best) {
+                break;
+            }
+
+            k = best - 1;
+            current = dissolve(
+                graph,
+                &best_coloring,
+                best,
+                smallest_class(&best_coloring, best),
+            );
+            conflicts = recolor_descent(graph, &mut current, k);
+            neighborhood = 1;
+            continue;
+        }
+
+        if iterations == params.iterations || progress::stop_requested() {
+            break;
+        }
+
+        iterations += 1;
+
+        let mut shaken = current.clone();
+        shake(graph, &mut shaken, k, neighborhood);
+        let shaken_conflicts = recolor_descent(graph, &mut shaken, k);
+
+        progress::record_iteration();
+        progress::record_conflicts(shaken_conflicts);
+
+        if shaken_conflicts < conflicts {
+            current = shaken;
+            conflicts = shaken_conflicts;
+            neighborhood = 1;
+        } else {
+            neighborhood = neighborhood % NEIGHBORHOODS + 1;
+        }
+    }
+
+    (best, best_coloring)
+}
+
+/// Perturbs `coloring` (with colors in `1..=k`) with a random move of the given neighborhood.
+fn shake(graph: &AdjList, coloring: &mut [usize], k: usize, neighborhood: usize) {
+    let mut rng = rng();
+    let vertex = rng.gen_range(0..coloring.len());
+
+    match neighborhood {
+        1 => coloring[vertex] = rng.gen_range(1..=k),
+        2 => {
+            let color = coloring[vertex];
+            let other = rng.gen_range(1..=k);
+            let chain = kempe_chain(graph, coloring, vertex, other);
+
+            swap_chain(coloring, &chain, color, other);
+        }
+        // Dissolving the only class would leave no class to move its vertices to
+        _ if k > 1 => {
+            let class = rng.gen_range(1..=k);
+            let dissolved = dissolve(graph, coloring, k, class);
+
+            coloring.copy_from_slice(&dissolved);
+        }
+        _ => {}
+    }
+}
+
+/// The coloring where the vertices of `class` (a color of `coloring`, which has colors in
+/// `1..=k`) are moved to the other classes where they have the fewest neighbors. The classes are
+/// renumbered from 1 to `k - 1`.
+fn dissolve(graph: &AdjList, coloring: &[usize], k: usize, class: usize) -> Vec<usize> {
+    let mut class_list = vec![Vec::new(); k];
+
+    for (v, color) in coloring.iter().enumerate() {
+        class_list[color - 1].push(v);
+    }
+
+    let vertices = class_list.remove(class - 1);
+    redistribute(graph, &mut class_list, vertices);
+
+    get_coloring_from_class_list(coloring.len(), &class_list)
+}
+
+/// The color of `coloring` (with colors in `1..=k`) with the fewest vertices.
+fn smallest_class(coloring: &[usize], k: usize) -> usize {
+    let mut sizes = vec![0; k + 1];
+
+    for color in coloring {
+        sizes[*color] += 1;
+    }
+
+    (1..=k).min_by_key(|color| sizes[*color]).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::solve_fixtures_optimally;
+
+    #[test]
+    fn test_vns() {
+        solve_fixtures_optimally(|fixture| {
+            let params = VnsParams {
+                iterations: 1000,
+                lower_bound: Some(fixture.chromatic_number),
+            };
+
+            vns(&fixture.graph, &params)
+        });
+    }
+
+    #[test]
+    fn test_dissolve() {
+        // The path 0 - 1 - 2 - 3, with a needless third color
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+
+        let coloring = dissolve(&graph, &[1, 2, 3, 2], 3, 3);
+
+        assert_eq!(coloring, vec![1, 2, 1, 2]);
+    }
+}
@@ -0,0 +1,230 @@
+use super::{exact::extend_coloring, partition::induced_subgraph, relabel_colors, Solution};
+use crate::graph::adj_list::AdjList;
+use crate::progress;
+use crate::rng::rng;
+use rand::seq::SliceRandom;
+
+#[derive(Debug)]
+pub struct XrlfParams {
+    /// Colorings built, keeping the best one.
+    pub iterations: usize,
+    /// Independent sets built for each color, keeping the one leaving the fewest edges among the
+    /// uncolored vertices.
+    pub trials: usize,
+    /// Vertices sampled at each step of a trial, among which the one added to the set is picked.
+    pub candidates: usize,
+    /// Once at most this many vertices are left uncolored, they're colored exactly.
+    pub exact_limit: usize,
+    /// Known lower bound on the number of colors: the search stops as soon as it's matched.
+    pub lower_bound: Option<usize>,
+}
+
+impl Default for XrlfParams {
+    fn default() -> Self {
+        XrlfParams {
+            iterations: 10,
+            trials: 20,
+            candidates: 50,
+            exact_limit: 20,
+            lower_bound: None,
+        }
+    }
+}
+
+/// Colors `graph` with XRLF (Johnson et al., 1991), a randomized Recursive Largest First.
+///
+/// Each color class is the best of several independent sets built from the uncolored vertices:
+/// starting from one of the largest degree, the vertex added at each step is the one with the most
+/// neighbors among the vertices that can't join the set anymore, from a random sample of the ones
+/// that still can. The best set is the one leaving the fewest edges among the uncolored vertices.
+///
+/// Once few vertices are left, they're colored with an exact search, with as few colors as
+/// possible.
+pub fn xrlf(graph: &AdjList, params: &XrlfParams) -> Solution {
+    let mut best: Option<Solution> = None;
+
+    for _ in 0..params.iterations.max(1) {
+        let mut coloring = xrlf_coloring(graph, params);
+        relabel_colors(&mut coloring);
+
+        let num_colors = coloring.iter().max().copied().unwrap_or(0);
+
+        progress::record_iteration();
+        progress::record_colors(num_colors);
+
+        if best.as_ref().is_none_or(|(colors, _)| num_colors < *colors) {
+            best = Some((num_colors, coloring));
+        }
+
+        let best_colors = best.as_ref().unwrap().0;
+        if params
+            .lower_bound
+            .is_some_and(|lower_bound| best_colors <= lower_bound)
+            || progress::stop_requested()
+        {
+            break;
+        }
+    }
+
+    best.unwrap()
+}
+
+fn xrlf_coloring(graph: &AdjList, params: &XrlfParams) -> Vec<usize> {
+    let n = graph.num_vertices();
+    let mut coloring = vec![0; n];
+    let mut uncolored: Vec<usize> = (0..n).collect();
+    let mut color = 0;
+
+    while uncolored.len() > params.exact_limit {
+        let mut best_set = Vec::new();
+        let mut best_edges = 0;
+
+        for _ in 0..params.trials.max(1) {
+            let (set, edges) = build_set(graph, &coloring, &uncolored, params.candidates);
+
+            if best_set.is_empty() || edges > best_edges {
+                best_set = set;
+                best_edges = edges;
+            }
+        }
+
+        color += 1;
+        for v in best_set {
+            coloring[v] = color;
+        }
+        uncolored.retain(|v| coloring[*v] == 0);
+    }
+
+    if !uncolored.is_empty() {
+        let residual = induced_subgraph(graph, &uncolored);
+        let precoloring = vec![None; uncolored.len()];
+        let residual_coloring = (1..=uncolored.len())
+            .find_map(|k| extend_coloring(&residual, &precoloring, k))
+            .unwrap();
+
+        for (v, residual_color) in uncolored.iter().zip(residual_coloring) {
+            coloring[*v] = color + residual_color;
+        }
+    }
+
+    coloring
+}
+
+/// Builds an independent set of uncolored vertices (the ones with color 0 in `coloring`), as a
+/// trial of [`xrlf`]. Returns it along with the number of edges between it and the uncolored
+/// vertices, which leave the residual graph along with it.
+fn build_set(
+    graph: &AdjList,
+    coloring: &[usize],
+    uncolored: &[usize],
+    candidates: usize,
+) -> (Vec<usize>, usize) {
+    let mut rng = rng();
+    let adj_list = graph.adj_list();
+    // Vertices that can still join the set
+    let mut allowed = vec![false; coloring.len()];
+    // Number of neighbors of each vertex among the excluded ones
+    let mut excluded_degree = vec![0; coloring.len()];
+    let mut pool: Vec<usize> = uncolored.to_vec();
+    let mut set = Vec::new();
+    let mut edges = 0;
+
+    uncolored.iter().for_each(|v| allowed[*v] = true);
+
+    let residual_degree = |v: usize| adj_list[v].iter().filter(|u| coloring[**u] == 0).count();
+
+    // The first vertex is one of the largest degree among the uncolored ones
+    let max_degree = pool.iter().map(|v| residual_degree(*v)).max().unwrap();
+    let largest: Vec<usize> = pool
+        .iter()
+        .copied()
+        .filter(|v| residual_degree(*v) == max_degree)
+        .collect();
+    let mut next = *largest.choose(&mut rng).unwrap();
+
+    loop {
+        set.push(next);
+        edges += residual_degree(next);
+        allowed[next] = false;
+
+        for u in adj_list[next].iter() {
+            if allowed[*u] {
+                allowed[*u] = false;
+
+                for w in adj_list[*u].iter() {
+                    excluded_degree[*w] += 1;
+                }
+            }
+        }
+
+        pool.retain(|v| allowed[*v]);
+        if pool.is_empty() {
+            break;
+        }
+
+        next = *pool
+            .choose_multiple(&mut rng, candidates.max(1))
+            .max_by_key(|v| excluded_degree[**v])
+            .unwrap();
+    }
+
+    (set, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{solve_fixtures, solve_fixtures_optimally};
+
+    #[test]
+    fn test_xrlf() {
+        for exact_limit in [0, 8] {
+            solve_fixtures_optimally(|fixture| {
+                let params = XrlfParams {
+                    iterations: 3,
+                    exact_limit,
+                    lower_bound: Some(fixture.chromatic_number),
+                    ..Default::default()
+                };
+
+                xrlf(&fixture.graph, &params)
+            });
+        }
+
+        // The whole graph colored exactly is optimal (the larger ones take too long, so only their
+        // last vertices are)
+        let fixtures = solve_fixtures(|fixture| {
+            let params = XrlfParams {
+                iterations: 1,
+                exact_limit: fixture.graph.num_vertices().min(12),
+                ..Default::default()
+            };
+
+            xrlf(&fixture.graph, &params)
+        });
+        for (fixture, num_colors) in fixtures {
+            if fixture.graph.num_vertices() <= 12 {
+                assert_eq!(num_colors, fixture.chromatic_number, "{}", fixture.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_build_set() {
+        let graph = crate::testing::myciel4();
+        let uncolored: Vec<usize> = (0..graph.num_vertices()).collect();
+        let coloring = vec![0; graph.num_vertices()];
+
+        let (set, edges) = build_set(&graph, &coloring, &uncolored, 5);
+
+        assert!(set
+            .iter()
+            .all(|v| set.iter().all(|u| !graph.adj_list()[*v].contains(u))));
+        assert_eq!(
+            edges,
+            set.iter()
+                .map(|v| graph.adj_list()[*v].len())
+                .sum::<usize>()
+        );
+    }
+}
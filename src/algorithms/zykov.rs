@@ -0,0 +1,219 @@
+use super::{relabel_colors, Solution};
+use crate::graph::adj_list::AdjList;
+use crate::graph::bounds::{degeneracy_ordering, greedy_coloring};
+use crate::graph::clique::greedy_clique;
+use crate::progress;
+
+#[derive(Debug, Default)]
+pub struct ZykovParams {
+    /// Maximum nodes of the search tree visited. Unbounded if `None`.
+    pub max_nodes: Option<usize>,
+    /// Known lower bound on the number of colors: the search stops as soon as it's matched.
+    pub lower_bound: Option<usize>,
+}
+
+/// State of the branch and bound.
+struct Search<'a> {
+    params: &'a ZykovParams,
+    /// The best coloring of the original graph found so far.
+    best: Solution,
+    /// No coloring can use fewer colors than this.
+    lower_bound: usize,
+    nodes: usize,
+    /// Whether some subtree was skipped, because of the node limit or a stop request.
+    aborted: bool,
+}
+
+/// Colors `graph` exactly with Zykov's branch and bound, which never assigns colors: each node of
+/// the search tree picks two non-adjacent vertices, and either merges them (they share a color,
+/// see [`AdjList::contract`]) or joins them with an edge (they don't). Either way, the chromatic
+/// number of the graph is that of one of the two children, and the leaves are complete graphs.
+///
+/// Each node is colored greedily (in smallest-last order) for an upper bound, and is pruned when a
+/// greedy clique shows it can't beat the best coloring found so far. The pair branched on is a
+/// vertex of maximum degree with the non-neighbor it shares the most neighbors with.
+///
+/// Returns the best coloring found along with a lower bound on the chromatic number, which is the
+/// number of colors of the coloring unless the search was cut short.
+pub fn zykov(graph: &AdjList, params: &ZykovParams) -> (Solution, usize) {
+    let n = graph.num_vertices();
+    let (_, ordering) = degeneracy_ordering(graph);
+    let mut coloring = greedy_coloring(graph, &ordering);
+    relabel_colors(&mut coloring);
+
+    let clique = greedy_clique(graph).len();
+    let mut search = Search {
+        params,
+        best: (coloring.iter().max().copied().unwrap_or(0), coloring),
+        lower_bound: clique.max(params.lower_bound.unwrap_or(0)),
+        nodes: 0,
+        aborted: false,
+    };
+
+    progress::record_colors(search.best.0);
+
+    let members: Vec<Vec<usize>> = (0..n).map(|v| vec![v]).collect();
+    search.branch(graph, &members);
+
+    let lower_bound = match search.aborted {
+        true => search.lower_bound.min(search.best.0),
+        false => search.best.0,
+    };
+
+    (search.best, lower_bound)
+}
+
+impl Search<'_> {
+    /// Explores the node of `graph`, whose `i`-th vertex stands for the original vertices
+    /// `members[i]`.
+    fn branch(&mut self, graph: &AdjList, members: &[Vec<usize>]) {
+        if self.best.0 <= self.lower_bound {
+            return;
+        }
+        if self
+            .params
+            .max_nodes
+            .is_some_and(|max_nodes| self.nodes >= max_nodes)
+            || progress::stop_requested()
+        {
+            self.aborted = true;
+            return;
+        }
+
+        self.nodes += 1;
+        progress::record_iteration();
+
+        let clique = greedy_clique(graph).len();
+        if clique >= self.best.0 {
+            return;
+        }
+
+        let (_, ordering) = degeneracy_ordering(graph);
+        let coloring = greedy_coloring(graph, &ordering);
+        let num_colors = coloring.iter().max().copied().unwrap_or(0);
+
+        if num_colors < self.best.0 {
+            let mut original = vec![0; self.best.1.len()];
+            for (v, color) in coloring.iter().enumerate() {
+                for member in members[v].iter() {
+                    original[*member] = *color;
+                }
+            }
+            relabel_colors(&mut original);
+
+            self.best = (num_colors, original);
+            progress::record_colors(num_colors);
+        }
+
+        // The greedy coloring is optimal for this node, which includes complete graphs
+        if num_colors == clique {
+            return;
+        }
+
+        let Some((u, v)) = branching_pair(graph) else {
+            return;
+        };
+
+        // Same color
+        let mut merged = members.to_vec();
+        let mut merged_members = merged.remove(v);
+        merged[if u > v { u - 1 } else { u }].append(&mut merged_members);
+        self.branch(&graph.contract(u, v), &merged);
+
+        // Different colors
+        let mut separated = graph.clone();
+        separated.add_edge(u, v);
+        self.branch(&separated, members);
+    }
+}
+
+/// A vertex of maximum degree among those not adjacent to every other one, along with the
+/// non-neighbor it shares the most neighbors with. `None` if the graph is complete.
+///
+/// Edges listed more than once (and loops) are only counted once, so they can't make a vertex look
+/// adjacent to every other one.
+fn branching_pair(graph: &AdjList) -> Option<(usize, usize)> {
+    let n = graph.num_vertices();
+    let neighbors: Vec<Vec<usize>> = graph
+        .adj_list()
+        .iter()
+        .enumerate()
+        .map(|(v, list)| {
+            let mut list: Vec<usize> = list.iter().copied().filter(|w| *w != v).collect();
+            list.sort_unstable();
+            list.dedup();
+            list
+        })
+        .collect();
+
+    let u = (0..n)
+        .filter(|v| neighbors[*v].len() < n - 1)
+        .max_by_key(|v| neighbors[*v].len())?;
+
+    let mut is_neighbor = vec![false; n];
+    is_neighbor[u] = true;
+    for w in neighbors[u].iter() {
+        is_neighbor[*w] = true;
+    }
+
+    let v = (0..n)
+        .filter(|w| !is_neighbor[*w])
+        .max_by_key(|w| neighbors[*w].iter().filter(|x| is_neighbor[**x]).count())?;
+
+    Some((u, v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::is_coloring_valid;
+    use crate::testing::{self, solve_fixtures_optimally};
+
+    #[test]
+    fn test_zykov() {
+        solve_fixtures_optimally(|fixture| {
+            let (solution, lower_bound) = zykov(&fixture.graph, &ZykovParams::default());
+
+            assert_eq!(lower_bound, fixture.chromatic_number, "{}", fixture.name);
+            solution
+        });
+
+        // Without any nodes, only the greedy coloring and the clique are known
+        let params = ZykovParams {
+            max_nodes: Some(0),
+            lower_bound: None,
+        };
+        let ((num_colors, _), lower_bound) = zykov(&testing::myciel4(), &params);
+        assert!(lower_bound <= 5 && 5 <= num_colors);
+    }
+
+    #[test]
+    fn test_branching_pair() {
+        // The path 0 - 1 - 2: 1 is adjacent to every other vertex, so the ends are branched on
+        let mut graph = AdjList::new(3);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+
+        let (u, v) = branching_pair(&graph).unwrap();
+        assert_eq!((u.min(v), u.max(v)), (0, 2));
+
+        assert_eq!(branching_pair(&AdjList::complete(3)), None);
+    }
+
+    #[test]
+    fn test_repeated_edges() {
+        // C5 with every edge listed twice: each vertex has 4 entries but only 2 neighbors
+        let mut graph = AdjList::new(5);
+        for v in 0..5 {
+            graph.add_edge(v, (v + 1) % 5);
+            graph.add_edge(v, (v + 1) % 5);
+        }
+
+        let (u, v) = branching_pair(&graph).unwrap();
+        assert!(!graph.adj_list()[u].contains(&v));
+
+        let ((num_colors, coloring), lower_bound) = zykov(&graph, &ZykovParams::default());
+        assert!(is_coloring_valid(&graph, &coloring));
+        assert_eq!((num_colors, lower_bound), (3, 3));
+    }
+}
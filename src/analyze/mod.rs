@@ -0,0 +1,176 @@
+use crate::args::Algorithm;
+use crate::graph::adj_list::AdjList;
+use crate::graph::bounds::{degeneracy_ordering, greedy_coloring};
+use crate::graph::clique::greedy_clique;
+use crate::rng::rng;
+use rand::seq::SliceRandom;
+
+/// Cheap measurements of how hard an instance is likely to be.
+#[derive(Debug)]
+pub struct Analysis {
+    pub num_vertices: usize,
+    pub num_edges: usize,
+    /// Fraction of the possible edges present in the graph.
+    pub density: f64,
+    /// Size of a clique found by a greedy heuristic (a lower bound on the chromatic number).
+    pub clique: usize,
+    pub degeneracy: usize,
+    /// Colors used by the greedy coloring over the random orderings.
+    pub greedy_min: usize,
+    pub greedy_max: usize,
+    pub greedy_mean: f64,
+    pub greedy_std: f64,
+}
+
+/// An algorithm to solve an instance, along with the parameters that suit it.
+#[derive(Debug)]
+pub struct Recommendation {
+    pub algorithm: Algorithm,
+    /// Command line flags, besides the algorithm.
+    pub flags: &'static str,
+    pub reason: &'static str,
+}
+
+/// Analyzes `graph`, greedily coloring it with `num_orderings` random vertex orderings.
+pub fn analyze(graph: &AdjList, num_orderings: usize) -> Analysis {
+    let n = graph.num_vertices();
+    let num_edges = graph.edges().len();
+    let density = if n > 1 {
+        2.0 * num_edges as f64 / (n * (n - 1)) as f64
+    } else {
+        0.0
+    };
+    let (degeneracy, _) = degeneracy_ordering(graph);
+
+    let mut rng = rng();
+    let mut order: Vec<usize> = (0..n).collect();
+    let results: Vec<usize> = (0..num_orderings.max(1))
+        .map(|_| {
+            order.shuffle(&mut rng);
+            greedy_coloring(graph, &order)
+                .into_iter()
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let greedy_mean = results.iter().sum::<usize>() as f64 / results.len() as f64;
+    let greedy_variance = results
+        .iter()
+        .map(|result| (*result as f64 - greedy_mean).powi(2))
+        .sum::<f64>()
+        / results.len() as f64;
+
+    Analysis {
+        num_vertices: n,
+        num_edges,
+        density,
+        clique: greedy_clique(graph).len(),
+        degeneracy,
+        greedy_min: results.iter().min().copied().unwrap(),
+        greedy_max: results.iter().max().copied().unwrap(),
+        greedy_mean,
+        greedy_std: greedy_variance.sqrt(),
+    }
+}
+
+/// Recommends an algorithm for the analyzed instance, based on the gap between the greedy
+/// colorings and the clique bound, and on how much randomization changes the greedy colorings.
+pub fn recommend(analysis: &Analysis) -> Recommendation {
+    let gap = analysis.greedy_min - analysis.clique;
+
+    if gap == 0 {
+        Recommendation {
+            algorithm: Algorithm::Grasp,
+            flags: "--grasp-iterations 1",
+            reason: "a greedy coloring already matches the clique bound, so it's optimal",
+        }
+    } else if gap <= 2 && analysis.greedy_std < 1.0 {
+        Recommendation {
+            algorithm: Algorithm::Grasp,
+            flags: "",
+            reason: "the greedy colorings are close to the clique bound",
+        }
+    } else if analysis.density >= 0.4 || gap > 5 {
+        Recommendation {
+            algorithm: Algorithm::Grasp,
+            flags: "--improvement tabu --tabu-iterations 10000",
+            reason: "the instance is dense or far from the clique bound, which calls for a \
+                     stronger improvement phase",
+        }
+    } else if analysis.greedy_std >= 1.0 {
+        Recommendation {
+            algorithm: Algorithm::GraspPR,
+            flags: "--pr-solutions 10 --pr-local-search-iterations 1000",
+            reason: "the greedy colorings vary a lot with the ordering, so combining diverse \
+                     solutions should pay off",
+        }
+    } else {
+        Recommendation {
+            algorithm: Algorithm::Grasp,
+            flags: "--improvement local-then-tabu",
+            reason: "the greedy colorings are a few colors above the clique bound whatever the \
+                     ordering, so the improvement phase has to close the gap",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn test_analyze() {
+        let analysis = analyze(&AdjList::complete(5), 10);
+
+        assert_eq!(analysis.num_edges, 10);
+        assert_eq!(analysis.density, 1.0);
+        assert_eq!(analysis.clique, 5);
+        assert_eq!(analysis.degeneracy, 4);
+        assert_eq!((analysis.greedy_min, analysis.greedy_max), (5, 5));
+        assert_eq!(analysis.greedy_std, 0.0);
+        assert!(matches!(recommend(&analysis).algorithm, Algorithm::Grasp));
+
+        let graph = testing::myciel4();
+
+        let analysis = analyze(&graph, 50);
+
+        // Mycielski graphs are triangle-free, but need 5 colors
+        assert_eq!(analysis.clique, 2);
+        assert!(analysis.greedy_min >= 5);
+        assert!(analysis.greedy_min as f64 <= analysis.greedy_mean);
+        assert!(analysis.greedy_mean <= analysis.greedy_max as f64);
+    }
+
+    #[test]
+    fn test_recommend() {
+        let analysis = |greedy_min, greedy_std| Analysis {
+            num_vertices: 50,
+            num_edges: 200,
+            density: 0.16,
+            clique: 3,
+            degeneracy: 6,
+            greedy_min,
+            greedy_max: greedy_min + 2,
+            greedy_mean: greedy_min as f64 + 1.0,
+            greedy_std,
+        };
+
+        assert_eq!(recommend(&analysis(3, 0.0)).flags, "--grasp-iterations 1");
+        assert_eq!(
+            recommend(&analysis(9, 0.5)).flags,
+            "--improvement tabu --tabu-iterations 10000"
+        );
+
+        // Within a few colors of the clique bound, only diverse greedy colorings call for path
+        // relinking
+        assert!(matches!(
+            recommend(&analysis(6, 1.5)).algorithm,
+            Algorithm::GraspPR
+        ));
+        assert!(matches!(
+            recommend(&analysis(6, 0.4)).algorithm,
+            Algorithm::Grasp
+        ));
+    }
+}
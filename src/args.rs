@@ -1,27 +1,288 @@
-#[derive(Debug, clap::ValueEnum, Clone)]
+use crate::algorithms::decision::{Decider, Search};
+use crate::algorithms::genetic::{Crossover, GeneticMode, RepairStrategy};
+use crate::algorithms::grasp::{Construction, Improvement, MemoryBias, Merge};
+use crate::algorithms::grasp_pr::Direction;
+use crate::algorithms::lns::{Acceptance, Destroy};
+use crate::algorithms::pareto::Objective;
+use crate::export::qubo::QuboFormat;
+use crate::generate::{batch::Batch, families::Family, partite::Partite};
+use crate::output::{format::Format, SolutionFormat};
+use crate::sparsity::Compression;
+
+#[derive(Debug, clap::ValueEnum, Clone, Default)]
 pub enum Algorithm {
     Genetic,
+    #[default]
     Grasp,
     GraspPR,
+    GraspGA,
+    Bandit,
+    Xrlf,
+    Portfolio,
+    Tabu,
+    #[cfg(feature = "sat")]
+    Sat,
+    Aco,
+    Memetic,
+    Hea,
+    Vns,
+    Lns,
+    PartialCol,
+    Greedy,
+    GreedyPortfolio,
+    Zykov,
+    Multilevel,
+    FixedK,
+    #[cfg(feature = "ilp")]
+    Ilp,
 }
 
 #[derive(Debug, clap::Parser)]
 #[clap(author, version, about)]
+#[command(subcommand_negates_reqs = true)]
 pub struct Args {
-    /// Path to a Graph Coloring instance
-    #[arg(short, long)]
-    pub path: String,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Path to a Graph Coloring instance, in the DIMACS format or, by its extension, as an edge
+    /// list (`.edges`, `.txt`), a JSON object (`.json`) or a DOT file (`.dot`, `.gv`), whose
+    /// vertices may be named by any labels
+    #[arg(short, long, required_unless_present = "stdio")]
+    pub path: Option<String>,
 
     /// Heuristic approach used to solve the instance
-    #[arg(short, long)]
-    pub algorithm: Algorithm,
+    #[arg(short, long, required_unless_present = "stdio")]
+    pub algorithm: Option<Algorithm>,
+
+    /// Skip computing a clique as a lower bound on the chromatic number, which may take a while
+    /// on huge graphs. Without it, the algorithms can't stop early when they find an optimal
+    /// coloring
+    #[arg(long)]
+    pub skip_lower_bound: bool,
+
+    /// Also bound the fractional chromatic number by column generation (with up to this many rounds
+    /// of pricing), stopping the algorithms early if they reach it. Stronger than the clique bound
+    /// on sparse instances, but slower
+    #[arg(long)]
+    pub fractional_bound: Option<usize>,
+
+    /// Seed for the random number generator. The GRASP iterations derive their own seeds from it,
+    /// so GRASP gives the same results on any number of threads (without --grasp-memory, --reactive
+    /// or time limits, and unless it stops early at the lower bound).
+    /// A random seed is used if not provided.
+    #[arg(long)]
+    pub seed: Option<u64>,
 
     /// Number of GRASP solutions to use in PR for GRASP+PR.
-    /// Does NOT affect the actual GRASP parameters.
-    /// Defaults to 5 if not provided.
+    /// The GRASP parameters are shared with the plain GRASP.
+    /// Defaults to 10 if not provided.
     #[arg(long)]
     pub pr_solutions: Option<usize>,
 
+    #[arg(long, value_enum)]
+    /// Direction of the relinking paths in GRASP+PR.
+    /// Defaults to forward if not provided.
+    pub pr_direction: Option<Direction>,
+
+    #[arg(long)]
+    /// Maximum iterations of the tabu search applied to promising intermediate colorings in
+    /// GRASP+PR. Defaults to 0 (disabled) if not provided.
+    pub pr_local_search_iterations: Option<usize>,
+
+    #[arg(long, value_parser = parse_unit_interval)]
+    /// Fraction of each relinking path actually walked in GRASP+PR.
+    /// Defaults to 1 (the whole path) if not provided.
+    pub pr_truncation: Option<f64>,
+
+    #[arg(long)]
+    /// Minimum number of vertices that must change classes to turn an elite coloring of GRASP+PR
+    /// into a new one for it to join them, unless it's better than all of them.
+    /// Defaults to 1 (only repeated colorings are turned down) if not provided.
+    pub pr_min_distance: Option<usize>,
+
+    #[arg(long)]
+    /// Evolutionary path relinking in GRASP+PR: relink every pair of the --pr-solutions best
+    /// colorings, feeding the colorings found back into them, until none is.
+    pub pr_evolutionary: bool,
+
+    #[arg(long)]
+    /// Relink the best coloring of the Genetic Algorithm with the best GRASP colorings at the end
+    /// of grasp-ga, with the parameters of GRASP+PR.
+    pub hybrid_pr: bool,
+
+    #[arg(long, value_parser = parse_positive_f64)]
+    /// Total time (in seconds) split among the algorithms by bandit.
+    /// Defaults to 5 if not provided.
+    pub bandit_budget: Option<f64>,
+
+    #[arg(long, value_parser = parse_positive)]
+    /// Number of epochs the time of bandit is split into, each running a single algorithm.
+    /// Defaults to 10 if not provided.
+    pub bandit_epochs: Option<usize>,
+
+    #[arg(long, value_parser = parse_positive)]
+    /// Colorings built by XRLF, keeping the best one.
+    /// Defaults to 10 if not provided.
+    pub xrlf_iterations: Option<usize>,
+
+    #[arg(long, value_parser = parse_positive)]
+    /// Independent sets built by XRLF for each color, keeping the one leaving the fewest edges.
+    /// Defaults to 20 if not provided.
+    pub xrlf_trials: Option<usize>,
+
+    #[arg(long, value_parser = parse_positive)]
+    /// Vertices sampled by XRLF at each step of building an independent set.
+    /// Defaults to 50 if not provided.
+    pub xrlf_candidates: Option<usize>,
+
+    #[arg(long)]
+    /// Number of uncolored vertices from which XRLF colors the rest with an exact search.
+    /// Defaults to 20 if not provided.
+    pub xrlf_exact_limit: Option<usize>,
+
+    #[arg(long)]
+    /// Maximum iterations of TabuCol for each number of colors it tries.
+    /// Defaults to 10000 if not provided.
+    pub tabucol_iterations: Option<usize>,
+
+    #[cfg(feature = "sat")]
+    #[arg(long)]
+    /// Maximum conflicts of the SAT solver for each number of colors it tries, after which the best
+    /// coloring found so far is returned. Unbounded (so the result is optimal) if not provided.
+    pub sat_conflicts: Option<usize>,
+
+    #[arg(long, value_parser = parse_positive)]
+    /// Colorings built by the ants of ACO in each cycle.
+    /// Defaults to 10 if not provided.
+    pub aco_ants: Option<usize>,
+
+    #[arg(long, value_parser = parse_positive)]
+    /// Cycles of ACO, each building colorings and updating the pheromone.
+    /// Defaults to 100 if not provided.
+    pub aco_iterations: Option<usize>,
+
+    #[arg(long)]
+    /// Weight of the pheromone when an ant of ACO chooses a color.
+    /// Defaults to 1 if not provided.
+    pub aco_alpha: Option<f64>,
+
+    #[arg(long)]
+    /// Weight of the preference for the smallest colors when an ant of ACO chooses a color.
+    /// Defaults to 2 if not provided.
+    pub aco_beta: Option<f64>,
+
+    #[arg(long, value_parser = parse_unit_interval)]
+    /// Fraction of the pheromone of ACO that evaporates after each cycle.
+    /// Defaults to 0.1 if not provided.
+    pub aco_evaporation: Option<f64>,
+
+    #[arg(long, value_parser = parse_positive)]
+    /// Population size for HEA.
+    /// Defaults to 10 if not provided.
+    pub hea_population: Option<usize>,
+
+    #[arg(long)]
+    /// Number of generations (crossovers) for HEA.
+    /// Defaults to 1000 if not provided.
+    pub hea_generations: Option<usize>,
+
+    #[arg(long)]
+    /// Maximum iterations of the tabu search applied to each individual of HEA.
+    /// Defaults to 2000 if not provided.
+    pub hea_tabu_iterations: Option<usize>,
+
+    #[arg(long)]
+    /// Number of shakes (each followed by the local search) for VNS.
+    /// Defaults to 10000 if not provided.
+    pub vns_iterations: Option<usize>,
+
+    #[arg(long)]
+    /// Number of rounds of destroying and repairing the coloring for LNS.
+    /// Defaults to 10000 if not provided.
+    pub lns_iterations: Option<usize>,
+
+    #[arg(long, value_parser = parse_unit_interval)]
+    /// Fraction of the vertices uncolored in each round of LNS.
+    /// Defaults to 0.1 if not provided.
+    pub lns_destroy_fraction: Option<f64>,
+
+    #[arg(long, value_enum)]
+    /// How LNS chooses the vertices to uncolor.
+    /// Defaults to conflicts if not provided.
+    pub lns_destroy: Option<Destroy>,
+
+    #[arg(long, value_enum)]
+    /// Which repaired colorings replace the current one in LNS.
+    /// Defaults to better if not provided.
+    pub lns_acceptance: Option<Acceptance>,
+
+    #[arg(long)]
+    /// Maximum iterations of the PartialCol search for each number of colors tried.
+    /// Defaults to 10000 if not provided.
+    pub partialcol_iterations: Option<usize>,
+
+    #[arg(long)]
+    /// Number of random vertex orders tried by the greedy portfolio, besides the degree and
+    /// saturation based ones.
+    /// Defaults to 100 if not provided.
+    pub greedy_random_orderings: Option<usize>,
+
+    #[arg(long)]
+    /// Maximum nodes of the search tree visited by the Zykov branch and bound, which gives up on
+    /// proving optimality past them.
+    /// Unbounded if not provided.
+    pub zykov_nodes: Option<usize>,
+
+    #[arg(long, value_enum)]
+    /// Algorithm coloring the coarsest graph of the multilevel scheme.
+    /// Defaults to grasp if not provided.
+    pub multilevel_algorithm: Option<Algorithm>,
+
+    #[arg(long)]
+    /// The multilevel scheme stops coarsening once the graph has at most this many vertices.
+    /// Defaults to 500 if not provided.
+    pub multilevel_coarsest: Option<usize>,
+
+    #[arg(long)]
+    /// Maximum iterations of the tabu search refining each level of the multilevel scheme, for
+    /// each number of colors tried.
+    /// Defaults to 1000 if not provided.
+    pub multilevel_refine_iterations: Option<usize>,
+
+    #[arg(long, value_enum)]
+    /// Fixed-k solver deciding each number of colors tried by the fixed-k driver.
+    /// Defaults to tabucol if not provided.
+    pub fixed_k_solver: Option<Decider>,
+
+    #[arg(long, value_enum)]
+    /// How the fixed-k driver picks the next number of colors to try.
+    /// Defaults to decrement if not provided.
+    pub fixed_k_search: Option<Search>,
+
+    #[arg(long)]
+    /// Maximum iterations of the heuristic fixed-k solvers for each number of colors tried (the SAT
+    /// solver is bounded by --sat-conflicts instead).
+    /// Defaults to 10000 if not provided.
+    pub fixed_k_iterations: Option<usize>,
+
+    #[cfg(feature = "ilp")]
+    #[arg(long, value_parser = parse_positive_f64)]
+    /// Time (in seconds) the ILP solver is given to prove its coloring optimal, after which the
+    /// gap to the lower bound is reported.
+    /// Unbounded if not provided.
+    pub ilp_time_limit: Option<f64>,
+
+    #[arg(long, value_parser = parse_positive)]
+    /// Split the instance into this many parts, color each part with the algorithm on its own
+    /// thread, then merge the colorings, removing the conflicts between the parts with TabuCol.
+    /// For instances too large for the algorithm as a whole.
+    pub partitions: Option<usize>,
+
+    #[arg(long)]
+    /// Iterations of the TabuCol search merging the colorings of the parts given by --partitions.
+    /// Defaults to 10000 if not provided.
+    pub merge_iterations: Option<usize>,
+
     #[arg(long)]
     /// Total GRASP iterations.
     /// Defaults to 10 if not provided.
@@ -37,6 +298,87 @@ pub struct Args {
     /// Defaults to 5 if not provided.
     pub color_list_size: Option<usize>,
 
+    #[arg(long, alias = "alpha", value_parser = parse_unit_interval, conflicts_with = "color_list_size")]
+    /// Use a value-based candidate list in GRASP instead of a fixed number of vertices: every
+    /// vertex whose degree is within alpha (from 0, greedy, to 1, random) of the largest one.
+    /// Can't be combined with --color-list-size.
+    pub rcl_alpha: Option<f64>,
+
+    #[arg(long)]
+    /// Adapt the GRASP candidate list over the iterations (Reactive GRASP), favoring the sizes
+    /// (or, with --rcl-alpha, the thresholds) that found the fewest colors. Replaces the value of
+    /// --color-list-size or --rcl-alpha.
+    pub reactive: bool,
+
+    #[arg(long, value_enum)]
+    /// Construction heuristic used by GRASP. --color-iterations only applies to
+    /// independent-set. Defaults to independent-set if not provided.
+    pub construction: Option<Construction>,
+
+    #[arg(long, value_enum)]
+    /// Bias the GRASP candidate selection with a long-term memory of how often each vertex
+    /// ended up in a large color class. Disabled if not provided.
+    pub grasp_memory: Option<MemoryBias>,
+
+    #[arg(long, value_enum)]
+    /// How the GRASP improvement phase picks the color class to eliminate.
+    /// Defaults to two-smallest if not provided.
+    pub merge: Option<Merge>,
+
+    #[arg(long, value_enum)]
+    /// Method used in the GRASP improvement phase.
+    /// Defaults to local if not provided.
+    pub improvement: Option<Improvement>,
+
+    #[arg(long)]
+    /// Maximum iterations of each tabu search in the GRASP improvement phase (with tabu or
+    /// local-then-tabu).
+    /// Defaults to 1000 if not provided.
+    pub tabu_iterations: Option<usize>,
+
+    #[arg(long)]
+    /// The GRASP local search gives up after this factor times the initial number of
+    /// forbidden edges consecutive iterations without improvement.
+    /// Defaults to 2 if not provided.
+    pub no_improvement_factor: Option<usize>,
+
+    #[arg(long)]
+    /// Maximum iterations of each local search in the GRASP improvement phase.
+    /// Unbounded if not provided.
+    pub local_search_iterations: Option<usize>,
+
+    #[arg(long)]
+    /// Also try Kempe-chain interchanges in the GRASP local search when recoloring a vertex doesn't
+    /// help.
+    pub kempe_moves: bool,
+
+    #[arg(long)]
+    /// Consecutive failed class merges tolerated before the GRASP improvement phase stops.
+    /// Defaults to 0 if not provided.
+    pub merge_retries: Option<usize>,
+
+    #[arg(long)]
+    /// Times each GRASP iteration perturbs its coloring (dissolving a random color class) and
+    /// improves it again once the improvement phase stalls.
+    /// Defaults to 0 if not provided.
+    pub perturbation_restarts: Option<usize>,
+
+    #[arg(long)]
+    /// Maximum class merges tried by each GRASP improvement phase.
+    /// Unbounded if not provided.
+    pub improvement_merges: Option<usize>,
+
+    #[arg(long, value_parser = parse_positive_f64)]
+    /// Maximum time (in seconds) of each GRASP improvement phase, checked between merges.
+    /// Unbounded if not provided.
+    pub improvement_time: Option<f64>,
+
+    #[arg(long, value_parser = parse_positive_f64)]
+    /// Maximum time (in seconds) of each GRASP iteration, past which it stops improving and returns
+    /// the best coloring it has. Checked between steps, so it's a soft limit.
+    /// Unbounded if not provided.
+    pub grasp_iteration_timeout: Option<f64>,
+
     #[arg(long)]
     /// Number of generations for the Genetic Algorithm.
     /// Defaults to 10000 if not provided.
@@ -61,4 +403,575 @@ pub struct Args {
     /// Population selection ratio for the Genetic Algorithm
     /// Defaults to 0.2 if not provided.
     pub population_ratio: Option<f64>,
+
+    #[arg(long, value_enum)]
+    /// Objective of the Genetic Algorithm.
+    /// Defaults to min-colors if not provided.
+    pub genetic_mode: Option<GeneticMode>,
+
+    #[arg(long)]
+    /// Number of generations for the memetic algorithm, which shares the other parameters of the
+    /// Genetic Algorithm. Defaults to 1000 if not provided.
+    pub memetic_generations: Option<usize>,
+
+    #[arg(long)]
+    /// Maximum iterations of the tabu search applied to each offspring by the memetic algorithm.
+    /// Defaults to 1000 if not provided.
+    pub memetic_tabu_iterations: Option<usize>,
+
+    #[arg(long, value_enum)]
+    /// Minimize this objective along with the number of colors, printing the Pareto front of
+    /// colorings found by NSGA-II (with the parameters of the Genetic Algorithm). Requires
+    /// `-a genetic`.
+    pub pareto: Option<Objective>,
+
+    #[arg(long, value_enum)]
+    /// Offspring repair strategy for the Genetic Algorithm.
+    /// Defaults to first-legal if not provided.
+    pub repair_strategy: Option<RepairStrategy>,
+
+    #[arg(long, value_enum)]
+    /// Crossover operator of the Genetic Algorithm.
+    /// Defaults to one-point if not provided.
+    pub crossover: Option<Crossover>,
+
+    #[arg(long)]
+    /// File to write the final population of the Genetic Algorithm to (as JSON).
+    pub dump_population: Option<String>,
+
+    #[arg(long)]
+    /// File with a population (as written by --dump-population) used to start the
+    /// Genetic Algorithm. Missing individuals are randomly generated.
+    pub load_population: Option<String>,
+
+    #[arg(long)]
+    /// File with a coloring to start from: the color of each vertex (as printed by a run) or its
+    /// color classes (as printed with --output-format partition). The algorithms that start from
+    /// colorings start from it, and a worse result is never returned.
+    pub initial_solution: Option<String>,
+
+    #[arg(long)]
+    /// Print the color classes as named groups, with their sizes, instead of the color of each
+    /// vertex.
+    pub groups: bool,
+
+    #[arg(long)]
+    /// Name of the groups printed by --groups, followed by their color.
+    /// Defaults to "Group" if not provided.
+    pub group_label: Option<String>,
+
+    #[arg(long)]
+    /// File to write the groups to (as JSON), as printed by --groups.
+    pub groups_output: Option<String>,
+
+    #[arg(long, value_enum)]
+    /// How the coloring is printed, unless --groups is given.
+    /// Defaults to assignment if not provided.
+    pub output_format: Option<SolutionFormat>,
+
+    #[arg(long, value_parser = parse_positive)]
+    /// Maximum number of vertices with the same color. GRASP (on its own or as part of another
+    /// algorithm) keeps its classes within it as it builds and improves them; larger classes
+    /// found by the other algorithms (and in the colorings printed by --diverse) are split after
+    /// the search, which may take more colors. Unbounded if not provided.
+    pub max_group_size: Option<usize>,
+
+    #[arg(long, value_parser = parse_positive)]
+    /// Also print up to this many colorings (the best one included) that are far apart from each
+    /// other, picked from the final population of the Genetic Algorithm, or from the best GRASP
+    /// colorings for the other algorithms.
+    pub diverse: Option<usize>,
+
+    #[arg(long)]
+    /// Extra colors (over the best coloring) allowed for the colorings printed by --diverse.
+    /// Defaults to 0 if not provided.
+    pub diverse_tolerance: Option<usize>,
+
+    #[arg(long)]
+    /// Minimum partition distance (vertices that change classes) between the colorings printed by
+    /// --diverse. Defaults to 1 if not provided.
+    pub diverse_min_distance: Option<usize>,
+
+    #[cfg(feature = "tui")]
+    #[arg(long)]
+    /// Show a live dashboard (on standard error) with the best number of colors, the iterations
+    /// of each thread and the convergence so far, while the algorithm runs.
+    pub tui: bool,
+
+    #[cfg(feature = "plot")]
+    #[arg(long)]
+    /// File to write a chart of the best number of colors (and conflicts) over time to (as SVG).
+    pub plot: Option<String>,
+
+    #[arg(long)]
+    /// File to write the best number of colors (and conflicts) over time to (as JSON), so runs
+    /// can be overlaid with the plot subcommand (built with the `plot` feature).
+    pub trace: Option<String>,
+
+    #[arg(long)]
+    /// Print each improvement of the best number of colors along with the phase that found it
+    /// (construction, merge + local search, crossover, mutation or path relinking), and how many
+    /// improvements each phase found.
+    pub explain: bool,
+
+    #[arg(long)]
+    /// Address (such as 0.0.0.0:9184) to serve the progress of the run on, at /metrics, in the
+    /// Prometheus text format.
+    pub metrics: Option<String>,
+
+    #[arg(long)]
+    /// Answer JSON-RPC requests (load, solve, cancel and get-incumbent) read from standard input,
+    /// one per line, on standard output, instead of solving an instance given by --path.
+    pub stdio: bool,
+
+    #[arg(long)]
+    /// Return the cached result of a previous run with the same instance, algorithm, parameters,
+    /// seed and version instead of solving it again, caching the result otherwise. Cached results
+    /// that aren't valid colorings of the instance are ignored.
+    pub cached: bool,
+
+    #[arg(long)]
+    /// Directory where --cached keeps the results.
+    /// Defaults to ".gcp-cache" if not provided.
+    pub cache_dir: Option<String>,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Command {
+    /// Generate Graph Coloring instances
+    Generate(Box<GenerateArgs>),
+    /// Print cheap lower and upper bounds on the chromatic number of an instance
+    Bounds(BoundsArgs),
+    /// Estimate how hard an instance is and recommend an algorithm for it
+    Analyze(AnalyzeArgs),
+    /// Run every algorithm over small instances with known chromatic numbers, failing if any
+    /// result is worse than the stored baselines
+    Regress(RegressArgs),
+    /// Compare the structure of two colorings of an instance
+    Diff(DiffArgs),
+    /// Schedule exams in as few timeslots as possible, so no student has two exams at once
+    Timetable(TimetableArgs),
+    /// Assign registers to the vertices of an interference graph, reporting the ones that must
+    /// be spilled
+    Regalloc(RegallocArgs),
+    /// Solve a Sudoku puzzle as a precolored 9-coloring of the Sudoku graph
+    Sudoku(SudokuArgs),
+    /// Color the regions of a map, so regions sharing a border have different colors
+    Map(MapArgs),
+    /// Assign frequencies to the radio links of a CELAR/GRAPH frequency assignment instance
+    Fap(FapArgs),
+    /// Assign tasks with start and end times to as few machines (or rooms) as possible, so no
+    /// machine runs two tasks at once
+    Intervals(IntervalsArgs),
+    /// Group the columns of a sparse matrix so each group can be evaluated at once when computing
+    /// a Jacobian or Hessian by finite differences or automatic differentiation
+    Sparsity(SparsityArgs),
+    /// Assign wavelengths to demands routed over an optical network, so no link carries two
+    /// demands with the same wavelength
+    Wavelength(WavelengthArgs),
+    /// Schedule the games of a tournament in as few rounds as possible, so no team plays twice in
+    /// a round
+    RoundRobin(RoundRobinArgs),
+    /// Serve an HTTP API to submit instances, solve them in the background and fetch their
+    /// colorings
+    Serve(ServeArgs),
+    /// Run a worker that solves the instances sent by a coordinator
+    Worker(WorkerArgs),
+    /// Solve an instance by farming out runs of an algorithm to workers, keeping the best
+    /// colorings they find
+    Coordinate(CoordinateArgs),
+    /// Chart the convergence of runs, from the traces written by --trace, overlaid on the same
+    /// axes
+    #[cfg(feature = "plot")]
+    Plot(PlotArgs),
+    /// Color a graph as its edges arrive on the standard input, keeping a valid coloring all
+    /// along and printing its number of colors on demand
+    Online(OnlineArgs),
+    /// Write whether an instance can be colored with k colors as a CNF formula (in the DIMACS CNF
+    /// format), to be solved by an external SAT solver
+    ExportSat(ExportSatArgs),
+    /// Write whether an instance can be colored with k colors as a QUBO problem, to be solved by a
+    /// quantum or digital annealer, or a simulated annealing QUBO solver
+    ExportQubo(ExportQuboArgs),
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ExportSatArgs {
+    /// Path to a Graph Coloring instance
+    #[arg(short, long)]
+    pub path: String,
+
+    /// Number of colors
+    #[arg(short, value_parser = parse_positive)]
+    pub k: usize,
+
+    /// File where the formula is written.
+    /// Defaults to the standard output if not provided.
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ExportQuboArgs {
+    /// Path to a Graph Coloring instance
+    #[arg(short, long)]
+    pub path: String,
+
+    /// Number of colors
+    #[arg(short, value_parser = parse_positive)]
+    pub k: usize,
+
+    /// File where the QUBO is written.
+    /// Defaults to the standard output if not provided.
+    #[arg(short, long)]
+    pub output: Option<String>,
+
+    /// Format the QUBO is written in
+    #[arg(long, value_enum, default_value_t)]
+    pub format: QuboFormat,
+
+    /// Penalty for each vertex without exactly one color
+    #[arg(long, default_value_t = 1)]
+    pub vertex_penalty: i64,
+
+    /// Penalty for each edge and color its ends share
+    #[arg(long, default_value_t = 1)]
+    pub edge_penalty: i64,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct RegallocArgs {
+    /// Path to the interference graph, in the DIMACS format. Vertices precolored with a machine
+    /// register are given by `r <vertex> <register>` lines
+    #[arg(short, long)]
+    pub path: String,
+
+    /// Number of registers
+    #[arg(short)]
+    pub k: usize,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct TimetableArgs {
+    /// CSV file with one student,exam enrollment per line
+    pub enrollments: String,
+
+    /// Algorithm used to assign the timeslots.
+    /// Defaults to "grasp" if not provided.
+    #[arg(short, long, value_enum)]
+    pub algorithm: Option<Algorithm>,
+
+    /// CSV file where the timeslot of each exam is written.
+    /// The timeslots are printed after the statistics if not provided.
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct DiffArgs {
+    /// File with the first coloring, either the output of a run or a list of colors
+    pub first: String,
+
+    /// File with the second coloring, either the output of a run or a list of colors
+    pub second: String,
+
+    /// Path to the Graph Coloring instance that was colored
+    #[arg(short, long)]
+    pub path: String,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct AnalyzeArgs {
+    /// Path to a Graph Coloring instance
+    #[arg(short, long)]
+    pub path: String,
+
+    /// Number of random vertex orderings colored greedily.
+    /// Defaults to 100 if not provided.
+    #[arg(long)]
+    pub orderings: Option<usize>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct RegressArgs {
+    /// File with the number of colors found by each algorithm on each instance.
+    /// Defaults to "data/baselines.json" if not provided.
+    #[arg(short, long)]
+    pub baselines: Option<String>,
+
+    /// Number of extra colors allowed over the baselines.
+    /// Defaults to 0 if not provided.
+    #[arg(short, long)]
+    pub tolerance: Option<usize>,
+
+    /// Number of runs of each algorithm on each instance, of which the best one is compared.
+    /// Defaults to 3 if not provided.
+    #[arg(short, long)]
+    pub runs: Option<usize>,
+
+    /// Store the current results as the new baselines
+    #[arg(long)]
+    pub update: bool,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct BoundsArgs {
+    /// Path to a Graph Coloring instance
+    #[arg(short, long)]
+    pub path: String,
+
+    /// Also bound the fractional chromatic number by column generation, with up to this many
+    /// rounds of pricing. Stronger than the clique bound on sparse instances, but slower
+    #[arg(long)]
+    pub fractional: Option<usize>,
+}
+
+#[derive(Debug, clap::Args)]
+#[command(subcommand_negates_reqs = true)]
+pub struct GenerateArgs {
+    /// Generate a graph from a structured family (or a whole sweep), instead of a random graph
+    #[command(subcommand)]
+    pub command: Option<GenerateCommand>,
+
+    /// Number of vertices of the random graph
+    #[arg(short, long, required = true)]
+    pub vertices: Option<usize>,
+
+    /// Probability of each edge being in the random graph.
+    /// Defaults to 0.1 if not provided.
+    #[arg(short, long, value_parser = parse_unit_interval)]
+    pub probability: Option<f64>,
+
+    /// Seed for the random number generator, making the instance reproducible.
+    /// A random seed is used if not provided.
+    #[arg(short, long)]
+    pub seed: Option<u64>,
+
+    /// Hide a coloring with this many colors in the instance: vertices are randomly partitioned
+    /// into as many classes, and only edges across classes are added.
+    /// The number of colors is written in a comment line, as an upper bound on the chromatic number.
+    #[arg(long)]
+    pub planted: Option<usize>,
+
+    /// Make the planted instance flat: the classes have (almost) the same size, and the degrees of
+    /// the vertices of each class differ by at most this much (0 gives the flattest graphs).
+    #[arg(long, requires = "planted")]
+    pub flatness: Option<usize>,
+
+    /// Give each vertex a random weight between 1 and this value (for weighted coloring),
+    /// written as `n <vertex> <weight>` lines. Uses --seed, if provided.
+    #[arg(long, global = true)]
+    pub max_weight: Option<usize>,
+
+    /// Give each edge a random distance requirement between 1 and this value (for bandwidth
+    /// coloring), written after the endpoints of each edge. Uses --seed, if provided.
+    #[arg(long, global = true)]
+    pub max_distance: Option<usize>,
+
+    /// File where the instance is written. If it's a directory, the instance is written to a
+    /// file within it, named after the generator parameters.
+    /// Defaults to the standard output if not provided, except for batches, which require a
+    /// directory.
+    #[arg(short, long, global = true)]
+    pub output: Option<String>,
+
+    /// Format of the instance.
+    /// Defaults to DIMACS if not provided.
+    #[arg(short, long, value_enum, global = true)]
+    pub format: Option<Format>,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum GenerateCommand {
+    #[command(flatten)]
+    Family(Family),
+    /// Generate a random k-partite graph, with a density for each pair of parts
+    Partite(Partite),
+    /// Generate a parameter sweep of random graphs into a directory, along with a manifest
+    /// (`manifest.json`) describing each instance
+    Batch(Batch),
+}
+
+pub fn parse_unit_interval(s: &str) -> Result<f64, String> {
+    let value: f64 = s.parse().map_err(|_| format!("`{s}` isn't a number"))?;
+
+    if (0.0..=1.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(String::from("the value must be within [0, 1]"))
+    }
+}
+
+pub fn parse_positive(s: &str) -> Result<usize, String> {
+    match s.parse() {
+        Ok(0) => Err(String::from("the value must be positive")),
+        Ok(value) => Ok(value),
+        Err(_) => Err(format!("`{s}` isn't a positive integer")),
+    }
+}
+
+pub fn parse_positive_f64(s: &str) -> Result<f64, String> {
+    let value: f64 = s.parse().map_err(|_| format!("`{s}` isn't a number"))?;
+
+    if value > 0.0 && value.is_finite() {
+        Ok(value)
+    } else {
+        Err(String::from("the value must be positive"))
+    }
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SudokuArgs {
+    /// Path to the puzzle: 81 cells, row by row, with `0` or `.` for the blanks
+    pub puzzle: String,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct MapArgs {
+    /// GeoJSON FeatureCollection of Polygon or MultiPolygon regions
+    pub geojson: String,
+
+    /// Algorithm used to color the regions.
+    /// Defaults to "grasp" if not provided.
+    #[arg(short, long, value_enum)]
+    pub algorithm: Option<Algorithm>,
+
+    /// GeoJSON file where the regions are written, with their color in the `color` property
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct FapArgs {
+    /// Directory of the instance, with the dom.txt, var.txt and ctr.txt files
+    pub instance: String,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct IntervalsArgs {
+    /// CSV file with one task,start,end[,resource] line per task. Only tasks with the same
+    /// resource compete for machines
+    pub tasks: String,
+
+    /// Algorithm used to assign the machines.
+    /// Defaults to "grasp" if not provided.
+    #[arg(short, long, value_enum)]
+    pub algorithm: Option<Algorithm>,
+
+    /// CSV file where the machine of each task is written.
+    /// The machines are printed after the summary if not provided.
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SparsityArgs {
+    /// Sparsity pattern of the matrix, in the Matrix Market coordinate format
+    pub matrix: String,
+
+    /// Which matrix is compressed.
+    /// Defaults to jacobian if not provided.
+    #[arg(short, long, value_enum)]
+    pub compression: Option<Compression>,
+
+    /// Algorithm used to color the columns.
+    /// Defaults to "grasp" if not provided.
+    #[arg(short, long, value_enum)]
+    pub algorithm: Option<Algorithm>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct WavelengthArgs {
+    /// File with one `<demand> <node>-<node> ...` line per demand, listing the links of its path
+    pub paths: String,
+
+    /// Algorithm used to assign the wavelengths.
+    /// Defaults to "grasp" if not provided.
+    #[arg(short, long, value_enum)]
+    pub algorithm: Option<Algorithm>,
+
+    /// CSV file where the wavelength of each demand is written.
+    /// The wavelengths are printed after the summary if not provided.
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct RoundRobinArgs {
+    /// Number of teams of a single round robin, where each pair of teams plays once
+    #[arg(short = 'n', long, required_unless_present = "fixtures")]
+    pub teams: Option<usize>,
+
+    /// CSV file with one home,away game per line, instead of a single round robin
+    #[arg(short, long, conflicts_with = "teams")]
+    pub fixtures: Option<String>,
+
+    /// Algorithm used to assign the rounds.
+    /// Defaults to "grasp" if not provided.
+    #[arg(short, long, value_enum)]
+    pub algorithm: Option<Algorithm>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct ServeArgs {
+    /// Address to listen on, such as 127.0.0.1 to only accept local connections
+    #[arg(long, default_value = "0.0.0.0")]
+    pub host: String,
+
+    /// Port to listen on
+    #[arg(long, default_value_t = 8080)]
+    pub port: u16,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct WorkerArgs {
+    /// Address to listen on
+    #[arg(short, long, default_value = "0.0.0.0:7878")]
+    pub listen: String,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct CoordinateArgs {
+    /// Path to a Graph Coloring instance
+    #[arg(short, long)]
+    pub path: String,
+
+    /// Heuristic approach run by the workers.
+    /// Defaults to "grasp" if not provided.
+    #[arg(short, long, value_enum)]
+    pub algorithm: Option<Algorithm>,
+
+    /// Addresses of the workers, separated by commas
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    pub workers: Vec<String>,
+
+    /// Rounds of runs, with one run on each worker per round
+    #[arg(short, long, default_value_t = 3, value_parser = parse_positive)]
+    pub rounds: usize,
+
+    /// Number of best colorings kept, and sent to the Genetic Algorithm runs of the next round
+    #[arg(long, default_value_t = 10)]
+    pub elite_size: usize,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct PlotArgs {
+    /// Traces of the runs, as written by --trace
+    #[arg(required = true)]
+    pub traces: Vec<String>,
+
+    /// File where the chart is written (as SVG)
+    #[arg(short, long)]
+    pub output: String,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct OnlineArgs {
+    /// Distance from a new conflicting edge within which vertices may be recolored
+    #[arg(long, default_value_t = 2)]
+    pub radius: usize,
+
+    /// Maximum number of recoloring moves per update, before giving up and using a new color
+    #[arg(long, default_value_t = 1000)]
+    pub max_moves: usize,
 }
@@ -0,0 +1,143 @@
+//! Results cached on disk, keyed on the content of the instance, the algorithm, its parameters,
+//! the seed and the version of the crate, so reruns of the same experiments are answered at once.
+
+use crate::algorithms::{count_colors, is_coloring_valid};
+use crate::args::Algorithm;
+use crate::graph::adj_list::AdjList;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Directory where the results are cached, if no other one is given.
+pub const DEFAULT_DIRECTORY: &str = ".gcp-cache";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub num_colors: usize,
+    pub coloring: Vec<usize>,
+    /// Duration of the run that found the result, in milliseconds.
+    pub duration: u128,
+}
+
+/// The 64-bit FNV-1a hash of `bytes`, which (unlike the hasher of the standard library) is the same
+/// across builds.
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// The key of a run of `algorithm` with `params` (described in any way that changes along with
+/// them) over `instance`.
+///
+/// Without the `deterministic` feature, the seed is random, so the key stands for any run with the
+/// same parameters. Results of other versions of the crate have other keys, since the algorithms
+/// may have changed since.
+pub fn key(instance: &[u8], algorithm: &Algorithm, params: &str) -> String {
+    let seed = if cfg!(feature = "deterministic") {
+        "0"
+    } else {
+        "random"
+    };
+    let description = format!(
+        "{} {params} seed={seed} version={}",
+        algorithm.to_possible_value().unwrap().get_name(),
+        env!("CARGO_PKG_VERSION")
+    );
+
+    format!(
+        "{:016x}-{:016x}",
+        fnv1a(instance),
+        fnv1a(description.as_bytes())
+    )
+}
+
+/// The entry cached under `key` in `directory` for `graph`, if any. Unreadable entries, and the ones
+/// that aren't a valid coloring of `graph` with their number of colors (corrupt or stale), are
+/// treated as missing.
+pub fn load(directory: &str, key: &str, graph: &AdjList) -> Option<Entry> {
+    let file = File::open(Path::new(directory).join(format!("{key}.json"))).ok()?;
+    let entry: Entry = serde_json::from_reader(BufReader::new(file)).ok()?;
+
+    (entry.coloring.len() == graph.num_vertices()
+        && is_coloring_valid(graph, &entry.coloring)
+        && count_colors(&entry.coloring) == entry.num_colors)
+        .then_some(entry)
+}
+
+pub fn store(directory: &str, key: &str, entry: &Entry) -> Result<(), Box<dyn Error>> {
+    fs::create_dir_all(directory)?;
+
+    let file = File::create(Path::new(directory).join(format!("{key}.json")))?;
+    let mut writer = BufWriter::new(file);
+
+    serde_json::to_writer(&mut writer, entry)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key() {
+        assert_eq!(fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+
+        let instance = b"p edge 2 1\ne 1 2\n";
+        let base = key(instance, &Algorithm::Grasp, "iterations=10");
+
+        assert_eq!(base, key(instance, &Algorithm::Grasp, "iterations=10"));
+        assert_ne!(base, key(instance, &Algorithm::Genetic, "iterations=10"));
+        assert_ne!(base, key(instance, &Algorithm::Grasp, "iterations=20"));
+        assert_ne!(
+            base,
+            key(b"p edge 2 0\n", &Algorithm::Grasp, "iterations=10")
+        );
+    }
+
+    #[test]
+    fn test_load_and_store() {
+        let directory = std::env::temp_dir().join("gcp-heuristics-test-cache");
+        let directory = directory.to_str().unwrap();
+        let graph = AdjList::complete(2);
+        let entry = Entry {
+            num_colors: 2,
+            coloring: vec![1, 2],
+            duration: 7,
+        };
+
+        let _ = fs::remove_dir_all(directory);
+        assert_eq!(load(directory, "key", &graph), None);
+
+        store(directory, "key", &entry).unwrap();
+        assert_eq!(load(directory, "key", &graph), Some(entry.clone()));
+
+        // Entries that don't color the graph properly are misses
+        for coloring in [vec![1, 1], vec![1, 2, 3]] {
+            let entry = Entry {
+                coloring,
+                ..entry.clone()
+            };
+
+            store(directory, "key", &entry).unwrap();
+            assert_eq!(load(directory, "key", &graph), None);
+        }
+        let entry = Entry {
+            num_colors: 3,
+            ..entry
+        };
+        store(directory, "key", &entry).unwrap();
+        assert_eq!(load(directory, "key", &graph), None);
+
+        fs::write(Path::new(directory).join("key.json"), "{\"num_colors\": 2").unwrap();
+        assert_eq!(load(directory, "key", &graph), None);
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
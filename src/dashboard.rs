@@ -0,0 +1,190 @@
+//! A terminal dashboard showing the [`progress`](crate::progress) of the running algorithm.
+//!
+//! It's drawn with ratatui, in place below the cursor of standard error (so the results on
+//! standard output are kept clean), a few times per second.
+
+use crate::progress::{Snapshot, MAX_THREADS, PROGRESS};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Bar, BarChart, Block, Paragraph, Sparkline};
+use ratatui::{backend::CrosstermBackend, Frame, Terminal, TerminalOptions, Viewport};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+/// Lines of the statistics at the top of the dashboard.
+const STATS_HEIGHT: u16 = 4;
+/// Lines of the convergence sparkline, including its borders.
+const SPARKLINE_HEIGHT: u16 = 3;
+/// Most threads shown at once.
+const MAX_THREAD_ROWS: u16 = 16;
+
+/// Redraws the dashboard until `stop` is set, then draws it a last time and leaves it on screen.
+///
+/// Fails if standard error isn't a terminal the dashboard can be drawn on.
+pub fn run(title: &str, stop: &AtomicBool) -> io::Result<()> {
+    let threads = thread::available_parallelism().map_or(1, usize::from) + 1;
+    let thread_rows = threads.min(MAX_THREADS) as u16;
+    let height = 2 + STATS_HEIGHT + thread_rows.min(MAX_THREAD_ROWS) + SPARKLINE_HEIGHT;
+
+    let mut terminal = Terminal::with_options(
+        CrosstermBackend::new(io::stderr()),
+        TerminalOptions {
+            viewport: Viewport::Inline(height),
+        },
+    )?;
+    let mut previous_iterations = 0;
+    let mut area = Rect::default();
+
+    loop {
+        let is_last = stop.load(Ordering::Relaxed);
+        let snapshot = PROGRESS.snapshot();
+        let iterations = snapshot.total_iterations();
+        let rate = (iterations - previous_iterations) as f64 / REFRESH_INTERVAL.as_secs_f64();
+        previous_iterations = iterations;
+
+        terminal.draw(|frame| {
+            area = frame.area();
+            draw(frame, title, &snapshot, rate);
+        })?;
+
+        if is_last {
+            break;
+        }
+
+        thread::sleep(REFRESH_INTERVAL);
+    }
+
+    // Move below the dashboard, so it isn't overwritten
+    terminal.set_cursor_position((0, area.bottom().saturating_sub(1)))?;
+    terminal.show_cursor()?;
+    eprintln!();
+
+    Ok(())
+}
+
+/// Draws the dashboard for `snapshot`, with `rate` iterations per second, on the whole `frame`.
+pub fn draw(frame: &mut Frame, title: &str, snapshot: &Snapshot, rate: f64) {
+    let optional = |value: Option<usize>| value.map_or("-".to_string(), |v| v.to_string());
+
+    let block = Block::bordered().title(title.to_string());
+    let area = block.inner(frame.area());
+    frame.render_widget(block, frame.area());
+
+    let [stats_area, threads_area, sparkline_area] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(STATS_HEIGHT),
+            Constraint::Min(1),
+            Constraint::Length(SPARKLINE_HEIGHT),
+        ])
+        .areas(area);
+
+    let stats = vec![
+        Line::from(format!(
+            "Elapsed:     {:.1}s",
+            snapshot.elapsed.as_secs_f64()
+        )),
+        Line::from(format!("Best colors: {}", optional(snapshot.best_colors))),
+        Line::from(format!("Conflicts:   {}", optional(snapshot.conflicts))),
+        Line::from(format!(
+            "Iterations:  {} ({rate:.0}/s)",
+            snapshot.total_iterations()
+        )),
+    ];
+    frame.render_widget(Paragraph::new(stats), stats_area);
+
+    let bars: Vec<Bar> = snapshot
+        .iterations
+        .iter()
+        .enumerate()
+        .filter(|(_, iterations)| **iterations > 0)
+        .map(|(thread, iterations)| {
+            let name = match thread {
+                0 => "main".to_string(),
+                _ => format!("#{}", thread - 1),
+            };
+
+            Bar::with_label(name, *iterations as u64)
+        })
+        .collect();
+    let threads = BarChart::horizontal(bars)
+        .bar_width(1)
+        .bar_gap(0)
+        .bar_style(Style::default().fg(Color::Cyan));
+    frame.render_widget(threads, threads_area);
+
+    frame.render_widget(
+        convergence(&snapshot.history, sparkline_area),
+        sparkline_area,
+    );
+}
+
+/// Sparkline of the best number of colors over `history` (the larger, the taller), keeping the
+/// last values that fit in `area`. The heights are relative to the smallest number of colors shown.
+fn convergence(history: &[(Duration, usize)], area: Rect) -> Sparkline<'static> {
+    let width = area.width.saturating_sub(2) as usize;
+    let values = &history[history.len().saturating_sub(width)..];
+    let min = values.iter().map(|(_, best)| *best).min().unwrap_or(0);
+
+    // Shifted by one, so the smallest values are still drawn
+    let data: Vec<u64> = values
+        .iter()
+        .map(|(_, best)| (best - min + 1) as u64)
+        .collect();
+
+    Sparkline::default()
+        .block(Block::bordered().title("Convergence"))
+        .data(data)
+        .style(Style::default().fg(Color::Green))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+
+    fn render(snapshot: &Snapshot, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| draw(frame, "GRASP on myciel3", snapshot, 20.0))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_draw() {
+        let snapshot = Snapshot {
+            elapsed: Duration::from_millis(1500),
+            best_colors: Some(4),
+            conflicts: None,
+            iterations: vec![0, 10, 5],
+            history: vec![(Duration::ZERO, 6), (Duration::from_secs(1), 4)],
+            conflict_history: Vec::new(),
+        };
+        let lines = render(&snapshot, 40, 12);
+        let contains = |text: &str| lines.iter().any(|line| line.contains(text));
+
+        assert!(lines[0].contains("GRASP on myciel3"));
+        assert!(contains("Best colors: 4"));
+        assert!(contains("Conflicts:   -"));
+        assert!(contains("Iterations:  15 (20/s)"));
+        assert!(!contains("main"));
+        assert!(contains("#0"));
+        assert!(contains("#1"));
+        assert!(contains("Convergence"));
+        // The best number of colors went down, from a full bar to a third of one
+        assert!(contains("█▂"));
+    }
+}
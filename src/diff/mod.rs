@@ -0,0 +1,70 @@
+use crate::algorithms::{canonical_classes, count_colors, match_colors};
+use std::collections::BTreeMap;
+
+/// Structural differences between two colorings of the same graph.
+#[derive(Debug, PartialEq)]
+pub struct ColoringDiff {
+    pub num_colors: (usize, usize),
+    /// Whether both colorings have the same canonical form, i.e., the same classes.
+    pub same_partition: bool,
+    /// Number of vertices that must change classes to turn the first partition into the second.
+    /// Classes are paired greedily, so this is an upper bound on the exact distance.
+    pub partition_distance: usize,
+    /// For each class of the first coloring, the classes of the second coloring it shares
+    /// vertices with, and how many.
+    pub overlaps: BTreeMap<usize, BTreeMap<usize, usize>>,
+    /// Vertices whose class isn't paired with their class in the first coloring.
+    pub moved: Vec<usize>,
+}
+
+/// Compares the partitions induced by `first` and `second`, regardless of their color labels.
+pub fn diff(first: &[usize], second: &[usize]) -> ColoringDiff {
+    let mut overlaps: BTreeMap<usize, BTreeMap<usize, usize>> = BTreeMap::new();
+
+    for (color, other_color) in first.iter().zip(second.iter()) {
+        *overlaps
+            .entry(*color)
+            .or_default()
+            .entry(*other_color)
+            .or_default() += 1;
+    }
+
+    let matched = match_colors(first, second);
+    let moved: Vec<usize> = (0..first.len())
+        .filter(|v| first[*v] != matched[*v])
+        .collect();
+
+    ColoringDiff {
+        num_colors: (count_colors(first), count_colors(second)),
+        same_partition: canonical_classes(first) == canonical_classes(second),
+        partition_distance: moved.len(),
+        overlaps,
+        moved,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        // Same partition, different labels
+        let same = diff(&[1, 1, 2, 3], &[3, 3, 1, 2]);
+
+        assert_eq!(same.num_colors, (3, 3));
+        assert!(same.same_partition);
+        assert_eq!(same.partition_distance, 0);
+        assert!(same.moved.is_empty());
+
+        // Vertex 2 moves to the class of vertex 3, and vertex 4 gets a class of its own
+        let moved = diff(&[1, 1, 1, 2, 2], &[1, 1, 2, 2, 3]);
+
+        assert_eq!(moved.num_colors, (2, 3));
+        assert!(!moved.same_partition);
+        assert_eq!(moved.partition_distance, 2);
+        assert_eq!(moved.moved, vec![2, 4]);
+        assert_eq!(moved.overlaps[&1], BTreeMap::from([(1, 2), (2, 1)]));
+        assert_eq!(moved.overlaps[&2], BTreeMap::from([(2, 1), (3, 1)]));
+    }
+}
@@ -0,0 +1,343 @@
+//! Solving across machines: a coordinator farms out runs of an algorithm to workers over TCP and
+//! keeps the best (elite) colorings they find.
+//!
+//! The protocol is line-based JSON: the coordinator sends a [`Job`] per line, and the worker
+//! answers each one with a [`JobResult`] line, over a connection that lasts the whole search. The
+//! elite colorings are sent along with each job, so Genetic Algorithm runs start from them (as
+//! islands with migration, where the elite is the migrating population).
+
+use crate::algorithms::{count_colors, is_coloring_valid, relabel_colors, solve_seeded, Solution};
+use crate::args::Algorithm;
+use crate::graph::adj_list::AdjList;
+use crate::input;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    pub num_vertices: usize,
+    /// Edges, as pairs of 0-indexed vertices.
+    pub edges: Vec<(usize, usize)>,
+    /// Name of the algorithm, as given to `-a`.
+    pub algorithm: String,
+    pub lower_bound: Option<usize>,
+    /// Colorings the Genetic Algorithm starts from.
+    pub elite: Vec<Vec<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JobResult {
+    pub num_colors: usize,
+    pub coloring: Vec<usize>,
+}
+
+/// Runs a worker on `listener` until the process is stopped, handling each coordinator on its own
+/// thread.
+///
+/// A connection that fails to be accepted is logged and dropped, so later coordinators are still
+/// served.
+pub fn work(listener: TcpListener) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("Failed to accept a coordinator: {err}");
+                continue;
+            }
+        };
+
+        thread::spawn(move || {
+            if let Err(err) = serve_coordinator(stream) {
+                eprintln!("Failed to serve a coordinator: {err}");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn serve_coordinator(stream: TcpStream) -> Result<(), Box<dyn Error>> {
+    let mut writer = stream.try_clone()?;
+
+    for line in BufReader::new(stream).lines() {
+        let job: Job = serde_json::from_str(&line?)?;
+        let result = run_job(&job)?;
+
+        serde_json::to_writer(&mut writer, &result)?;
+        writeln!(writer)?;
+        writer.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Solves the instance of `job` with its algorithm.
+///
+/// Fails on malformed jobs, as they come from the network: too many vertices (see
+/// [`input::check_num_vertices`]), invalid edges, or elite colorings that don't assign a
+/// (1-indexed) color to each vertex.
+pub fn run_job(job: &Job) -> Result<JobResult, String> {
+    let algorithm = Algorithm::from_str(&job.algorithm, true)
+        .map_err(|_| format!("unknown algorithm {}", job.algorithm))?;
+    input::check_num_vertices(job.num_vertices as u64).map_err(|err| err.to_string())?;
+
+    for (i, coloring) in job.elite.iter().enumerate() {
+        if coloring.len() != job.num_vertices {
+            return Err(format!(
+                "elite coloring {i} colors {} vertices, but the instance has {}",
+                coloring.len(),
+                job.num_vertices
+            ));
+        }
+        if coloring.contains(&0) {
+            return Err(format!(
+                "elite coloring {i} uses color 0, but colors are 1-indexed"
+            ));
+        }
+    }
+
+    let mut graph = AdjList::new(job.num_vertices);
+
+    for (u, v) in job.edges.iter() {
+        if *u >= job.num_vertices || *v >= job.num_vertices || u == v {
+            return Err(format!("invalid edge ({u}, {v})"));
+        }
+
+        graph.add_edge(*u, *v);
+    }
+
+    let elite = (!job.elite.is_empty()).then(|| job.elite.clone());
+    let (num_colors, coloring) = solve_seeded(&graph, &algorithm, job.lower_bound, elite);
+
+    Ok(JobResult {
+        num_colors,
+        coloring,
+    })
+}
+
+/// Coordinates `rounds` rounds of runs of `algorithm` over `graph`, with a run on each of the
+/// `workers` per round, keeping the best `elite_size` distinct colorings found. Stops early once
+/// `lower_bound` is reached. There must be at least one round.
+///
+/// Returns the best coloring, along with the best number of colors after each round. The workers
+/// aren't trusted: results that aren't valid colorings are discarded, and workers that can't be
+/// reached or fail a run are dropped, stopping early once none is left.
+pub fn coordinate(
+    graph: &AdjList,
+    algorithm: &Algorithm,
+    workers: &[String],
+    rounds: usize,
+    elite_size: usize,
+    lower_bound: Option<usize>,
+) -> Result<(Solution, Vec<usize>), Box<dyn Error>> {
+    assert!(rounds > 0, "there must be at least one round");
+
+    let mut connections = Vec::new();
+
+    for worker in workers {
+        match TcpStream::connect(worker).and_then(|stream| Ok((stream.try_clone()?, stream))) {
+            Ok((reader, writer)) => connections.push((worker, BufReader::new(reader), writer)),
+            Err(err) => eprintln!("Can't connect to {worker}: {err}"),
+        }
+    }
+
+    if connections.is_empty() {
+        return Err("can't connect to any worker".into());
+    }
+
+    let mut job = Job {
+        num_vertices: graph.num_vertices(),
+        edges: graph.edges(),
+        algorithm: algorithm
+            .to_possible_value()
+            .unwrap()
+            .get_name()
+            .to_string(),
+        lower_bound,
+        elite: Vec::new(),
+    };
+    let mut elite: Vec<Solution> = Vec::new();
+    let mut history = Vec::new();
+
+    for _ in 0..rounds {
+        if connections.is_empty() {
+            eprintln!("No worker is left, stopping after {} rounds", history.len());
+            break;
+        }
+
+        let message = serde_json::to_string(&job)? + "\n";
+
+        let results: Vec<Result<JobResult, String>> = thread::scope(|scope| {
+            let handles: Vec<_> = connections
+                .iter_mut()
+                .map(|(_, reader, writer)| {
+                    let message = &message;
+
+                    scope.spawn(move || {
+                        request(reader, writer, message).map_err(|err| err.to_string())
+                    })
+                })
+                .collect();
+
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        let mut colorings = Vec::new();
+        connections = connections
+            .into_iter()
+            .zip(results)
+            .filter_map(|(connection, result)| match result {
+                Ok(result) => {
+                    colorings.push(result.coloring);
+                    Some(connection)
+                }
+                Err(err) => {
+                    eprintln!("Dropping the worker {}: {err}", connection.0);
+                    None
+                }
+            })
+            .collect();
+
+        for mut coloring in colorings {
+            if coloring.len() != graph.num_vertices() || !is_coloring_valid(graph, &coloring) {
+                continue;
+            }
+
+            relabel_colors(&mut coloring);
+            if !elite.iter().any(|(_, other)| *other == coloring) {
+                elite.push((count_colors(&coloring), coloring));
+            }
+        }
+
+        elite.sort();
+        elite.truncate(elite_size.max(1));
+
+        let Some((best, _)) = elite.first() else {
+            return Err("no worker returned a valid coloring".into());
+        };
+        history.push(*best);
+
+        if lower_bound.is_some_and(|lower_bound| *best <= lower_bound) {
+            break;
+        }
+
+        job.elite = elite.iter().map(|(_, coloring)| coloring.clone()).collect();
+    }
+
+    Ok((elite.swap_remove(0), history))
+}
+
+fn request(
+    reader: &mut impl BufRead,
+    writer: &mut impl Write,
+    message: &str,
+) -> Result<JobResult, Box<dyn Error>> {
+    writer.write_all(message.as_bytes())?;
+    writer.flush()?;
+
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err("the worker closed the connection".into());
+    }
+
+    Ok(serde_json::from_str(&line)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn test_run_job() {
+        let job = Job {
+            num_vertices: 3,
+            edges: vec![(0, 1), (1, 2), (0, 2)],
+            algorithm: "genetic".to_string(),
+            lower_bound: Some(3),
+            elite: vec![vec![1, 2, 3]],
+        };
+
+        assert_eq!(run_job(&job).unwrap().num_colors, 3);
+
+        let invalid = [
+            Job {
+                algorithm: "magic".to_string(),
+                ..job.clone()
+            },
+            Job {
+                num_vertices: usize::MAX,
+                ..job.clone()
+            },
+            Job {
+                elite: vec![vec![1, 2]],
+                ..job.clone()
+            },
+            Job {
+                elite: vec![vec![0, 1, 2]],
+                ..job
+            },
+        ];
+
+        assert!(invalid.iter().all(|job| run_job(job).is_err()));
+    }
+
+    #[test]
+    fn test_coordinate() {
+        let workers: Vec<String> = (0..2)
+            .map(|_| {
+                let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+                let address = listener.local_addr().unwrap().to_string();
+
+                thread::spawn(move || work(listener));
+
+                address
+            })
+            .collect();
+
+        let graph = testing::myciel4();
+        let ((num_colors, coloring), history) =
+            coordinate(&graph, &Algorithm::Grasp, &workers, 3, 4, Some(5)).unwrap();
+
+        assert!(is_coloring_valid(&graph, &coloring));
+        assert_eq!(history.last(), Some(&num_colors));
+        assert!(history.windows(2).all(|pair| pair[1] <= pair[0]));
+
+        assert!(coordinate(
+            &graph,
+            &Algorithm::Grasp,
+            &["127.0.0.1:1".to_string()],
+            1,
+            1,
+            None
+        )
+        .is_err());
+
+        // A worker that hangs up is dropped, and the others carry on
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let failing = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                drop(stream);
+            }
+        });
+
+        let ((num_colors, coloring), history) = coordinate(
+            &graph,
+            &Algorithm::Grasp,
+            &[failing, workers[0].clone()],
+            2,
+            4,
+            None,
+        )
+        .unwrap();
+
+        assert!(is_coloring_valid(&graph, &coloring));
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.last(), Some(&num_colors));
+    }
+}
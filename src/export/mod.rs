@@ -0,0 +1,3 @@
+//! Encodings of the Graph Coloring Problem for external solvers.
+
+pub mod qubo;
@@ -0,0 +1,185 @@
+//! Encoding of "can the graph be colored with `k` colors?" as a QUBO (Quadratic Unconstrained
+//! Binary Optimization) problem, so it can be handed to a quantum or digital annealer, or to a
+//! simulated annealing QUBO solver.
+//!
+//! Each pair of a vertex and a color is a binary variable, 1 if the vertex has the color. The
+//! energy to minimize adds a penalty for each vertex without exactly one color and for each edge
+//! whose ends share a color, so it's 0 exactly for the valid `k`-colorings.
+
+use crate::graph::adj_list::AdjList;
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// Format the QUBO is written in.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum QuboFormat {
+    /// The text format of qbsolv: a program line followed by a line `i j weight` for each term,
+    /// the diagonal first.
+    #[default]
+    Qubo,
+    /// A JSON object with the number of variables, the offset and the terms as `[i, j, weight]`.
+    Json,
+}
+
+/// An upper triangular QUBO matrix: the energy of an assignment `x` is
+/// `offset + sum of weight * x[i] * x[j]` over the terms.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Qubo {
+    pub num_variables: usize,
+    /// Constant added to the energy, so valid assignments have energy 0.
+    pub offset: i64,
+    /// The nonzero entries `(i, j, weight)` with `i <= j`, the diagonal ones (the linear terms)
+    /// first.
+    pub terms: Vec<(usize, usize, i64)>,
+}
+
+/// The variable of `vertex` having `color` (both 0-indexed) with `k` colors.
+pub fn variable(vertex: usize, color: usize, k: usize) -> usize {
+    vertex * k + color
+}
+
+/// Encodes the `k`-coloring of `graph` as a QUBO, whose minimum energy is 0 exactly when the graph
+/// can be colored with `k` colors.
+///
+/// Each vertex contributes `vertex_penalty * (1 - sum of its variables)^2`, and each edge
+/// `edge_penalty` times the number of colors its ends share.
+pub fn encode(graph: &AdjList, k: usize, vertex_penalty: i64, edge_penalty: i64) -> Qubo {
+    let n = graph.num_vertices();
+    let mut terms = Vec::new();
+
+    // Since x^2 = x, (1 - sum of x)^2 = 1 - sum of x + 2 * sum of x * y over the pairs
+    for v in 0..n {
+        for color in 0..k {
+            let x = variable(v, color, k);
+            terms.push((x, x, -vertex_penalty));
+        }
+    }
+
+    for v in 0..n {
+        for first in 0..k {
+            for second in first + 1..k {
+                terms.push((
+                    variable(v, first, k),
+                    variable(v, second, k),
+                    2 * vertex_penalty,
+                ));
+            }
+        }
+    }
+
+    for (u, neighbors) in graph.adj_list().iter().enumerate() {
+        for v in neighbors.iter().filter(|v| u < **v) {
+            for color in 0..k {
+                terms.push((variable(u, color, k), variable(*v, color, k), edge_penalty));
+            }
+        }
+    }
+
+    Qubo {
+        num_variables: n * k,
+        offset: n as i64 * vertex_penalty,
+        terms,
+    }
+}
+
+/// Writes `qubo` in the text format of qbsolv, with each line of `comment` as a comment line.
+pub fn write_qubo<W: Write>(writer: &mut W, qubo: &Qubo, comment: &str) -> io::Result<()> {
+    let diagonal = qubo.terms.iter().filter(|(i, j, _)| i == j).count();
+
+    for line in comment.lines() {
+        writeln!(writer, "c {line}")?;
+    }
+    writeln!(writer, "c offset {}", qubo.offset)?;
+    writeln!(
+        writer,
+        "p qubo 0 {} {} {}",
+        qubo.num_variables,
+        diagonal,
+        qubo.terms.len() - diagonal
+    )?;
+
+    for (i, j, weight) in qubo.terms.iter() {
+        writeln!(writer, "{i} {j} {weight}")?;
+    }
+
+    Ok(())
+}
+
+/// Writes `qubo` as a JSON object.
+pub fn write_json<W: Write>(writer: &mut W, qubo: &Qubo) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, qubo)?;
+    writeln!(writer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    /// The energy of `assignment` (a value for each variable) in `qubo`.
+    fn energy(qubo: &Qubo, assignment: &[bool]) -> i64 {
+        qubo.offset
+            + qubo
+                .terms
+                .iter()
+                .filter(|(i, j, _)| assignment[*i] && assignment[*j])
+                .map(|(_, _, weight)| weight)
+                .sum::<i64>()
+    }
+
+    /// The assignment of the variables given by `coloring` (with colors from 1).
+    fn assignment(coloring: &[usize], k: usize) -> Vec<bool> {
+        let mut assignment = vec![false; coloring.len() * k];
+
+        for (v, color) in coloring.iter().enumerate() {
+            assignment[variable(v, color - 1, k)] = true;
+        }
+
+        assignment
+    }
+
+    #[test]
+    fn test_encode() {
+        let graph = testing::cycle(4);
+        let qubo = encode(&graph, 2, 1, 1);
+
+        assert_eq!(qubo.num_variables, 8);
+        // A linear term for each variable, a pair of colors for each vertex and a conflict term
+        // for each edge and color
+        assert_eq!(qubo.terms.len(), 8 + 4 + 4 * 2);
+
+        assert_eq!(energy(&qubo, &assignment(&[1, 2, 1, 2], 2)), 0);
+        // Each conflicting edge costs the edge penalty
+        assert_eq!(energy(&qubo, &assignment(&[1, 1, 1, 2], 2)), 2);
+        // As does each vertex without a color, or with two
+        let mut uncolored = assignment(&[1, 2, 1, 2], 2);
+        uncolored[variable(0, 0, 2)] = false;
+        assert_eq!(energy(&qubo, &uncolored), 1);
+        let mut doubled = assignment(&[1, 2, 1, 2], 2);
+        doubled[variable(1, 0, 2)] = true;
+        assert_eq!(energy(&qubo, &doubled), 1 + 2);
+    }
+
+    #[test]
+    fn test_write_qubo() {
+        let mut graph = AdjList::new(2);
+        graph.add_edge(0, 1);
+        let qubo = encode(&graph, 1, 2, 1);
+
+        let mut output = Vec::new();
+        write_qubo(&mut output, &qubo, "1-coloring").unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "c 1-coloring\nc offset 4\np qubo 0 2 2 1\n0 0 -2\n1 1 -2\n0 1 1\n"
+        );
+
+        let mut output = Vec::new();
+        write_json(&mut output, &qubo).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "{\"num_variables\":2,\"offset\":4,\"terms\":[[0,0,-2],[1,1,-2],[0,1,1]]}\n"
+        );
+    }
+}
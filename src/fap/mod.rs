@@ -0,0 +1,313 @@
+use crate::graph::adj_list::AdjList;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Separation between the frequencies of two links.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Separation {
+    /// `|f(u) - f(v)| > distance`
+    Greater(usize),
+    /// `|f(u) - f(v)| = distance`
+    Equal(usize),
+}
+
+impl Separation {
+    fn is_satisfied(self, first: usize, second: usize) -> bool {
+        match self {
+            Separation::Greater(distance) => first.abs_diff(second) > distance,
+            Separation::Equal(distance) => first.abs_diff(second) == distance,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Constraint {
+    pub u: usize,
+    pub v: usize,
+    pub separation: Separation,
+}
+
+/// A radio link frequency assignment instance. Links are referred to by their index.
+#[derive(Debug)]
+pub struct Instance {
+    /// Identifier of each link, as in the instance files.
+    pub links: Vec<usize>,
+    /// Frequencies available to each link.
+    pub domains: Vec<Vec<usize>>,
+    pub constraints: Vec<Constraint>,
+}
+
+/// Reads an instance in the CELAR/GRAPH format from a directory with the `dom.txt`, `var.txt`
+/// and `ctr.txt` files.
+pub fn read_instance(dir: &Path) -> Result<Instance, Box<dyn Error>> {
+    let open = |name: &str| -> Result<BufReader<File>, Box<dyn Error>> {
+        let path = dir.join(name);
+        let file = File::open(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+
+        Ok(BufReader::new(file))
+    };
+
+    let domains = read_domains(open("dom.txt")?)?;
+    let (links, link_domains) = read_variables(open("var.txt")?)?;
+
+    let domains = link_domains
+        .iter()
+        .zip(links.iter())
+        .map(|(domain, link)| {
+            domains
+                .get(domain)
+                .cloned()
+                .ok_or_else(|| format!("link {link} has an unknown domain {domain}"))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let constraints = read_constraints(open("ctr.txt")?, &links)?;
+
+    Ok(Instance {
+        links,
+        domains,
+        constraints,
+    })
+}
+
+/// Reads the domains, given as `<domain> <number of frequencies> <frequencies>...` lines.
+fn read_domains(reader: impl BufRead) -> Result<HashMap<usize, Vec<usize>>, Box<dyn Error>> {
+    let mut domains = HashMap::new();
+
+    for line in reader.lines() {
+        let numbers = parse_numbers(&line?)?;
+
+        let [domain, count, ref frequencies @ ..] = numbers[..] else {
+            continue;
+        };
+        if frequencies.len() != count {
+            return Err(format!(
+                "domain {domain} should have {count} frequencies, but has {}",
+                frequencies.len()
+            )
+            .into());
+        }
+
+        domains.insert(domain, frequencies.to_vec());
+    }
+
+    Ok(domains)
+}
+
+/// Reads the links and their domains, given as `<link> <domain>` lines. Any following columns (the
+/// initial frequency and its mobility, in some instances) are ignored.
+fn read_variables(reader: impl BufRead) -> Result<(Vec<usize>, Vec<usize>), Box<dyn Error>> {
+    let mut links = Vec::new();
+    let mut domains = Vec::new();
+
+    for line in reader.lines() {
+        let numbers = parse_numbers(&line?)?;
+
+        if let [link, domain, ..] = numbers[..] {
+            links.push(link);
+            domains.push(domain);
+        }
+    }
+
+    Ok((links, domains))
+}
+
+/// Reads the constraints, given as `<link> <link> <type> <operator> <distance>` lines, where the
+/// operator is `>` or `=`. Any following columns (the weight, in some instances) are ignored.
+fn read_constraints(
+    reader: impl BufRead,
+    links: &[usize],
+) -> Result<Vec<Constraint>, Box<dyn Error>> {
+    let indices: HashMap<usize, usize> = links.iter().enumerate().map(|(i, l)| (*l, i)).collect();
+    let index = |link: &str| -> Result<usize, Box<dyn Error>> {
+        let link: usize = link.parse()?;
+
+        indices
+            .get(&link)
+            .copied()
+            .ok_or_else(|| format!("link {link} isn't declared").into())
+    };
+
+    let mut constraints = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let splits: Vec<&str> = line.split_whitespace().collect();
+
+        if splits.is_empty() {
+            continue;
+        }
+        let [u, v, _, operator, distance, ..] = splits[..] else {
+            return Err(format!("malformed constraint: {line}").into());
+        };
+
+        let distance = distance.parse()?;
+        let separation = match operator {
+            ">" => Separation::Greater(distance),
+            "=" => Separation::Equal(distance),
+            _ => return Err(format!("unknown operator {operator} in constraint: {line}").into()),
+        };
+
+        constraints.push(Constraint {
+            u: index(u)?,
+            v: index(v)?,
+            separation,
+        });
+    }
+
+    Ok(constraints)
+}
+
+fn parse_numbers(line: &str) -> Result<Vec<usize>, Box<dyn Error>> {
+    Ok(line
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()?)
+}
+
+/// The interference graph of the instance: two links are adjacent if there's a constraint on them.
+pub fn interference_graph(instance: &Instance) -> AdjList {
+    let mut graph = AdjList::new(instance.links.len());
+    let mut edges = BTreeSet::new();
+
+    for constraint in instance.constraints.iter() {
+        let (u, v) = (constraint.u, constraint.v);
+
+        if u != v && edges.insert((u.min(v), u.max(v))) {
+            graph.add_edge(u, v);
+        }
+    }
+
+    graph
+}
+
+/// Assigns a frequency of its domain to each link, satisfying every constraint with the links
+/// assigned before it.
+///
+/// Works like DSATUR over the interference graph, where the colors are the frequencies: the next
+/// link is always the one with the fewest feasible frequencies left, and it takes a frequency
+/// already in use if possible (or the lowest one otherwise), so few frequencies are used. Links
+/// with no feasible frequency are left unassigned.
+pub fn assign(instance: &Instance) -> Vec<Option<usize>> {
+    let n = instance.links.len();
+    let mut constraints: Vec<Vec<(usize, Separation)>> = vec![Vec::new(); n];
+
+    for constraint in instance.constraints.iter() {
+        constraints[constraint.u].push((constraint.v, constraint.separation));
+        constraints[constraint.v].push((constraint.u, constraint.separation));
+    }
+
+    let mut frequencies: Vec<Option<usize>> = vec![None; n];
+    let mut is_done = vec![false; n];
+    let mut used = BTreeSet::new();
+
+    let feasible = |frequencies: &[Option<usize>], link: usize| -> Vec<usize> {
+        instance.domains[link]
+            .iter()
+            .copied()
+            .filter(|frequency| {
+                constraints[link].iter().all(|(other, separation)| {
+                    frequencies[*other]
+                        .is_none_or(|other| separation.is_satisfied(*frequency, other))
+                })
+            })
+            .collect()
+    };
+
+    for _ in 0..n {
+        let (link, options) = (0..n)
+            .filter(|link| !is_done[*link])
+            .map(|link| (link, feasible(&frequencies, link)))
+            .min_by_key(|(link, options)| (options.len(), Reverse(constraints[*link].len())))
+            .unwrap();
+
+        is_done[link] = true;
+        frequencies[link] = options
+            .iter()
+            .find(|frequency| used.contains(*frequency))
+            .or(options.iter().min())
+            .copied();
+
+        if let Some(frequency) = frequencies[link] {
+            used.insert(frequency);
+        }
+    }
+
+    frequencies
+}
+
+/// Number of constraints that aren't satisfied, counting the ones on unassigned links.
+pub fn count_violations(instance: &Instance, frequencies: &[Option<usize>]) -> usize {
+    instance
+        .constraints
+        .iter()
+        .filter(
+            |constraint| match (frequencies[constraint.u], frequencies[constraint.v]) {
+                (Some(first), Some(second)) => !constraint.separation.is_satisfied(first, second),
+                _ => true,
+            },
+        )
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOMAINS: &str = "1 4 10 20 30 40\n2 2 10 40\n";
+    const VARIABLES: &str = "3 1\n7 1\n8 2 40 1\n";
+    const CONSTRAINTS: &str = "3 7 D = 10\n7 8 C > 15 2\n3 8 C > 5\n";
+
+    fn instance() -> Instance {
+        let domains = read_domains(DOMAINS.as_bytes()).unwrap();
+        let (links, link_domains) = read_variables(VARIABLES.as_bytes()).unwrap();
+        let constraints = read_constraints(CONSTRAINTS.as_bytes(), &links).unwrap();
+
+        Instance {
+            domains: link_domains.iter().map(|d| domains[d].clone()).collect(),
+            links,
+            constraints,
+        }
+    }
+
+    #[test]
+    fn test_read() {
+        let instance = instance();
+
+        assert_eq!(instance.links, vec![3, 7, 8]);
+        assert_eq!(instance.domains[2], vec![10, 40]);
+        assert_eq!(
+            instance.constraints[1],
+            Constraint {
+                u: 1,
+                v: 2,
+                separation: Separation::Greater(15)
+            }
+        );
+        assert_eq!(interference_graph(&instance).edges().len(), 3);
+
+        assert!(read_domains("1 3 10 20\n".as_bytes()).is_err());
+        assert!(read_constraints("3 9 D = 10\n".as_bytes(), &[3, 7]).is_err());
+        assert!(read_constraints("3 7 D < 10\n".as_bytes(), &[3, 7]).is_err());
+    }
+
+    #[test]
+    fn test_assign() {
+        let instance = instance();
+        let frequencies = assign(&instance);
+
+        assert_eq!(count_violations(&instance, &frequencies), 0);
+
+        // No frequencies of the domains are 25 apart
+        let mut instance = instance;
+        instance.constraints[0].separation = Separation::Equal(25);
+        let frequencies = assign(&instance);
+
+        assert!(frequencies.contains(&None));
+        assert!(count_violations(&instance, &frequencies) > 0);
+    }
+}
@@ -0,0 +1,137 @@
+use super::random::{generate_random, RandomParams};
+use super::weights::{add_weights, Weighting};
+use crate::args::parse_unit_interval;
+use crate::output::format::{write_graph, Format};
+use serde::Serialize;
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// Name of the manifest file written along with the instances.
+pub const MANIFEST: &str = "manifest.json";
+
+/// Parameter sweep of random graphs: one instance is generated for each combination of number of
+/// vertices, probability and seed.
+#[derive(Debug, clap::Args)]
+pub struct Batch {
+    /// Numbers of vertices of the graphs, separated by commas
+    #[arg(short, long, value_delimiter = ',', required = true)]
+    vertices: Vec<usize>,
+
+    /// Edge probabilities of the graphs, separated by commas
+    #[arg(short, long, value_delimiter = ',', value_parser = parse_unit_interval, required = true)]
+    probabilities: Vec<f64>,
+
+    /// Number of instances generated for each combination, with the seeds 0, 1, ...
+    /// Defaults to 1 if not provided.
+    #[arg(short, long)]
+    seeds: Option<u64>,
+
+    /// Number of colors of a planted coloring, hidden in every instance
+    #[arg(long)]
+    planted: Option<usize>,
+
+    /// Make the planted instances flat, with this flatness
+    #[arg(long, requires = "planted")]
+    flatness: Option<usize>,
+}
+
+/// An entry of the manifest, describing a generated instance.
+#[derive(Debug, Serialize)]
+pub struct ManifestEntry {
+    /// Path of the instance, relative to the manifest.
+    pub file: String,
+    #[serde(flatten)]
+    pub params: RandomParams,
+    #[serde(flatten)]
+    pub weighting: Weighting,
+    pub num_edges: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+/// Generates every instance of the sweep into `directory` (which is created if needed), along with
+/// a manifest listing them. Each instance is weighted according to `weighting`.
+pub fn generate_batch(
+    batch: &Batch,
+    directory: &Path,
+    format: Format,
+    weighting: Weighting,
+) -> Result<Vec<ManifestEntry>, Box<dyn Error>> {
+    fs::create_dir_all(directory)?;
+
+    let mut manifest = vec![];
+
+    for num_vertices in batch.vertices.iter() {
+        for probability in batch.probabilities.iter() {
+            for seed in 0..batch.seeds.unwrap_or(1) {
+                let params = RandomParams {
+                    num_vertices: *num_vertices,
+                    probability: *probability,
+                    seed: Some(seed),
+                    planted: batch.planted,
+                    flatness: batch.flatness,
+                };
+                let mut instance = generate_random(&params)?;
+
+                add_weights(&mut instance, weighting, Some(seed))?;
+
+                let file = format!("{}.{}", instance.name, format.extension());
+                let mut writer = BufWriter::new(File::create(directory.join(&file))?);
+
+                write_graph(&mut writer, &instance.graph, &instance.annotations, format)?;
+                writer.flush()?;
+
+                manifest.push(ManifestEntry {
+                    file,
+                    params,
+                    weighting,
+                    num_edges: instance.graph.edges().len(),
+                    comment: instance.annotations.comment,
+                });
+            }
+        }
+    }
+
+    let writer = BufWriter::new(File::create(directory.join(MANIFEST))?);
+    serde_json::to_writer_pretty(writer, &manifest)?;
+
+    Ok(manifest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_batch() {
+        let directory = std::env::temp_dir().join("gcp-heuristics-test-batch");
+        let batch = Batch {
+            vertices: vec![10, 20],
+            probabilities: vec![0.1, 0.5, 0.9],
+            seeds: Some(2),
+            planted: None,
+            flatness: None,
+        };
+
+        let manifest =
+            generate_batch(&batch, &directory, Format::Dimacs, Weighting::default()).unwrap();
+
+        assert_eq!(manifest.len(), 2 * 3 * 2);
+        assert!(manifest
+            .iter()
+            .all(|entry| directory.join(&entry.file).is_file()));
+
+        let written: serde_json::Value =
+            serde_json::from_reader(File::open(directory.join(MANIFEST)).unwrap()).unwrap();
+
+        assert_eq!(written[0]["file"], "random_n10_p0.1_s0.col");
+        assert_eq!(written[0]["num_vertices"], 10);
+        assert_eq!(written[11]["seed"], 1);
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
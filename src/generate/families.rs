@@ -0,0 +1,232 @@
+use crate::graph::adj_list::AdjList;
+
+/// Graph families with known chromatic numbers (or at least tight bounds).
+#[derive(Debug, clap::Subcommand)]
+pub enum Family {
+    /// Queen graph q(n, n): the squares of an n x n board, adjacent if a queen can move between them
+    Queen {
+        /// Side of the board
+        n: usize,
+    },
+    /// Mycielski graph M_k, which is triangle-free and has chromatic number k.
+    /// M_2 is an edge, M_3 is the cycle with 5 vertices, and so on
+    Mycielski {
+        /// Chromatic number of the graph
+        k: usize,
+    },
+    /// Kneser graph K(n, k): the k-subsets of {1, ..., n}, adjacent if disjoint
+    Kneser {
+        /// Size of the ground set
+        n: usize,
+        /// Size of the subsets
+        k: usize,
+    },
+    /// Complete multipartite graph: vertices are adjacent if they belong to different parts
+    Multipartite {
+        /// Size of each part
+        #[arg(required = true)]
+        sizes: Vec<usize>,
+    },
+}
+
+impl Family {
+    pub fn generate(&self) -> Result<AdjList, String> {
+        let graph = match self {
+            Family::Queen { n } => queen(*n),
+            Family::Mycielski { k } => {
+                if *k == 0 {
+                    return Err(String::from("M_k is only defined for k >= 1"));
+                }
+                mycielski(*k)
+            }
+            Family::Kneser { n, k } => {
+                if *k == 0 || k > n || *n >= usize::BITS as usize {
+                    return Err(String::from("K(n, k) needs 1 <= k <= n < 64"));
+                }
+                kneser(*n, *k)
+            }
+            Family::Multipartite { sizes } => multipartite(sizes),
+        };
+
+        Ok(graph)
+    }
+
+    /// Name of the graph, such as `kneser_5_2`.
+    pub fn name(&self) -> String {
+        match self {
+            Family::Queen { n } => format!("queen_{n}"),
+            Family::Mycielski { k } => format!("mycielski_{k}"),
+            Family::Kneser { n, k } => format!("kneser_{n}_{k}"),
+            Family::Multipartite { sizes } => {
+                let sizes: Vec<String> = sizes.iter().map(|size| size.to_string()).collect();
+                format!("multipartite_{}", sizes.join("_"))
+            }
+        }
+    }
+
+    /// Describes what is known about the chromatic number of the graph.
+    pub fn chromatic_number(&self) -> String {
+        match self {
+            // The n squares of a row form a clique, and a coloring with n colors exists if and only
+            // if n is congruent to 1 or 5 modulo 6
+            Family::Queen { n } if *n <= 1 || n % 6 == 1 || n % 6 == 5 => {
+                format!("the chromatic number is {n}")
+            }
+            Family::Queen { n } => format!("the chromatic number is at least {}", n + 1),
+            Family::Mycielski { k } => format!("the chromatic number is {k}"),
+            // Lovász's theorem
+            Family::Kneser { n, k } if *n >= 2 * k => {
+                format!("the chromatic number is {}", n - 2 * k + 2)
+            }
+            Family::Kneser { .. } => String::from("the chromatic number is 1"),
+            Family::Multipartite { sizes } => format!(
+                "the chromatic number is {}",
+                sizes.iter().filter(|size| **size > 0).count()
+            ),
+        }
+    }
+}
+
+fn queen(n: usize) -> AdjList {
+    let mut graph = AdjList::new(n * n);
+
+    for i in 0..n * n {
+        let (row_i, col_i) = (i / n, i % n);
+
+        for j in i + 1..n * n {
+            let (row_j, col_j) = (j / n, j % n);
+
+            if row_i == row_j || col_i == col_j || row_i.abs_diff(row_j) == col_i.abs_diff(col_j) {
+                graph.add_edge(i, j);
+            }
+        }
+    }
+
+    graph
+}
+
+/// Builds M_k by applying the Mycielski construction to M_2 (a single edge) `k - 2` times.
+fn mycielski(k: usize) -> AdjList {
+    if k == 1 {
+        return AdjList::new(1);
+    }
+
+    let mut graph = AdjList::new(2);
+    graph.add_edge(0, 1);
+
+    for _ in 2..k {
+        // Each vertex `v` gets a shadow `n + v`, adjacent to the neighbors of `v`, and every shadow
+        // is adjacent to a new vertex `2 * n`
+        let n = graph.num_vertices();
+        let mut new_graph = AdjList::new(2 * n + 1);
+
+        for (u, v) in graph.edges() {
+            new_graph.add_edge(u, v);
+            new_graph.add_edge(u, n + v);
+            new_graph.add_edge(v, n + u);
+        }
+        for v in 0..n {
+            new_graph.add_edge(n + v, 2 * n);
+        }
+
+        graph = new_graph;
+    }
+
+    graph
+}
+
+fn kneser(n: usize, k: usize) -> AdjList {
+    // Subsets are represented as bitmasks
+    let subsets: Vec<u64> = (0..1u64 << n)
+        .filter(|subset| subset.count_ones() as usize == k)
+        .collect();
+    let mut graph = AdjList::new(subsets.len());
+
+    for i in 0..subsets.len() {
+        for j in i + 1..subsets.len() {
+            if subsets[i] & subsets[j] == 0 {
+                graph.add_edge(i, j);
+            }
+        }
+    }
+
+    graph
+}
+
+fn multipartite(sizes: &[usize]) -> AdjList {
+    let parts: Vec<usize> = sizes
+        .iter()
+        .enumerate()
+        .flat_map(|(part, size)| std::iter::repeat_n(part, *size))
+        .collect();
+    let mut graph = AdjList::new(parts.len());
+
+    for i in 0..parts.len() {
+        for j in i + 1..parts.len() {
+            if parts[i] != parts[j] {
+                graph.add_edge(i, j);
+            }
+        }
+    }
+
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input;
+
+    #[test]
+    fn test_queen() {
+        let graph = queen(3);
+        let edges = graph.edges();
+
+        // A corner attacks the center, but not the squares a knight's move away
+        assert_eq!(graph.num_vertices(), 9);
+        assert_eq!(edges.len(), 28);
+        assert!(edges.contains(&(0, 4)));
+        assert!(!edges.contains(&(0, 5)));
+    }
+
+    #[test]
+    fn test_mycielski() {
+        assert_eq!(mycielski(1).num_vertices(), 1);
+        assert_eq!(mycielski(2).edges(), vec![(0, 1)]);
+
+        // M_k is the myciel(k - 1) instance of the DIMACS benchmarks
+        for (k, num_vertices, num_edges) in [(4, 11, 20), (5, 23, 71), (6, 47, 236)] {
+            let graph = mycielski(k);
+
+            assert_eq!(graph.num_vertices(), num_vertices);
+            assert_eq!(graph.edges().len(), num_edges);
+
+            // The benchmarks may not be packaged along with the code
+            let path = format!("data/myc/myciel{}.col", k - 1);
+            if let Ok(Some(benchmark)) = input::read_graph_from_file(&path) {
+                assert_eq!(graph.edges(), benchmark.edges());
+            }
+        }
+    }
+
+    #[test]
+    fn test_kneser() {
+        // K(5, 2) is the Petersen graph
+        let graph = kneser(5, 2);
+
+        assert_eq!(graph.num_vertices(), 10);
+        assert_eq!(graph.edges().len(), 15);
+        assert_eq!(
+            Family::Kneser { n: 5, k: 2 }.chromatic_number(),
+            "the chromatic number is 3"
+        );
+    }
+
+    #[test]
+    fn test_multipartite() {
+        let graph = multipartite(&[1, 2, 3]);
+
+        assert_eq!(graph.num_vertices(), 6);
+        assert_eq!(graph.edges().len(), 2 + 3 + 2 * 3);
+    }
+}
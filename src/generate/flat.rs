@@ -0,0 +1,125 @@
+use crate::graph::adj_list::AdjList;
+use rand::{seq::SliceRandom, Rng};
+
+/// Splits `num_vertices` vertices into `k` classes whose sizes differ by at most one, returning
+/// the class of each vertex.
+pub fn generate_equipartition(num_vertices: usize, k: usize, rng: &mut impl Rng) -> Vec<usize> {
+    let mut classes: Vec<usize> = (0..num_vertices).map(|v| v % k).collect();
+
+    classes.shuffle(rng);
+
+    classes
+}
+
+/// Generates a flat graph, in the style of Culberson's generator: each pair of
+/// classes gets a `probability` fraction of the possible edges between them, and these edges are
+/// spread so that the degrees (towards the other class) of the vertices of a class differ by at
+/// most `flatness` (plus one, as the edges rarely divide evenly).
+///
+/// Since every vertex looks alike, these graphs give no hints to greedy or local search methods.
+pub fn generate_flat_graph(
+    classes: &[usize],
+    probability: f64,
+    flatness: usize,
+    rng: &mut impl Rng,
+) -> AdjList {
+    let k = classes.iter().max().map_or(0, |k| k + 1);
+    let mut members = vec![vec![]; k];
+    let mut graph = AdjList::new(classes.len());
+
+    for (v, class) in classes.iter().enumerate() {
+        members[*class].push(v);
+    }
+
+    for a in 0..k {
+        for b in a + 1..k {
+            let (lhs, rhs) = (&members[a], &members[b]);
+            let num_edges = (probability * (lhs.len() * rhs.len()) as f64).round() as usize;
+            let mut adjacent = vec![vec![false; rhs.len()]; lhs.len()];
+            let mut lhs_degrees = vec![0; lhs.len()];
+            let mut rhs_degrees = vec![0; rhs.len()];
+
+            for _ in 0..num_edges {
+                let (u, v) = choose_flat_pair(&adjacent, &lhs_degrees, &rhs_degrees, flatness, rng);
+
+                adjacent[u][v] = true;
+                lhs_degrees[u] += 1;
+                rhs_degrees[v] += 1;
+
+                graph.add_edge(lhs[u], rhs[v]);
+            }
+        }
+    }
+
+    graph
+}
+
+/// Randomly chooses a pair of non-adjacent vertices whose degrees are at most `flatness` above the
+/// smallest degrees of their classes. If there's no such pair, the bound is gradually relaxed.
+fn choose_flat_pair(
+    adjacent: &[Vec<bool>],
+    lhs_degrees: &[usize],
+    rhs_degrees: &[usize],
+    flatness: usize,
+    rng: &mut impl Rng,
+) -> (usize, usize) {
+    let lhs_min = lhs_degrees.iter().min().unwrap();
+    let rhs_min = rhs_degrees.iter().min().unwrap();
+
+    for slack in flatness.. {
+        let mut lhs_candidates: Vec<usize> = (0..lhs_degrees.len())
+            .filter(|u| lhs_degrees[*u] <= lhs_min + slack)
+            .collect();
+
+        lhs_candidates.shuffle(rng);
+
+        for u in lhs_candidates {
+            let rhs_candidates: Vec<usize> = (0..rhs_degrees.len())
+                .filter(|v| rhs_degrees[*v] <= rhs_min + slack && !adjacent[u][*v])
+                .collect();
+
+            if let Some(v) = rhs_candidates.choose(rng) {
+                return (u, *v);
+            }
+        }
+    }
+
+    unreachable!("the classes are already completely connected")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_generate_equipartition() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let classes = generate_equipartition(10, 3, &mut rng);
+        let mut sizes = [0; 3];
+
+        for class in classes {
+            sizes[class] += 1;
+        }
+
+        assert_eq!(sizes, [4, 3, 3]);
+    }
+
+    #[test]
+    fn test_generate_flat_edges() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let classes = generate_equipartition(60, 3, &mut rng);
+        let graph = generate_flat_graph(&classes, 0.3, 0, &mut rng);
+        let edges = graph.edges();
+
+        // 3 pairs of classes, each with 20 * 20 possible edges
+        assert_eq!(edges.len(), 3 * 120);
+        assert!(edges.iter().all(|(u, v)| classes[*u] != classes[*v]));
+
+        // Every vertex has about 6 neighbors in each of the other classes
+        assert!(graph
+            .adj_list()
+            .iter()
+            .all(|neighbors| (11..=13).contains(&neighbors.len())));
+    }
+}
@@ -0,0 +1,83 @@
+pub mod batch;
+pub mod families;
+mod flat;
+pub mod partite;
+pub mod random;
+mod weights;
+
+use crate::args::{GenerateArgs, GenerateCommand};
+use crate::graph::adj_list::AdjList;
+use crate::output::format::{write_graph, Annotations};
+use batch::generate_batch;
+use partite::generate_partite;
+use random::{generate_random, RandomParams};
+use std::{
+    error::Error,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+use weights::{add_weights, Weighting};
+
+/// A generated graph, along with what is known about its chromatic number (and, optionally,
+/// vertex weights and edge distances).
+pub struct Instance {
+    /// Name used for the file when writing to a directory.
+    pub name: String,
+    pub graph: AdjList,
+    pub annotations: Annotations,
+}
+
+/// Generates the instance (or the batch of instances) described by `args`, writing it to the
+/// requested destination.
+pub fn generate(args: &GenerateArgs) -> Result<(), Box<dyn Error>> {
+    let format = args.format.unwrap_or_default();
+    let weighting = Weighting {
+        max_weight: args.max_weight,
+        max_distance: args.max_distance,
+    };
+    let mut instance = match &args.command {
+        Some(GenerateCommand::Batch(batch)) => {
+            let Some(directory) = &args.output else {
+                return Err("batch generation needs an output directory".into());
+            };
+            generate_batch(batch, Path::new(directory), format, weighting)?;
+
+            return Ok(());
+        }
+        Some(GenerateCommand::Family(family)) => Instance {
+            name: family.name(),
+            graph: family.generate()?,
+            annotations: Annotations {
+                comment: Some(family.chromatic_number()),
+                ..Default::default()
+            },
+        },
+        Some(GenerateCommand::Partite(partite)) => generate_partite(partite)?,
+        None => generate_random(&RandomParams {
+            num_vertices: args.vertices.unwrap_or_default(),
+            probability: args.probability.unwrap_or(0.1),
+            seed: args.seed,
+            planted: args.planted,
+            flatness: args.flatness,
+        })?,
+    };
+
+    add_weights(&mut instance, weighting, args.seed)?;
+
+    let mut writer: Box<dyn Write> = match &args.output {
+        Some(path) if Path::new(path).is_dir() => {
+            let filename = format!("{}.{}", instance.name, format.extension());
+            Box::new(BufWriter::new(File::create(
+                Path::new(path).join(filename),
+            )?))
+        }
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+
+    write_graph(&mut writer, &instance.graph, &instance.annotations, format)?;
+    writer.flush()?;
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use super::Instance;
+use crate::args::parse_unit_interval;
+use crate::graph::adj_list::AdjList;
+use crate::output::format::Annotations;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Random k-partite graph: only vertices of different parts may be adjacent.
+#[derive(Debug, clap::Args)]
+pub struct Partite {
+    /// Size of each part, separated by commas (two parts give a bipartite graph)
+    #[arg(long, value_delimiter = ',', required = true)]
+    sizes: Vec<usize>,
+
+    /// Probability of each edge between two parts, separated by commas, for the pairs of parts
+    /// (1, 2), (1, 3), ..., (2, 3), ... in this order. A single value applies to every pair.
+    /// Defaults to 0.5 if not provided.
+    #[arg(short, long, value_delimiter = ',', value_parser = parse_unit_interval)]
+    densities: Vec<f64>,
+
+    /// Seed for the random number generator, making the instance reproducible.
+    /// A random seed is used if not provided.
+    #[arg(short, long)]
+    seed: Option<u64>,
+}
+
+pub fn generate_partite(params: &Partite) -> Result<Instance, String> {
+    let k = params.sizes.len();
+    let num_pairs = k * k.saturating_sub(1) / 2;
+    let densities = match params.densities.len() {
+        0 => vec![0.5; num_pairs],
+        1 => vec![params.densities[0]; num_pairs],
+        n if n == num_pairs => params.densities.clone(),
+        n => {
+            return Err(format!(
+                "{k} parts need {num_pairs} densities (or a single one), but {n} were given"
+            ))
+        }
+    };
+    let mut rng = match params.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    // The first vertex of each part
+    let starts: Vec<usize> = params
+        .sizes
+        .iter()
+        .scan(0, |start, size| {
+            *start += size;
+            Some(*start - size)
+        })
+        .collect();
+    let mut graph = AdjList::new(params.sizes.iter().sum());
+    let mut pair = 0;
+
+    for a in 0..k {
+        for b in a + 1..k {
+            for u in starts[a]..starts[a] + params.sizes[a] {
+                for v in starts[b]..starts[b] + params.sizes[b] {
+                    if rng.gen::<f64>() < densities[pair] {
+                        graph.add_edge(u, v);
+                    }
+                }
+            }
+
+            pair += 1;
+        }
+    }
+
+    let sizes: Vec<String> = params.sizes.iter().map(|size| size.to_string()).collect();
+    let mut name = format!("partite_{}", sizes.join("_"));
+    if let Some(seed) = params.seed {
+        name.push_str(&format!("_s{seed}"));
+    }
+
+    let num_parts = params.sizes.iter().filter(|size| **size > 0).count();
+
+    Ok(Instance {
+        name,
+        graph,
+        annotations: Annotations {
+            comment: Some(format!(
+                "{num_parts}-partite: the chromatic number is at most {num_parts}"
+            )),
+            ..Default::default()
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_partite() {
+        // A complete bipartite graph
+        let params = Partite {
+            sizes: vec![2, 3],
+            densities: vec![1.0],
+            seed: Some(42),
+        };
+        let instance = generate_partite(&params).unwrap();
+
+        assert_eq!(instance.name, "partite_2_3_s42");
+        assert_eq!(instance.graph.num_vertices(), 5);
+        assert_eq!(instance.graph.edges().len(), 6);
+
+        // The parts {0}, {1, 2} and {3} with no edges between the first two
+        let params = Partite {
+            sizes: vec![1, 2, 1],
+            densities: vec![0.0, 1.0, 1.0],
+            seed: Some(42),
+        };
+        let instance = generate_partite(&params).unwrap();
+
+        assert_eq!(instance.graph.edges(), vec![(0, 3), (1, 3), (2, 3)]);
+
+        let params = Partite {
+            sizes: vec![1, 2, 1],
+            densities: vec![0.0, 1.0],
+            seed: Some(42),
+        };
+
+        assert!(generate_partite(&params).is_err());
+    }
+}
@@ -0,0 +1,168 @@
+use super::flat::{generate_equipartition, generate_flat_graph};
+use super::Instance;
+use crate::graph::adj_list::AdjList;
+use crate::output::format::Annotations;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Serialize;
+
+/// Parameters of a random graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct RandomParams {
+    pub num_vertices: usize,
+    pub probability: f64,
+    /// A random seed is used if missing.
+    pub seed: Option<u64>,
+    /// Number of colors of the planted coloring, if any.
+    pub planted: Option<usize>,
+    /// Flatness of the planted coloring, if the graph is flat.
+    pub flatness: Option<usize>,
+}
+
+/// Generates a random graph (with a planted coloring, if requested).
+pub fn generate_random(params: &RandomParams) -> Result<Instance, String> {
+    let RandomParams {
+        num_vertices,
+        probability,
+        seed,
+        planted,
+        flatness,
+    } = *params;
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let graph = match (planted, flatness) {
+        (Some(0), _) => return Err(String::from("a planted coloring needs at least one color")),
+        (Some(k), Some(flatness)) => {
+            let classes = generate_equipartition(num_vertices, k, &mut rng);
+            generate_flat_graph(&classes, probability, flatness, &mut rng)
+        }
+        (Some(k), None) => {
+            let classes = generate_classes(num_vertices, k, &mut rng);
+            generate_graph(num_vertices, probability, Some(&classes), &mut rng)
+        }
+        (None, _) => generate_graph(num_vertices, probability, None, &mut rng),
+    };
+    let comment =
+        planted.map(|k| format!("planted {k}-coloring: the chromatic number is at most {k}"));
+
+    let mut name = format!("random_n{num_vertices}_p{probability}");
+    if let Some(k) = planted {
+        name.push_str(&format!("_k{k}"));
+    }
+    if let Some(flatness) = flatness {
+        name.push_str(&format!("_f{flatness}"));
+    }
+    if let Some(seed) = seed {
+        name.push_str(&format!("_s{seed}"));
+    }
+
+    Ok(Instance {
+        name,
+        graph,
+        annotations: Annotations {
+            comment,
+            ..Default::default()
+        },
+    })
+}
+
+/// Randomly partitions `num_vertices` vertices into `k` classes, returning the class of each vertex.
+fn generate_classes(num_vertices: usize, k: usize, rng: &mut impl Rng) -> Vec<usize> {
+    (0..num_vertices).map(|_| rng.gen_range(0..k)).collect()
+}
+
+/// Generates a random graph with `num_vertices` vertices, in which each edge is included with the
+/// given `probability`.
+///
+/// If `classes` is given, edges between vertices of the same class are never included, so the
+/// classes are a valid coloring of the graph.
+fn generate_graph(
+    num_vertices: usize,
+    probability: f64,
+    classes: Option<&[usize]>,
+    rng: &mut impl Rng,
+) -> AdjList {
+    let mut graph = AdjList::new(num_vertices);
+
+    for i in 0..num_vertices {
+        for j in i + 1..num_vertices {
+            if classes.is_some_and(|classes| classes[i] == classes[j]) {
+                continue;
+            }
+
+            if rng.gen::<f64>() < probability {
+                graph.add_edge(i, j);
+            }
+        }
+    }
+
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_random() {
+        let params = RandomParams {
+            num_vertices: 20,
+            probability: 0.5,
+            seed: Some(7),
+            planted: Some(3),
+            flatness: None,
+        };
+        let instance = generate_random(&params).unwrap();
+
+        assert_eq!(instance.name, "random_n20_p0.5_k3_s7");
+        assert_eq!(
+            instance.graph.edges(),
+            generate_random(&params).unwrap().graph.edges()
+        );
+
+        let params = RandomParams {
+            planted: Some(0),
+            ..params
+        };
+
+        assert!(generate_random(&params).is_err());
+    }
+
+    #[test]
+    fn test_generate_graph() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let edges = generate_graph(30, 0.5, None, &mut rng).edges();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        // The same seed generates the same graph
+        assert_eq!(edges, generate_graph(30, 0.5, None, &mut rng).edges());
+
+        assert!(generate_graph(30, 0.0, None, &mut rng).edges().is_empty());
+        assert_eq!(
+            generate_graph(30, 1.0, None, &mut rng).edges().len(),
+            30 * 29 / 2
+        );
+    }
+
+    #[test]
+    fn test_generate_planted_graph() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let classes = generate_classes(30, 4, &mut rng);
+
+        assert!(classes.iter().all(|class| *class < 4));
+
+        // Even with every edge allowed, no edge joins vertices of the same class
+        let edges = generate_graph(30, 1.0, Some(&classes), &mut rng).edges();
+
+        assert!(edges.iter().all(|(u, v)| classes[*u] != classes[*v]));
+
+        let same_class_pairs: usize = (0..4)
+            .map(|class| classes.iter().filter(|c| **c == class).count())
+            .map(|size| size * size.saturating_sub(1) / 2)
+            .sum();
+
+        assert_eq!(edges.len(), 30 * 29 / 2 - same_class_pairs);
+    }
+}
@@ -0,0 +1,88 @@
+use super::Instance;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Serialize;
+
+/// Ranges of the random vertex weights and edge distances added to generated instances.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct Weighting {
+    /// Vertices get weights in `1..=max_weight`, if given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_weight: Option<usize>,
+    /// Edges get distances in `1..=max_distance`, if given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_distance: Option<usize>,
+}
+
+/// Gives random weights to the vertices and random distances to the edges of `instance`, as
+/// requested by `weighting`. Does nothing if neither is requested.
+pub fn add_weights(
+    instance: &mut Instance,
+    weighting: Weighting,
+    seed: Option<u64>,
+) -> Result<(), String> {
+    if weighting.max_weight == Some(0) || weighting.max_distance == Some(0) {
+        return Err(String::from("weights and distances must be at least 1"));
+    }
+
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    if let Some(max_weight) = weighting.max_weight {
+        let weights = (0..instance.graph.num_vertices())
+            .map(|_| rng.gen_range(1..=max_weight))
+            .collect();
+
+        instance.annotations.weights = Some(weights);
+        instance.name.push_str(&format!("_w{max_weight}"));
+    }
+    if let Some(max_distance) = weighting.max_distance {
+        let distances = (0..instance.graph.edges().len())
+            .map(|_| rng.gen_range(1..=max_distance))
+            .collect();
+
+        instance.annotations.distances = Some(distances);
+        instance.name.push_str(&format!("_d{max_distance}"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adj_list::AdjList;
+    use crate::output::format::Annotations;
+
+    #[test]
+    fn test_add_weights() {
+        let mut instance = Instance {
+            name: String::from("complete"),
+            graph: AdjList::complete(4),
+            annotations: Annotations::default(),
+        };
+        let weighting = Weighting {
+            max_weight: Some(3),
+            max_distance: Some(5),
+        };
+
+        add_weights(&mut instance, weighting, Some(42)).unwrap();
+
+        let weights = instance.annotations.weights.as_ref().unwrap();
+        let distances = instance.annotations.distances.as_ref().unwrap();
+
+        assert_eq!(instance.name, "complete_w3_d5");
+        assert_eq!(weights.len(), 4);
+        assert!(weights.iter().all(|weight| (1..=3).contains(weight)));
+        assert_eq!(distances.len(), 6);
+        assert!(distances.iter().all(|distance| (1..=5).contains(distance)));
+
+        let weighting = Weighting {
+            max_weight: Some(0),
+            max_distance: None,
+        };
+
+        assert!(add_weights(&mut instance, weighting, None).is_err());
+    }
+}
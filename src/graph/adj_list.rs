@@ -1,6 +1,9 @@
+#[derive(Debug, Clone)]
 pub struct AdjList {
     adj_list: Vec<Vec<usize>>,
     num_vertices: usize,
+    /// Names of the vertices, for instances whose vertices aren't numbered.
+    labels: Option<Vec<String>>,
 }
 
 impl AdjList {
@@ -10,6 +13,7 @@ impl AdjList {
         AdjList {
             num_vertices,
             adj_list,
+            labels: None,
         }
     }
 
@@ -27,6 +31,7 @@ impl AdjList {
         AdjList {
             num_vertices,
             adj_list,
+            labels: None,
         }
     }
 
@@ -42,6 +47,29 @@ impl AdjList {
         self.num_vertices
     }
 
+    pub fn labels(&self) -> Option<&[String]> {
+        self.labels.as_deref()
+    }
+
+    /// Names the vertices, in order.
+    pub fn set_labels(&mut self, labels: Vec<String>) {
+        assert_eq!(
+            labels.len(),
+            self.num_vertices,
+            "each vertex must have a label"
+        );
+
+        self.labels = Some(labels);
+    }
+
+    /// The name of `v`: its label, or its 1-indexed number if the vertices have no labels.
+    pub fn vertex_name(&self, v: usize) -> String {
+        match &self.labels {
+            Some(labels) => labels[v].clone(),
+            None => (v + 1).to_string(),
+        }
+    }
+
     pub fn get_degree_in_list(&self, i: usize, list: &[usize]) -> usize {
         if i < self.num_vertices {
             self.adj_list()[i]
@@ -53,13 +81,80 @@ impl AdjList {
         }
     }
 
-    #[cfg(test)]
+    /// Adds an isolated vertex, returning its index.
+    pub fn add_vertex(&mut self) -> usize {
+        self.adj_list.push(Vec::new());
+        self.num_vertices += 1;
+
+        if let Some(labels) = self.labels.as_mut() {
+            labels.push(self.num_vertices.to_string());
+        }
+
+        self.num_vertices - 1
+    }
+
     pub fn add_edge(&mut self, u: usize, v: usize) {
         self.adj_list_mut()[u].push(v);
         self.adj_list_mut()[v].push(u);
     }
 
-    #[cfg(test)]
+    /// Lists each edge once, as `(u, v)` with `u < v`, in increasing order.
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        let mut edges: Vec<(usize, usize)> = self
+            .adj_list
+            .iter()
+            .enumerate()
+            .flat_map(|(u, neighbors)| neighbors.iter().map(move |v| (u, *v)))
+            .filter(|(u, v)| u < v)
+            .collect();
+
+        edges.sort();
+
+        edges
+    }
+
+    /// Merges `v` into `u`, which must not be adjacent: `u` gets the neighbors of both, and `v` is
+    /// removed, the vertices after it moving down by one.
+    ///
+    /// Colorings of the contracted graph are the colorings of this one where `u` and `v` share a
+    /// color, as in Zykov's branching. Vertices that can always share a color, such as one whose
+    /// neighbors are all neighbors of the other, can also be merged to shrink an instance.
+    pub fn contract(&self, u: usize, v: usize) -> AdjList {
+        assert!(
+            u != v && !self.adj_list[u].contains(&v),
+            "only distinct non-adjacent vertices can be contracted"
+        );
+
+        let rename = |w: usize| {
+            let w = if w == v { u } else { w };
+            if w > v {
+                w - 1
+            } else {
+                w
+            }
+        };
+        let mut edges: Vec<(usize, usize)> = self
+            .edges()
+            .into_iter()
+            .map(|(a, b)| (rename(a).min(rename(b)), rename(a).max(rename(b))))
+            .collect();
+        edges.sort();
+        edges.dedup();
+
+        let mut contracted = AdjList::new(self.num_vertices - 1);
+        for (a, b) in edges {
+            contracted.add_edge(a, b);
+        }
+
+        if let Some(labels) = &self.labels {
+            let mut labels = labels.clone();
+            labels.remove(v);
+            contracted.set_labels(labels);
+        }
+
+        contracted
+    }
+
     pub fn sub_edge(&mut self, u: usize, v: usize) {
         if let Some(index) = self.adj_list[u].iter().position(|x| *x == v) {
             self.adj_list[u].swap_remove(index);
@@ -72,21 +167,48 @@ impl AdjList {
 
 #[cfg(test)]
 mod tests {
-    use crate::input;
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn test_edges() {
+        let mut graph = AdjList::new(4);
+        graph.add_edge(2, 1);
+        graph.add_edge(0, 3);
+        graph.add_edge(1, 0);
+
+        assert_eq!(graph.edges(), vec![(0, 1), (0, 3), (1, 2)]);
+        assert_eq!(AdjList::complete(3).edges(), vec![(0, 1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn test_contract() {
+        // The path 0 - 1 - 2 - 3: merging its ends makes a triangle
+        let mut graph = AdjList::new(4);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+
+        let contracted = graph.contract(0, 3);
+        assert_eq!(contracted.num_vertices(), 3);
+        assert_eq!(contracted.edges(), vec![(0, 1), (0, 2), (1, 2)]);
+
+        // Shared neighbors don't make parallel edges, and the later vertices move down
+        let contracted = graph.contract(2, 0);
+        assert_eq!(contracted.edges(), vec![(0, 1), (1, 2)]);
+    }
 
     #[test]
     fn test_get_degree_in_list() {
-        if let Ok(Some(graph)) = input::read_graph_from_file("data/myc/myciel3.col") {
-            let list = vec![0, 1, 2];
-            let num_vertices = graph.num_vertices();
-            let degree = graph.get_degree_in_list(1, &list);
+        let graph = testing::myciel3();
 
-            assert_eq!(degree, 2);
+        let list = vec![0, 1, 2];
+        let num_vertices = graph.num_vertices();
+        let degree = graph.get_degree_in_list(1, &list);
 
-            let degree = graph.get_degree_in_list(num_vertices + 1, &list);
-            assert_eq!(degree, 0);
-        } else {
-            panic!("The file containing the test graph is missing")
-        }
+        assert_eq!(degree, 2);
+
+        let degree = graph.get_degree_in_list(num_vertices + 1, &list);
+        assert_eq!(degree, 0);
     }
 }
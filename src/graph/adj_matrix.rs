@@ -0,0 +1,134 @@
+use super::adj_list::AdjList;
+use super::bitset::BitMatrix;
+
+/// A graph as a symmetric adjacency matrix, for phases that mostly ask whether two vertices are
+/// adjacent. Repeated edges are only stored once.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdjMatrix {
+    matrix: Vec<Vec<bool>>,
+    degrees: Vec<usize>,
+}
+
+impl AdjMatrix {
+    pub fn new(num_vertices: usize) -> Self {
+        AdjMatrix {
+            matrix: vec![vec![false; num_vertices]; num_vertices],
+            degrees: vec![0; num_vertices],
+        }
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.matrix.len()
+    }
+
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        if u == v || self.matrix[u][v] {
+            return;
+        }
+
+        self.matrix[u][v] = true;
+        self.matrix[v][u] = true;
+        self.degrees[u] += 1;
+        self.degrees[v] += 1;
+    }
+
+    pub fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.matrix[u][v]
+    }
+
+    pub fn degree(&self, v: usize) -> usize {
+        self.degrees[v]
+    }
+
+    /// The neighbors of `v`, in increasing order.
+    pub fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.num_vertices()).filter(move |u| self.matrix[v][*u])
+    }
+
+    /// The same graph as an adjacency list, whose neighbors are in increasing order.
+    pub fn to_adj_list(&self) -> AdjList {
+        let mut graph = AdjList::new(self.num_vertices());
+
+        for (u, v) in (0..self.num_vertices()).flat_map(|u| self.neighbors(u).map(move |v| (u, v)))
+        {
+            if u < v {
+                graph.add_edge(u, v);
+            }
+        }
+
+        graph
+    }
+
+    /// The same graph with its rows packed into bits, for adjacency queries over sets of vertices.
+    pub fn to_bitset(&self) -> BitMatrix {
+        let mut bitset = BitMatrix::new(self.num_vertices());
+
+        for (u, row) in self.matrix.iter().enumerate() {
+            for (v, is_adjacent) in row.iter().enumerate() {
+                if *is_adjacent {
+                    bitset.set(u, v);
+                }
+            }
+        }
+
+        bitset
+    }
+}
+
+impl AdjList {
+    /// The same graph as an adjacency matrix, where repeated edges are merged.
+    pub fn to_adj_matrix(&self) -> AdjMatrix {
+        let mut matrix = AdjMatrix::new(self.num_vertices());
+
+        for (u, neighbors) in self.adj_list().iter().enumerate() {
+            for v in neighbors {
+                matrix.add_edge(u, *v);
+            }
+        }
+
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing;
+    use crate::testing::{arbitrary_graph, NUM_CASES};
+    use proptest::prelude::*;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(NUM_CASES))]
+
+        #[test]
+        fn test_conversions(graph in arbitrary_graph()) {
+            let matrix = graph.to_adj_matrix();
+            let bitset = matrix.to_bitset();
+
+            prop_assert_eq!(matrix.num_vertices(), graph.num_vertices());
+            prop_assert_eq!(matrix.to_adj_list().edges(), graph.edges());
+            prop_assert_eq!(matrix.to_adj_list().to_adj_matrix(), matrix.clone());
+
+            for u in 0..graph.num_vertices() {
+                prop_assert_eq!(matrix.degree(u), graph.adj_list()[u].len());
+                for v in 0..graph.num_vertices() {
+                    let is_adjacent = graph.adj_list()[u].contains(&v);
+
+                    prop_assert_eq!(matrix.has_edge(u, v), is_adjacent);
+                    prop_assert_eq!(bitset.has_edge(u, v), is_adjacent);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_repeated_edges() {
+        let mut graph = testing::cycle(4);
+        graph.add_edge(1, 0);
+
+        let matrix = graph.to_adj_matrix();
+
+        assert_eq!(matrix.degree(0), 2);
+        assert_eq!(matrix.neighbors(0).collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(matrix.to_adj_list().adj_list()[0], [1, 3]);
+    }
+}
@@ -0,0 +1,45 @@
+/// A graph as a symmetric adjacency matrix whose rows are packed into 64-bit words.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitMatrix {
+    rows: Vec<Vec<u64>>,
+}
+
+impl BitMatrix {
+    pub fn new(num_vertices: usize) -> Self {
+        BitMatrix {
+            rows: vec![vec![0; num_vertices.div_ceil(64)]; num_vertices],
+        }
+    }
+
+    /// Marks `v` as a neighbor of `u`, but not the other way around.
+    pub fn set(&mut self, u: usize, v: usize) {
+        self.rows[u][v / 64] |= 1 << (v % 64);
+    }
+
+    pub fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.rows[u][v / 64] & (1 << (v % 64)) != 0
+    }
+
+    /// Whether `v` is adjacent to every vertex of `vertices`.
+    pub fn is_adjacent_to_all(&self, v: usize, vertices: &[usize]) -> bool {
+        vertices.iter().all(|u| self.has_edge(v, *u))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_matrix() {
+        let mut bitset = BitMatrix::new(70);
+        bitset.set(0, 65);
+        bitset.set(65, 0);
+        bitset.set(65, 3);
+
+        assert!(bitset.has_edge(0, 65) && bitset.has_edge(65, 3));
+        assert!(!bitset.has_edge(3, 65) && !bitset.has_edge(0, 1));
+        assert!(bitset.is_adjacent_to_all(65, &[0, 3]));
+        assert!(!bitset.is_adjacent_to_all(0, &[3, 65]));
+    }
+}
@@ -0,0 +1,227 @@
+use super::adj_list::AdjList;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Maximum number of power iterations used to approximate each eigenvalue.
+const POWER_ITERATIONS: usize = 1000;
+const POWER_TOLERANCE: f64 = 1e-9;
+
+/// Computes a smallest-last ordering of the vertices, i.e., repeatedly removes a vertex of
+/// minimum degree and places it before the ones already removed.
+///
+/// Returns the degeneracy of the graph (the largest degree seen at a removal) and the ordering.
+pub fn degeneracy_ordering(graph: &AdjList) -> (usize, Vec<usize>) {
+    let n = graph.num_vertices();
+    let mut degrees: Vec<usize> = graph.adj_list().iter().map(Vec::len).collect();
+    let mut removed = vec![false; n];
+    let mut heap: BinaryHeap<Reverse<(usize, usize)>> =
+        (0..n).map(|v| Reverse((degrees[v], v))).collect();
+    let mut degeneracy = 0;
+    let mut ordering = Vec::with_capacity(n);
+
+    while let Some(Reverse((degree, v))) = heap.pop() {
+        // Outdated entries are skipped instead of removed from the heap
+        if removed[v] || degree != degrees[v] {
+            continue;
+        }
+
+        removed[v] = true;
+        degeneracy = degeneracy.max(degree);
+        ordering.push(v);
+
+        for u in graph.adj_list()[v].iter() {
+            if !removed[*u] {
+                degrees[*u] -= 1;
+                heap.push(Reverse((degrees[*u], *u)));
+            }
+        }
+    }
+
+    ordering.reverse();
+
+    (degeneracy, ordering)
+}
+
+/// Colors the vertices in the given `order`, each one with the smallest (1-indexed) color not
+/// used by its neighbors.
+pub fn greedy_coloring(graph: &AdjList, order: &[usize]) -> Vec<usize> {
+    let mut coloring = vec![0; graph.num_vertices()];
+
+    for v in order {
+        let mut used = vec![false; graph.adj_list()[*v].len() + 2];
+
+        for u in graph.adj_list()[*v].iter() {
+            if coloring[*u] < used.len() {
+                used[coloring[*u]] = true;
+            }
+        }
+
+        coloring[*v] = (1..used.len()).find(|color| !used[*color]).unwrap();
+    }
+
+    coloring
+}
+
+/// Upper bound from Brooks' theorem: a connected graph can be colored with as many colors as its
+/// maximum degree, unless it's a complete graph or an odd cycle, which need one more.
+pub fn brooks_bound(graph: &AdjList) -> usize {
+    connected_components(graph)
+        .iter()
+        .map(|component| {
+            let degrees: Vec<usize> = component
+                .iter()
+                .map(|v| graph.adj_list()[*v].len())
+                .collect();
+            let max_degree = degrees.iter().max().copied().unwrap_or(0);
+            let is_complete = degrees.iter().all(|degree| *degree == component.len() - 1);
+            let is_odd_cycle =
+                component.len() % 2 == 1 && degrees.iter().all(|degree| *degree == 2);
+
+            if is_complete || is_odd_cycle {
+                max_degree + 1
+            } else {
+                max_degree
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Lower bound from Hoffman's theorem: `1 + λ_max / -λ_min`, where `λ_max` and `λ_min` are the
+/// largest and smallest eigenvalues of the adjacency matrix.
+///
+/// The eigenvalues are approximated with the power method.
+pub fn hoffman_bound(graph: &AdjList) -> usize {
+    if graph.adj_list().iter().all(Vec::is_empty) {
+        return graph.num_vertices().min(1);
+    }
+
+    let max_degree = graph.adj_list().iter().map(Vec::len).max().unwrap() as f64;
+
+    // Every eigenvalue is within [-max_degree, max_degree], so both shifted matrices are positive
+    // semidefinite, and their dominant eigenvalues give the extreme eigenvalues of the graph
+    let lambda_max = dominant_eigenvalue(graph, max_degree, 1.0) - max_degree;
+    let lambda_min = lambda_max - dominant_eigenvalue(graph, lambda_max, -1.0);
+
+    let bound = 1.0 + lambda_max / -lambda_min;
+
+    // Allow for the error of the approximation
+    (bound - 1e-6).ceil() as usize
+}
+
+/// Approximates the dominant eigenvalue of `shift * I + sign * A`, where `A` is the adjacency
+/// matrix of `graph`.
+fn dominant_eigenvalue(graph: &AdjList, shift: f64, sign: f64) -> f64 {
+    let n = graph.num_vertices();
+    // An uneven starting vector, since the uniform one is orthogonal to many eigenvectors
+    let mut vector: Vec<f64> = (0..n).map(|i| 1.0 + (i % 7) as f64 / 7.0).collect();
+    let mut eigenvalue = 0.0;
+
+    for _ in 0..POWER_ITERATIONS {
+        let norm = vector.iter().map(|x| x * x).sum::<f64>().sqrt();
+        vector.iter_mut().for_each(|x| *x /= norm);
+
+        let product: Vec<f64> = (0..n)
+            .map(|v| {
+                let neighbors: f64 = graph.adj_list()[v].iter().map(|u| vector[*u]).sum();
+                shift * vector[v] + sign * neighbors
+            })
+            .collect();
+
+        // Rayleigh quotient
+        let estimate: f64 = product.iter().zip(vector.iter()).map(|(a, b)| a * b).sum();
+
+        vector = product;
+
+        if (estimate - eigenvalue).abs() < POWER_TOLERANCE {
+            return estimate;
+        }
+
+        eigenvalue = estimate;
+    }
+
+    eigenvalue
+}
+
+pub fn connected_components(graph: &AdjList) -> Vec<Vec<usize>> {
+    let mut visited = vec![false; graph.num_vertices()];
+    let mut components = Vec::new();
+
+    for start in 0..graph.num_vertices() {
+        if visited[start] {
+            continue;
+        }
+
+        let mut component = vec![start];
+        let mut stack = vec![start];
+        visited[start] = true;
+
+        while let Some(v) = stack.pop() {
+            for u in graph.adj_list()[v].iter() {
+                if !visited[*u] {
+                    visited[*u] = true;
+                    component.push(*u);
+                    stack.push(*u);
+                }
+            }
+        }
+
+        components.push(component);
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::is_coloring_valid;
+    use crate::testing::{cycle, petersen};
+
+    #[test]
+    fn test_degeneracy_ordering() {
+        assert_eq!(degeneracy_ordering(&cycle(6)).0, 2);
+        assert_eq!(degeneracy_ordering(&AdjList::complete(5)).0, 4);
+
+        // A star is a tree, so its degeneracy is 1
+        let mut star = AdjList::new(5);
+        for v in 1..5 {
+            star.add_edge(0, v);
+        }
+
+        let (degeneracy, ordering) = degeneracy_ordering(&star);
+
+        assert_eq!(degeneracy, 1);
+        assert_eq!(ordering.len(), 5);
+    }
+
+    #[test]
+    fn test_greedy_coloring() {
+        let graph = crate::testing::myciel4();
+
+        let (degeneracy, ordering) = degeneracy_ordering(&graph);
+        let coloring = greedy_coloring(&graph, &ordering);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+        assert!(*coloring.iter().max().unwrap() <= degeneracy + 1);
+    }
+
+    #[test]
+    fn test_brooks_bound() {
+        assert_eq!(brooks_bound(&AdjList::complete(4)), 4);
+        assert_eq!(brooks_bound(&cycle(5)), 3);
+        assert_eq!(brooks_bound(&cycle(6)), 2);
+        assert_eq!(brooks_bound(&AdjList::new(3)), 1);
+    }
+
+    #[test]
+    fn test_hoffman_bound() {
+        // The bound is tight for complete graphs and bipartite graphs
+        assert_eq!(hoffman_bound(&AdjList::complete(5)), 5);
+        assert_eq!(hoffman_bound(&cycle(6)), 2);
+        assert_eq!(hoffman_bound(&AdjList::new(3)), 1);
+
+        // The Petersen graph has eigenvalues 3 and -2
+        assert_eq!(hoffman_bound(&petersen()), 3);
+    }
+}
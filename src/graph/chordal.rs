@@ -0,0 +1,260 @@
+use super::adj_list::AdjList;
+use super::bounds::greedy_coloring;
+
+/// A class of the partition refined by [`lex_bfs`], in a doubly linked list of classes.
+struct Class {
+    vertices: Vec<usize>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// Orders the vertices by a lexicographic breadth-first search (Lex-BFS), in linear time.
+///
+/// The unvisited vertices are kept in a list of classes, the vertices of each class sharing the
+/// same neighbors among the visited ones. The next vertex is taken from the first class, and its
+/// unvisited neighbors are moved to new classes right before their own.
+pub fn lex_bfs(graph: &AdjList) -> Vec<usize> {
+    let n = graph.num_vertices();
+    let mut classes = vec![Class {
+        vertices: (0..n).collect(),
+        prev: None,
+        next: None,
+    }];
+    let mut head = (n > 0).then_some(0);
+    let mut class_of = vec![0; n];
+    let mut position: Vec<usize> = (0..n).collect();
+    let mut is_visited = vec![false; n];
+    // The class split from each class during the current step, if any
+    let mut split: Vec<Option<usize>> = vec![None];
+    let mut ordering = Vec::with_capacity(n);
+
+    while let Some(first) = head {
+        let v = classes[first].vertices.pop().unwrap();
+        is_visited[v] = true;
+        ordering.push(v);
+
+        let mut touched = vec![first];
+
+        for u in graph.adj_list()[v].iter().copied() {
+            if is_visited[u] {
+                continue;
+            }
+
+            let class = class_of[u];
+            let new_class = *split[class].get_or_insert_with(|| {
+                classes.push(Class {
+                    vertices: Vec::new(),
+                    prev: classes[class].prev,
+                    next: Some(class),
+                });
+                let new_class = classes.len() - 1;
+
+                match classes[class].prev {
+                    Some(prev) => classes[prev].next = Some(new_class),
+                    None => head = Some(new_class),
+                }
+                classes[class].prev = Some(new_class);
+                touched.push(class);
+
+                new_class
+            });
+            split.resize(classes.len(), None);
+
+            // Moves `u` to the new class, filling its position with the last vertex of its class
+            let last = *classes[class].vertices.last().unwrap();
+            classes[class].vertices.swap_remove(position[u]);
+            if last != u {
+                position[last] = position[u];
+            }
+            position[u] = classes[new_class].vertices.len();
+            classes[new_class].vertices.push(u);
+            class_of[u] = new_class;
+        }
+
+        for class in touched {
+            split[class] = None;
+
+            if classes[class].vertices.is_empty() {
+                let (prev, next) = (classes[class].prev, classes[class].next);
+
+                match prev {
+                    Some(prev) => classes[prev].next = next,
+                    None => head = next,
+                }
+                if let Some(next) = next {
+                    classes[next].prev = prev;
+                }
+            }
+        }
+    }
+
+    ordering
+}
+
+/// Returns a perfect elimination ordering of `graph` (each vertex and its neighbors after it
+/// form a clique), if it's chordal, i.e., it has no induced cycle longer than 3.
+///
+/// The reverse of a Lex-BFS ordering is a perfect elimination ordering if and only if the graph is
+/// chordal. To check it, the neighbors visited before each vertex (but the last of them, its
+/// parent) must also be neighbors of its parent.
+pub fn perfect_elimination_ordering(graph: &AdjList) -> Option<Vec<usize>> {
+    let n = graph.num_vertices();
+    let mut ordering = lex_bfs(graph);
+    let mut index = vec![0; n];
+    for (i, v) in ordering.iter().enumerate() {
+        index[*v] = i;
+    }
+
+    // The vertices that must be neighbors of each parent
+    let mut required: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for v in 0..n {
+        let earlier = graph.adj_list()[v]
+            .iter()
+            .copied()
+            .filter(|u| index[*u] < index[v]);
+
+        if let Some(parent) = earlier.clone().max_by_key(|u| index[*u]) {
+            required[parent].extend(earlier.filter(|u| *u != parent));
+        }
+    }
+
+    let mut is_neighbor = vec![false; n];
+    for (neighbors, required) in graph.adj_list().iter().zip(required) {
+        neighbors.iter().for_each(|u| is_neighbor[*u] = true);
+
+        if required.iter().any(|u| !is_neighbor[*u]) {
+            return None;
+        }
+
+        neighbors.iter().for_each(|u| is_neighbor[*u] = false);
+    }
+
+    ordering.reverse();
+
+    Some(ordering)
+}
+
+/// Colors `graph` optimally if it's chordal: greedily, in the reverse of a perfect elimination
+/// ordering, each vertex meets its earlier neighbors as a clique, so the number of colors is the
+/// size of the largest clique.
+pub fn chordal_coloring(graph: &AdjList) -> Option<Vec<usize>> {
+    let mut ordering = perfect_elimination_ordering(graph)?;
+    ordering.reverse();
+
+    Some(greedy_coloring(graph, &ordering))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{count_colors, is_coloring_valid};
+    use crate::rng::rng;
+    use crate::testing::{cycle, myciel3, petersen};
+    use rand::Rng;
+
+    /// Checks that each vertex and its neighbors after it in `ordering` form a clique.
+    fn is_perfect_elimination_ordering(graph: &AdjList, ordering: &[usize]) -> bool {
+        let mut index = vec![0; ordering.len()];
+        for (i, v) in ordering.iter().enumerate() {
+            index[*v] = i;
+        }
+
+        (0..ordering.len()).all(|v| {
+            let later: Vec<usize> = graph.adj_list()[v]
+                .iter()
+                .copied()
+                .filter(|u| index[*u] > index[v])
+                .collect();
+
+            later.iter().all(|u| {
+                later
+                    .iter()
+                    .all(|w| u == w || graph.adj_list()[*u].contains(w))
+            })
+        })
+    }
+
+    #[test]
+    fn test_lex_bfs() {
+        let ordering = lex_bfs(&petersen());
+        let mut vertices = ordering.clone();
+        vertices.sort();
+
+        assert_eq!(vertices, (0..10).collect::<Vec<_>>());
+        assert!(lex_bfs(&AdjList::new(0)).is_empty());
+    }
+
+    #[test]
+    fn test_perfect_elimination_ordering() {
+        // A triangulated hexagon: the cycle 0 - ... - 5 with the chords of a fan from 0
+        let mut fan = cycle(6);
+        for v in 2..5 {
+            fan.add_edge(0, v);
+        }
+        // Two triangles sharing the vertex 2, and an isolated vertex
+        let mut bowtie = AdjList::new(6);
+        for (u, v) in [(0, 1), (1, 2), (0, 2), (2, 3), (3, 4), (2, 4)] {
+            bowtie.add_edge(u, v);
+        }
+
+        for graph in [fan, bowtie, AdjList::complete(5), cycle(3)] {
+            let ordering = perfect_elimination_ordering(&graph).unwrap();
+            assert!(is_perfect_elimination_ordering(&graph, &ordering));
+        }
+
+        // Random 3-trees: each new vertex is joined to a triangle of the graph
+        for _ in 0..20 {
+            let mut graph = AdjList::complete(3);
+            let mut triangles = vec![[0, 1, 2]];
+
+            for v in 3..30 {
+                let triangle = triangles[rng().gen_range(0..triangles.len())];
+
+                graph.add_vertex();
+                for (i, u) in triangle.iter().enumerate() {
+                    graph.add_edge(*u, v);
+
+                    let mut next = triangle;
+                    next[i] = v;
+                    triangles.push(next);
+                }
+            }
+
+            let ordering = perfect_elimination_ordering(&graph).unwrap();
+            assert!(is_perfect_elimination_ordering(&graph, &ordering));
+            assert_eq!(count_colors(&chordal_coloring(&graph).unwrap()), 4);
+
+            // A path 0 - 30 - 31 - 1 closes a chordless 4-cycle with the edge (0, 1)
+            graph.add_vertex();
+            graph.add_vertex();
+            graph.add_edge(0, 30);
+            graph.add_edge(30, 31);
+            graph.add_edge(31, 1);
+            assert_eq!(perfect_elimination_ordering(&graph), None);
+        }
+
+        for graph in [cycle(4), cycle(7), petersen(), myciel3()] {
+            assert_eq!(perfect_elimination_ordering(&graph), None);
+        }
+    }
+
+    #[test]
+    fn test_chordal_coloring() {
+        // An interval graph: [0, 2], [1, 4], [3, 6], [5, 7] and [1, 6]
+        let intervals = [(0, 2), (1, 4), (3, 6), (5, 7), (1, 6)];
+        let mut graph = AdjList::new(intervals.len());
+        for (u, (a, b)) in intervals.iter().enumerate() {
+            for (v, (c, d)) in intervals.iter().enumerate().skip(u + 1) {
+                if a <= d && c <= b {
+                    graph.add_edge(u, v);
+                }
+            }
+        }
+
+        let coloring = chordal_coloring(&graph).unwrap();
+
+        assert!(is_coloring_valid(&graph, &coloring));
+        assert_eq!(count_colors(&coloring), 3);
+        assert_eq!(chordal_coloring(&cycle(5)), None);
+    }
+}
@@ -0,0 +1,80 @@
+use super::adj_list::AdjList;
+use std::cmp::Reverse;
+
+/// Finds a large clique with a greedy heuristic: starting from each vertex, its neighbors are
+/// visited in decreasing order of degree, and each one adjacent to the whole clique is added.
+///
+/// The size of the clique is a lower bound on the chromatic number.
+pub fn greedy_clique(graph: &AdjList) -> Vec<usize> {
+    let matrix = graph.to_adj_matrix();
+    let mut best_clique = Vec::new();
+
+    for v in 0..graph.num_vertices() {
+        // Can't beat the best clique
+        if matrix.degree(v) < best_clique.len() {
+            continue;
+        }
+
+        let mut candidates: Vec<usize> = matrix.neighbors(v).collect();
+        candidates.sort_by_key(|u| (Reverse(matrix.degree(*u)), *u));
+
+        let mut clique = vec![v];
+
+        for u in candidates {
+            if clique.iter().all(|w| matrix.has_edge(u, *w)) {
+                clique.push(u);
+            }
+        }
+
+        if clique.len() > best_clique.len() {
+            best_clique = clique;
+        }
+    }
+
+    best_clique
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn is_clique(graph: &AdjList, clique: &[usize]) -> bool {
+        clique.iter().enumerate().all(|(i, u)| {
+            clique[i + 1..]
+                .iter()
+                .all(|v| graph.adj_list()[*u].contains(v))
+        })
+    }
+
+    #[test]
+    fn test_greedy_clique() {
+        assert!(greedy_clique(&AdjList::new(0)).is_empty());
+        assert_eq!(greedy_clique(&AdjList::new(3)).len(), 1);
+        assert_eq!(greedy_clique(&AdjList::complete(5)).len(), 5);
+
+        // A triangle 0---1---2 with a pendant path 2---3---4
+        let mut graph = AdjList::new(5);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 0);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 4);
+
+        let clique = greedy_clique(&graph);
+
+        assert_eq!(clique.len(), 3);
+        assert!(is_clique(&graph, &clique));
+    }
+
+    #[test]
+    fn test_greedy_clique_myciel() {
+        // Mycielski graphs are triangle-free
+        let graph = testing::myciel5();
+
+        let clique = greedy_clique(&graph);
+
+        assert_eq!(clique.len(), 2);
+        assert!(is_clique(&graph, &clique));
+    }
+}
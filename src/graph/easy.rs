@@ -0,0 +1,143 @@
+use super::adj_list::AdjList;
+use super::bounds::connected_components;
+use super::chordal::chordal_coloring;
+use std::collections::VecDeque;
+
+/// Instances colored optimally without any heuristic, from the cheapest to check.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EasyCase {
+    /// No edges: a single color.
+    Edgeless,
+    /// Every pair of vertices is adjacent: a color per vertex.
+    Complete,
+    /// No cycles: two colors.
+    Forest,
+    /// No odd cycles: two colors.
+    Bipartite,
+    /// No induced cycles longer than 3: as many colors as the largest clique.
+    Chordal,
+}
+
+impl EasyCase {
+    pub fn description(&self) -> &'static str {
+        match self {
+            EasyCase::Edgeless => "edgeless",
+            EasyCase::Complete => "complete",
+            EasyCase::Forest => "a forest",
+            EasyCase::Bipartite => "bipartite",
+            EasyCase::Chordal => "chordal",
+        }
+    }
+}
+
+/// Colors `graph` with two colors, with a breadth-first search from each component, if it's
+/// bipartite.
+pub fn two_coloring(graph: &AdjList) -> Option<Vec<usize>> {
+    let mut coloring = vec![0; graph.num_vertices()];
+
+    for start in 0..graph.num_vertices() {
+        if coloring[start] != 0 {
+            continue;
+        }
+
+        coloring[start] = 1;
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(v) = queue.pop_front() {
+            for u in graph.adj_list()[v].iter() {
+                if coloring[*u] == coloring[v] {
+                    return None;
+                }
+                if coloring[*u] == 0 {
+                    coloring[*u] = 3 - coloring[v];
+                    queue.push_back(*u);
+                }
+            }
+        }
+    }
+
+    Some(coloring)
+}
+
+/// Colors `graph` optimally if it's one of the [`EasyCase`]s, returning the case along with the
+/// coloring.
+pub fn easy_coloring(graph: &AdjList) -> Option<(EasyCase, Vec<usize>)> {
+    let n = graph.num_vertices();
+    // Instances may list an edge more than once
+    let mut edges = graph.edges();
+    edges.dedup();
+    let num_edges = edges.len();
+
+    if num_edges == 0 {
+        return Some((EasyCase::Edgeless, vec![1; n]));
+    }
+    if num_edges == n * (n - 1) / 2 {
+        return Some((EasyCase::Complete, (1..=n).collect()));
+    }
+    if let Some(coloring) = two_coloring(graph) {
+        let case = if num_edges + connected_components(graph).len() == n {
+            EasyCase::Forest
+        } else {
+            EasyCase::Bipartite
+        };
+
+        return Some((case, coloring));
+    }
+
+    chordal_coloring(graph).map(|coloring| (EasyCase::Chordal, coloring))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{count_colors, is_coloring_valid};
+    use crate::testing::{cycle, myciel3, petersen};
+
+    #[test]
+    fn test_two_coloring() {
+        let coloring = two_coloring(&cycle(6)).unwrap();
+
+        assert!(is_coloring_valid(&cycle(6), &coloring));
+        assert_eq!(count_colors(&coloring), 2);
+        assert_eq!(two_coloring(&cycle(5)), None);
+    }
+
+    #[test]
+    fn test_easy_coloring() {
+        let mut star = AdjList::new(4);
+        for v in 1..4 {
+            star.add_edge(0, v);
+        }
+        // A path 0 - 1 - 2 with an edge given twice
+        let mut repeated = AdjList::new(3);
+        for (u, v) in [(0, 1), (1, 2), (2, 1)] {
+            repeated.add_edge(u, v);
+        }
+        let mut fan = cycle(5);
+        fan.add_edge(0, 2);
+        fan.add_edge(0, 3);
+
+        let cases = [
+            (AdjList::new(3), Some((EasyCase::Edgeless, 1))),
+            (AdjList::complete(4), Some((EasyCase::Complete, 4))),
+            (repeated, Some((EasyCase::Forest, 2))),
+            (star, Some((EasyCase::Forest, 2))),
+            (cycle(8), Some((EasyCase::Bipartite, 2))),
+            (fan, Some((EasyCase::Chordal, 3))),
+            (petersen(), None),
+            (myciel3(), None),
+        ];
+
+        for (graph, expected) in cases {
+            let result = easy_coloring(&graph);
+
+            if let Some((_, coloring)) = &result {
+                assert!(is_coloring_valid(&graph, coloring));
+            }
+            assert_eq!(
+                result.map(|(case, coloring)| (case, count_colors(&coloring))),
+                expected
+            );
+        }
+    }
+}
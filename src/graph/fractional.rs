@@ -0,0 +1,361 @@
+use super::adj_list::AdjList;
+use super::bitset::BitMatrix;
+use super::bounds::{degeneracy_ordering, greedy_coloring};
+
+const EPSILON: f64 = 1e-9;
+/// Pivots of each run of the simplex method, after which the best point so far is kept.
+const MAX_PIVOTS: usize = 20000;
+/// Nodes of the search for the heaviest independent set, after which it settles for a bound.
+const MAX_NODES: usize = 100000;
+
+/// A lower bound on the chromatic number from the fractional chromatic number, along with how it
+/// was found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FractionalBound {
+    pub lower_bound: usize,
+    /// Value of the last restricted master problem, an upper bound on the fractional chromatic
+    /// number.
+    pub lp_value: f64,
+    /// Number of independent sets (columns) generated.
+    pub num_columns: usize,
+}
+
+/// Bounds the fractional chromatic number from below with (restricted) column generation.
+///
+/// The master problem covers the vertices with as few independent sets (fractionally) as
+/// possible. Its dual gives each vertex a weight `y`, with no independent set weighing more
+/// than 1. Independent sets that do are found by greedy heuristics (pricing), or by a bounded
+/// exact search once they fail, and added as columns, for up to `max_iterations` rounds.
+///
+/// For any weights, the sum of the weights over the weight of the heaviest independent set is a
+/// lower bound on the fractional (and so, on the) chromatic number. When the exact search runs out
+/// of nodes, the heaviest independent set is bounded from above by covering the vertices with
+/// cliques instead, since it has at most one vertex of each clique.
+pub fn fractional_bound(graph: &AdjList, max_iterations: usize) -> FractionalBound {
+    let n = graph.num_vertices();
+    if n == 0 {
+        return FractionalBound {
+            lower_bound: 0,
+            lp_value: 0.0,
+            num_columns: 0,
+        };
+    }
+
+    let neighbors = graph.to_adj_matrix().to_bitset();
+    let (_, ordering) = degeneracy_ordering(graph);
+    let coloring = greedy_coloring(graph, &ordering);
+    let num_colors = coloring.iter().max().copied().unwrap_or(0);
+    let mut columns: Vec<Vec<usize>> = vec![Vec::new(); num_colors];
+    for (v, color) in coloring.iter().enumerate() {
+        columns[color - 1].push(v);
+    }
+
+    let mut best_bound: f64 = 1.0;
+    let mut lp_value = num_colors as f64;
+
+    for _ in 0..max_iterations.max(1) {
+        let weights = solve_packing(&columns, n);
+        let total: f64 = weights.iter().sum();
+        let weight = |set: &[usize]| set.iter().map(|v| weights[*v]).sum::<f64>();
+
+        lp_value = total;
+
+        let mut new_columns: Vec<Vec<usize>> = price(graph, &weights)
+            .into_iter()
+            .filter(|set| weight(set) > 1.0 + EPSILON && !columns.contains(set))
+            .collect();
+        new_columns.dedup();
+
+        let upper_bound = if new_columns.is_empty() {
+            let (set, upper_bound) = heaviest_set(&neighbors, &weights);
+
+            if weight(&set) > 1.0 + EPSILON && !columns.contains(&set) {
+                new_columns.push(set);
+            }
+
+            upper_bound
+        } else {
+            let mut vertices: Vec<usize> = (0..n).collect();
+            vertices.sort_by(|a, b| weights[*b].total_cmp(&weights[*a]));
+
+            clique_cover_bound(&neighbors, &vertices, &weights)
+        };
+
+        best_bound = best_bound.max(total / upper_bound.max(EPSILON));
+
+        if new_columns.is_empty() {
+            break;
+        }
+
+        columns.extend(new_columns);
+    }
+
+    FractionalBound {
+        // Allow for the rounding errors
+        lower_bound: (best_bound - 1e-6).ceil() as usize,
+        lp_value,
+        num_columns: columns.len(),
+    }
+}
+
+/// Maximizes the sum of `y` subject to `y(S) <= 1` for each set `S` of `columns`, and `y >= 0`,
+/// with the (dense) simplex method, which can start from `y = 0`.
+fn solve_packing(columns: &[Vec<usize>], n: usize) -> Vec<f64> {
+    let m = columns.len();
+    let width = n + m + 1;
+    let rhs = width - 1;
+    let mut tableau: Vec<Vec<f64>> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let mut row = vec![0.0; width];
+            column.iter().for_each(|v| row[*v] = 1.0);
+            row[n + i] = 1.0;
+            row[rhs] = 1.0;
+            row
+        })
+        .collect();
+    // Reduced costs: a positive one means the variable improves the objective
+    let mut costs = vec![0.0; width];
+    costs[..n].iter_mut().for_each(|cost| *cost = 1.0);
+    let mut basis: Vec<usize> = (n..n + m).collect();
+
+    for _ in 0..MAX_PIVOTS {
+        let Some(entering) = (0..n + m)
+            .filter(|j| costs[*j] > EPSILON)
+            .max_by(|a, b| costs[*a].total_cmp(&costs[*b]))
+        else {
+            break;
+        };
+
+        let Some(leaving) = (0..m)
+            .filter(|i| tableau[*i][entering] > EPSILON)
+            .min_by(|a, b| {
+                let ratio = |i: usize| tableau[i][rhs] / tableau[i][entering];
+                ratio(*a)
+                    .total_cmp(&ratio(*b))
+                    .then(basis[*a].cmp(&basis[*b]))
+            })
+        else {
+            break;
+        };
+
+        let pivot = tableau[leaving][entering];
+        tableau[leaving].iter_mut().for_each(|x| *x /= pivot);
+        let pivot_row = tableau[leaving].clone();
+
+        for (i, row) in tableau.iter_mut().enumerate() {
+            let factor = row[entering];
+            if i != leaving && factor.abs() > EPSILON {
+                row.iter_mut()
+                    .zip(pivot_row.iter())
+                    .for_each(|(x, p)| *x -= factor * p);
+            }
+        }
+
+        let factor = costs[entering];
+        costs
+            .iter_mut()
+            .zip(pivot_row.iter())
+            .for_each(|(x, p)| *x -= factor * p);
+
+        basis[leaving] = entering;
+    }
+
+    let mut weights = vec![0.0; n];
+    for (i, variable) in basis.iter().enumerate() {
+        if *variable < n {
+            weights[*variable] = tableau[i][rhs].max(0.0);
+        }
+    }
+
+    weights
+}
+
+/// Independent sets of large weight, built greedily in a few orders of the vertices: by
+/// decreasing weight, and by decreasing weight over degree.
+fn price(graph: &AdjList, weights: &[f64]) -> Vec<Vec<usize>> {
+    let n = graph.num_vertices();
+    let scores: [Box<dyn Fn(usize) -> f64>; 2] = [
+        Box::new(|v| weights[v]),
+        Box::new(|v| weights[v] / (graph.adj_list()[v].len() + 1) as f64),
+    ];
+
+    scores
+        .iter()
+        .map(|score| {
+            let mut order: Vec<usize> = (0..n).filter(|v| weights[*v] > EPSILON).collect();
+            order.sort_by(|a, b| score(*b).total_cmp(&score(*a)));
+
+            let mut is_blocked = vec![false; n];
+            let mut set = Vec::new();
+
+            // The vertices of weight 0 complete the set, so it's maximal
+            for v in order.into_iter().chain(0..n) {
+                if !is_blocked[v] {
+                    set.push(v);
+                    is_blocked[v] = true;
+                    graph.adj_list()[v]
+                        .iter()
+                        .for_each(|u| is_blocked[*u] = true);
+                }
+            }
+
+            set.sort();
+            set
+        })
+        .collect()
+}
+
+/// Searches for the heaviest independent set by branch and bound, for at most [`MAX_NODES`]
+/// nodes. Returns the heaviest set found, along with an upper bound on the weight of any
+/// independent set: its own weight, if the search was complete.
+fn heaviest_set(neighbors: &BitMatrix, weights: &[f64]) -> (Vec<usize>, f64) {
+    let mut candidates: Vec<usize> = (0..weights.len())
+        .filter(|v| weights[*v] > EPSILON)
+        .collect();
+    candidates.sort_by(|a, b| weights[*b].total_cmp(&weights[*a]));
+
+    let root_bound = clique_cover_bound(neighbors, &candidates, weights);
+    let mut search = Search {
+        neighbors,
+        weights,
+        best: (Vec::new(), 0.0),
+        nodes: 0,
+    };
+
+    search.branch(&mut Vec::new(), 0.0, candidates);
+
+    let (set, weight) = search.best;
+    if search.nodes > MAX_NODES {
+        (set, root_bound)
+    } else {
+        (set, weight)
+    }
+}
+
+struct Search<'a> {
+    neighbors: &'a BitMatrix,
+    weights: &'a [f64],
+    best: (Vec<usize>, f64),
+    nodes: usize,
+}
+
+impl Search<'_> {
+    /// Extends `set` (of weight `weight`) with vertices of `candidates`, which are sorted by
+    /// decreasing weight and not adjacent to `set`.
+    fn branch(&mut self, set: &mut Vec<usize>, weight: f64, candidates: Vec<usize>) {
+        self.nodes += 1;
+
+        if weight > self.best.1 {
+            self.best = (set.clone(), weight);
+        }
+        if self.nodes > MAX_NODES
+            || candidates.is_empty()
+            || weight + clique_cover_bound(self.neighbors, &candidates, self.weights)
+                <= self.best.1 + EPSILON
+        {
+            return;
+        }
+
+        let v = candidates[0];
+        let rest: Vec<usize> = candidates[1..]
+            .iter()
+            .copied()
+            .filter(|u| !self.neighbors.has_edge(v, *u))
+            .collect();
+
+        set.push(v);
+        self.branch(set, weight + self.weights[v], rest);
+        set.pop();
+
+        self.branch(set, weight, candidates[1..].to_vec());
+    }
+}
+
+/// An upper bound on the weight of any independent set among `vertices` (sorted by decreasing
+/// weight): the sum of the largest weight of each clique of a greedy clique cover, so heavy
+/// vertices share cliques.
+fn clique_cover_bound(neighbors: &BitMatrix, vertices: &[usize], weights: &[f64]) -> f64 {
+    let mut cliques: Vec<Vec<usize>> = Vec::new();
+    let mut bound = 0.0;
+
+    for v in vertices {
+        match cliques
+            .iter_mut()
+            .find(|clique| neighbors.is_adjacent_to_all(*v, clique))
+        {
+            Some(clique) => clique.push(*v),
+            None => {
+                // The first vertex of each clique is its heaviest
+                bound += weights[*v];
+                cliques.push(vec![*v]);
+            }
+        }
+    }
+
+    bound
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{cycle, myciel3, petersen};
+
+    #[test]
+    fn test_solve_packing() {
+        // The triangle 0 - 1 - 2 is covered by its vertices, each one being independent
+        let weights = solve_packing(&[vec![0], vec![1], vec![2]], 3);
+        assert_eq!(weights, [1.0, 1.0, 1.0]);
+
+        // The sets of the 5-cycle of size 2 give each vertex a weight of 1/2
+        let columns: Vec<Vec<usize>> = (0..5).map(|v| vec![v, (v + 2) % 5]).collect();
+        let weights = solve_packing(&columns, 5);
+        assert!((weights.iter().sum::<f64>() - 2.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_heaviest_set() {
+        let graph = petersen();
+        let neighbors = graph.to_adj_matrix().to_bitset();
+
+        for seed in 0..10 {
+            let weights: Vec<f64> = (0..10).map(|v| ((v * 7 + seed * 3) % 5) as f64).collect();
+
+            // Every subset of the vertices, by brute force
+            let best = (0..1usize << 10)
+                .filter(|mask| {
+                    graph
+                        .edges()
+                        .iter()
+                        .all(|(u, v)| mask & (1 << u) == 0 || mask & (1 << v) == 0)
+                })
+                .map(|mask| {
+                    (0..10)
+                        .filter(|v| mask & (1 << v) != 0)
+                        .map(|v| weights[v])
+                        .sum()
+                })
+                .fold(0.0, f64::max);
+
+            let (set, weight) = heaviest_set(&neighbors, &weights);
+
+            assert_eq!(weight, best);
+            assert_eq!(set.iter().map(|v| weights[*v]).sum::<f64>(), best);
+        }
+    }
+
+    #[test]
+    fn test_fractional_bound() {
+        // The fractional chromatic number of odd cycles is 2 + 1 / k, so the bound is 3
+        assert_eq!(fractional_bound(&cycle(7), 50).lower_bound, 3);
+        assert_eq!(fractional_bound(&AdjList::complete(5), 50).lower_bound, 5);
+        // The Petersen graph has no triangles, but its fractional chromatic number is 5 / 2
+        assert_eq!(fractional_bound(&petersen(), 50).lower_bound, 3);
+
+        // The fractional chromatic number of myciel3 is 29 / 10
+        let bound = fractional_bound(&myciel3(), 50);
+        assert_eq!(bound.lower_bound, 3);
+        assert!((bound.lp_value - 2.9).abs() < 1e-6);
+    }
+}
@@ -1 +1,8 @@
 pub mod adj_list;
+pub mod adj_matrix;
+pub mod bitset;
+pub mod bounds;
+pub mod chordal;
+pub mod clique;
+pub mod easy;
+pub mod fractional;
@@ -1,14 +1,46 @@
+use crate::algorithms::genetic::Population;
 use crate::graph::adj_list::AdjList;
+use crate::output::PARTITION_HEADER;
+use std::collections::HashMap;
 use std::error::Error;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
+use std::path::Path;
 
+pub mod named;
+
+/// Most vertices an instance may declare. Each vertex takes memory before it has any edges, so
+/// absurd counts are rejected instead of exhausting it.
+pub const MAX_VERTICES: u64 = 1 << 26;
+
+/// Reads the graph in `filename`, in a format picked by its extension: an edge list of labels
+/// (`.edges` or `.txt`), a JSON object (`.json`) or a Graphviz DOT file (`.dot` or `.gv`), whose
+/// vertices keep their labels (see [`named`]), or DIMACS otherwise.
 pub fn read_graph_from_file(filename: &str) -> Result<Option<AdjList>, Box<dyn Error>> {
-    let file = File::open(filename)?;
-    let reader = BufReader::new(file);
+    let file = BufReader::new(File::open(filename)?);
+    let extension = Path::new(filename)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default();
+
+    match extension {
+        "edges" | "txt" => named::read_edge_list(file).map(Some),
+        "json" => named::read_json(file).map(Some),
+        "dot" | "gv" => named::read_dot(file).map(Some),
+        _ => read_graph(file),
+    }
+}
+
+/// Reads a graph in the DIMACS format from `reader`. Returns `None` if there's no problem line.
+///
+/// Fails on malformed lines, instead of panicking: lines that aren't valid UTF-8, edges before the
+/// problem line, vertices out of the (1-indexed) range, loops and more than [`MAX_VERTICES`]
+/// vertices are all rejected.
+pub fn read_graph(reader: impl BufRead) -> Result<Option<AdjList>, Box<dyn Error>> {
     let mut graph: Option<AdjList> = None;
 
-    for line in reader.lines().flatten() {
+    for line in reader.lines() {
+        let line = line?;
         let splits: Vec<&str> = line.split_whitespace().collect();
 
         if splits.is_empty() {
@@ -18,18 +50,23 @@ pub fn read_graph_from_file(filename: &str) -> Result<Option<AdjList>, Box<dyn E
         match splits[0] {
             "p" => {
                 if let Some(num_vertices) = splits.get(2) {
-                    let num_vertices: usize = num_vertices.parse()?;
+                    let num_vertices = check_num_vertices(num_vertices.parse()?)?;
                     graph = Some(AdjList::new(num_vertices));
                 }
             }
             "e" => {
                 if let (Some(from), Some(to)) = (splits.get(1), splits.get(2)) {
-                    if let Some(graph) = graph.as_mut() {
-                        let from = from.parse::<usize>()? - 1;
-                        let to = to.parse::<usize>()? - 1;
-                        graph.adj_list_mut()[from].push(to);
-                        graph.adj_list_mut()[to].push(from);
+                    let Some(graph) = graph.as_mut() else {
+                        return Err("an edge comes before the problem line".into());
+                    };
+                    let from = parse_vertex(from, graph.num_vertices())?;
+                    let to = parse_vertex(to, graph.num_vertices())?;
+
+                    if from == to {
+                        return Err(format!("vertex {} has a loop", from + 1).into());
                     }
+
+                    graph.add_edge(from, to);
                 }
             }
             _ => {}
@@ -38,3 +75,285 @@ pub fn read_graph_from_file(filename: &str) -> Result<Option<AdjList>, Box<dyn E
 
     Ok(graph)
 }
+
+/// Checks that an instance may declare `num_vertices` vertices (see [`MAX_VERTICES`]).
+pub fn check_num_vertices(num_vertices: u64) -> Result<usize, Box<dyn Error>> {
+    if num_vertices > MAX_VERTICES {
+        return Err(format!(
+            "the instance has {num_vertices} vertices, more than the {MAX_VERTICES} supported"
+        )
+        .into());
+    }
+
+    Ok(num_vertices as usize)
+}
+
+/// Parses a 1-indexed vertex of a graph with `num_vertices` vertices, returning it 0-indexed.
+fn parse_vertex(vertex: &str, num_vertices: usize) -> Result<usize, Box<dyn Error>> {
+    let vertex: usize = vertex.parse()?;
+
+    if vertex == 0 || vertex > num_vertices {
+        return Err(format!("vertex {vertex} is out of the range 1..={num_vertices}").into());
+    }
+
+    Ok(vertex - 1)
+}
+
+/// Reads a population exported by `--dump-population`, returning the colorings of its individuals.
+///
+/// Fails if any coloring doesn't assign a (1-indexed) color to each of the `num_vertices` vertices.
+pub fn read_population_from_file(
+    filename: &str,
+    num_vertices: usize,
+) -> Result<Vec<Vec<usize>>, Box<dyn Error>> {
+    let file = File::open(filename)?;
+    let population: Population = serde_json::from_reader(BufReader::new(file))?;
+
+    for (i, individual) in population.individuals.iter().enumerate() {
+        if individual.coloring.len() != num_vertices {
+            return Err(format!(
+                "individual {i} colors {} vertices, but the instance has {num_vertices}",
+                individual.coloring.len()
+            )
+            .into());
+        }
+        if individual.coloring.contains(&0) {
+            return Err(format!("individual {i} uses color 0, but colors are 1-indexed").into());
+        }
+    }
+
+    Ok(population
+        .individuals
+        .into_iter()
+        .map(|individual| individual.coloring)
+        .collect())
+}
+
+/// Reads a coloring of `graph`, either as printed by a run of the algorithms (the `Color
+/// assignment` line of its output) or as a plain list of colors, separated by whitespace or commas.
+/// The coloring may also be a JSON object from the names of the vertices (see
+/// [`AdjList::vertex_name`]) to their colors, as printed for instances with labeled vertices, or
+/// its color classes in the partition format (see [`read_partition`]).
+///
+/// Fails if the coloring doesn't assign a (1-indexed) color to each vertex of the graph.
+pub fn read_coloring_from_file(
+    filename: &str,
+    graph: &AdjList,
+) -> Result<Vec<usize>, Box<dyn Error>> {
+    let num_vertices = graph.num_vertices();
+    let contents = std::fs::read_to_string(filename)?;
+
+    if let Some(partition) = contents.split_once(PARTITION_HEADER).map(|(_, rest)| rest) {
+        return read_partition(partition, graph);
+    }
+
+    let colors = contents
+        .lines()
+        .find_map(|line| line.strip_prefix("Color assignment:"))
+        .unwrap_or(&contents);
+
+    let coloring = if colors.trim_start().starts_with('{') {
+        let colors: HashMap<String, usize> = serde_json::from_str(colors)?;
+
+        (0..num_vertices)
+            .map(|v| {
+                let name = graph.vertex_name(v);
+                colors
+                    .get(&name)
+                    .copied()
+                    .ok_or_else(|| format!("vertex {name} has no color"))
+            })
+            .collect::<Result<Vec<usize>, _>>()?
+    } else {
+        colors
+            .split(|c: char| c.is_whitespace() || matches!(c, ',' | '[' | ']'))
+            .filter(|color| !color.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<usize>, _>>()?
+    };
+
+    if coloring.len() != num_vertices {
+        return Err(format!(
+            "the coloring has {} vertices, but the instance has {num_vertices}",
+            coloring.len()
+        )
+        .into());
+    }
+    if coloring.contains(&0) {
+        return Err("the coloring uses color 0, but colors are 1-indexed".into());
+    }
+
+    Ok(coloring)
+}
+
+/// Reads the color classes listed one per line after the [`PARTITION_HEADER`] (which must be
+/// stripped already), as the names of their vertices (see [`AdjList::vertex_name`]). The list ends
+/// with the input, or with a line whose first field ends with `:` (the next line of the output of
+/// a run). The `i`-th class gets the color `i`.
+///
+/// Fails if a vertex is unknown, or if the vertices aren't in exactly one class each.
+pub fn read_partition(partition: &str, graph: &AdjList) -> Result<Vec<usize>, Box<dyn Error>> {
+    let vertices: HashMap<String, usize> = (0..graph.num_vertices())
+        .map(|v| (graph.vertex_name(v), v))
+        .collect();
+    let mut coloring = vec![0; graph.num_vertices()];
+    let mut color = 0;
+
+    for line in partition.lines() {
+        let names: Vec<&str> = line.split_whitespace().collect();
+
+        match names.first() {
+            None => continue,
+            Some(first) if first.ends_with(':') => break,
+            Some(_) => color += 1,
+        }
+
+        for name in names {
+            let Some(v) = vertices.get(name) else {
+                return Err(format!("vertex {name} isn't in the instance").into());
+            };
+            if coloring[*v] != 0 {
+                return Err(format!("vertex {name} is in more than one class").into());
+            }
+
+            coloring[*v] = color;
+        }
+    }
+
+    if let Some(v) = coloring.iter().position(|color| *color == 0) {
+        return Err(format!("vertex {} isn't in any class", graph.vertex_name(v)).into());
+    }
+
+    Ok(coloring)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::format::{write_graph, Annotations, Format};
+    use crate::testing::{arbitrary_graph, NUM_CASES};
+    use proptest::prelude::*;
+    use std::env;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(NUM_CASES))]
+
+        #[test]
+        fn test_read_written_graph(graph in arbitrary_graph()) {
+            let mut buffer = Vec::new();
+
+            write_graph(&mut buffer, &graph, &Annotations::default(), Format::Dimacs).unwrap();
+
+            let read_graph = read_graph(buffer.as_slice()).unwrap().unwrap();
+
+            prop_assert_eq!(read_graph.num_vertices(), graph.num_vertices());
+            prop_assert_eq!(read_graph.edges(), graph.edges());
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(10000))]
+
+        /// Random lines built from DIMACS-like tokens must never make the parser panic.
+        #[test]
+        fn test_read_token_soup(
+            lines in proptest::collection::vec(
+                proptest::collection::vec(proptest::sample::select(&TOKENS[..]), 0..5),
+                1..6,
+            )
+        ) {
+            let input: String = lines.iter().map(|line| line.join(" ") + "\n").collect();
+
+            if let Ok(Some(graph)) = read_graph(input.as_bytes()) {
+                prop_assert!(graph
+                    .adj_list()
+                    .iter()
+                    .flatten()
+                    .all(|v| *v < graph.num_vertices()));
+            }
+        }
+    }
+
+    const TOKENS: [&str; 13] = [
+        "p", "edge", "e", "c", "n", "0", "1", "2", "5", "-1", "x", "", "\u{0}",
+    ];
+
+    #[test]
+    fn test_read_coloring() {
+        let path = env::temp_dir().join(format!("gcp-heuristics-{}.col", std::process::id()));
+        let filename = path.to_str().unwrap();
+
+        std::fs::write(
+            &path,
+            "Number of colors used: 2\nColor assignment: [1, 2, 1]\n",
+        )
+        .unwrap();
+        let (three, four) = (AdjList::new(3), AdjList::new(4));
+        assert_eq!(
+            read_coloring_from_file(filename, &three).unwrap(),
+            vec![1, 2, 1]
+        );
+        assert!(read_coloring_from_file(filename, &four).is_err());
+
+        std::fs::write(&path, "3 1\n2\n").unwrap();
+        assert_eq!(
+            read_coloring_from_file(filename, &three).unwrap(),
+            vec![3, 1, 2]
+        );
+
+        let mut named = AdjList::new(3);
+        named.set_labels(["a", "b", "c"].map(String::from).to_vec());
+        std::fs::write(&path, "Color assignment: {\"c\": 2, \"a\": 1, \"b\": 3}\n").unwrap();
+        assert_eq!(
+            read_coloring_from_file(filename, &named).unwrap(),
+            vec![1, 3, 2]
+        );
+        std::fs::write(&path, "{\"a\": 1, \"b\": 3}").unwrap();
+        assert!(read_coloring_from_file(filename, &named).is_err());
+
+        std::fs::write(
+            &path,
+            "Number of colors used: 2\nColor classes:\nb\n\na c\nDuration: 0\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_coloring_from_file(filename, &named).unwrap(),
+            vec![2, 1, 2]
+        );
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_read_partition() {
+        let graph = AdjList::new(4);
+
+        assert_eq!(
+            read_partition("2 4\n1\n3\n", &graph).unwrap(),
+            vec![2, 1, 3, 1]
+        );
+        // Missing, repeated and unknown vertices
+        assert!(read_partition("1 2\n3\n", &graph).is_err());
+        assert!(read_partition("1 2\n3 4 1\n", &graph).is_err());
+        assert!(read_partition("1 2\n3 4 5\n", &graph).is_err());
+    }
+
+    #[test]
+    fn test_read_malformed_graph() {
+        assert!(read_graph("e 1 2\np edge 2 1\n".as_bytes()).is_err());
+        assert!(read_graph("p edge 2 1\ne 0 1\n".as_bytes()).is_err());
+        assert!(read_graph("p edge 2 1\ne 1 3\n".as_bytes()).is_err());
+        assert!(read_graph("p edge 2 1\ne 2 2\n".as_bytes()).is_err());
+        assert!(read_graph("p edge 2 1\ne 1 -2\n".as_bytes()).is_err());
+        assert!(read_graph("p edge two 1\n".as_bytes()).is_err());
+        assert!(read_graph(&b"p edge 3 3\ne 1 2\nc auteur \xE9\ne 2 3\ne 1 3\n"[..]).is_err());
+        assert!(read_graph("p edge 99999999999999 0\n".as_bytes()).is_err());
+        assert!(
+            named::read_json(r#"{"num_vertices": 99999999999999, "edges": []}"#.as_bytes())
+                .is_err()
+        );
+        assert!(read_graph("c no problem line\n".as_bytes())
+            .unwrap()
+            .is_none());
+    }
+}
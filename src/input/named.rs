@@ -0,0 +1,297 @@
+//! Instances whose vertices are named by arbitrary labels, instead of numbered as in DIMACS. The
+//! vertices are indexed in the order their labels first appear, and the graph keeps the labels.
+
+use crate::graph::adj_list::AdjList;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::BufRead;
+
+/// Builds a graph from labeled vertices and edges, indexing each label as it first appears.
+struct Builder {
+    graph: AdjList,
+    indices: HashMap<String, usize>,
+    labels: Vec<String>,
+    edges: HashSet<(usize, usize)>,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Builder {
+            graph: AdjList::new(0),
+            indices: HashMap::new(),
+            labels: Vec::new(),
+            edges: HashSet::new(),
+        }
+    }
+
+    fn vertex(&mut self, label: &str) -> usize {
+        if let Some(v) = self.indices.get(label) {
+            return *v;
+        }
+
+        let v = self.graph.add_vertex();
+        self.indices.insert(label.to_string(), v);
+        self.labels.push(label.to_string());
+
+        v
+    }
+
+    /// Adds an edge between the vertices labeled `u` and `v`. Repeated edges are ignored, but
+    /// loops are rejected.
+    fn edge(&mut self, u: &str, v: &str) -> Result<(), Box<dyn Error>> {
+        if u == v {
+            return Err(format!("vertex {u} has a loop").into());
+        }
+
+        let (u, v) = (self.vertex(u), self.vertex(v));
+        if self.edges.insert((u.min(v), u.max(v))) {
+            self.graph.add_edge(u, v);
+        }
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> AdjList {
+        self.graph.set_labels(self.labels);
+
+        self.graph
+    }
+}
+
+/// Reads a graph with one edge per line, as two labels separated by whitespace. A line with a
+/// single label adds an isolated vertex. Empty lines and lines starting with `#` are skipped, and
+/// fields after the second one (such as the distances of the edge list format) are ignored.
+pub fn read_edge_list(reader: impl BufRead) -> Result<AdjList, Box<dyn Error>> {
+    let mut builder = Builder::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let splits: Vec<&str> = line.split_whitespace().collect();
+
+        match splits.as_slice() {
+            [] => {}
+            [first, ..] if first.starts_with('#') => {}
+            [vertex] => {
+                builder.vertex(vertex);
+            }
+            [u, v, ..] => builder.edge(u, v)?,
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+/// Reads a graph from a JSON object with its `edges`, as pairs of labels (strings or numbers), and
+/// optionally its `vertices`, so isolated ones are kept and the order of the labels is set.
+///
+/// Objects written in the JSON format (with `num_vertices` instead of `vertices`) are read with the
+/// labels `1..=num_vertices`, so the vertices keep their numbers.
+pub fn read_json(reader: impl BufRead) -> Result<AdjList, Box<dyn Error>> {
+    let json: Value = serde_json::from_reader(reader)?;
+    let mut builder = Builder::new();
+
+    if let Some(vertices) = json.get("vertices") {
+        let vertices = vertices.as_array().ok_or("`vertices` must be an array")?;
+
+        for vertex in vertices {
+            builder.vertex(&json_label(vertex)?);
+        }
+    } else if let Some(num_vertices) = json.get("num_vertices") {
+        let num_vertices = num_vertices
+            .as_u64()
+            .ok_or("`num_vertices` must be a number")?;
+        super::check_num_vertices(num_vertices)?;
+
+        for v in 1..=num_vertices {
+            builder.vertex(&v.to_string());
+        }
+    }
+
+    let edges = json
+        .get("edges")
+        .and_then(Value::as_array)
+        .ok_or("the object must have an array of `edges`")?;
+
+    for edge in edges {
+        match edge.as_array().map(Vec::as_slice) {
+            Some([u, v]) => builder.edge(&json_label(u)?, &json_label(v)?)?,
+            _ => return Err(format!("the edge {edge} isn't a pair of vertices").into()),
+        }
+    }
+
+    if json.get("vertices").is_none() {
+        if let Some(num_vertices) = json.get("num_vertices").and_then(Value::as_u64) {
+            if builder.labels.len() as u64 > num_vertices {
+                return Err(
+                    format!("an edge has a vertex out of the range 1..={num_vertices}").into(),
+                );
+            }
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+fn json_label(value: &Value) -> Result<String, Box<dyn Error>> {
+    match value {
+        Value::String(label) => Ok(label.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        _ => Err(format!("{value} isn't a vertex label").into()),
+    }
+}
+
+/// Reads a graph from a (simple) Graphviz DOT file: the statements of its body are either node
+/// statements, which add isolated vertices, or chains of edges (`a -- b -- c`). Attribute lists,
+/// attribute statements and comments are skipped, and `->` is read as `--`. Subgraphs aren't
+/// supported.
+pub fn read_dot(reader: impl BufRead) -> Result<AdjList, Box<dyn Error>> {
+    let mut source = String::new();
+
+    for line in reader.lines() {
+        let line = line?;
+
+        if line.trim_start().starts_with('#') {
+            continue;
+        }
+        let line = line.split("//").next().unwrap_or_default();
+        source.push_str(line);
+        source.push('\n');
+    }
+
+    let (Some(start), Some(end)) = (source.find('{'), source.rfind('}')) else {
+        return Err("the graph has no body".into());
+    };
+    if end < start {
+        return Err("the body of the graph isn't closed".into());
+    }
+    let header = source[..start].trim();
+    if !header
+        .split_whitespace()
+        .any(|word| word == "graph" || word == "digraph")
+    {
+        return Err("the body isn't preceded by a graph header".into());
+    }
+
+    let mut builder = Builder::new();
+
+    for statement in source[start + 1..end].split([';', '\n']) {
+        let statement = strip_attributes(statement);
+        let statement = statement.trim();
+
+        if statement.is_empty() {
+            continue;
+        }
+        if statement.contains('{') || statement.contains('}') {
+            return Err("subgraphs aren't supported".into());
+        }
+
+        let ids: Vec<&str> = statement
+            .split("--")
+            .flat_map(|part| part.split("->"))
+            .map(|id| id.trim().trim_matches('"'))
+            .collect();
+
+        match ids.as_slice() {
+            [id] => {
+                let keyword = matches!(*id, "graph" | "node" | "edge");
+                if !keyword && !id.contains('=') {
+                    builder.vertex(id);
+                }
+            }
+            _ => {
+                for pair in ids.windows(2) {
+                    if pair[0].is_empty() || pair[1].is_empty() {
+                        return Err(
+                            format!("the edge statement `{statement}` is incomplete").into()
+                        );
+                    }
+                    builder.edge(pair[0], pair[1])?;
+                }
+            }
+        }
+    }
+
+    Ok(builder.finish())
+}
+
+/// `statement` without its attribute lists (`[...]`).
+fn strip_attributes(statement: &str) -> String {
+    let mut stripped = String::with_capacity(statement.len());
+    let mut depth = 0;
+
+    for c in statement.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            _ if depth == 0 => stripped.push(c),
+            _ => {}
+        }
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(graph: &AdjList) -> Vec<&str> {
+        graph.labels().unwrap().iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_read_edge_list() {
+        let graph = read_edge_list(
+            "# a triangle\nalice bob\nbob carol 3\n\ncarol alice\nalice bob\ndave\n".as_bytes(),
+        )
+        .unwrap();
+
+        assert_eq!(labels(&graph), ["alice", "bob", "carol", "dave"]);
+        assert_eq!(graph.edges(), vec![(0, 1), (0, 2), (1, 2)]);
+        assert!(read_edge_list("a a\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_read_json() {
+        let json = r#"{"vertices": ["x", "y", "z"], "edges": [["z", "x"], ["y", 7]]}"#;
+        let graph = read_json(json.as_bytes()).unwrap();
+
+        assert_eq!(labels(&graph), ["x", "y", "z", "7"]);
+        assert_eq!(graph.edges(), vec![(0, 2), (1, 3)]);
+
+        // As written by the JSON format
+        let json = r#"{"num_vertices": 3, "edges": [[3, 1]]}"#;
+        let graph = read_json(json.as_bytes()).unwrap();
+
+        assert_eq!(labels(&graph), ["1", "2", "3"]);
+        assert_eq!(graph.edges(), vec![(0, 2)]);
+
+        assert!(read_json(r#"{"num_vertices": 2, "edges": [[1, 3]]}"#.as_bytes()).is_err());
+        assert!(read_json(r#"{"edges": [["a"]]}"#.as_bytes()).is_err());
+        assert!(read_json(r#"{"vertices": ["a"]}"#.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_read_dot() {
+        let dot = r#"
+            // A path and an isolated vertex
+            strict graph G {
+                node [shape=circle];
+                rankdir=LR
+                "New York" -- boston -- chicago [color=red];
+                denver
+                boston -> "New York"
+            }
+        "#;
+        let graph = read_dot(dot.as_bytes()).unwrap();
+
+        assert_eq!(labels(&graph), ["New York", "boston", "chicago", "denver"]);
+        assert_eq!(graph.edges(), vec![(0, 1), (1, 2)]);
+
+        assert!(read_dot("a -- b".as_bytes()).is_err());
+        assert!(read_dot("graph { a -- }".as_bytes()).is_err());
+        assert!(read_dot("graph { subgraph { a -- b } }".as_bytes()).is_err());
+        assert!(read_dot("} graph {".as_bytes()).is_err());
+    }
+}
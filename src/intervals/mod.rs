@@ -0,0 +1,209 @@
+use crate::graph::adj_list::AdjList;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+/// A task that occupies a machine (or room) during `[start, end)`.
+#[derive(Debug, PartialEq)]
+pub struct Task {
+    pub name: String,
+    pub start: f64,
+    pub end: f64,
+    /// Kind of machine the task needs: only tasks with the same resource compete for machines.
+    pub resource: Option<String>,
+}
+
+/// Reads tasks from a CSV file with one `task,start,end[,resource]` line per task.
+///
+/// Blank lines, lines starting with `#` and a header starting with `task,` are skipped.
+pub fn read_tasks(reader: impl BufRead) -> Result<Vec<Task>, Box<dyn Error>> {
+    let mut tasks = Vec::new();
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line.starts_with("task,") {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let (name, start, end, resource) = match fields[..] {
+            [name, start, end] => (name, start, end, None),
+            [name, start, end, resource] => (name, start, end, Some(resource.to_string())),
+            _ => {
+                return Err(
+                    format!("line {} isn't a task,start,end[,resource] line", number + 1).into(),
+                )
+            }
+        };
+
+        let start: f64 = start.parse()?;
+        let end: f64 = end.parse()?;
+
+        if start.partial_cmp(&end) != Some(std::cmp::Ordering::Less) {
+            return Err(format!("task {name} doesn't end after it starts").into());
+        }
+
+        tasks.push(Task {
+            name: name.to_string(),
+            start,
+            end,
+            resource,
+        });
+    }
+
+    Ok(tasks)
+}
+
+/// Builds the conflict graph of the tasks: two tasks are adjacent if they need the same resource
+/// and their intervals overlap. Touching intervals (one ending when the other starts) don't.
+pub fn conflict_graph(tasks: &[Task]) -> AdjList {
+    let mut graph = AdjList::new(tasks.len());
+    let mut order: Vec<usize> = (0..tasks.len()).collect();
+    order.sort_by(|u, v| tasks[*u].start.total_cmp(&tasks[*v].start));
+
+    // Sweep in order of start: a task conflicts with the earlier ones still running
+    for (i, u) in order.iter().enumerate() {
+        for v in order[..i].iter() {
+            if tasks[*v].end > tasks[*u].start && tasks[*v].resource == tasks[*u].resource {
+                graph.add_edge(*v, *u);
+            }
+        }
+    }
+
+    graph
+}
+
+/// Minimum number of machines of each resource (with `None` for the tasks without one): the most
+/// tasks running at once, since interval graphs have a coloring as small as their largest clique.
+pub fn min_machines(tasks: &[Task]) -> BTreeMap<Option<String>, usize> {
+    let mut events: BTreeMap<Option<String>, Vec<(f64, isize)>> = BTreeMap::new();
+
+    for task in tasks {
+        let events = events.entry(task.resource.clone()).or_default();
+        events.push((task.start, 1));
+        events.push((task.end, -1));
+    }
+
+    events
+        .into_iter()
+        .map(|(resource, mut events)| {
+            // Ends come before starts at the same time, since touching intervals don't overlap
+            events.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+            let mut running = 0;
+            let mut most = 0;
+
+            for (_, change) in events {
+                running += change;
+                most = most.max(running);
+            }
+
+            (resource, most as usize)
+        })
+        .collect()
+}
+
+/// Writes the (1-indexed) machine of each task as `task,machine` lines, with a header.
+///
+/// Machines are numbered per resource, so tasks with different resources may share a number.
+pub fn write_schedule(
+    writer: &mut impl Write,
+    tasks: &[Task],
+    machines: &[usize],
+) -> io::Result<()> {
+    writeln!(writer, "task,machine")?;
+
+    for (task, machine) in tasks.iter().zip(machines) {
+        writeln!(writer, "{},{machine}", task.name)?;
+    }
+
+    Ok(())
+}
+
+/// Numbers the machines of `coloring` from 1 within each resource, in order of first appearance.
+pub fn machines_per_resource(tasks: &[Task], coloring: &[usize]) -> Vec<usize> {
+    let mut labels: BTreeMap<(Option<&String>, usize), usize> = BTreeMap::new();
+    let mut counts: BTreeMap<Option<&String>, usize> = BTreeMap::new();
+
+    tasks
+        .iter()
+        .zip(coloring)
+        .map(|(task, color)| {
+            let resource = task.resource.as_ref();
+
+            *labels.entry((resource, *color)).or_insert_with(|| {
+                let count = counts.entry(resource).or_default();
+                *count += 1;
+                *count
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "task,start,end,resource
+# The lathe is free between 10 and 11
+a,9,10,lathe
+b,9.5,12,lathe
+c,10,11,lathe
+d,11,13,lathe
+e,9,17
+f,10,11
+";
+
+    #[test]
+    fn test_read_tasks() {
+        let tasks = read_tasks(CSV.as_bytes()).unwrap();
+
+        assert_eq!(tasks.len(), 6);
+        assert_eq!(
+            tasks[1],
+            Task {
+                name: "b".to_string(),
+                start: 9.5,
+                end: 12.0,
+                resource: Some("lathe".to_string()),
+            }
+        );
+        assert_eq!(tasks[4].resource, None);
+
+        assert!(read_tasks("a,10,9\n".as_bytes()).is_err());
+        assert!(read_tasks("a,9\n".as_bytes()).is_err());
+        assert!(read_tasks("a,nine,10\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_conflict_graph() {
+        let tasks = read_tasks(CSV.as_bytes()).unwrap();
+        let mut edges = conflict_graph(&tasks).edges();
+        edges.sort();
+
+        // a and c, and c and d, only touch; e and f don't compete with the lathe tasks
+        assert_eq!(edges, vec![(0, 1), (1, 2), (1, 3), (4, 5)]);
+        assert_eq!(
+            min_machines(&tasks),
+            BTreeMap::from([(None, 2), (Some("lathe".to_string()), 2)])
+        );
+    }
+
+    #[test]
+    fn test_write_schedule() {
+        let tasks = read_tasks(CSV.as_bytes()).unwrap();
+        let machines = machines_per_resource(&tasks, &[3, 1, 3, 3, 1, 2]);
+
+        assert_eq!(machines, vec![1, 2, 1, 1, 1, 2]);
+
+        let mut buffer = Vec::new();
+        write_schedule(&mut buffer, &tasks[..2], &machines).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "task,machine\na,1\nb,2\n"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+pub mod algorithms;
+pub mod analyze;
+pub mod args;
+pub mod cache;
+#[cfg(feature = "tui")]
+pub mod dashboard;
+pub mod diff;
+pub mod distributed;
+pub mod export;
+pub mod fap;
+pub mod generate;
+pub mod graph;
+pub mod input;
+pub mod intervals;
+pub mod map;
+pub mod online;
+pub mod output;
+pub mod plot;
+pub mod progress;
+pub mod regalloc;
+pub mod regress;
+pub mod rng;
+pub mod roundrobin;
+pub mod rpc;
+pub mod sat;
+pub mod server;
+pub mod sparsity;
+pub mod sudoku;
+#[cfg(test)]
+mod testing;
+pub mod timetable;
+pub mod wavelength;
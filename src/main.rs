@@ -1,58 +1,1453 @@
-mod algorithms;
-mod args;
-mod graph;
-mod input;
+use gcp_heuristics::{
+    algorithms, analyze, args, cache, diff, distributed, export, fap, generate, graph, input,
+    intervals, map, online, output, plot, progress, regalloc, regress, rng, roundrobin, rpc, sat,
+    server, sparsity, sudoku, timetable, wavelength,
+};
 
-use algorithms::{genetic::genetic, grasp::grasp_wrapper, grasp_pr::grasp_path_relinking};
+#[cfg(feature = "ilp")]
+use algorithms::ilp::{ilp, IlpParams};
+#[cfg(feature = "sat")]
+use algorithms::sat::{sat, SatParams};
+use algorithms::{
+    aco::{aco, AcoParams},
+    bandit::{bandit, BanditParams},
+    cap_class_sizes, count_colors,
+    decision::{fixed_k, FixedKParams},
+    diverse::{diverse_pool, partition_distance},
+    genetic::{genetic, GeneticParams},
+    grasp::{grasp, grasp_wrapper, CandidateList, GraspParams},
+    grasp_pr::{grasp_path_relinking, PathRelinkingParams},
+    greedy::{greedy_portfolio, welsh_powell, GreedyPortfolioParams},
+    hea::{hea, HeaParams},
+    hybrid::grasp_ga,
+    is_coloring_valid,
+    lns::{lns, LnsParams},
+    memetic::{memetic, MemeticParams},
+    multilevel::{multilevel, MultilevelParams},
+    pareto::nsga2,
+    partialcol::{partial_col, PartialColParams},
+    partition::{partition_and_merge, PartitionParams},
+    portfolio::portfolio,
+    recolor::RecolorParams,
+    relabel_colors, solve_with_defaults,
+    tabucol::{tabu, TabuParams},
+    vns::{vns, VnsParams},
+    xrlf::{xrlf, XrlfParams},
+    zykov::{zykov, ZykovParams},
+};
+use analyze::{analyze, recommend};
 use args::Algorithm;
 use args::Args;
-use clap::Parser;
+use args::Command;
+use args::CoordinateArgs;
+use args::ExportQuboArgs;
+use args::ExportSatArgs;
+use args::FapArgs;
+use args::IntervalsArgs;
+use args::MapArgs;
+use args::OnlineArgs;
+#[cfg(feature = "plot")]
+use args::PlotArgs;
+use args::RegallocArgs;
+use args::RoundRobinArgs;
+use args::SparsityArgs;
+use args::SudokuArgs;
+use args::TimetableArgs;
+use args::WavelengthArgs;
+use clap::{Parser, ValueEnum};
+use export::qubo::{self, QuboFormat};
+#[cfg(feature = "tui")]
+use gcp_heuristics::dashboard;
+use graph::adj_list::AdjList;
+use graph::bounds::{brooks_bound, degeneracy_ordering, greedy_coloring, hoffman_bound};
+use graph::clique::greedy_clique;
+use graph::easy::easy_coloring;
+use graph::fractional::fractional_bound;
+use output::SolutionFormat;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
 use std::process;
-use std::time::Instant;
+#[cfg(feature = "tui")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "tui")]
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Exit code used when an algorithm returns an invalid solution, to tell it apart from bad input.
+const INVALID_SOLUTION_EXIT_CODE: i32 = 2;
 
 fn main() {
     let Args {
+        command,
         algorithm,
         path,
+        skip_lower_bound,
+        fractional_bound: fractional_rounds,
+        seed,
         pr_solutions,
+        pr_direction,
+        pr_local_search_iterations,
+        pr_truncation,
+        pr_min_distance,
+        pr_evolutionary,
+        hybrid_pr,
+        bandit_budget,
+        bandit_epochs,
+        xrlf_iterations,
+        xrlf_trials,
+        xrlf_candidates,
+        xrlf_exact_limit,
+        tabucol_iterations,
+        #[cfg(feature = "sat")]
+        sat_conflicts,
+        aco_ants,
+        aco_iterations,
+        aco_alpha,
+        aco_beta,
+        aco_evaporation,
+        hea_population,
+        hea_generations,
+        hea_tabu_iterations,
+        vns_iterations,
+        lns_iterations,
+        lns_destroy_fraction,
+        lns_destroy,
+        lns_acceptance,
+        partialcol_iterations,
+        greedy_random_orderings,
+        zykov_nodes,
+        multilevel_algorithm,
+        multilevel_coarsest,
+        multilevel_refine_iterations,
+        fixed_k_solver,
+        fixed_k_search,
+        fixed_k_iterations,
+        #[cfg(feature = "ilp")]
+        ilp_time_limit,
+        partitions,
+        merge_iterations,
         grasp_iterations,
         color_iterations,
         color_list_size,
+        rcl_alpha,
+        reactive,
+        construction,
+        grasp_memory,
+        merge,
+        improvement,
+        tabu_iterations,
+        no_improvement_factor,
+        local_search_iterations,
+        kempe_moves,
+        merge_retries,
+        perturbation_restarts,
+        improvement_merges,
+        improvement_time,
+        grasp_iteration_timeout,
         generations,
         population_size,
         offspring_size,
         mutation_probaility,
         population_ratio,
+        genetic_mode,
+        memetic_generations,
+        memetic_tabu_iterations,
+        pareto,
+        repair_strategy,
+        crossover,
+        dump_population,
+        load_population,
+        initial_solution,
+        groups,
+        group_label,
+        groups_output,
+        output_format,
+        max_group_size,
+        diverse,
+        diverse_tolerance,
+        diverse_min_distance,
+        #[cfg(feature = "tui")]
+        tui,
+        #[cfg(feature = "plot")]
+        plot,
+        trace,
+        explain,
+        metrics,
+        stdio,
+        cached,
+        cache_dir,
     } = Args::parse();
 
+    match command {
+        Some(Command::Generate(args)) => {
+            if let Err(err) = generate::generate(&args) {
+                eprintln!("Failed to generate the instance: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Bounds(args)) => {
+            if let Ok(Some(graph)) = input::read_graph_from_file(&args.path) {
+                print_bounds(&graph, args.fractional);
+            } else {
+                eprintln!("Failed to open the specified instance: {}", args.path);
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Analyze(args)) => {
+            if let Ok(Some(graph)) = input::read_graph_from_file(&args.path) {
+                print_analysis(&graph, args.orderings.unwrap_or(100));
+            } else {
+                eprintln!("Failed to open the specified instance: {}", args.path);
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Regress(args)) => {
+            let baselines = args
+                .baselines
+                .unwrap_or_else(|| String::from("data/baselines.json"));
+
+            match regress::regress(
+                &baselines,
+                args.tolerance.unwrap_or(0),
+                args.runs.unwrap_or(3),
+                args.update,
+            ) {
+                Ok(true) => {}
+                Ok(false) => {
+                    eprintln!("Some results are worse than the baselines");
+                    process::exit(1);
+                }
+                Err(err) => {
+                    eprintln!("Failed to run the regression suite: {err}");
+                    process::exit(1);
+                }
+            }
+
+            return;
+        }
+        Some(Command::Diff(args)) => {
+            if let Ok(Some(graph)) = input::read_graph_from_file(&args.path) {
+                let read = |file: &str| {
+                    input::read_coloring_from_file(file, &graph).unwrap_or_else(|err| {
+                        eprintln!("Failed to read the coloring from {file}: {err}");
+                        process::exit(1);
+                    })
+                };
+
+                print_diff(&graph, &read(&args.first), &read(&args.second));
+            } else {
+                eprintln!("Failed to open the specified instance: {}", args.path);
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Timetable(args)) => {
+            if let Err(err) = run_timetable(&args) {
+                eprintln!("Failed to build the timetable: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Regalloc(args)) => {
+            if let Err(err) = run_regalloc(&args) {
+                eprintln!("Failed to allocate the registers: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Sudoku(args)) => {
+            if let Err(err) = run_sudoku(&args) {
+                eprintln!("Failed to solve the puzzle: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Map(args)) => {
+            if let Err(err) = run_map(&args) {
+                eprintln!("Failed to color the map: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Fap(args)) => {
+            if let Err(err) = run_fap(&args) {
+                eprintln!("Failed to assign the frequencies: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Intervals(args)) => {
+            if let Err(err) = run_intervals(&args) {
+                eprintln!("Failed to assign the machines: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Sparsity(args)) => {
+            if let Err(err) = run_sparsity(&args) {
+                eprintln!("Failed to compress the matrix: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Wavelength(args)) => {
+            if let Err(err) = run_wavelength(&args) {
+                eprintln!("Failed to assign the wavelengths: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::RoundRobin(args)) => {
+            if let Err(err) = run_round_robin(&args) {
+                eprintln!("Failed to schedule the tournament: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Serve(args)) => {
+            println!("Listening on {}:{}", args.host, args.port);
+
+            if let Err(err) = server::serve(&args.host, args.port) {
+                eprintln!("Failed to serve: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Worker(args)) => {
+            let listener = std::net::TcpListener::bind(&args.listen).unwrap_or_else(|err| {
+                eprintln!("Failed to listen on {}: {err}", args.listen);
+                process::exit(1);
+            });
+            println!("Listening on {}", args.listen);
+
+            if let Err(err) = distributed::work(listener) {
+                eprintln!("Failed to work: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Coordinate(args)) => {
+            if let Err(err) = run_coordinator(&args) {
+                eprintln!("Failed to coordinate the workers: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        #[cfg(feature = "plot")]
+        Some(Command::Plot(args)) => {
+            if let Err(err) = run_plot(&args) {
+                eprintln!("Failed to plot the traces: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::Online(args)) => {
+            if let Err(err) = run_online(&args) {
+                eprintln!("Failed to color the graph: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::ExportSat(args)) => {
+            if let Err(err) = run_export_sat(&args) {
+                eprintln!("Failed to export the formula: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        Some(Command::ExportQubo(args)) => {
+            if let Err(err) = run_export_qubo(&args) {
+                eprintln!("Failed to export the QUBO: {err}");
+                process::exit(1);
+            }
+
+            return;
+        }
+        None => {}
+    }
+
+    if stdio {
+        if let Err(err) = rpc::serve(io::stdin().lock(), &mut io::stdout().lock()) {
+            eprintln!("Failed to answer the requests: {err}");
+            process::exit(1);
+        }
+
+        return;
+    }
+
+    // Both are required when there's no subcommand, unless serving over stdio
+    let (Some(path), Some(algorithm)) = (path, algorithm) else {
+        unreachable!()
+    };
+
+    if let Some(seed) = seed {
+        rng::reseed(seed);
+    }
+
     if let Ok(Some(graph)) = input::read_graph_from_file(path.as_str()) {
+        let lower_bound = (!skip_lower_bound).then(|| {
+            let clique = greedy_clique(&graph).len();
+
+            fractional_rounds.map_or(clique, |rounds| {
+                clique.max(fractional_bound(&graph, rounds).lower_bound)
+            })
+        });
+        let grasp_params = GraspParams {
+            grasp_iterations: grasp_iterations.unwrap_or(25),
+            color_iterations: color_iterations.unwrap_or(25),
+            candidate_list: match rcl_alpha {
+                Some(alpha) => CandidateList::Threshold(alpha),
+                None => CandidateList::Cardinality(color_list_size.unwrap_or(3)),
+            },
+            construction: construction.unwrap_or_default(),
+            memory: grasp_memory,
+            reactive,
+            merge: merge.unwrap_or_default(),
+            improvement: improvement.unwrap_or_default(),
+            tabu_iterations: tabu_iterations.unwrap_or(1000),
+            no_improvement_factor: no_improvement_factor.unwrap_or(2),
+            local_search_iterations,
+            kempe_moves,
+            merge_retries: merge_retries.unwrap_or(0),
+            perturbation_restarts: perturbation_restarts.unwrap_or(0),
+            improvement_merges,
+            improvement_time: improvement_time.map(Duration::from_secs_f64),
+            iteration_time: grasp_iteration_timeout.map(Duration::from_secs_f64),
+            seed,
+            lower_bound,
+            max_class_size: max_group_size,
+        };
+        let genetic_params = GeneticParams {
+            generations: generations.unwrap_or(80000),
+            population_size: population_size.unwrap_or(100),
+            offsprings_per_generation: offspring_size.unwrap_or(2),
+            mutation_probability: mutation_probaility.unwrap_or(0.01),
+            selected_population_ratio: population_ratio.unwrap_or(0.2),
+            mode: genetic_mode.unwrap_or_default(),
+            repair_strategy: repair_strategy.unwrap_or_default(),
+            crossover: crossover.unwrap_or_default(),
+            lower_bound,
+        };
+        let pr_params = PathRelinkingParams {
+            num_solutions: pr_solutions.unwrap_or(10),
+            direction: pr_direction.unwrap_or_default(),
+            local_search_iterations: pr_local_search_iterations.unwrap_or(0),
+            truncation: pr_truncation.unwrap_or(1.0),
+            min_distance: pr_min_distance.unwrap_or(1),
+            evolutionary: pr_evolutionary,
+        };
+        let bandit_params = BanditParams {
+            budget: Duration::from_secs_f64(bandit_budget.unwrap_or(5.0)),
+            epochs: bandit_epochs.unwrap_or(10),
+            lower_bound,
+        };
+
+        let xrlf_params = XrlfParams {
+            iterations: xrlf_iterations.unwrap_or(10),
+            trials: xrlf_trials.unwrap_or(20),
+            candidates: xrlf_candidates.unwrap_or(50),
+            exact_limit: xrlf_exact_limit.unwrap_or(20),
+            lower_bound,
+        };
+        let tabu_params = TabuParams {
+            iterations: tabucol_iterations.unwrap_or(10000),
+            lower_bound,
+        };
+        #[cfg(feature = "sat")]
+        let sat_params = SatParams {
+            max_conflicts: sat_conflicts,
+            lower_bound,
+        };
+        let hea_params = HeaParams {
+            population_size: hea_population.unwrap_or(10),
+            generations: hea_generations.unwrap_or(1000),
+            tabu_iterations: hea_tabu_iterations.unwrap_or(2000),
+            lower_bound,
+        };
+        let vns_params = VnsParams {
+            iterations: vns_iterations.unwrap_or(10000),
+            lower_bound,
+        };
+        let lns_params = LnsParams {
+            iterations: lns_iterations.unwrap_or(10000),
+            destroy_fraction: lns_destroy_fraction.unwrap_or(0.1),
+            destroy: lns_destroy.unwrap_or_default(),
+            acceptance: lns_acceptance.unwrap_or_default(),
+            lower_bound,
+        };
+        let greedy_portfolio_params = GreedyPortfolioParams {
+            random_orderings: greedy_random_orderings.unwrap_or(100),
+        };
+        let zykov_params = ZykovParams {
+            max_nodes: zykov_nodes,
+            lower_bound,
+        };
+        let multilevel_params = MultilevelParams {
+            algorithm: multilevel_algorithm.unwrap_or_default(),
+            coarsest_size: multilevel_coarsest.unwrap_or(500),
+            refine_iterations: multilevel_refine_iterations.unwrap_or(1000),
+            lower_bound,
+        };
+        let fixed_k_params = FixedKParams {
+            decider: fixed_k_solver.unwrap_or_default(),
+            search: fixed_k_search.unwrap_or_default(),
+            iterations: fixed_k_iterations.unwrap_or(10000),
+            #[cfg(feature = "sat")]
+            max_conflicts: sat_conflicts,
+            lower_bound,
+        };
+        #[cfg(feature = "ilp")]
+        let ilp_params = IlpParams {
+            time_limit: ilp_time_limit.map(Duration::from_secs_f64),
+            lower_bound,
+        };
+        let partialcol_params = PartialColParams {
+            iterations: partialcol_iterations.unwrap_or(10000),
+            lower_bound,
+        };
+        let memetic_params = MemeticParams {
+            generations: memetic_generations.unwrap_or(1000),
+            tabu_iterations: memetic_tabu_iterations.unwrap_or(1000),
+        };
+        let aco_params = AcoParams {
+            ants: aco_ants.unwrap_or(10),
+            iterations: aco_iterations.unwrap_or(100),
+            alpha: aco_alpha.unwrap_or(1.0),
+            beta: aco_beta.unwrap_or(2.0),
+            evaporation: aco_evaporation.unwrap_or(0.1),
+            lower_bound,
+        };
+
+        if let Some(objective) = pareto {
+            if !matches!(algorithm, Algorithm::Genetic) {
+                eprintln!("--pareto requires -a genetic");
+                process::exit(1);
+            }
+
+            progress::PROGRESS.reset();
+            let start = Instant::now();
+            let front = nsga2(&graph, &genetic_params, objective);
+            let duration = start.elapsed().as_millis();
+
+            if let Some(member) = front
+                .iter()
+                .find(|member| !is_coloring_valid(&graph, &member.coloring))
+            {
+                eprintln!(
+                    "ERROR: NSGA-II returned an invalid coloring: {:?}",
+                    member.coloring
+                );
+                process::exit(INVALID_SOLUTION_EXIT_CODE);
+            }
+
+            let name = objective.to_possible_value().unwrap();
+            println!("Pareto front:");
+            for member in front {
+                println!(
+                    "{} colors, {} {}: {}",
+                    member.num_colors,
+                    name.get_name(),
+                    member.objective,
+                    output::format_coloring(&graph, &member.coloring)
+                );
+            }
+            println!("Duration: {:?}", duration);
+
+            return;
+        }
+
+        let cache_dir = cache_dir.unwrap_or_else(|| cache::DEFAULT_DIRECTORY.to_string());
+        let cache_key = cached.then(|| {
+            let read = |file: &str| {
+                std::fs::read(file).unwrap_or_else(|err| {
+                    eprintln!("Failed to read {file}: {err}");
+                    process::exit(1);
+                })
+            };
+            let params = match algorithm {
+                Algorithm::Genetic => format!("{genetic_params:?}"),
+                Algorithm::Grasp => format!("{grasp_params:?}"),
+                Algorithm::GraspPR => format!("{grasp_params:?} {pr_params:?}"),
+                Algorithm::GraspGA => format!(
+                    "{grasp_params:?} {genetic_params:?} {:?}",
+                    hybrid_pr.then_some(&pr_params)
+                ),
+                Algorithm::Bandit => format!("{bandit_params:?}"),
+                Algorithm::Xrlf => format!("{xrlf_params:?}"),
+                Algorithm::Portfolio => format!("{grasp_params:?} {genetic_params:?}"),
+                Algorithm::Tabu => format!("{tabu_params:?}"),
+                #[cfg(feature = "sat")]
+                Algorithm::Sat => format!("{sat_params:?}"),
+                Algorithm::Aco => format!("{aco_params:?}"),
+                Algorithm::Memetic => format!("{genetic_params:?} {memetic_params:?}"),
+                Algorithm::Hea => format!("{hea_params:?}"),
+                Algorithm::Vns => format!("{vns_params:?}"),
+                Algorithm::Lns => format!("{lns_params:?}"),
+                Algorithm::PartialCol => format!("{partialcol_params:?}"),
+                Algorithm::Greedy => String::new(),
+                Algorithm::GreedyPortfolio => format!("{greedy_portfolio_params:?}"),
+                Algorithm::Zykov => format!("{zykov_params:?}"),
+                Algorithm::Multilevel => format!("{multilevel_params:?}"),
+                Algorithm::FixedK => format!("{fixed_k_params:?}"),
+                #[cfg(feature = "ilp")]
+                Algorithm::Ilp => format!("{ilp_params:?}"),
+            };
+            let population = load_population
+                .as_deref()
+                .map_or(0, |file| cache::fnv1a(&read(file)));
+            let initial = initial_solution
+                .as_deref()
+                .map_or(0, |file| cache::fnv1a(&read(file)));
+            let params = format!(
+                "{params} population={population:016x} initial={initial:016x} partitions={partitions:?} merge={merge_iterations:?} seed={seed:?} max_group_size={max_group_size:?}"
+            );
+
+            cache::key(&read(&path), &algorithm, &params)
+        });
+        let cached_entry = cache_key
+            .as_deref()
+            .and_then(|key| cache::load(&cache_dir, key, &graph));
+
         let start = Instant::now();
 
-        let (num_colors, coloring) = match algorithm {
-            Algorithm::Genetic => genetic(
-                &graph,
-                generations.unwrap_or(80000),
-                population_size.unwrap_or(100),
-                offspring_size.unwrap_or(2),
-                mutation_probaility.unwrap_or(0.01),
-                population_ratio.unwrap_or(0.2),
-            ),
-            Algorithm::Grasp => grasp_wrapper(
-                &graph,
-                grasp_iterations.unwrap_or(25),
-                color_iterations.unwrap_or(25),
-                color_list_size.unwrap_or(3),
+        progress::PROGRESS.reset();
+        if let Some(address) = metrics {
+            let listener = std::net::TcpListener::bind(&address).unwrap_or_else(|err| {
+                eprintln!("Failed to listen on {address}: {err}");
+                process::exit(1);
+            });
+
+            thread::spawn(move || server::serve_metrics(listener));
+        }
+        #[cfg(feature = "tui")]
+        let stop_dashboard = Arc::new(AtomicBool::new(false));
+        #[cfg(feature = "tui")]
+        let dashboard = tui.then(|| {
+            let title = format!("{algorithm:?} on {path}");
+            let stop = Arc::clone(&stop_dashboard);
+
+            thread::spawn(move || dashboard::run(&title, &stop))
+        });
+
+        let initial_population = load_population
+            .filter(|_| cached_entry.is_none())
+            .map(|file| {
+                input::read_population_from_file(&file, graph.num_vertices()).unwrap_or_else(
+                    |err| {
+                        eprintln!("Failed to load the population from {file}: {err}");
+                        process::exit(1);
+                    },
+                )
+            });
+        let initial_coloring = initial_solution
+            .filter(|_| cached_entry.is_none())
+            .map(|file| {
+                input::read_coloring_from_file(&file, &graph).unwrap_or_else(|err| {
+                    eprintln!("Failed to load the initial solution from {file}: {err}");
+                    process::exit(1);
+                })
+            });
+        let initial_population = match &initial_coloring {
+            Some(initial) => Some(
+                [initial.clone()]
+                    .into_iter()
+                    .chain(initial_population.unwrap_or_default())
+                    .collect(),
             ),
-            Algorithm::GraspPR => grasp_path_relinking(&graph, pr_solutions.unwrap_or(10)),
+            None => initial_population,
         };
 
-        let duration = start.elapsed().as_millis();
+        // Easy instances (such as bipartite or chordal ones) are colored optimally at once, so the
+        // heuristics are skipped
+        let easy_coloring = easy_coloring(&graph);
+        if let Some((case, _)) = &easy_coloring {
+            match max_group_size {
+                Some(max_group_size) => eprintln!(
+                    "The instance is {}: coloring it without {algorithm:?}, then splitting the groups larger than {max_group_size}",
+                    case.description()
+                ),
+                None => eprintln!(
+                    "The instance is {}: coloring it optimally without {algorithm:?}",
+                    case.description()
+                ),
+            }
+        }
+        // Taken before any capping, which may need more colors than χ
+        let easy_lower_bound = easy_coloring
+            .as_ref()
+            .map(|(_, coloring)| count_colors(coloring));
+
+        let mut final_population: Vec<Vec<usize>> = Vec::new();
+        // Lower bound proven by the exact algorithms, which may beat the clique one
+        let mut proven_lower_bound = None;
+        let mut best_order = None;
+        let (mut num_colors, mut coloring) = match (&cached_entry, &easy_coloring) {
+            (Some(entry), _) => (entry.num_colors, entry.coloring.clone()),
+            (None, Some((_, coloring))) => (count_colors(coloring), coloring.clone()),
+            (None, None) if partitions.is_some() => {
+                let params = PartitionParams {
+                    num_parts: partitions.unwrap(),
+                    merge_iterations: merge_iterations.unwrap_or(10000),
+                };
+
+                partition_and_merge(&graph, &params, |part| match algorithm {
+                    Algorithm::Genetic => genetic(part, &genetic_params, None).0,
+                    Algorithm::Grasp => grasp_wrapper(part, &grasp_params),
+                    Algorithm::GraspPR => grasp_path_relinking(part, &grasp_params, &pr_params),
+                    Algorithm::GraspGA => grasp_ga(
+                        part,
+                        &grasp_params,
+                        &genetic_params,
+                        hybrid_pr.then_some(&pr_params),
+                        None,
+                    ),
+                    Algorithm::Bandit => bandit(part, &bandit_params).0,
+                    Algorithm::Xrlf => xrlf(part, &xrlf_params),
+                    Algorithm::Portfolio => portfolio(part, &grasp_params, &genetic_params),
+                    Algorithm::Tabu => tabu(part, &tabu_params),
+                    #[cfg(feature = "sat")]
+                    Algorithm::Sat => sat(part, &sat_params).0,
+                    Algorithm::Aco => aco(part, &aco_params),
+                    Algorithm::Memetic => memetic(part, &genetic_params, &memetic_params, None),
+                    Algorithm::Hea => hea(part, &hea_params),
+                    Algorithm::Vns => vns(part, &vns_params),
+                    Algorithm::Lns => lns(part, &lns_params),
+                    Algorithm::PartialCol => partial_col(part, &partialcol_params),
+                    Algorithm::Greedy => welsh_powell(part),
+                    Algorithm::GreedyPortfolio => {
+                        greedy_portfolio(part, &greedy_portfolio_params).0
+                    }
+                    Algorithm::Zykov => zykov(part, &zykov_params).0,
+                    Algorithm::Multilevel => multilevel(part, &multilevel_params),
+                    Algorithm::FixedK => fixed_k(part, &fixed_k_params).0,
+                    #[cfg(feature = "ilp")]
+                    Algorithm::Ilp => ilp(part, &ilp_params).0,
+                })
+            }
+            (None, None) => match algorithm {
+                Algorithm::Genetic => {
+                    let (solution, population) =
+                        genetic(&graph, &genetic_params, initial_population);
+
+                    final_population = population
+                        .individuals
+                        .iter()
+                        .map(|individual| individual.coloring.clone())
+                        .collect();
+
+                    if let Some(file) = dump_population {
+                        if let Err(err) = output::write_population_to_file(&file, &population) {
+                            eprintln!("Failed to write the population to {file}: {err}");
+                        }
+                    }
+
+                    solution
+                }
+                Algorithm::Grasp => grasp_wrapper(&graph, &grasp_params),
+                Algorithm::GraspPR => grasp_path_relinking(&graph, &grasp_params, &pr_params),
+                Algorithm::GraspGA => grasp_ga(
+                    &graph,
+                    &grasp_params,
+                    &genetic_params,
+                    hybrid_pr.then_some(&pr_params),
+                    initial_population,
+                ),
+                Algorithm::Bandit => bandit(&graph, &bandit_params).0,
+                Algorithm::Xrlf => xrlf(&graph, &xrlf_params),
+                Algorithm::Portfolio => portfolio(&graph, &grasp_params, &genetic_params),
+                Algorithm::Tabu => tabu(&graph, &tabu_params),
+                #[cfg(feature = "sat")]
+                Algorithm::Sat => {
+                    let (solution, proven) = sat(&graph, &sat_params);
+
+                    proven_lower_bound = Some(proven);
+                    solution
+                }
+                Algorithm::Aco => aco(&graph, &aco_params),
+                Algorithm::Memetic => {
+                    memetic(&graph, &genetic_params, &memetic_params, initial_population)
+                }
+                Algorithm::Hea => hea(&graph, &hea_params),
+                Algorithm::Vns => vns(&graph, &vns_params),
+                Algorithm::Lns => lns(&graph, &lns_params),
+                Algorithm::PartialCol => partial_col(&graph, &partialcol_params),
+                Algorithm::Greedy => welsh_powell(&graph),
+                Algorithm::GreedyPortfolio => {
+                    let (solution, order) = greedy_portfolio(&graph, &greedy_portfolio_params);
+
+                    best_order = Some(order);
+                    solution
+                }
+                Algorithm::Zykov => {
+                    let (solution, proven) = zykov(&graph, &zykov_params);
+
+                    proven_lower_bound = Some(proven);
+                    solution
+                }
+                Algorithm::Multilevel => multilevel(&graph, &multilevel_params),
+                Algorithm::FixedK => {
+                    let (solution, proven) = fixed_k(&graph, &fixed_k_params);
+
+                    proven_lower_bound = Some(proven);
+                    solution
+                }
+                #[cfg(feature = "ilp")]
+                Algorithm::Ilp => {
+                    let (solution, proven) = ilp(&graph, &ilp_params);
+
+                    proven_lower_bound = Some(proven);
+                    solution
+                }
+            },
+        };
+
+        if let Some(initial) = initial_coloring {
+            if is_coloring_valid(&graph, &initial) && count_colors(&initial) < num_colors {
+                num_colors = count_colors(&initial);
+                coloring = initial;
+            }
+        }
+
+        // Capped before the checks, so a capped coloring is verified like any other
+        if let Some(max_group_size) = max_group_size {
+            num_colors = cap_class_sizes(&graph, &mut coloring, max_group_size);
+        }
+
+        let duration = match &cached_entry {
+            Some(entry) => entry.duration,
+            None => start.elapsed().as_millis(),
+        };
+
+        #[cfg(feature = "tui")]
+        if let Some(dashboard) = dashboard {
+            stop_dashboard.store(true, Ordering::Relaxed);
+            if let Err(err) = dashboard.join().unwrap() {
+                eprintln!("Failed to draw the dashboard: {err}");
+            }
+        }
+
+        let run_trace = || {
+            plot::Trace::from_snapshot(
+                &format!("{algorithm:?} on {path}"),
+                &progress::PROGRESS.snapshot(),
+            )
+        };
+        if let Some(file) = trace {
+            if let Err(err) = plot::write_trace_to_file(&file, &run_trace()) {
+                eprintln!("Failed to write the trace to {file}: {err}");
+            }
+        }
+        #[cfg(feature = "plot")]
+        if let Some(file) = plot {
+            if let Err(err) = plot::write_plot_to_file(&file, &[run_trace()]) {
+                eprintln!("Failed to write the plot to {file}: {err}");
+            }
+        }
+
+        if coloring.len() != graph.num_vertices() || !is_coloring_valid(&graph, &coloring) {
+            eprintln!(
+                "ERROR: the {algorithm:?} algorithm returned an invalid coloring: {coloring:?}"
+            );
+            process::exit(INVALID_SOLUTION_EXIT_CODE);
+        }
+        if count_colors(&coloring) != num_colors {
+            eprintln!(
+                "ERROR: the {algorithm:?} algorithm reported {num_colors} colors, but its coloring uses {}",
+                count_colors(&coloring)
+            );
+            process::exit(INVALID_SOLUTION_EXIT_CODE);
+        }
+
+        // Runs that find the same partition print (and cache) the same coloring
+        relabel_colors(&mut coloring);
+
+        match (&cache_key, &cached_entry) {
+            (Some(key), Some(_)) => eprintln!("Using the cached result {key}"),
+            (Some(key), None) => {
+                let entry = cache::Entry {
+                    num_colors,
+                    coloring: coloring.clone(),
+                    duration,
+                };
+
+                if let Err(err) = cache::store(&cache_dir, key, &entry) {
+                    eprintln!("Failed to cache the result in {cache_dir}: {err}");
+                }
+            }
+            _ => {}
+        }
+
+        let alternatives = diverse.map(|count| {
+            let pool = if final_population.is_empty() {
+                grasp(&graph, &grasp_params, count * 5)
+                    .into_iter()
+                    .map(|(_, coloring)| coloring)
+                    .collect()
+            } else {
+                final_population
+            };
+            let candidates = [(num_colors, coloring.clone())]
+                .into_iter()
+                .chain(
+                    pool.into_iter()
+                        .filter(|coloring| is_coloring_valid(&graph, coloring))
+                        .map(|mut coloring| match max_group_size {
+                            Some(max_group_size) => (
+                                cap_class_sizes(&graph, &mut coloring, max_group_size),
+                                coloring,
+                            ),
+                            None => (count_colors(&coloring), coloring),
+                        }),
+                )
+                .collect();
+
+            diverse_pool(
+                candidates,
+                count,
+                diverse_tolerance.unwrap_or(0),
+                diverse_min_distance.unwrap_or(1),
+            )
+        });
+
+        let lower_bound = easy_lower_bound.or(proven_lower_bound.max(lower_bound));
 
         println!("Number of colors used: {:?}", num_colors);
-        println!("Color assignment: {:?}", coloring);
+        let groups_list = (groups || groups_output.is_some()).then(|| {
+            output::groups::groups(
+                &coloring,
+                group_label.as_deref().unwrap_or("Group"),
+                graph.labels(),
+            )
+        });
+        match &groups_list {
+            Some(groups_list) if groups => {
+                output::groups::write_groups(&mut io::stdout().lock(), groups_list).unwrap()
+            }
+            _ => match output_format.unwrap_or_default() {
+                SolutionFormat::Assignment => println!(
+                    "Color assignment: {}",
+                    output::format_coloring(&graph, &coloring)
+                ),
+                SolutionFormat::Partition => {
+                    println!("{}", output::format_partition(&graph, &coloring))
+                }
+            },
+        }
         println!("Duration: {:?}", duration);
+
+        if let (Some(file), Some(groups_list)) = (groups_output, &groups_list) {
+            if let Err(err) = output::write_groups_to_file(&file, groups_list) {
+                eprintln!("Failed to write the groups to {file}: {err}");
+            }
+        }
+
+        if let Some(lower_bound) = lower_bound {
+            // A capped coloring is only optimal among the ones with small enough groups
+            if lower_bound == num_colors && max_group_size.is_none() {
+                println!("Chromatic number bounds: {lower_bound} ≤ χ ≤ {num_colors} (optimal)");
+            } else {
+                println!("Chromatic number bounds: {lower_bound} ≤ χ ≤ {num_colors}");
+            }
+        }
+
+        if let Some(order) = best_order {
+            println!("Best vertex order: {}", order.name());
+        }
+
+        if explain {
+            print_explanation(&progress::PROGRESS.attributed_history());
+        }
+
+        if let Some(alternatives) = alternatives {
+            println!("Diverse colorings:");
+            for (alternative_colors, alternative) in alternatives.iter() {
+                println!(
+                    "{alternative_colors} colors, distance {} from the first: {}",
+                    partition_distance(&alternatives[0].1, alternative),
+                    output::format_coloring(&graph, alternative)
+                );
+            }
+        }
     } else {
         eprintln!("Failed to open the specified instance: {path}");
         process::exit(1);
     }
 }
+
+fn print_bounds(graph: &AdjList, fractional_rounds: Option<usize>) {
+    let clique = greedy_clique(graph).len();
+    let hoffman = hoffman_bound(graph);
+    let brooks = brooks_bound(graph);
+    let (degeneracy, ordering) = degeneracy_ordering(graph);
+    let greedy = greedy_coloring(graph, &ordering)
+        .into_iter()
+        .max()
+        .unwrap_or(0);
+
+    println!("Clique (lower bound): {clique}");
+    println!("Hoffman (lower bound): {hoffman}");
+    println!("Brooks (upper bound): {brooks}");
+    println!("Degeneracy + 1 (upper bound): {}", degeneracy + 1);
+    println!("Greedy (upper bound): {greedy}");
+
+    let fractional = fractional_rounds.map(|rounds| fractional_bound(graph, rounds));
+    if let Some(fractional) = &fractional {
+        println!(
+            "Fractional (lower bound): {} (LP value {:.3} over {} independent sets)",
+            fractional.lower_bound, fractional.lp_value, fractional.num_columns
+        );
+    }
+
+    let lower_bound = clique
+        .max(hoffman)
+        .max(fractional.map_or(0, |fractional| fractional.lower_bound));
+    let upper_bound = brooks.min(degeneracy + 1).min(greedy);
+
+    println!("Chromatic number bounds: {lower_bound} ≤ χ ≤ {upper_bound}");
+}
+
+fn print_explanation(history: &[(Duration, usize, Option<progress::Phase>)]) {
+    let description =
+        |phase: Option<progress::Phase>| phase.map_or("unattributed", |phase| phase.description());
+
+    println!("Improvements:");
+    for (elapsed, num_colors, phase) in history {
+        println!(
+            "{elapsed:?}: {num_colors} colors by {}",
+            description(*phase)
+        );
+    }
+
+    println!("Improvements by phase:");
+    for (phase, count) in progress::improvements_by_phase(history) {
+        if phase.is_some() || count > 0 {
+            println!("{}: {count}", description(phase));
+        }
+    }
+}
+
+fn print_analysis(graph: &AdjList, num_orderings: usize) {
+    let analysis = analyze(graph, num_orderings);
+    let recommendation = recommend(&analysis);
+    let algorithm = recommendation.algorithm.to_possible_value().unwrap();
+
+    println!("Vertices: {}", analysis.num_vertices);
+    println!("Edges: {}", analysis.num_edges);
+    println!("Density: {:.3}", analysis.density);
+    println!("Clique (lower bound): {}", analysis.clique);
+    println!("Degeneracy: {}", analysis.degeneracy);
+    println!(
+        "Greedy colors over {num_orderings} random orderings: min {}, max {}, mean {:.2}, std {:.2}",
+        analysis.greedy_min, analysis.greedy_max, analysis.greedy_mean, analysis.greedy_std
+    );
+    println!(
+        "Recommendation: -a {} {}",
+        algorithm.get_name(),
+        recommendation.flags
+    );
+    println!("Reason: {}", recommendation.reason);
+}
+
+fn print_diff(graph: &AdjList, first: &[usize], second: &[usize]) {
+    let diff = diff::diff(first, second);
+    let (first_colors, second_colors) = diff.num_colors;
+
+    for (name, coloring) in [("first", first), ("second", second)] {
+        if !is_coloring_valid(graph, coloring) {
+            println!("Warning: the {name} coloring isn't valid");
+        }
+    }
+
+    println!(
+        "Number of colors: {first_colors} vs {second_colors} ({:+})",
+        second_colors as isize - first_colors as isize
+    );
+    println!(
+        "Same partition: {}",
+        if diff.same_partition { "yes" } else { "no" }
+    );
+    println!("Partition distance: {}", diff.partition_distance);
+    println!("Class overlaps (first -> second, shared vertices):");
+    for (color, overlaps) in diff.overlaps.iter() {
+        let overlaps: Vec<String> = overlaps
+            .iter()
+            .map(|(other_color, shared)| format!("{other_color} ({shared})"))
+            .collect();
+        println!("  {color} -> {}", overlaps.join(", "));
+    }
+    // Vertices are 1-indexed, as in the instance
+    let moved: Vec<String> = diff.moved.iter().map(|v| (v + 1).to_string()).collect();
+    println!("Moved vertices: {}", moved.join(" "));
+}
+
+fn run_timetable(args: &TimetableArgs) -> Result<(), Box<dyn Error>> {
+    let file = File::open(&args.enrollments)?;
+    let enrollments = timetable::read_enrollments(BufReader::new(file))?;
+    let graph = timetable::conflict_graph(&enrollments);
+    let lower_bound = greedy_clique(&graph).len();
+    let algorithm = args.algorithm.clone().unwrap_or_default();
+
+    let (_, mut timeslots) = solve_with_defaults(&graph, &algorithm, Some(lower_bound));
+    relabel_colors(&mut timeslots);
+
+    let statistics = timetable::statistics(&enrollments, &timeslots);
+
+    println!("Exams: {}", statistics.num_exams);
+    println!("Students: {}", statistics.num_students);
+    println!("Enrollments: {}", statistics.num_enrollments);
+    println!("Conflicting pairs of exams: {}", statistics.num_conflicts);
+    println!(
+        "Timeslots: {} (at least {lower_bound} are needed)",
+        statistics.num_timeslots
+    );
+    println!("Clashes: {}", statistics.clashes);
+    println!("Back-to-back exams: {}", statistics.back_to_back);
+
+    match &args.output {
+        Some(output) => {
+            let mut writer = BufWriter::new(File::create(output)?);
+            timetable::write_timetable(&mut writer, &enrollments, &timeslots)?;
+        }
+        None => timetable::write_timetable(&mut io::stdout().lock(), &enrollments, &timeslots)?,
+    }
+
+    Ok(())
+}
+
+fn run_regalloc(args: &RegallocArgs) -> Result<(), Box<dyn Error>> {
+    let Some(graph) = input::read_graph_from_file(&args.path)? else {
+        return Err(format!("{} has no problem line", args.path).into());
+    };
+    let file = File::open(&args.path)?;
+    let precoloring =
+        regalloc::read_precoloring(BufReader::new(file), graph.num_vertices(), args.k)?;
+    let allocation = regalloc::allocate(&graph, &precoloring, args.k)?;
+
+    // Vertices are 1-indexed, as in the instance
+    let spilled: Vec<String> = allocation
+        .spilled
+        .iter()
+        .map(|v| (v + 1).to_string())
+        .collect();
+
+    println!("Registers: {}", args.k);
+    println!("Spilled: {} ({})", spilled.len(), spilled.join(" "));
+    println!("Assignment:");
+    for (v, register) in allocation.registers.iter().enumerate() {
+        if let Some(register) = register {
+            println!("  {} -> {register}", v + 1);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_sudoku(args: &SudokuArgs) -> Result<(), Box<dyn Error>> {
+    let cells = sudoku::parse_puzzle(&std::fs::read_to_string(&args.puzzle)?)?;
+    let Some((grid, solver)) = sudoku::solve(&cells) else {
+        return Err(format!("{} has no solution", args.puzzle).into());
+    };
+
+    println!("Solver: {solver:?}");
+    print!("{}", sudoku::format_grid(&grid));
+
+    Ok(())
+}
+
+fn run_map(args: &MapArgs) -> Result<(), Box<dyn Error>> {
+    let file = File::open(&args.geojson)?;
+    let regions = map::read_regions(BufReader::new(file))?;
+    let graph = map::adjacency_graph(&regions);
+    let algorithm = args.algorithm.clone().unwrap_or_default();
+
+    let (num_colors, mut colors) =
+        solve_with_defaults(&graph, &algorithm, Some(greedy_clique(&graph).len()));
+    relabel_colors(&mut colors);
+
+    println!("Regions: {}", regions.names.len());
+    println!("Borders: {}", graph.edges().len());
+    println!("Colors: {num_colors}");
+    for (name, color) in regions.names.iter().zip(colors.iter()) {
+        println!("  {name} -> {color}");
+    }
+
+    if let Some(output) = &args.output {
+        map::write_colored(BufWriter::new(File::create(output)?), &regions, &colors)?;
+    }
+
+    Ok(())
+}
+
+fn run_fap(args: &FapArgs) -> Result<(), Box<dyn Error>> {
+    let instance = fap::read_instance(std::path::Path::new(&args.instance))?;
+    let graph = fap::interference_graph(&instance);
+    let frequencies = fap::assign(&instance);
+
+    let used: BTreeSet<usize> = frequencies.iter().flatten().copied().collect();
+    let unassigned = frequencies.iter().filter(|f| f.is_none()).count();
+
+    println!("Links: {}", instance.links.len());
+    println!("Constraints: {}", instance.constraints.len());
+    println!("Interfering pairs of links: {}", graph.edges().len());
+    println!("Unassigned links: {unassigned}");
+    println!(
+        "Violated constraints: {}",
+        fap::count_violations(&instance, &frequencies)
+    );
+    println!("Frequencies used: {}", used.len());
+    if let (Some(first), Some(last)) = (used.first(), used.last()) {
+        println!("Span: {}", last - first);
+    }
+    println!("Assignment:");
+    for (link, frequency) in instance.links.iter().zip(frequencies.iter()) {
+        if let Some(frequency) = frequency {
+            println!("  {link} -> {frequency}");
+        }
+    }
+
+    Ok(())
+}
+
+fn run_intervals(args: &IntervalsArgs) -> Result<(), Box<dyn Error>> {
+    let file = File::open(&args.tasks)?;
+    let tasks = intervals::read_tasks(BufReader::new(file))?;
+    let graph = intervals::conflict_graph(&tasks);
+    let min_machines = intervals::min_machines(&tasks);
+    let lower_bound = min_machines.values().max().copied();
+    let algorithm = args.algorithm.clone().unwrap_or_default();
+
+    let (_, coloring) = solve_with_defaults(&graph, &algorithm, lower_bound);
+    let machines = intervals::machines_per_resource(&tasks, &coloring);
+
+    println!("Tasks: {}", tasks.len());
+    println!("Overlapping pairs of tasks: {}", graph.edges().len());
+    for (resource, minimum) in min_machines.iter() {
+        let used = tasks
+            .iter()
+            .zip(machines.iter())
+            .filter(|(task, _)| task.resource == *resource)
+            .map(|(_, machine)| *machine)
+            .max()
+            .unwrap_or(0);
+
+        println!(
+            "Machines ({}): {used} (at least {minimum} are needed)",
+            resource.as_deref().unwrap_or("no resource")
+        );
+    }
+
+    match &args.output {
+        Some(output) => {
+            let mut writer = BufWriter::new(File::create(output)?);
+            intervals::write_schedule(&mut writer, &tasks, &machines)?;
+        }
+        None => intervals::write_schedule(&mut io::stdout().lock(), &tasks, &machines)?,
+    }
+
+    Ok(())
+}
+
+fn run_sparsity(args: &SparsityArgs) -> Result<(), Box<dyn Error>> {
+    let file = File::open(&args.matrix)?;
+    let pattern = sparsity::read_pattern(BufReader::new(file))?;
+    let compression = args.compression.unwrap_or_default();
+    let graph = sparsity::compression_graph(&pattern, compression)?;
+    let lower_bound = sparsity::max_row_nonzeros(&pattern).max(greedy_clique(&graph).len());
+    let algorithm = args.algorithm.clone().unwrap_or_default();
+
+    let (num_colors, mut coloring) = solve_with_defaults(&graph, &algorithm, Some(lower_bound));
+    relabel_colors(&mut coloring);
+
+    println!("Matrix: {}x{}", pattern.num_rows, pattern.num_cols);
+    println!(
+        "Nonzeros: {}",
+        pattern.rows.iter().map(Vec::len).sum::<usize>()
+    );
+    println!("Seed columns: {num_colors} (at least {lower_bound} are needed)");
+    // Columns are 1-indexed, as in the matrix file
+    output::groups::write_groups(
+        &mut io::stdout().lock(),
+        &output::groups::groups(&coloring, "Seed column", None),
+    )?;
+
+    Ok(())
+}
+
+fn run_wavelength(args: &WavelengthArgs) -> Result<(), Box<dyn Error>> {
+    let file = File::open(&args.paths)?;
+    let demands = wavelength::read_demands(BufReader::new(file))?;
+    let graph = wavelength::conflict_graph(&demands);
+    let load = wavelength::max_link_load(&demands);
+    let lower_bound = load.max(greedy_clique(&graph).len());
+    let algorithm = args.algorithm.clone().unwrap_or_default();
+
+    let (num_wavelengths, mut wavelengths) =
+        solve_with_defaults(&graph, &algorithm, Some(lower_bound));
+    relabel_colors(&mut wavelengths);
+
+    println!("Demands: {}", demands.len());
+    println!("Conflicting pairs of demands: {}", graph.edges().len());
+    println!("Maximum link load: {load}");
+    println!("Wavelengths: {num_wavelengths} (at least {lower_bound} are needed)");
+
+    match &args.output {
+        Some(output) => {
+            let mut writer = BufWriter::new(File::create(output)?);
+            wavelength::write_wavelengths(&mut writer, &demands, &wavelengths)?;
+        }
+        None => wavelength::write_wavelengths(&mut io::stdout().lock(), &demands, &wavelengths)?,
+    }
+
+    Ok(())
+}
+
+fn run_round_robin(args: &RoundRobinArgs) -> Result<(), Box<dyn Error>> {
+    let tournament = match (&args.fixtures, args.teams) {
+        (Some(fixtures), _) => roundrobin::read_fixtures(BufReader::new(File::open(fixtures)?))?,
+        (None, Some(teams)) => roundrobin::single_round_robin(teams),
+        (None, None) => unreachable!(),
+    };
+    let graph = roundrobin::conflict_graph(&tournament);
+    let (lower_bound, upper_bound) = roundrobin::vizing_bounds(&tournament);
+    let algorithm = args.algorithm.clone().unwrap_or_default();
+
+    let (num_rounds, mut rounds) = solve_with_defaults(&graph, &algorithm, Some(lower_bound));
+    relabel_colors(&mut rounds);
+
+    if !is_coloring_valid(&graph, &rounds) {
+        return Err("a team plays twice in the same round".into());
+    }
+
+    println!("Teams: {}", tournament.teams.len());
+    println!("Games: {}", tournament.games.len());
+    println!("Rounds: {num_rounds}");
+    println!("Vizing bounds: {lower_bound} ≤ rounds ≤ {upper_bound}");
+    if num_rounds > upper_bound {
+        println!("Warning: the schedule has more rounds than Vizing's upper bound");
+    }
+    roundrobin::write_rounds(&mut io::stdout().lock(), &tournament, &rounds)?;
+
+    Ok(())
+}
+
+fn run_coordinator(args: &CoordinateArgs) -> Result<(), Box<dyn Error>> {
+    let Some(graph) = input::read_graph_from_file(&args.path)? else {
+        return Err(format!("{} has no problem line", args.path).into());
+    };
+    let lower_bound = greedy_clique(&graph).len();
+    let algorithm = args.algorithm.clone().unwrap_or_default();
+
+    let start = Instant::now();
+    let ((num_colors, coloring), history) = distributed::coordinate(
+        &graph,
+        &algorithm,
+        &args.workers,
+        args.rounds,
+        args.elite_size,
+        Some(lower_bound),
+    )?;
+    let duration = start.elapsed().as_millis();
+
+    for (round, best) in history.iter().enumerate() {
+        println!("Round {}: {best} colors", round + 1);
+    }
+    println!("Number of colors used: {num_colors}");
+    println!(
+        "Color assignment: {}",
+        output::format_coloring(&graph, &coloring)
+    );
+    println!("Duration: {duration}");
+
+    Ok(())
+}
+
+#[cfg(feature = "plot")]
+fn run_plot(args: &PlotArgs) -> Result<(), Box<dyn Error>> {
+    let traces = args
+        .traces
+        .iter()
+        .map(|file| plot::read_trace_from_file(file).map_err(|err| format!("{file}: {err}")))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    plot::write_plot_to_file(&args.output, &traces)
+}
+
+fn run_online(args: &OnlineArgs) -> Result<(), Box<dyn Error>> {
+    let params = RecolorParams {
+        radius: args.radius,
+        max_iterations: args.max_moves,
+        ..Default::default()
+    };
+
+    online::serve(io::stdin().lock(), &mut io::stdout().lock(), params)?;
+
+    Ok(())
+}
+
+fn run_export_sat(args: &ExportSatArgs) -> Result<(), Box<dyn Error>> {
+    let Some(graph) = input::read_graph_from_file(&args.path)? else {
+        return Err(format!("{} has no problem line", args.path).into());
+    };
+    let cnf = sat::encode(&graph, args.k);
+    let comment = format!(
+        "{}-coloring of {}\nVariable v * {} + c + 1 is true if vertex v + 1 has color c + 1",
+        args.k, args.path, args.k
+    );
+
+    match &args.output {
+        Some(file) => {
+            let mut writer = BufWriter::new(File::create(file)?);
+            sat::write_cnf(&mut writer, &cnf, &comment)?;
+        }
+        None => sat::write_cnf(&mut io::stdout().lock(), &cnf, &comment)?,
+    }
+
+    Ok(())
+}
+
+fn run_export_qubo(args: &ExportQuboArgs) -> Result<(), Box<dyn Error>> {
+    let Some(graph) = input::read_graph_from_file(&args.path)? else {
+        return Err(format!("{} has no problem line", args.path).into());
+    };
+    let qubo = qubo::encode(&graph, args.k, args.vertex_penalty, args.edge_penalty);
+    let comment = format!(
+        "{}-coloring of {}\nVariable v * {} + c is 1 if vertex v + 1 has color c + 1",
+        args.k, args.path, args.k
+    );
+    let mut writer: Box<dyn Write> = match &args.output {
+        Some(file) => Box::new(BufWriter::new(File::create(file)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    match args.format {
+        QuboFormat::Qubo => qubo::write_qubo(&mut writer, &qubo, &comment)?,
+        QuboFormat::Json => qubo::write_json(&mut writer, &qubo)?,
+    }
+
+    Ok(())
+}
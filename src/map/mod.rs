@@ -0,0 +1,349 @@
+use crate::graph::adj_list::AdjList;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::{self, Read, Write};
+
+/// A point of a polygon ring, by the bits of its coordinates, so it can be hashed.
+type Point = (u64, u64);
+
+/// The regions of a GeoJSON feature collection.
+#[derive(Debug)]
+pub struct Regions {
+    /// Name of each feature: its `name` property, or its (1-indexed) position if there's none.
+    pub names: Vec<String>,
+    /// Border segments of each feature, with the endpoints in order.
+    borders: Vec<HashSet<(Point, Point)>>,
+    /// The collection, as read, so it can be written back with the colors.
+    collection: Value,
+}
+
+/// Reads the regions of a GeoJSON `FeatureCollection` whose geometries are `Polygon`s or
+/// `MultiPolygon`s.
+pub fn read_regions(reader: impl Read) -> Result<Regions, Box<dyn Error>> {
+    let collection: Value = serde_json::from_reader(reader)?;
+
+    if collection["type"] != "FeatureCollection" {
+        return Err("the GeoJSON isn't a FeatureCollection".into());
+    }
+    let Some(features) = collection["features"].as_array() else {
+        return Err("the FeatureCollection has no features".into());
+    };
+
+    let mut names = Vec::new();
+    let mut borders = Vec::new();
+
+    for (i, feature) in features.iter().enumerate() {
+        let name = match &feature["properties"]["name"] {
+            Value::String(name) => name.clone(),
+            _ => (i + 1).to_string(),
+        };
+        let geometry = &feature["geometry"];
+        let polygons = match geometry["type"].as_str() {
+            Some("Polygon") => vec![&geometry["coordinates"]],
+            Some("MultiPolygon") => geometry["coordinates"]
+                .as_array()
+                .ok_or_else(|| format!("feature {name} has no coordinates"))?
+                .iter()
+                .collect(),
+            _ => return Err(format!("feature {name} isn't a Polygon or MultiPolygon").into()),
+        };
+
+        let mut segments = HashSet::new();
+
+        for rings in polygons {
+            let rings = rings
+                .as_array()
+                .ok_or_else(|| format!("feature {name} has malformed coordinates"))?;
+
+            for ring in rings {
+                let points = read_ring(ring)
+                    .ok_or_else(|| format!("feature {name} has malformed coordinates"))?;
+
+                for pair in points.windows(2) {
+                    if pair[0] != pair[1] {
+                        segments.insert((pair[0].min(pair[1]), pair[0].max(pair[1])));
+                    }
+                }
+            }
+        }
+
+        names.push(name);
+        borders.push(segments);
+    }
+
+    Ok(Regions {
+        names,
+        borders,
+        collection,
+    })
+}
+
+fn read_ring(ring: &Value) -> Option<Vec<Point>> {
+    ring.as_array()?
+        .iter()
+        .map(|position| {
+            let x = position.get(0)?.as_f64()?;
+            let y = position.get(1)?.as_f64()?;
+
+            // Treat -0.0 as 0.0, so both have the same bits
+            Some(((x + 0.0).to_bits(), (y + 0.0).to_bits()))
+        })
+        .collect()
+}
+
+/// Builds the adjacency graph of the regions: two regions are adjacent if their borders overlap
+/// along a segment. Regions that only touch at a point aren't adjacent, as is usual in
+/// cartography.
+///
+/// Shared borders don't need the same vertices on both sides: a border split at a T-junction on
+/// one side still overlaps the unsplit border on the other.
+pub fn adjacency_graph(regions: &Regions) -> AdjList {
+    let mut graph = AdjList::new(regions.names.len());
+    let mut owners: HashMap<(Point, Point), Vec<usize>> = HashMap::new();
+    let mut adjacencies = HashSet::new();
+
+    for (region, segments) in regions.borders.iter().enumerate() {
+        for segment in segments {
+            let others = owners.entry(*segment).or_default();
+
+            for other in others.iter() {
+                if adjacencies.insert((*other, region)) {
+                    graph.add_edge(*other, region);
+                }
+            }
+
+            others.push(region);
+        }
+    }
+
+    // The segments that aren't shared exactly may still overlap: compare those that share a cell
+    // of a grid about as fine as the segments are long
+    let segments: Vec<_> = owners
+        .into_iter()
+        .filter(|(_, owners)| owners.len() == 1)
+        .map(|((p, q), owners)| (owners[0], coordinates(p), coordinates(q)))
+        .collect();
+    let total_length: f64 = segments
+        .iter()
+        .map(|(_, p, q)| (q.0 - p.0).abs().max((q.1 - p.1).abs()))
+        .sum();
+    let cell_size = total_length / segments.len() as f64;
+
+    if !cell_size.is_normal() {
+        return graph;
+    }
+
+    let mut cells: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+
+    for (i, (_, p, q)) in segments.iter().enumerate() {
+        for cell in grid_cells(*p, *q, cell_size) {
+            cells.entry(cell).or_default().push(i);
+        }
+    }
+
+    for members in cells.values() {
+        for (k, &i) in members.iter().enumerate() {
+            for &j in &members[k + 1..] {
+                let (u, p, q) = segments[i];
+                let (v, r, s) = segments[j];
+                let pair = (u.min(v), u.max(v));
+
+                if u != v && !adjacencies.contains(&pair) && overlap((p, q), (r, s)) {
+                    adjacencies.insert(pair);
+                    graph.add_edge(u, v);
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+/// The cells of a grid of `size` that the segment from `p` to `q` passes through, walking along
+/// its longer axis so a long diagonal doesn't cover its whole bounding box.
+fn grid_cells(p: (f64, f64), q: (f64, f64), size: f64) -> Vec<(i64, i64)> {
+    let transposed = (q.1 - p.1).abs() > (q.0 - p.0).abs();
+    let (p, q) = match transposed {
+        true => ((p.1, p.0), (q.1, q.0)),
+        false => (p, q),
+    };
+    let (p, q) = if p.0 <= q.0 { (p, q) } else { (q, p) };
+    let cell = |value: f64| (value / size).floor() as i64;
+    let y_at = |x: f64| p.1 + (q.1 - p.1) * (x - p.0) / (q.0 - p.0);
+    let mut cells = Vec::new();
+
+    for column in cell(p.0)..=cell(q.0) {
+        let from = y_at((column as f64 * size).max(p.0));
+        let to = y_at(((column + 1) as f64 * size).min(q.0));
+
+        for row in cell(from.min(to))..=cell(from.max(to)) {
+            cells.push(if transposed {
+                (row, column)
+            } else {
+                (column, row)
+            });
+        }
+    }
+
+    cells
+}
+
+fn coordinates((x, y): Point) -> (f64, f64) {
+    (f64::from_bits(x), f64::from_bits(y))
+}
+
+/// Whether the segments lie on the same line and share more than a point, up to a tolerance
+/// relative to the length of the first.
+fn overlap((a, b): ((f64, f64), (f64, f64)), (c, d): ((f64, f64), (f64, f64))) -> bool {
+    let length = (b.0 - a.0).hypot(b.1 - a.1);
+    let tolerance = 1e-9 * length;
+    let direction = ((b.0 - a.0) / length, (b.1 - a.1) / length);
+    // The distance of a point to the line through a and b, and its position along that line
+    let distance = |p: (f64, f64)| (direction.0 * (p.1 - a.1) - direction.1 * (p.0 - a.0)).abs();
+    let position = |p: (f64, f64)| direction.0 * (p.0 - a.0) + direction.1 * (p.1 - a.1);
+
+    if distance(c) > tolerance || distance(d) > tolerance {
+        return false;
+    }
+
+    let (start, end) = (position(c).min(position(d)), position(c).max(position(d)));
+
+    end.min(length) - start.max(0.0) > tolerance
+}
+
+/// Writes the regions back as GeoJSON, with the color of each feature in its `color` property.
+pub fn write_colored(writer: impl Write, regions: &Regions, colors: &[usize]) -> io::Result<()> {
+    let mut collection = regions.collection.clone();
+
+    if let Some(features) = collection["features"].as_array_mut() {
+        for (feature, color) in features.iter_mut().zip(colors) {
+            if !feature["properties"].is_object() {
+                feature["properties"] = Value::Object(Default::default());
+            }
+
+            feature["properties"]["color"] = Value::from(*color);
+        }
+    }
+
+    serde_json::to_writer(writer, &collection)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn square(x: f64, y: f64) -> Value {
+        json!([[
+            [x, y],
+            [x + 1.0, y],
+            [x + 1.0, y + 1.0],
+            [x, y + 1.0],
+            [x, y]
+        ]])
+    }
+
+    fn feature(name: &str, geometry: Value) -> Value {
+        json!({"type": "Feature", "properties": {"name": name}, "geometry": geometry})
+    }
+
+    /// A 2x2 grid of squares named a, b (top) and c, d (bottom), where d also has an island far
+    /// away, plus an unnamed square far away.
+    fn grid() -> String {
+        json!({
+            "type": "FeatureCollection",
+            "features": [
+                feature("a", json!({"type": "Polygon", "coordinates": square(0.0, 1.0)})),
+                feature("b", json!({"type": "Polygon", "coordinates": square(1.0, 1.0)})),
+                feature("c", json!({"type": "Polygon", "coordinates": square(0.0, 0.0)})),
+                feature("d", json!({
+                    "type": "MultiPolygon",
+                    "coordinates": [square(1.0, 0.0), square(5.0, 5.0)],
+                })),
+                {"type": "Feature", "properties": null, "geometry": {
+                    "type": "Polygon", "coordinates": square(7.0, 7.0),
+                }},
+            ],
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_adjacency_graph() {
+        let regions = read_regions(grid().as_bytes()).unwrap();
+
+        assert_eq!(regions.names, vec!["a", "b", "c", "d", "5"]);
+
+        // The diagonals (a and d, b and c) only touch at a point
+        let mut edges = adjacency_graph(&regions).edges();
+        edges.sort();
+
+        assert_eq!(edges, vec![(0, 1), (0, 2), (1, 3), (2, 3)]);
+
+        assert!(read_regions("[]".as_bytes()).is_err());
+        assert!(read_regions(
+            r#"{"type": "FeatureCollection", "features": [{"geometry": {"type": "Point"}}]}"#
+                .as_bytes()
+        )
+        .is_err());
+    }
+
+    fn rectangle(from: (f64, f64), to: (f64, f64)) -> Value {
+        json!({"type": "Polygon", "coordinates": [[
+            [from.0, from.1],
+            [to.0, from.1],
+            [to.0, to.1],
+            [from.0, to.1],
+            [from.0, from.1]
+        ]]})
+    }
+
+    #[test]
+    fn test_adjacency_graph_t_junctions() {
+        // c lies on top of a and b, with a single segment along both of their tops, while e only
+        // touches c at a point, along the same line
+        let collection = json!({
+            "type": "FeatureCollection",
+            "features": [
+                feature("a", rectangle((0.0, 0.0), (1.0, 1.0))),
+                feature("b", rectangle((1.0, 0.0), (2.0, 1.0))),
+                feature("c", rectangle((0.0, 1.0), (2.0, 2.0))),
+                feature("e", rectangle((2.0, 0.0), (3.0, 1.0))),
+            ],
+        });
+        let regions = read_regions(collection.to_string().as_bytes()).unwrap();
+
+        let mut edges = adjacency_graph(&regions).edges();
+        edges.sort();
+
+        assert_eq!(edges, vec![(0, 1), (0, 2), (1, 2), (1, 3)]);
+
+        // A long diagonal only passes through the cells along it
+        assert_eq!(grid_cells((0.0, 0.0), (1000.0, 1000.0), 1.0).len(), 2001);
+        assert_eq!(
+            grid_cells((1.0, 4.0), (1.5, 0.5), 1.0),
+            vec![(1, 0), (1, 1), (1, 2), (1, 3), (1, 4)]
+        );
+
+        let diagonal = ((0.0, 0.0), (2.0, 2.0));
+        assert!(overlap(diagonal, ((3.0, 3.0), (1.0, 1.0))));
+        assert!(!overlap(diagonal, ((2.0, 2.0), (3.0, 3.0))));
+        assert!(!overlap(diagonal, ((0.0, 1.0), (2.0, 3.0))));
+    }
+
+    #[test]
+    fn test_write_colored() {
+        let regions = read_regions(grid().as_bytes()).unwrap();
+        let mut buffer = Vec::new();
+        write_colored(&mut buffer, &regions, &[1, 2, 2, 1, 1]).unwrap();
+
+        let colored: Value = serde_json::from_slice(&buffer).unwrap();
+
+        assert_eq!(colored["features"][1]["properties"]["color"], 2);
+        assert_eq!(colored["features"][1]["properties"]["name"], "b");
+        assert_eq!(colored["features"][4]["properties"]["color"], 1);
+    }
+}
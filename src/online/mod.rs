@@ -0,0 +1,176 @@
+//! Coloring a graph as it arrives, one update per line, keeping a valid coloring all along. The
+//! vertices are 1-indexed, and created as needed.
+//!
+//! - `e <u> <v>` adds the edge `(u, v)`.
+//! - `v <u> <neighbors>...` adds the vertex `u` along with its edges to `neighbors`.
+//! - `d <u> <v>` removes the edge `(u, v)`.
+//! - `c` prints the current number of colors.
+//! - `p` prints the current coloring.
+//!
+//! Each update is repaired by a [`Recolorer`], so the work per update is bounded by its
+//! parameters rather than by the size of the graph.
+
+use crate::algorithms::recolor::{RecolorParams, Recolorer};
+use crate::graph::adj_list::AdjList;
+use std::io::{self, BufRead, Write};
+
+pub struct OnlineColoring {
+    recolorer: Recolorer,
+}
+
+impl OnlineColoring {
+    pub fn new(params: RecolorParams) -> Self {
+        OnlineColoring {
+            recolorer: Recolorer::new(AdjList::new(0), Vec::new(), params),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn recolorer(&self) -> &Recolorer {
+        &self.recolorer
+    }
+
+    /// Applies the update in `line`, returning what's printed for it, if anything.
+    pub fn apply(&mut self, line: &str) -> Result<Option<String>, String> {
+        let splits: Vec<&str> = line.split_whitespace().collect();
+
+        match splits.as_slice() {
+            [] => Ok(None),
+            ["e", u, v] => {
+                let (u, v) = (self.vertex(u)?, self.vertex(v)?);
+                self.add_edge(u, v)?;
+
+                Ok(None)
+            }
+            ["v", u, neighbors @ ..] => {
+                let u = self.vertex(u)?;
+                for v in neighbors {
+                    let v = self.vertex(v)?;
+                    self.add_edge(u, v)?;
+                }
+
+                Ok(None)
+            }
+            ["d", u, v] => {
+                let (u, v) = (self.vertex(u)?, self.vertex(v)?);
+                self.recolorer.remove_edge(u, v);
+
+                Ok(None)
+            }
+            ["c"] => Ok(Some(format!("colors {}", self.recolorer.num_colors()))),
+            ["p"] => {
+                let coloring: Vec<String> = self
+                    .recolorer
+                    .coloring()
+                    .iter()
+                    .map(usize::to_string)
+                    .collect();
+
+                Ok(Some(format!("coloring {}", coloring.join(" "))))
+            }
+            _ => Err(format!("unknown update {line:?}")),
+        }
+    }
+
+    fn add_edge(&mut self, u: usize, v: usize) -> Result<(), String> {
+        if u == v {
+            return Err(format!("vertex {} can't have a loop", u + 1));
+        }
+
+        self.recolorer.add_edge(u, v);
+
+        Ok(())
+    }
+
+    /// The (0-indexed) vertex named by `name`, creating it (and the ones before it) if it's new.
+    fn vertex(&mut self, name: &str) -> Result<usize, String> {
+        let vertex = match name.parse::<usize>() {
+            Ok(vertex) if vertex > 0 => vertex - 1,
+            _ => return Err(format!("{name} isn't a vertex")),
+        };
+
+        while self.recolorer.graph().num_vertices() <= vertex {
+            self.recolorer.add_vertex();
+        }
+
+        Ok(vertex)
+    }
+}
+
+/// Applies each update read from `reader`, writing what's printed for them (and their errors,
+/// which don't stop the stream) on `writer`.
+pub fn serve(
+    reader: impl BufRead,
+    writer: &mut impl Write,
+    params: RecolorParams,
+) -> io::Result<()> {
+    let mut coloring = OnlineColoring::new(params);
+
+    for line in reader.lines() {
+        match coloring.apply(&line?) {
+            Ok(None) => continue,
+            Ok(Some(output)) => writeln!(writer, "{output}")?,
+            Err(err) => writeln!(writer, "error {err}")?,
+        }
+        writer.flush()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::is_coloring_valid;
+    use crate::testing;
+
+    #[test]
+    fn test_online_coloring() {
+        let mut coloring = OnlineColoring::new(RecolorParams::default());
+
+        assert_eq!(coloring.apply("c"), Ok(Some("colors 0".to_string())));
+
+        coloring.apply("e 1 2").unwrap();
+        coloring.apply("v 3 1 2").unwrap();
+        assert_eq!(coloring.apply("c"), Ok(Some("colors 3".to_string())));
+
+        coloring.apply("d 2 3").unwrap();
+        assert_eq!(coloring.apply("c"), Ok(Some("colors 2".to_string())));
+        assert!(coloring
+            .apply("p")
+            .unwrap()
+            .unwrap()
+            .starts_with("coloring "));
+
+        assert!(coloring.apply("e 1 1").is_err());
+        assert!(coloring.apply("e 0 1").is_err());
+        assert!(coloring.apply("x").is_err());
+    }
+
+    #[test]
+    fn test_serve() {
+        let graph = testing::myciel4();
+        let updates: String = graph
+            .edges()
+            .iter()
+            .map(|(u, v)| format!("e {} {}\n", u + 1, v + 1))
+            .chain(["c\n".to_string(), "q\n".to_string()])
+            .collect();
+        let mut output = Vec::new();
+
+        serve(updates.as_bytes(), &mut output, RecolorParams::default()).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        let num_colors: usize = lines[0].strip_prefix("colors ").unwrap().parse().unwrap();
+
+        assert!(num_colors >= 5);
+        assert!(lines[1].starts_with("error"));
+
+        let mut coloring = OnlineColoring::new(RecolorParams::default());
+        for line in updates.lines().take(graph.edges().len()) {
+            coloring.apply(line).unwrap();
+        }
+        assert!(is_coloring_valid(&graph, coloring.recolorer().coloring()));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::graph::adj_list::AdjList;
+use serde::Serialize;
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Format {
+    /// DIMACS text format (`p edge` and `e` lines)
+    #[default]
+    Dimacs,
+    /// DIMACS binary format (a text preamble followed by the bit-packed lower triangle of the
+    /// adjacency matrix)
+    DimacsBinary,
+    /// One edge per line, as two 1-indexed vertices separated by a space
+    EdgeList,
+    /// A JSON object with the number of vertices, the edges and the comment
+    Json,
+}
+
+impl Format {
+    /// Extension of the files written in this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Dimacs => "col",
+            Format::DimacsBinary => "col.b",
+            Format::EdgeList => "edges",
+            Format::Json => "json",
+        }
+    }
+}
+
+/// Data written along with a graph, when the format supports it.
+#[derive(Debug, Default, Serialize)]
+pub struct Annotations {
+    /// What is known about the chromatic number, for instance.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /// Weight of each vertex (for weighted coloring), written as `n <vertex> <weight>` lines.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<usize>>,
+    /// Minimum distance between the colors of the endpoints of each edge (for bandwidth
+    /// coloring), in the order of [`AdjList::edges`]. Written after the endpoints of each edge.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distances: Option<Vec<usize>>,
+}
+
+#[derive(Serialize)]
+struct JsonGraph<'a> {
+    num_vertices: usize,
+    /// Edges as pairs of 1-indexed vertices.
+    edges: Vec<(usize, usize)>,
+    #[serde(flatten)]
+    annotations: &'a Annotations,
+}
+
+/// Writes `graph` in the given `format`, along with its `annotations`.
+///
+/// Fails if the format can't hold the weights or the distances.
+pub fn write_graph(
+    writer: &mut impl Write,
+    graph: &AdjList,
+    annotations: &Annotations,
+    format: Format,
+) -> io::Result<()> {
+    let edges: Vec<(usize, usize)> = graph.edges().iter().map(|(u, v)| (u + 1, v + 1)).collect();
+    let comment = annotations.comment.as_deref();
+    // The distance of each edge, formatted as an extra field of its line
+    let distances: Vec<String> = match &annotations.distances {
+        Some(distances) => distances.iter().map(|d| format!(" {d}")).collect(),
+        None => vec![String::new(); edges.len()],
+    };
+
+    match format {
+        Format::Dimacs => {
+            write!(writer, "{}", dimacs_preamble(graph, edges.len(), comment))?;
+            for (v, weight) in annotations.weights.iter().flatten().enumerate() {
+                writeln!(writer, "n {} {weight}", v + 1)?;
+            }
+            for ((u, v), distance) in edges.iter().zip(distances) {
+                writeln!(writer, "e {u} {v}{distance}")?;
+            }
+        }
+        Format::DimacsBinary => {
+            if annotations.weights.is_some() || annotations.distances.is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "the DIMACS binary format can't hold weights or distances",
+                ));
+            }
+            write_dimacs_binary(writer, graph, &edges, comment)?
+        }
+        Format::EdgeList => {
+            if annotations.weights.is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "the edge list format can't hold weights",
+                ));
+            }
+            for ((u, v), distance) in edges.iter().zip(distances) {
+                writeln!(writer, "{u} {v}{distance}")?;
+            }
+        }
+        Format::Json => {
+            let json = JsonGraph {
+                num_vertices: graph.num_vertices(),
+                edges,
+                annotations,
+            };
+
+            serde_json::to_writer(&mut *writer, &json)?;
+            writeln!(writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn dimacs_preamble(graph: &AdjList, num_edges: usize, comment: Option<&str>) -> String {
+    let mut preamble = String::new();
+
+    if let Some(comment) = comment {
+        preamble.push_str(&format!("c {comment}\n"));
+    }
+    preamble.push_str(&format!("p edge {} {num_edges}\n", graph.num_vertices()));
+
+    preamble
+}
+
+/// Writes `graph` in the binary format of the DIMACS translator (`asc2bin`): the length of the
+/// preamble on its own line, the preamble itself and, for each vertex `i`, a row of `i / 8 + 1`
+/// bytes whose bits (most significant first) mark the neighbors `j <= i`.
+fn write_dimacs_binary(
+    writer: &mut impl Write,
+    graph: &AdjList,
+    edges: &[(usize, usize)],
+    comment: Option<&str>,
+) -> io::Result<()> {
+    let preamble = dimacs_preamble(graph, edges.len(), comment);
+    let mut rows: Vec<Vec<u8>> = (0..graph.num_vertices())
+        .map(|i| vec![0; i / 8 + 1])
+        .collect();
+
+    for (u, v) in edges.iter() {
+        let (i, j) = (u.max(v) - 1, u.min(v) - 1);
+        rows[i][j / 8] |= 0x80 >> (j % 8);
+    }
+
+    writeln!(writer, "{}", preamble.len())?;
+    write!(writer, "{preamble}")?;
+    for row in rows {
+        writer.write_all(&row)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_triangle(format: Format) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        let annotations = Annotations {
+            comment: Some(String::from("the chromatic number is 3")),
+            ..Default::default()
+        };
+
+        write_graph(&mut buffer, &AdjList::complete(3), &annotations, format).unwrap();
+
+        buffer
+    }
+
+    #[test]
+    fn test_write_dimacs() {
+        assert_eq!(
+            String::from_utf8(write_triangle(Format::Dimacs)).unwrap(),
+            "c the chromatic number is 3\np edge 3 3\ne 1 2\ne 1 3\ne 2 3\n"
+        );
+        assert_eq!(
+            String::from_utf8(write_triangle(Format::EdgeList)).unwrap(),
+            "1 2\n1 3\n2 3\n"
+        );
+    }
+
+    #[test]
+    fn test_write_dimacs_binary() {
+        let preamble = "c the chromatic number is 3\np edge 3 3\n";
+        let mut expected = format!("{}\n{preamble}", preamble.len()).into_bytes();
+
+        // Vertex 1 has no neighbors before it, vertex 2 has 1, and vertex 3 has 1 and 2
+        expected.extend([0b0000_0000, 0b1000_0000, 0b1100_0000]);
+
+        assert_eq!(write_triangle(Format::DimacsBinary), expected);
+    }
+
+    #[test]
+    fn test_write_json() {
+        let json: serde_json::Value =
+            serde_json::from_slice(&write_triangle(Format::Json)).unwrap();
+
+        assert_eq!(json["num_vertices"], 3);
+        assert_eq!(json["edges"], serde_json::json!([[1, 2], [1, 3], [2, 3]]));
+        assert_eq!(json["comment"], "the chromatic number is 3");
+        assert!(json.get("weights").is_none());
+    }
+
+    #[test]
+    fn test_write_weighted() {
+        let annotations = Annotations {
+            comment: None,
+            weights: Some(vec![3, 1, 2]),
+            distances: Some(vec![1, 2, 3]),
+        };
+        let mut buffer = Vec::new();
+
+        write_graph(
+            &mut buffer,
+            &AdjList::complete(3),
+            &annotations,
+            Format::Dimacs,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "p edge 3 3\nn 1 3\nn 2 1\nn 3 2\ne 1 2 1\ne 1 3 2\ne 2 3 3\n"
+        );
+
+        let mut buffer = Vec::new();
+
+        write_graph(
+            &mut buffer,
+            &AdjList::complete(3),
+            &annotations,
+            Format::Json,
+        )
+        .unwrap();
+
+        let json: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+
+        assert_eq!(json["weights"], serde_json::json!([3, 1, 2]));
+        assert_eq!(json["distances"], serde_json::json!([1, 2, 3]));
+
+        let mut buffer = Vec::new();
+
+        assert!(write_graph(
+            &mut buffer,
+            &AdjList::complete(3),
+            &annotations,
+            Format::DimacsBinary
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// A color class, as a named group of (1-indexed) vertices.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Group {
+    pub name: String,
+    pub size: usize,
+    pub vertices: Vec<usize>,
+    /// Labels of the vertices, if the instance names them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<Vec<String>>,
+}
+
+/// Groups the vertices by their color in `coloring`, naming each group `<label> <color>`, in
+/// order of color. The vertices keep their `labels`, if any.
+pub fn groups(coloring: &[usize], label: &str, labels: Option<&[String]>) -> Vec<Group> {
+    let mut colors: Vec<usize> = coloring.to_vec();
+    colors.sort();
+    colors.dedup();
+
+    colors
+        .into_iter()
+        .map(|color| {
+            let vertices: Vec<usize> = (0..coloring.len())
+                .filter(|v| coloring[*v] == color)
+                .map(|v| v + 1)
+                .collect();
+
+            Group {
+                name: format!("{label} {color}"),
+                size: vertices.len(),
+                labels: labels
+                    .map(|labels| vertices.iter().map(|v| labels[v - 1].clone()).collect()),
+                vertices,
+            }
+        })
+        .collect()
+}
+
+/// Writes each group as a `<name> (<size>): <vertices>...` line, with the labels of the vertices
+/// if they have any.
+pub fn write_groups(writer: &mut impl Write, groups: &[Group]) -> io::Result<()> {
+    for group in groups {
+        let vertices: Vec<String> = match &group.labels {
+            Some(labels) => labels.clone(),
+            None => group.vertices.iter().map(|v| v.to_string()).collect(),
+        };
+
+        writeln!(
+            writer,
+            "{} ({}): {}",
+            group.name,
+            group.size,
+            vertices.join(" ")
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups() {
+        let groups = groups(&[2, 1, 2, 5], "Slot", None);
+
+        assert_eq!(
+            groups[0],
+            Group {
+                name: "Slot 1".to_string(),
+                size: 1,
+                vertices: vec![2],
+                labels: None,
+            }
+        );
+        assert_eq!(groups[2].name, "Slot 5");
+
+        let mut buffer = Vec::new();
+        write_groups(&mut buffer, &groups).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "Slot 1 (1): 2\nSlot 2 (2): 1 3\nSlot 5 (1): 4\n"
+        );
+
+        let labels = ["a", "b", "c"].map(String::from);
+        let groups = super::groups(&[1, 2, 1], "Slot", Some(&labels));
+
+        assert_eq!(
+            groups[0].labels,
+            Some(vec!["a".to_string(), "c".to_string()])
+        );
+
+        let mut buffer = Vec::new();
+        write_groups(&mut buffer, &groups).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "Slot 1 (2): a c\nSlot 2 (1): b\n"
+        );
+    }
+}
@@ -0,0 +1,146 @@
+pub mod format;
+pub mod groups;
+
+use crate::algorithms::{canonical_classes, genetic::Population};
+use crate::graph::adj_list::AdjList;
+use serde_json::Value;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Header of the color classes of a solution in the partition format, which are listed one per
+/// line after it.
+pub const PARTITION_HEADER: &str = "Color classes:";
+
+/// How the coloring found by a run is printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+pub enum SolutionFormat {
+    /// The color of each vertex, in order
+    #[default]
+    Assignment,
+    /// The color classes, one per line, as the (1-indexed) vertices or the labels of their members
+    Partition,
+}
+
+/// Formats `coloring` as its color classes (in canonical form), one per line, after the
+/// [`PARTITION_HEADER`]. Vertices are written by name (see [`AdjList::vertex_name`]).
+pub fn format_partition(graph: &AdjList, coloring: &[usize]) -> String {
+    let mut partition = String::from(PARTITION_HEADER);
+
+    for class in canonical_classes(coloring) {
+        let names: Vec<String> = class.iter().map(|v| graph.vertex_name(*v)).collect();
+
+        partition.push('\n');
+        partition.push_str(&names.join(" "));
+    }
+
+    partition
+}
+
+/// Formats `coloring` for the output of a run: as a list of colors, or as a JSON object from the
+/// labels of the vertices to their colors if the instance names them.
+pub fn format_coloring(graph: &AdjList, coloring: &[usize]) -> String {
+    let Some(labels) = graph.labels() else {
+        return format!("{coloring:?}");
+    };
+
+    let entries: Vec<String> = labels
+        .iter()
+        .zip(coloring)
+        .map(|(label, color)| format!("{}: {color}", Value::from(label.as_str())))
+        .collect();
+
+    format!("{{{}}}", entries.join(", "))
+}
+
+/// Writes `population` to `filename` as JSON, so it can be loaded back with `--load-population`.
+pub fn write_population_to_file(
+    filename: &str,
+    population: &Population,
+) -> Result<(), Box<dyn Error>> {
+    let file = File::create(filename)?;
+    let mut writer = BufWriter::new(file);
+
+    serde_json::to_writer_pretty(&mut writer, population)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Writes `groups` to `filename` as JSON.
+pub fn write_groups_to_file(
+    filename: &str,
+    groups: &[groups::Group],
+) -> Result<(), Box<dyn Error>> {
+    let file = File::create(filename)?;
+    let mut writer = BufWriter::new(file);
+
+    serde_json::to_writer_pretty(&mut writer, groups)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::genetic::{GeneticMode, Individual};
+    use crate::input;
+
+    #[test]
+    fn test_format_coloring() {
+        let mut graph = AdjList::new(2);
+
+        assert_eq!(format_coloring(&graph, &[1, 2]), "[1, 2]");
+
+        graph.set_labels(vec!["a".to_string(), "b \"c\"".to_string()]);
+        assert_eq!(
+            format_coloring(&graph, &[1, 2]),
+            r#"{"a": 1, "b \"c\"": 2}"#
+        );
+    }
+
+    #[test]
+    fn test_format_partition() {
+        let mut graph = AdjList::new(4);
+
+        assert_eq!(
+            format_partition(&graph, &[2, 1, 2, 3]),
+            "Color classes:\n1 3\n2\n4"
+        );
+
+        graph.set_labels(["a", "b", "c", "d"].map(String::from).to_vec());
+        assert_eq!(
+            format_partition(&graph, &[2, 1, 2, 3]),
+            "Color classes:\na c\nb\nd"
+        );
+    }
+
+    #[test]
+    fn test_population_round_trip() {
+        let population = Population {
+            mode: GeneticMode::FixedK,
+            individuals: vec![
+                Individual {
+                    fitness: 0,
+                    coloring: vec![1, 2, 1],
+                },
+                Individual {
+                    fitness: 1,
+                    coloring: vec![1, 1, 2],
+                },
+            ],
+        };
+        let path = std::env::temp_dir().join("gcp-heuristics-test-population.json");
+        let path = path.to_str().unwrap();
+
+        write_population_to_file(path, &population).unwrap();
+
+        let colorings = input::read_population_from_file(path, 3).unwrap();
+
+        assert_eq!(colorings, vec![vec![1, 2, 1], vec![1, 1, 2]]);
+
+        // The instance must match the number of vertices of the individuals
+        assert!(input::read_population_from_file(path, 4).is_err());
+    }
+}
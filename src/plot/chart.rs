@@ -0,0 +1,168 @@
+//! Drawing of the convergence charts with plotters.
+
+use super::Trace;
+use plotters::{coord::Shift, prelude::*};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+const WIDTH: u32 = 800;
+const PANEL_HEIGHT: u32 = 260;
+const MARGIN: u32 = 10;
+/// Size of the areas of the labels of the axes, below and to the left of each panel.
+const LABEL_AREA_SIZE: u32 = 40;
+const PALETTE: [RGBColor; 8] = [
+    RGBColor(0x1f, 0x77, 0xb4),
+    RGBColor(0xd6, 0x27, 0x28),
+    RGBColor(0x2c, 0xa0, 0x2c),
+    RGBColor(0xff, 0x7f, 0x0e),
+    RGBColor(0x94, 0x67, 0xbd),
+    RGBColor(0x8c, 0x56, 0x4b),
+    RGBColor(0xe3, 0x77, 0xc2),
+    RGBColor(0x7f, 0x7f, 0x7f),
+];
+
+pub fn write_plot_to_file(filename: &str, traces: &[Trace]) -> Result<(), Box<dyn Error>> {
+    let mut writer = BufWriter::new(File::create(filename)?);
+
+    writer.write_all(render_svg(traces)?.as_bytes())?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Draws the colors of `traces` over time, overlaid on the same axes, as an SVG document. The
+/// conflicts are drawn in a second panel below, if any trace has them.
+pub fn render_svg(traces: &[Trace]) -> Result<String, Box<dyn Error>> {
+    let has_conflicts = traces.iter().any(|trace| !trace.conflicts.is_empty());
+    let num_panels = if has_conflicts { 2 } else { 1 };
+    let mut svg = String::new();
+
+    {
+        let root = SVGBackend::with_string(&mut svg, (WIDTH, num_panels * PANEL_HEIGHT))
+            .into_drawing_area();
+        root.fill(&WHITE)?;
+        let panels = root.split_evenly((num_panels as usize, 1));
+
+        let colors: Vec<(&Trace, &[(f64, usize)])> = traces
+            .iter()
+            .map(|trace| (trace, trace.colors.as_slice()))
+            .collect();
+        draw_panel(&panels[0], "Colors", &colors)?;
+
+        if has_conflicts {
+            let conflicts: Vec<(&Trace, &[(f64, usize)])> = traces
+                .iter()
+                .map(|trace| (trace, trace.conflicts.as_slice()))
+                .collect();
+            draw_panel(&panels[1], "Conflicts", &conflicts)?;
+        }
+
+        root.present()?;
+    }
+
+    Ok(svg)
+}
+
+/// Draws a panel with its axes on `area`, with a step line for the values of each trace, which hold
+/// until the next one (or the end of the run), and a legend at its top right corner.
+fn draw_panel(
+    area: &DrawingArea<SVGBackend, Shift>,
+    title: &str,
+    series: &[(&Trace, &[(f64, usize)])],
+) -> Result<(), Box<dyn Error>> {
+    let max_time = series
+        .iter()
+        .map(|(trace, values)| trace.duration.max(values.last().map_or(0.0, |(t, _)| *t)))
+        .fold(0.0, f64::max)
+        .max(f64::EPSILON);
+    let values = series.iter().flat_map(|(_, values)| values.iter());
+    let min_value = values.clone().map(|(_, v)| *v).min().unwrap_or(0);
+    let max_value = values
+        .map(|(_, v)| *v)
+        .max()
+        .unwrap_or(0)
+        .max(min_value + 1);
+
+    let mut chart = ChartBuilder::on(area)
+        .caption(title, ("sans-serif", 16))
+        .margin(MARGIN)
+        .x_label_area_size(LABEL_AREA_SIZE)
+        .y_label_area_size(LABEL_AREA_SIZE)
+        .build_cartesian_2d(0.0..max_time, min_value..max_value)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("Time (s)")
+        .y_desc(title)
+        .draw()?;
+
+    for (index, (trace, values)) in series.iter().enumerate() {
+        let Some((_, last)) = values.last() else {
+            continue;
+        };
+
+        let mut points = Vec::new();
+        for (i, (time, value)) in values.iter().enumerate() {
+            if i > 0 {
+                points.push((*time, values[i - 1].1));
+            }
+            points.push((*time, *value));
+        }
+        points.push((trace.duration.max(values[values.len() - 1].0), *last));
+
+        let color = PALETTE[index % PALETTE.len()];
+
+        chart
+            .draw_series(LineSeries::new(points, color.stroke_width(2)))?
+            .label(&trace.label)
+            .legend(move |(x, y)| PathElement::new([(x, y), (x + 20, y)], color.stroke_width(2)));
+    }
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperRight)
+        .background_style(WHITE)
+        .border_style(BLACK)
+        .draw()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_svg() {
+        let trace = Trace {
+            label: "GRASP <1>".to_string(),
+            duration: 2.0,
+            colors: vec![(0.0, 6), (1.0, 4)],
+            conflicts: Vec::new(),
+        };
+        let svg = render_svg(std::slice::from_ref(&trace)).unwrap();
+        let series = |svg: &str| {
+            svg.matches(r##"stroke="#1F77B4" stroke-width="2""##)
+                .count()
+        };
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("GRASP &lt;1&gt;"));
+        assert!(!svg.contains("Conflicts"));
+        // The line of the trace and its sample in the legend
+        assert_eq!(series(&svg), 2);
+        // Steps from 6 colors down to 4 after a second, then holds until the end of the run
+        assert!(svg.contains(r#"points="50,32 419,32 419,209 789,209 ""#));
+
+        let other = Trace {
+            label: "Genetic".to_string(),
+            conflicts: vec![(0.5, 3), (1.5, 0)],
+            ..trace
+        };
+        let svg = render_svg(&[other.clone(), other]).unwrap();
+
+        assert!(svg.contains("Conflicts"));
+        assert_eq!(series(&svg), 4);
+    }
+}
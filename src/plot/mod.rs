@@ -0,0 +1,81 @@
+//! Convergence charts: the best number of colors (and the conflicts, for the algorithms that allow
+//! them) over time, drawn as SVG from the [`progress`](crate::progress) of runs.
+
+use crate::progress::Snapshot;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+
+#[cfg(feature = "plot")]
+mod chart;
+
+#[cfg(feature = "plot")]
+pub use chart::{render_svg, write_plot_to_file};
+
+/// The values of a run over time, in seconds since its start.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trace {
+    pub label: String,
+    /// Duration of the whole run.
+    pub duration: f64,
+    pub colors: Vec<(f64, usize)>,
+    pub conflicts: Vec<(f64, usize)>,
+}
+
+impl Trace {
+    pub fn from_snapshot(label: &str, snapshot: &Snapshot) -> Self {
+        let seconds = |history: &[(std::time::Duration, usize)]| {
+            history
+                .iter()
+                .map(|(elapsed, value)| (elapsed.as_secs_f64(), *value))
+                .collect()
+        };
+
+        Trace {
+            label: label.to_string(),
+            duration: snapshot.elapsed.as_secs_f64(),
+            colors: seconds(&snapshot.history),
+            conflicts: seconds(&snapshot.conflict_history),
+        }
+    }
+}
+
+pub fn write_trace_to_file(filename: &str, trace: &Trace) -> Result<(), Box<dyn Error>> {
+    let mut writer = BufWriter::new(File::create(filename)?);
+
+    serde_json::to_writer_pretty(&mut writer, trace)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+pub fn read_trace_from_file(filename: &str) -> Result<Trace, Box<dyn Error>> {
+    Ok(serde_json::from_reader(BufReader::new(File::open(
+        filename,
+    )?))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_from_snapshot() {
+        let snapshot = Snapshot {
+            elapsed: Duration::from_secs(2),
+            best_colors: Some(4),
+            conflicts: None,
+            iterations: vec![10],
+            history: vec![(Duration::ZERO, 6), (Duration::from_secs(1), 4)],
+            conflict_history: vec![(Duration::from_millis(500), 3)],
+        };
+        let trace = Trace::from_snapshot("GRASP", &snapshot);
+
+        assert_eq!(trace.label, "GRASP");
+        assert_eq!(trace.duration, 2.0);
+        assert_eq!(trace.colors, vec![(0.0, 6), (1.0, 4)]);
+        assert_eq!(trace.conflicts, vec![(0.5, 3)]);
+    }
+}
@@ -0,0 +1,434 @@
+//! Live progress of the running algorithm, so long runs can be monitored.
+//!
+//! The algorithms record their iterations (GRASP iterations, GA generations and so on) and the best
+//! number of colors found so far in a global [`Progress`], which a monitor reads with
+//! [`Progress::snapshot`] while they run. Recording only touches atomics (and a lock when the best
+//! number of colors improves), so it's cheap enough to always be on.
+//!
+//! A monitor may also ask the algorithms to stop early with [`Progress::request_stop`], which they
+//! check where they'd stop on reaching the lower bound, so they still return their best coloring.
+//!
+//! Jobs solved at once (by the server, for instance) each record to their own [`Progress`] instead,
+//! running with [`run_job`], so stopping one of them doesn't stop the others.
+
+use std::cell::RefCell;
+use std::ops::Deref;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Threads tracked separately: the main thread and the first ones of the rayon pool. Other
+/// threads are counted along with the last one tracked.
+pub const MAX_THREADS: usize = 64;
+
+/// Stands for a value that wasn't recorded yet.
+const NONE: usize = usize::MAX;
+
+pub static PROGRESS: Progress = Progress::new();
+
+thread_local! {
+    /// The progress of the job the current thread runs, if any.
+    static CURRENT: RefCell<Option<Arc<Progress>>> = const { RefCell::new(None) };
+}
+
+pub struct Progress {
+    best_colors: AtomicUsize,
+    conflicts: AtomicUsize,
+    iterations: [AtomicUsize; MAX_THREADS],
+    start: Mutex<Option<Instant>>,
+    /// Time at which each improvement of the best number of colors was found, along with it and
+    /// the phase that found it (if it was attributed to one).
+    history: Mutex<Vec<(Duration, usize, Option<Phase>)>>,
+    /// Time at which each change of the number of conflicts was recorded, along with it.
+    conflict_history: Mutex<Vec<(Duration, usize)>>,
+    stop: AtomicBool,
+    /// Progress everything recorded is also recorded to, except for the stop requests.
+    parent: Option<&'static Progress>,
+}
+
+/// Phase of the algorithms an improvement of the best coloring may be attributed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    /// Building colorings from scratch: the GRASP construction, or the initial population.
+    Construction,
+    /// Merging color classes and removing the conflicts with a local (or tabu) search.
+    LocalSearch,
+    Crossover,
+    Mutation,
+    PathRelinking,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 5] = [
+        Phase::Construction,
+        Phase::LocalSearch,
+        Phase::Crossover,
+        Phase::Mutation,
+        Phase::PathRelinking,
+    ];
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Phase::Construction => "construction",
+            Phase::LocalSearch => "merge + local search",
+            Phase::Crossover => "crossover",
+            Phase::Mutation => "mutation",
+            Phase::PathRelinking => "path relinking",
+        }
+    }
+}
+
+/// The progress at some point of the run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub elapsed: Duration,
+    pub best_colors: Option<usize>,
+    /// Conflicting edges of the current best coloring of the algorithms that allow conflicts.
+    pub conflicts: Option<usize>,
+    /// Iterations of each tracked thread: the main one first, then the rayon ones.
+    pub iterations: Vec<usize>,
+    pub history: Vec<(Duration, usize)>,
+    pub conflict_history: Vec<(Duration, usize)>,
+}
+
+impl Snapshot {
+    pub fn total_iterations(&self) -> usize {
+        self.iterations.iter().sum()
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Progress::new()
+    }
+}
+
+impl Progress {
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub const fn new() -> Self {
+        const ZERO: AtomicUsize = AtomicUsize::new(0);
+
+        Progress {
+            best_colors: AtomicUsize::new(NONE),
+            conflicts: AtomicUsize::new(NONE),
+            iterations: [ZERO; MAX_THREADS],
+            start: Mutex::new(None),
+            history: Mutex::new(Vec::new()),
+            conflict_history: Mutex::new(Vec::new()),
+            stop: AtomicBool::new(false),
+            parent: None,
+        }
+    }
+
+    /// The progress of a job starting now, which also records to `parent`, if given.
+    pub fn for_job(parent: Option<&'static Progress>) -> Self {
+        let progress = Progress {
+            parent,
+            ..Progress::new()
+        };
+        progress.reset();
+
+        progress
+    }
+
+    /// Forgets everything recorded, starting a new run.
+    pub fn reset(&self) {
+        self.best_colors.store(NONE, Ordering::Relaxed);
+        self.conflicts.store(NONE, Ordering::Relaxed);
+        for iterations in self.iterations.iter() {
+            iterations.store(0, Ordering::Relaxed);
+        }
+        *self.start.lock().unwrap() = Some(Instant::now());
+        self.history.lock().unwrap().clear();
+        self.conflict_history.lock().unwrap().clear();
+        self.stop.store(false, Ordering::Relaxed);
+    }
+
+    /// Records an iteration of the current thread.
+    pub fn record_iteration(&self) {
+        let thread = rayon::current_thread_index().map_or(0, |index| index + 1);
+
+        self.iterations[thread.min(MAX_THREADS - 1)].fetch_add(1, Ordering::Relaxed);
+
+        if let Some(parent) = self.parent {
+            parent.record_iteration();
+        }
+    }
+
+    /// Records a (valid) coloring with `num_colors` colors, if it's the best so far.
+    pub fn record_colors(&self, num_colors: usize) {
+        self.record_improvement(num_colors, None);
+    }
+
+    /// Like [`Progress::record_colors`], attributing the coloring to `phase`.
+    pub fn record_colors_from(&self, phase: Phase, num_colors: usize) {
+        self.record_improvement(num_colors, Some(phase));
+    }
+
+    fn record_improvement(&self, num_colors: usize, phase: Option<Phase>) {
+        if let Some(parent) = self.parent {
+            parent.record_improvement(num_colors, phase);
+        }
+
+        if num_colors < self.best_colors.fetch_min(num_colors, Ordering::Relaxed) {
+            let elapsed = self.elapsed();
+            let mut history = self.history.lock().unwrap();
+
+            // Improvements from different threads may arrive out of order
+            if history.last().is_none_or(|(_, best, _)| num_colors < *best) {
+                history.push((elapsed, num_colors, phase));
+            }
+        }
+    }
+
+    /// Records the number of conflicting edges of the current best coloring.
+    pub fn record_conflicts(&self, conflicts: usize) {
+        if let Some(parent) = self.parent {
+            parent.record_conflicts(conflicts);
+        }
+
+        if self.conflicts.swap(conflicts, Ordering::Relaxed) != conflicts {
+            let elapsed = self.elapsed();
+            self.conflict_history
+                .lock()
+                .unwrap()
+                .push((elapsed, conflicts));
+        }
+    }
+
+    /// Asks the algorithms to stop as soon as they can, until the next reset.
+    pub fn request_stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Lets the algorithms run again after [`Progress::request_stop`], without forgetting the rest.
+    pub fn clear_stop(&self) {
+        self.stop.store(false, Ordering::Relaxed);
+    }
+
+    pub fn stop_requested(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.start
+            .lock()
+            .unwrap()
+            .map_or(Duration::ZERO, |start| start.elapsed())
+    }
+
+    /// Each improvement of the best number of colors so far, along with the phase it's attributed
+    /// to, if any.
+    pub fn attributed_history(&self) -> Vec<(Duration, usize, Option<Phase>)> {
+        self.history.lock().unwrap().clone()
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        let optional = |value: usize| (value != NONE).then_some(value);
+
+        Snapshot {
+            elapsed: self.elapsed(),
+            best_colors: optional(self.best_colors.load(Ordering::Relaxed)),
+            conflicts: optional(self.conflicts.load(Ordering::Relaxed)),
+            iterations: self
+                .iterations
+                .iter()
+                .map(|iterations| iterations.load(Ordering::Relaxed))
+                .collect(),
+            history: self
+                .history
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(elapsed, best, _)| (*elapsed, *best))
+                .collect(),
+            conflict_history: self.conflict_history.lock().unwrap().clone(),
+        }
+    }
+}
+
+/// Counts the improvements of `history` attributed to each phase (every one of them, in order),
+/// followed by the unattributed ones.
+pub fn improvements_by_phase(
+    history: &[(Duration, usize, Option<Phase>)],
+) -> Vec<(Option<Phase>, usize)> {
+    Phase::ALL
+        .into_iter()
+        .map(Some)
+        .chain([None])
+        .map(|phase| {
+            let count = history
+                .iter()
+                .filter(|(_, _, attributed)| *attributed == phase)
+                .count();
+
+            (phase, count)
+        })
+        .collect()
+}
+
+/// The progress of a thread: that of the job it runs, or the global [`PROGRESS`].
+#[derive(Clone, Default)]
+pub struct Handle(Option<Arc<Progress>>);
+
+impl Deref for Handle {
+    type Target = Progress;
+
+    fn deref(&self) -> &Progress {
+        self.0.as_deref().unwrap_or(&PROGRESS)
+    }
+}
+
+impl Handle {
+    /// Runs `task` recording to this progress, for threads spawned by the algorithms.
+    pub fn run<T: Send>(&self, task: impl FnOnce() -> T + Send) -> T {
+        match &self.0 {
+            Some(progress) => run_job(progress, task),
+            None => task(),
+        }
+    }
+}
+
+/// The progress of the current thread.
+pub fn current() -> Handle {
+    Handle(CURRENT.with(|current| current.borrow().clone()))
+}
+
+/// Runs `task` on a thread pool of its own, whose threads record to `progress` instead of the
+/// global [`PROGRESS`].
+pub fn run_job<T: Send>(progress: &Arc<Progress>, task: impl FnOnce() -> T + Send) -> T {
+    let handler_progress = Arc::clone(progress);
+    let pool = rayon::ThreadPoolBuilder::new()
+        .start_handler(move |_| {
+            CURRENT.with(|current| *current.borrow_mut() = Some(Arc::clone(&handler_progress)))
+        })
+        .build()
+        .expect("failed to start the threads of the job");
+
+    pool.install(task)
+}
+
+fn with_current<T>(f: impl FnOnce(&Progress) -> T) -> T {
+    CURRENT.with(|current| match current.borrow().as_deref() {
+        Some(progress) => f(progress),
+        None => f(&PROGRESS),
+    })
+}
+
+/// Records an iteration of the current thread in its progress.
+pub fn record_iteration() {
+    with_current(Progress::record_iteration);
+}
+
+/// Records a (valid) coloring with `num_colors` colors in the progress of the current thread.
+pub fn record_colors(num_colors: usize) {
+    with_current(|progress| progress.record_colors(num_colors));
+}
+
+/// Records a (valid) coloring with `num_colors` colors, found by `phase`, in the progress of the
+/// current thread.
+pub fn record_colors_from(phase: Phase, num_colors: usize) {
+    with_current(|progress| progress.record_colors_from(phase, num_colors));
+}
+
+/// Records the conflicts of the current best coloring in the progress of the current thread.
+pub fn record_conflicts(conflicts: usize) {
+    with_current(|progress| progress.record_conflicts(conflicts));
+}
+
+/// Whether the algorithms of the current thread were asked to stop through its progress.
+pub fn stop_requested() -> bool {
+    with_current(Progress::stop_requested)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress() {
+        let progress = Progress::new();
+        progress.reset();
+
+        assert_eq!(progress.snapshot().best_colors, None);
+
+        progress.record_colors(5);
+        progress.record_colors(7);
+        progress.record_colors(4);
+        progress.record_iteration();
+        progress.record_iteration();
+        progress.record_conflicts(3);
+        progress.record_conflicts(3);
+
+        let snapshot = progress.snapshot();
+        let history: Vec<usize> = snapshot.history.iter().map(|(_, best)| *best).collect();
+
+        assert_eq!(snapshot.best_colors, Some(4));
+        assert_eq!(snapshot.conflicts, Some(3));
+        assert_eq!(snapshot.total_iterations(), 2);
+        assert_eq!(history, vec![5, 4]);
+        assert_eq!(snapshot.conflict_history.len(), 1);
+
+        progress.record_colors_from(Phase::Mutation, 4);
+        progress.record_colors_from(Phase::Crossover, 3);
+        let phases: Vec<Option<Phase>> = progress
+            .attributed_history()
+            .iter()
+            .map(|(_, _, phase)| *phase)
+            .collect();
+
+        assert_eq!(phases, vec![None, None, Some(Phase::Crossover)]);
+        assert_eq!(
+            improvements_by_phase(&progress.attributed_history()),
+            vec![
+                (Some(Phase::Construction), 0),
+                (Some(Phase::LocalSearch), 0),
+                (Some(Phase::Crossover), 1),
+                (Some(Phase::Mutation), 0),
+                (Some(Phase::PathRelinking), 0),
+                (None, 2),
+            ]
+        );
+
+        progress.request_stop();
+        assert!(progress.stop_requested());
+        progress.clear_stop();
+        assert!(!progress.stop_requested());
+        assert_eq!(progress.snapshot().best_colors, Some(3));
+        progress.request_stop();
+
+        progress.reset();
+
+        assert!(!progress.stop_requested());
+
+        assert_eq!(progress.snapshot().total_iterations(), 0);
+        assert!(progress.snapshot().history.is_empty());
+    }
+
+    #[test]
+    fn test_run_job() {
+        static PARENT: Progress = Progress::new();
+
+        let first = Arc::new(Progress::for_job(Some(&PARENT)));
+        let second = Arc::new(Progress::for_job(Some(&PARENT)));
+
+        first.request_stop();
+
+        assert!(run_job(&first, stop_requested));
+        assert!(!run_job(&second, stop_requested));
+        assert!(!PARENT.stop_requested());
+
+        run_job(&first, || {
+            record_colors(5);
+            // The threads the job spawns record to it too
+            let handle = current();
+            std::thread::scope(|scope| scope.spawn(|| handle.run(|| record_colors(4))).join())
+                .unwrap();
+        });
+        run_job(&second, || record_colors(6));
+
+        assert_eq!(first.snapshot().best_colors, Some(4));
+        assert_eq!(second.snapshot().best_colors, Some(6));
+        assert_eq!(PARENT.snapshot().best_colors, Some(4));
+    }
+}
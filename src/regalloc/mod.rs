@@ -0,0 +1,217 @@
+use crate::graph::adj_list::AdjList;
+use std::error::Error;
+use std::io::BufRead;
+
+/// Registers assigned to the vertices of an interference graph.
+#[derive(Debug, PartialEq)]
+pub struct Allocation {
+    /// Register (in `1..=k`) of each vertex, or `None` if the vertex is spilled.
+    pub registers: Vec<Option<usize>>,
+    pub spilled: Vec<usize>,
+}
+
+/// Reads the precolored vertices (the machine registers) of an interference graph with
+/// `num_vertices` vertices, given as `r <vertex> <register>` lines next to the DIMACS edges.
+///
+/// Vertices and registers are 1-indexed. Returns the (0-indexed) register of each vertex, if any.
+pub fn read_precoloring(
+    reader: impl BufRead,
+    num_vertices: usize,
+    k: usize,
+) -> Result<Vec<Option<usize>>, Box<dyn Error>> {
+    let mut precoloring = vec![None; num_vertices];
+
+    for line in reader.lines() {
+        let line = line?;
+        let splits: Vec<&str> = line.split_whitespace().collect();
+
+        if let ["r", vertex, register] = splits[..] {
+            let vertex: usize = vertex.parse()?;
+            let register: usize = register.parse()?;
+
+            if vertex == 0 || vertex > num_vertices {
+                return Err(
+                    format!("vertex {vertex} is out of the range 1..={num_vertices}").into(),
+                );
+            }
+            if register == 0 || register > k {
+                return Err(format!("register {register} is out of the range 1..={k}").into());
+            }
+
+            precoloring[vertex - 1] = Some(register - 1);
+        }
+    }
+
+    Ok(precoloring)
+}
+
+/// Assigns one of `k` registers to as many vertices of the interference `graph` as possible,
+/// keeping the `precoloring`, and spills the others.
+///
+/// Follows Chaitin and Briggs: vertices with fewer than `k` neighbors left are removed (simplified)
+/// first, since they can always be colored; when there are none, the vertex with the most
+/// neighbors is removed optimistically. Vertices are then colored in the reverse order, spilling
+/// the ones with no register left. Finally, each spilled vertex is given the register of a
+/// neighbor that can move to another register, if possible.
+///
+/// Fails if two adjacent vertices are precolored with the same register.
+pub fn allocate(
+    graph: &AdjList,
+    precoloring: &[Option<usize>],
+    k: usize,
+) -> Result<Allocation, String> {
+    let n = graph.num_vertices();
+    let adj_list = graph.adj_list();
+
+    for (u, v) in graph.edges() {
+        if precoloring[u].is_some() && precoloring[u] == precoloring[v] {
+            return Err(format!(
+                "vertices {} and {} interfere, but are both precolored with register {}",
+                u + 1,
+                v + 1,
+                precoloring[u].unwrap() + 1
+            ));
+        }
+    }
+
+    // Precolored vertices are never removed, so they always count as neighbors
+    let mut degrees: Vec<usize> = adj_list.iter().map(Vec::len).collect();
+    let mut removed: Vec<bool> = precoloring.iter().map(Option::is_some).collect();
+    let mut stack = Vec::new();
+
+    while stack.len() + precoloring.iter().flatten().count() < n {
+        let remaining = (0..n).filter(|v| !removed[*v]);
+        let v = match remaining.clone().find(|v| degrees[*v] < k) {
+            Some(v) => v,
+            None => remaining.max_by_key(|v| degrees[*v]).unwrap(),
+        };
+
+        removed[v] = true;
+        stack.push(v);
+
+        for u in adj_list[v].iter() {
+            degrees[*u] -= 1;
+        }
+    }
+
+    let mut registers: Vec<Option<usize>> = precoloring.iter().map(|r| r.map(|r| r + 1)).collect();
+
+    while let Some(v) = stack.pop() {
+        registers[v] = free_register(graph, &registers, v, k);
+    }
+
+    // Repair: a spilled vertex takes the register of its only neighbor using it, if that neighbor
+    // can move to a free register
+    for v in 0..n {
+        if registers[v].is_some() {
+            continue;
+        }
+
+        for register in 1..=k {
+            let holders: Vec<usize> = adj_list[v]
+                .iter()
+                .copied()
+                .filter(|u| registers[*u] == Some(register))
+                .collect();
+
+            let [u] = holders[..] else {
+                continue;
+            };
+            if precoloring[u].is_some() {
+                continue;
+            }
+
+            registers[u] = None;
+            registers[v] = Some(register);
+
+            match free_register(graph, &registers, u, k) {
+                Some(other) => {
+                    registers[u] = Some(other);
+                    break;
+                }
+                None => {
+                    registers[v] = None;
+                    registers[u] = Some(register);
+                }
+            }
+        }
+    }
+
+    let spilled = (0..n).filter(|v| registers[*v].is_none()).collect();
+
+    Ok(Allocation { registers, spilled })
+}
+
+/// Smallest register in `1..=k` not used by the neighbors of `v`, if any.
+fn free_register(
+    graph: &AdjList,
+    registers: &[Option<usize>],
+    v: usize,
+    k: usize,
+) -> Option<usize> {
+    let mut used = vec![false; k + 1];
+
+    for u in graph.adj_list()[v].iter() {
+        if let Some(register) = registers[*u] {
+            used[register] = true;
+        }
+    }
+
+    (1..=k).find(|register| !used[*register])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    fn is_allocation_valid(graph: &AdjList, allocation: &Allocation) -> bool {
+        graph.edges().iter().all(|(u, v)| {
+            allocation.registers[*u].is_none()
+                || allocation.registers[*u] != allocation.registers[*v]
+        })
+    }
+
+    #[test]
+    fn test_allocate() {
+        // Enough registers: nothing is spilled
+        let graph = testing::myciel3();
+        let allocation = allocate(&graph, &[None; 11], 4).unwrap();
+
+        assert!(allocation.spilled.is_empty());
+        assert!(is_allocation_valid(&graph, &allocation));
+
+        // One vertex of K4 can't get one of 3 registers
+        let graph = AdjList::complete(4);
+        let allocation = allocate(&graph, &[None; 4], 3).unwrap();
+
+        assert_eq!(allocation.spilled.len(), 1);
+        assert!(is_allocation_valid(&graph, &allocation));
+
+        // Vertices 0 and 4 of a cycle with 6 vertices are at an even distance along both paths
+        // between them, so precoloring them with different registers forces a spill on each path
+        let graph = testing::cycle(6);
+        let precoloring = [Some(0), None, None, None, Some(1), None];
+        let allocation = allocate(&graph, &precoloring, 2).unwrap();
+
+        assert_eq!(allocation.registers[0], Some(1));
+        assert_eq!(allocation.registers[4], Some(2));
+        assert!(is_allocation_valid(&graph, &allocation));
+        assert_eq!(allocation.spilled.len(), 2);
+
+        // Adjacent vertices can't be precolored with the same register
+        assert!(allocate(&graph, &[Some(0), Some(0), None, None, None, None], 2).is_err());
+    }
+
+    #[test]
+    fn test_read_precoloring() {
+        let input = "p edge 3 2\ne 1 2\ne 2 3\nr 1 2\nr 3 1\n";
+
+        assert_eq!(
+            read_precoloring(input.as_bytes(), 3, 2).unwrap(),
+            vec![Some(1), None, Some(0)]
+        );
+        assert!(read_precoloring(input.as_bytes(), 3, 1).is_err());
+        assert!(read_precoloring("r 4 1\n".as_bytes(), 3, 2).is_err());
+    }
+}
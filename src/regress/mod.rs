@@ -0,0 +1,206 @@
+use crate::algorithms::{count_colors, is_coloring_valid, solve_with_defaults};
+use crate::args::Algorithm;
+use crate::generate::families::Family;
+use crate::generate::random::{generate_random, RandomParams};
+use crate::graph::adj_list::AdjList;
+use clap::ValueEnum;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+
+/// Number of colors found by each algorithm (by its command line name) on each instance.
+pub type Baselines = BTreeMap<String, BTreeMap<String, usize>>;
+
+/// A small instance whose chromatic number is known.
+pub struct Case {
+    pub name: String,
+    pub graph: AdjList,
+    pub chromatic_number: usize,
+}
+
+/// Outcome of running an algorithm on a [`Case`].
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    /// Within the tolerance of the baseline.
+    Ok,
+    /// Used more colors than the baseline allows.
+    Regressed,
+    /// Returned a coloring that isn't valid (or that disagrees with the reported number of colors).
+    Invalid,
+    /// There's no baseline to compare to.
+    Missing,
+}
+
+/// Builds the regression suite: the Mycielski instances of the DIMACS benchmarks, queen graphs and
+/// dense random instances with a planted coloring.
+pub fn suite() -> Result<Vec<Case>, Box<dyn Error>> {
+    let mut cases = Vec::new();
+
+    // The myciel instances of the DIMACS benchmarks, built in code
+    for k in [4, 5, 6] {
+        let family = Family::Mycielski { k };
+
+        cases.push(Case {
+            name: format!("myciel{}", k - 1),
+            graph: family.generate()?,
+            chromatic_number: k,
+        });
+    }
+
+    for (n, chromatic_number) in [(5, 5), (6, 7), (7, 7)] {
+        let family = Family::Queen { n };
+
+        cases.push(Case {
+            name: family.name(),
+            graph: family.generate()?,
+            chromatic_number,
+        });
+    }
+
+    // Dense enough that the planted coloring is the optimal one
+    for (k, seed) in [(4, 0), (6, 1)] {
+        let instance = generate_random(&RandomParams {
+            num_vertices: 60,
+            probability: 0.9,
+            seed: Some(seed),
+            planted: Some(k),
+            flatness: None,
+        })?;
+
+        cases.push(Case {
+            name: instance.name,
+            graph: instance.graph,
+            chromatic_number: k,
+        });
+    }
+
+    Ok(cases)
+}
+
+/// Compares the `coloring` found for `case` against the `baseline`, allowing `tolerance` extra
+/// colors.
+pub fn check(
+    case: &Case,
+    (num_colors, coloring): &(usize, Vec<usize>),
+    baseline: Option<usize>,
+    tolerance: usize,
+) -> Status {
+    if !is_coloring_valid(&case.graph, coloring) || count_colors(coloring) != *num_colors {
+        Status::Invalid
+    } else {
+        match baseline {
+            Some(baseline) if *num_colors > baseline + tolerance => Status::Regressed,
+            Some(_) => Status::Ok,
+            None => Status::Missing,
+        }
+    }
+}
+
+/// Runs every algorithm over the suite, `runs` times each, printing a report on the best result of
+/// each algorithm. If `update` is set, the results are stored as the new baselines instead.
+///
+/// Returns whether no algorithm regressed (or produced an invalid coloring).
+pub fn regress(
+    baselines_path: &str,
+    tolerance: usize,
+    runs: usize,
+    update: bool,
+) -> Result<bool, Box<dyn Error>> {
+    let mut baselines: Baselines = if update {
+        Baselines::new()
+    } else {
+        let file = File::open(baselines_path)
+            .map_err(|err| format!("failed to open the baselines at {baselines_path}: {err}"))?;
+        serde_json::from_reader(BufReader::new(file))?
+    };
+    let mut passed = true;
+
+    for case in suite()? {
+        for algorithm in Algorithm::value_variants() {
+            let name = algorithm
+                .to_possible_value()
+                .unwrap()
+                .get_name()
+                .to_string();
+            // The stochastic algorithms are compared by their best run, which varies less
+            let solution = (0..runs.max(1))
+                .map(|_| solve_with_defaults(&case.graph, algorithm, Some(case.chromatic_number)))
+                .min_by_key(|(num_colors, _)| *num_colors)
+                .unwrap();
+            let baseline = baselines
+                .get(&case.name)
+                .and_then(|b| b.get(&name))
+                .copied();
+            let status = check(&case, &solution, baseline, tolerance);
+
+            println!(
+                "{} {name}: {} colors (χ = {}, baseline {}) {status:?}",
+                case.name,
+                solution.0,
+                case.chromatic_number,
+                baseline.map_or(String::from("-"), |b| b.to_string()),
+            );
+
+            match status {
+                Status::Regressed | Status::Invalid => passed = false,
+                Status::Missing if !update => passed = false,
+                _ => {}
+            }
+
+            if update && status != Status::Invalid {
+                baselines
+                    .entry(case.name.clone())
+                    .or_default()
+                    .insert(name, solution.0);
+            }
+        }
+    }
+
+    if update {
+        let mut writer = BufWriter::new(File::create(baselines_path)?);
+        serde_json::to_writer_pretty(&mut writer, &baselines)?;
+        writeln!(writer)?;
+    }
+
+    Ok(passed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_suite() {
+        let cases = suite().unwrap();
+
+        assert_eq!(cases.len(), 8);
+        assert!(cases
+            .iter()
+            .all(|case| case.graph.num_vertices() >= case.chromatic_number));
+    }
+
+    #[test]
+    fn test_check() {
+        let case = Case {
+            name: String::from("triangle"),
+            graph: AdjList::complete(3),
+            chromatic_number: 3,
+        };
+        let solution = (3, vec![1, 2, 3]);
+
+        assert_eq!(check(&case, &solution, Some(3), 0), Status::Ok);
+        assert_eq!(check(&case, &solution, Some(2), 0), Status::Regressed);
+        assert_eq!(check(&case, &solution, Some(2), 1), Status::Ok);
+        assert_eq!(check(&case, &solution, None, 0), Status::Missing);
+        assert_eq!(
+            check(&case, &(2, vec![1, 2, 2]), Some(3), 0),
+            Status::Invalid
+        );
+        assert_eq!(check(&case, &(3, vec![1, 2, 4]), Some(3), 0), Status::Ok);
+        assert_eq!(
+            check(&case, &(2, vec![1, 2, 3]), Some(3), 0),
+            Status::Invalid
+        );
+    }
+}
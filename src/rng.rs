@@ -0,0 +1,148 @@
+//! Source of randomness for the algorithms.
+//!
+//! Every random choice comes from the generator of the current thread, which is
+//! `rand::thread_rng` unless the thread was seeded. Given a seed, parallel searches derive one for
+//! each of their tasks (see [`with_seed`]), so their results don't depend on which thread runs
+//! which task.
+//!
+//! With the `deterministic` feature, every thread starts seeded with a fixed seed (and the
+//! algorithms run on a single thread), so each run gives the same results.
+
+use rand::{rngs::StdRng, RngCore, SeedableRng};
+use std::cell::RefCell;
+
+/// Seed of the generator of each thread with the `deterministic` feature, until it's reseeded.
+const SEED: u64 = 0;
+
+thread_local! {
+    /// The seeded generator of the current thread, if any.
+    static RNG: RefCell<Option<StdRng>> =
+        RefCell::new(cfg!(feature = "deterministic").then(|| StdRng::seed_from_u64(SEED)));
+}
+
+/// A handle to the generator of the current thread.
+pub struct ThreadGenerator;
+
+pub fn rng() -> ThreadGenerator {
+    ThreadGenerator
+}
+
+/// Restarts the generator of the current thread from `seed`.
+pub fn reseed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = Some(StdRng::seed_from_u64(seed)));
+}
+
+/// Runs `task` with the generator of the current thread seeded with `seed`, restoring the previous
+/// one afterwards.
+pub fn with_seed<T>(seed: u64, task: impl FnOnce() -> T) -> T {
+    let previous = RNG.with(|rng| rng.replace(Some(StdRng::seed_from_u64(seed))));
+    let result = task();
+
+    RNG.with(|rng| *rng.borrow_mut() = previous);
+
+    result
+}
+
+/// The seed of the `index`-th task of a search seeded with `seed`, mixed (as in SplitMix64) so
+/// nearby indices give unrelated generators.
+pub fn derive_seed(seed: u64, index: u64) -> u64 {
+    let mut z = seed.wrapping_add(index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+    z ^ (z >> 31)
+}
+
+/// Runs `f` with the seeded generator of the current thread, or with `rand::thread_rng`.
+fn with_generator<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    RNG.with(|rng| match rng.borrow_mut().as_mut() {
+        Some(rng) => f(rng),
+        None => f(&mut rand::thread_rng()),
+    })
+}
+
+impl RngCore for ThreadGenerator {
+    fn next_u32(&mut self) -> u32 {
+        with_generator(|rng| rng.next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        with_generator(|rng| rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        with_generator(|rng| rng.fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        with_generator(|rng| rng.try_fill_bytes(dest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_reseed() {
+        reseed(42);
+        let first: Vec<u32> = (0..10).map(|_| rng().gen()).collect();
+        reseed(42);
+        let second: Vec<u32> = (0..10).map(|_| rng().gen()).collect();
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_with_seed() {
+        reseed(1);
+        let outside: u32 = rng().gen();
+
+        reseed(1);
+        let first: Vec<u32> = with_seed(7, || (0..10).map(|_| rng().gen()).collect());
+        let second: Vec<u32> = with_seed(7, || (0..10).map(|_| rng().gen()).collect());
+
+        assert_eq!(first, second);
+        // The generator of the thread picks up where it was
+        assert_eq!(rng().gen::<u32>(), outside);
+
+        assert_ne!(derive_seed(0, 0), derive_seed(0, 1));
+        assert_ne!(derive_seed(0, 1), derive_seed(1, 0));
+    }
+
+    #[cfg(feature = "deterministic")]
+    #[test]
+    fn test_deterministic_algorithms() {
+        use crate::algorithms::{
+            genetic::{genetic, GeneticParams},
+            grasp::{grasp_wrapper, GraspParams},
+        };
+        use crate::testing;
+
+        let graph = testing::myciel4();
+
+        let genetic_params = GeneticParams {
+            generations: 500,
+            population_size: 20,
+            offsprings_per_generation: 2,
+            mutation_probability: 0.01,
+            selected_population_ratio: 0.2,
+            mode: Default::default(),
+            repair_strategy: Default::default(),
+            crossover: Default::default(),
+            lower_bound: None,
+        };
+
+        reseed(0);
+        let grasp_solution = grasp_wrapper(&graph, &GraspParams::default());
+        let genetic_solution = genetic(&graph, &genetic_params, None).0;
+        reseed(0);
+
+        assert_eq!(
+            grasp_wrapper(&graph, &GraspParams::default()),
+            grasp_solution
+        );
+        assert_eq!(genetic(&graph, &genetic_params, None).0, genetic_solution);
+    }
+}
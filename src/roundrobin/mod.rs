@@ -0,0 +1,189 @@
+use crate::graph::adj_list::AdjList;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+/// Games between teams. Teams are referred to by their index.
+#[derive(Debug, PartialEq)]
+pub struct Tournament {
+    pub teams: Vec<String>,
+    /// Teams of each game. The same pair may play more than once.
+    pub games: Vec<(usize, usize)>,
+}
+
+/// A single round robin between `num_teams` teams, named from 1 to `num_teams`: each pair of teams
+/// plays once.
+pub fn single_round_robin(num_teams: usize) -> Tournament {
+    Tournament {
+        teams: (1..=num_teams).map(|team| team.to_string()).collect(),
+        games: (0..num_teams)
+            .flat_map(|u| (u + 1..num_teams).map(move |v| (u, v)))
+            .collect(),
+    }
+}
+
+/// Reads the games from a CSV file with one `home,away` pair per line.
+///
+/// Blank lines, lines starting with `#` and a `home,away` header are skipped.
+pub fn read_fixtures(reader: impl BufRead) -> Result<Tournament, Box<dyn Error>> {
+    let mut teams = Vec::new();
+    let mut indices: HashMap<String, usize> = HashMap::new();
+    let mut games = Vec::new();
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line.eq_ignore_ascii_case("home,away") {
+            continue;
+        }
+
+        let Some((home, away)) = line
+            .split_once(',')
+            .map(|(home, away)| (home.trim(), away.trim()))
+            .filter(|(home, away)| !home.is_empty() && !away.is_empty() && !away.contains(','))
+        else {
+            return Err(format!("line {} isn't a home,away pair", number + 1).into());
+        };
+
+        if home == away {
+            return Err(format!("line {}: {home} can't play itself", number + 1).into());
+        }
+
+        let mut index = |team: &str| {
+            *indices.entry(team.to_string()).or_insert_with(|| {
+                teams.push(team.to_string());
+                teams.len() - 1
+            })
+        };
+
+        games.push((index(home), index(away)));
+    }
+
+    Ok(Tournament { teams, games })
+}
+
+/// Builds the conflict graph of the games: two games are adjacent if they share a team, so it's
+/// the line graph of the (multi)graph of the teams. Its colorings are the edge colorings of the
+/// team graph, where the colors are the rounds.
+pub fn conflict_graph(tournament: &Tournament) -> AdjList {
+    let mut graph = AdjList::new(tournament.games.len());
+    let mut games_of_team: Vec<Vec<usize>> = vec![Vec::new(); tournament.teams.len()];
+
+    for (game, (home, away)) in tournament.games.iter().enumerate() {
+        let mut opponents: Vec<usize> = games_of_team[*home].clone();
+
+        // Games between the same teams share both of them, but are only adjacent once
+        for other in games_of_team[*away].iter() {
+            if !opponents.contains(other) {
+                opponents.push(*other);
+            }
+        }
+
+        for other in opponents {
+            graph.add_edge(other, game);
+        }
+
+        games_of_team[*home].push(game);
+        games_of_team[*away].push(game);
+    }
+
+    graph
+}
+
+/// Vizing's bounds on the number of rounds (the chromatic index of the team graph): at least the
+/// most games of a team, and at most that plus the most games between the same pair of teams.
+pub fn vizing_bounds(tournament: &Tournament) -> (usize, usize) {
+    let mut games_of_team = vec![0; tournament.teams.len()];
+    let mut multiplicities: HashMap<(usize, usize), usize> = HashMap::new();
+
+    for (home, away) in tournament.games.iter() {
+        games_of_team[*home] += 1;
+        games_of_team[*away] += 1;
+        *multiplicities
+            .entry((*home.min(away), *home.max(away)))
+            .or_default() += 1;
+    }
+
+    let max_degree = games_of_team.into_iter().max().unwrap_or(0);
+    let max_multiplicity = multiplicities.into_values().max().unwrap_or(0);
+
+    (max_degree, max_degree + max_multiplicity)
+}
+
+/// Writes the games of each (1-indexed) round as `Round <round>: <home>-<away> ...` lines.
+pub fn write_rounds(
+    writer: &mut impl Write,
+    tournament: &Tournament,
+    rounds: &[usize],
+) -> io::Result<()> {
+    let num_rounds = rounds.iter().max().copied().unwrap_or(0);
+
+    for round in 1..=num_rounds {
+        let games: Vec<String> = tournament
+            .games
+            .iter()
+            .zip(rounds)
+            .filter(|(_, r)| **r == round)
+            .map(|((home, away), _)| {
+                format!("{}-{}", tournament.teams[*home], tournament.teams[*away])
+            })
+            .collect();
+
+        writeln!(writer, "Round {round}: {}", games.join(" "))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{count_colors, grasp::grasp_wrapper, is_coloring_valid};
+
+    #[test]
+    fn test_single_round_robin() {
+        let tournament = single_round_robin(6);
+
+        assert_eq!(tournament.games.len(), 15);
+        assert_eq!(vizing_bounds(&tournament), (5, 6));
+
+        // Each game shares a team with 4 + 4 other games
+        let graph = conflict_graph(&tournament);
+
+        assert!(graph.adj_list().iter().all(|games| games.len() == 8));
+
+        // With an odd number of teams, one team rests each round, so there are as many rounds as
+        // teams
+        let tournament = single_round_robin(5);
+        let graph = conflict_graph(&tournament);
+        let (num_rounds, rounds) = grasp_wrapper(&graph, &Default::default());
+
+        assert!(is_coloring_valid(&graph, &rounds));
+        assert_eq!(count_colors(&rounds), num_rounds);
+        assert!(num_rounds >= 5);
+    }
+
+    #[test]
+    fn test_read_fixtures() {
+        let csv = "home,away\nreds,blues\nblues,greens\nblues,reds\n";
+        let tournament = read_fixtures(csv.as_bytes()).unwrap();
+
+        assert_eq!(tournament.teams, vec!["reds", "blues", "greens"]);
+        assert_eq!(tournament.games, vec![(0, 1), (1, 2), (1, 0)]);
+        // Blues play three games, two of them against the reds
+        assert_eq!(vizing_bounds(&tournament), (3, 5));
+        assert_eq!(conflict_graph(&tournament).edges().len(), 3);
+
+        let mut buffer = Vec::new();
+        write_rounds(&mut buffer, &tournament, &[1, 2, 3]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "Round 1: reds-blues\nRound 2: blues-greens\nRound 3: blues-reds\n"
+        );
+
+        assert!(read_fixtures("reds,reds\n".as_bytes()).is_err());
+        assert!(read_fixtures("reds\n".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,433 @@
+//! A JSON-RPC 2.0 protocol over standard input and output, one message per line, to embed the
+//! solver in other processes.
+//!
+//! - `load` takes the instance, from a file (`path`) or inline (`instance`, in the DIMACS format or
+//!   as a JSON object with `num_vertices` and 1-indexed `edges`), replacing the loaded one.
+//! - `solve` starts solving the loaded instance in the background with an `algorithm`.
+//! - `cancel` asks the running algorithm to stop, keeping the best coloring it found so far.
+//! - `get-incumbent` returns the status of the run and its best number of colors, along with the
+//!   coloring once it's done.
+//! - `add-edge` and `remove-edge` change an edge (`u` and `v`, 1-indexed) of the instance once it's
+//!   solved, repairing the coloring around it instead of solving it again (see
+//!   [`Recolorer`]).
+//!
+//! A single instance is solved at a time, recording its own [`progress`](crate::progress).
+
+use crate::algorithms::{
+    recolor::{RecolorParams, Recolorer},
+    solve_with_defaults, Solution,
+};
+use crate::args::Algorithm;
+use crate::graph::{adj_list::AdjList, clique::greedy_clique};
+use crate::input;
+use crate::progress::{self, Progress};
+use crate::server::parse_instance;
+use clap::ValueEnum;
+use serde_json::{json, Value};
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+struct Run {
+    algorithm: Algorithm,
+    cancelled: bool,
+    progress: Arc<Progress>,
+    handle: Option<JoinHandle<Solution>>,
+    result: Option<Solution>,
+}
+
+#[derive(Default)]
+pub struct Session {
+    graph: Option<Arc<AdjList>>,
+    run: Option<Run>,
+    /// The solved instance and its coloring, once an edge was changed.
+    recolorer: Option<Recolorer>,
+}
+
+/// Answers each request read from `reader` on `writer`, until the end of the input.
+pub fn serve(reader: impl BufRead, writer: &mut impl Write) -> io::Result<()> {
+    let mut session = Session::default();
+
+    for line in reader.lines() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(response) = session.handle(&line) {
+            writeln!(writer, "{response}")?;
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+impl Session {
+    /// Answers the request in `line`, unless it's a notification (a request without an id).
+    pub fn handle(&mut self, line: &str) -> Option<Value> {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(err) => return Some(error(Value::Null, PARSE_ERROR, err.to_string())),
+        };
+        let id = request.get("id").cloned();
+        let Some(method) = request.get("method").and_then(Value::as_str) else {
+            return Some(error(
+                id.unwrap_or(Value::Null),
+                INVALID_REQUEST,
+                "the request has no method",
+            ));
+        };
+        let params = request.get("params").cloned().unwrap_or(json!({}));
+
+        let result = match method {
+            "load" => self.load(&params),
+            "solve" => self.solve(&params),
+            "cancel" => Ok(self.cancel()),
+            "get-incumbent" => self.incumbent(),
+            "add-edge" => self.change_edge(&params, true),
+            "remove-edge" => self.change_edge(&params, false),
+            _ => Err((METHOD_NOT_FOUND, format!("there's no method {method}"))),
+        };
+
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error(id, code, message),
+        })
+    }
+
+    fn load(&mut self, params: &Value) -> Result<Value, (i64, String)> {
+        self.check_idle()?;
+
+        let graph = match (
+            params.get("path").and_then(Value::as_str),
+            params.get("instance").and_then(Value::as_str),
+        ) {
+            (Some(path), None) => match input::read_graph_from_file(path) {
+                Ok(Some(graph)) => graph,
+                Ok(None) => return Err((INVALID_PARAMS, format!("{path} has no problem line"))),
+                Err(err) => return Err((INVALID_PARAMS, format!("{path}: {err}"))),
+            },
+            (None, Some(instance)) => {
+                parse_instance(instance.as_bytes()).map_err(|err| (INVALID_PARAMS, err))?
+            }
+            _ => {
+                return Err((
+                    INVALID_PARAMS,
+                    "exactly one of path and instance is required".to_string(),
+                ))
+            }
+        };
+
+        let result = json!({
+            "num_vertices": graph.num_vertices(),
+            "num_edges": graph.edges().len(),
+        });
+        self.graph = Some(Arc::new(graph));
+        self.run = None;
+        self.recolorer = None;
+
+        Ok(result)
+    }
+
+    fn solve(&mut self, params: &Value) -> Result<Value, (i64, String)> {
+        self.check_idle()?;
+
+        let Some(graph) = self.graph.clone() else {
+            return Err((INVALID_PARAMS, "no instance was loaded".to_string()));
+        };
+        let algorithm = match params.get("algorithm").and_then(Value::as_str) {
+            Some(name) => Algorithm::from_str(name, true)
+                .map_err(|_| (INVALID_PARAMS, format!("unknown algorithm {name}")))?,
+            None => Algorithm::default(),
+        };
+
+        self.recolorer = None;
+        let progress = Arc::new(Progress::for_job(None));
+        let handle = {
+            let (algorithm, progress) = (algorithm.clone(), Arc::clone(&progress));
+
+            thread::spawn(move || {
+                let lower_bound = greedy_clique(&graph).len();
+                progress::run_job(&progress, || {
+                    solve_with_defaults(&graph, &algorithm, Some(lower_bound))
+                })
+            })
+        };
+
+        self.run = Some(Run {
+            algorithm,
+            cancelled: false,
+            progress,
+            handle: Some(handle),
+            result: None,
+        });
+
+        Ok(json!({ "started": true }))
+    }
+
+    fn cancel(&mut self) -> Value {
+        let Some(run) = self.run.as_mut().filter(|run| run.handle.is_some()) else {
+            return json!({ "cancelled": false });
+        };
+
+        run.progress.request_stop();
+        run.cancelled = true;
+
+        json!({ "cancelled": true })
+    }
+
+    fn incumbent(&mut self) -> Result<Value, (i64, String)> {
+        self.collect()?;
+
+        let Some(run) = &self.run else {
+            return Ok(json!({ "status": "idle" }));
+        };
+        let snapshot = run.progress.snapshot();
+        let algorithm = run.algorithm.to_possible_value().unwrap();
+
+        Ok(match &run.result {
+            None => json!({
+                "status": "running",
+                "algorithm": algorithm.get_name(),
+                "num_colors": snapshot.best_colors,
+                "elapsed": snapshot.elapsed.as_millis(),
+            }),
+            Some((num_colors, coloring)) => json!({
+                "status": if run.cancelled { "cancelled" } else { "done" },
+                "algorithm": algorithm.get_name(),
+                "num_colors": num_colors,
+                "coloring": coloring,
+            }),
+        })
+    }
+
+    fn change_edge(&mut self, params: &Value, add: bool) -> Result<Value, (i64, String)> {
+        self.check_idle()?;
+
+        let (Some(graph), Some(run)) = (&self.graph, &mut self.run) else {
+            return Err((INVALID_REQUEST, "no instance was solved".to_string()));
+        };
+        let Some((_, coloring)) = &run.result else {
+            return Err((INVALID_REQUEST, "no instance was solved".to_string()));
+        };
+
+        let n = graph.num_vertices();
+        let vertex = |name: &str| match params.get(name).and_then(Value::as_u64) {
+            Some(v) if (1..=n as u64).contains(&v) => Ok(v as usize - 1),
+            _ => Err((
+                INVALID_PARAMS,
+                format!("{name} must be a vertex in 1..={n}"),
+            )),
+        };
+        let (u, v) = (vertex("u")?, vertex("v")?);
+
+        let recolorer = self.recolorer.get_or_insert_with(|| {
+            Recolorer::new(
+                AdjList::clone(graph),
+                coloring.clone(),
+                RecolorParams::default(),
+            )
+        });
+
+        if add {
+            recolorer.add_edge(u, v);
+        } else {
+            recolorer.remove_edge(u, v);
+        }
+
+        let result = (recolorer.num_colors(), recolorer.coloring().to_vec());
+        self.graph = Some(Arc::new(recolorer.graph().clone()));
+        run.result = Some(result.clone());
+
+        Ok(json!({ "num_colors": result.0, "coloring": result.1 }))
+    }
+
+    /// Stores the result of the run, if it's done. A run that panicked is forgotten, and its panic
+    /// is reported as an error.
+    fn collect(&mut self) -> Result<(), (i64, String)> {
+        let Some(run) = self.run.as_mut() else {
+            return Ok(());
+        };
+
+        if !run
+            .handle
+            .as_ref()
+            .is_some_and(|handle| handle.is_finished())
+        {
+            return Ok(());
+        }
+
+        match run.handle.take().map(JoinHandle::join) {
+            Some(Ok(result)) => run.result = Some(result),
+            Some(Err(panic)) => {
+                self.run = None;
+                let message = panic
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| panic.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+
+                return Err((INTERNAL_ERROR, format!("the run failed: {message}")));
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    fn check_idle(&mut self) -> Result<(), (i64, String)> {
+        self.collect()?;
+
+        match &self.run {
+            Some(run) if run.handle.is_some() => Err((
+                INVALID_REQUEST,
+                "an instance is being solved; cancel it first".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn error(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::is_coloring_valid;
+    use std::time::Duration;
+
+    #[test]
+    fn test_session() {
+        let mut session = Session::default();
+        let mut call = |request: Value| session.handle(&request.to_string()).unwrap();
+
+        let response = call(json!({ "jsonrpc": "2.0", "id": 1, "method": "solve" }));
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let response = call(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "load",
+            "params": { "instance": "p edge 3 3\ne 1 2\ne 2 3\ne 1 3\n" },
+        }));
+        assert_eq!(
+            response["result"],
+            json!({ "num_vertices": 3, "num_edges": 3 })
+        );
+
+        let response = call(json!({ "jsonrpc": "2.0", "id": 3, "method": "get-incumbent" }));
+        assert_eq!(response["result"]["status"], "idle");
+
+        let response = call(json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "solve",
+            "params": { "algorithm": "genetic" },
+        }));
+        assert_eq!(response["result"]["started"], true);
+
+        let mut response = call(json!({ "jsonrpc": "2.0", "id": 5, "method": "get-incumbent" }));
+        while response["result"]["status"] == "running" {
+            thread::sleep(Duration::from_millis(10));
+            response = call(json!({ "jsonrpc": "2.0", "id": 5, "method": "get-incumbent" }));
+        }
+
+        let coloring: Vec<usize> =
+            serde_json::from_value(response["result"]["coloring"].clone()).unwrap();
+
+        assert_eq!(response["id"], 5);
+        assert_eq!(response["result"]["status"], "done");
+        assert_eq!(response["result"]["num_colors"], 3);
+        assert!(is_coloring_valid(&AdjList::complete(3), &coloring));
+
+        // The coloring is repaired as the edges of the triangle change
+        let response = call(json!({
+            "jsonrpc": "2.0",
+            "id": 6,
+            "method": "remove-edge",
+            "params": { "u": 1, "v": 2 },
+        }));
+        let coloring: Vec<usize> =
+            serde_json::from_value(response["result"]["coloring"].clone()).unwrap();
+        let mut graph = AdjList::new(3);
+        graph.add_edge(1, 2);
+        graph.add_edge(0, 2);
+
+        assert!(is_coloring_valid(&graph, &coloring));
+
+        let response = call(json!({
+            "jsonrpc": "2.0",
+            "id": 6,
+            "method": "add-edge",
+            "params": { "u": 2, "v": 1 },
+        }));
+        assert_eq!(response["result"]["num_colors"], 3);
+
+        let response = call(json!({
+            "jsonrpc": "2.0",
+            "id": 6,
+            "method": "add-edge",
+            "params": { "u": 1, "v": 4 },
+        }));
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        // Nothing is running anymore
+        let response = call(json!({ "jsonrpc": "2.0", "id": 6, "method": "cancel" }));
+        assert_eq!(response["result"]["cancelled"], false);
+
+        let response = call(json!({ "jsonrpc": "2.0", "id": 7, "method": "magic" }));
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        // Notifications aren't answered
+        assert!(session
+            .handle(r#"{"jsonrpc": "2.0", "method": "get-incumbent"}"#)
+            .is_none());
+        assert_eq!(session.handle("{").unwrap()["error"]["code"], PARSE_ERROR);
+    }
+
+    #[test]
+    fn test_cancelled_run() {
+        let mut session = Session::default();
+        let mut call = |method: &str, params: Value| {
+            session
+                .handle(
+                    &json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params })
+                        .to_string(),
+                )
+                .unwrap()
+        };
+
+        // An odd cycle, so the clique bound isn't met and GRASP doesn't finish at once
+        let edges: String = (1..=9).map(|v| format!("e {v} {}\n", v % 9 + 1)).collect();
+        call(
+            "load",
+            json!({ "instance": format!("p edge 9 9\n{edges}") }),
+        );
+        call("solve", json!({ "algorithm": "grasp" }));
+        call("cancel", json!({}));
+
+        let mut response = call("get-incumbent", json!({}));
+        while response["result"]["status"] == "running" {
+            thread::sleep(Duration::from_millis(10));
+            response = call("get-incumbent", json!({}));
+        }
+
+        assert_eq!(response["result"]["status"], "cancelled");
+        assert!(response["result"]["num_colors"].as_u64() >= Some(3));
+    }
+}
@@ -0,0 +1,136 @@
+//! Encoding of "can the graph be colored with `k` colors?" as a CNF formula, so it can be handed
+//! to a SAT solver.
+//!
+//! Each pair of a vertex and a color is a variable, true if the vertex has the color. The clauses
+//! state that every vertex has at least one color and at most one color, and that adjacent
+//! vertices don't share a color.
+
+use crate::graph::adj_list::AdjList;
+use crate::graph::clique::greedy_clique;
+use std::io::{self, Write};
+
+#[cfg(feature = "sat")]
+pub mod solver;
+
+/// A formula in conjunctive normal form, with DIMACS literals: variables are numbered from 1, and
+/// a negative literal is the negation of its variable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cnf {
+    pub num_variables: usize,
+    pub clauses: Vec<Vec<i64>>,
+}
+
+/// The variable of `vertex` having `color` (both 0-indexed) with `k` colors.
+pub fn variable(vertex: usize, color: usize, k: usize) -> i64 {
+    (vertex * k + color + 1) as i64
+}
+
+/// Encodes the `k`-coloring of `graph` as a CNF formula, satisfiable exactly when the graph can be
+/// colored with `k` colors.
+///
+/// The colors of a large clique are fixed (its `i`-th vertex takes the `i`-th color), since any
+/// coloring can be relabeled so they match, which rules out most of the symmetric assignments.
+pub fn encode(graph: &AdjList, k: usize) -> Cnf {
+    let n = graph.num_vertices();
+    let mut clauses = Vec::new();
+
+    for v in 0..n {
+        clauses.push((0..k).map(|color| variable(v, color, k)).collect());
+
+        for first in 0..k {
+            for second in first + 1..k {
+                clauses.push(vec![-variable(v, first, k), -variable(v, second, k)]);
+            }
+        }
+    }
+
+    for (u, neighbors) in graph.adj_list().iter().enumerate() {
+        for v in neighbors.iter().filter(|v| u < **v) {
+            for color in 0..k {
+                clauses.push(vec![-variable(u, color, k), -variable(*v, color, k)]);
+            }
+        }
+    }
+
+    for (color, v) in greedy_clique(graph).into_iter().enumerate() {
+        if color < k {
+            clauses.push(vec![variable(v, color, k)]);
+        } else {
+            // The clique alone needs more than `k` colors
+            clauses.push(Vec::new());
+            break;
+        }
+    }
+
+    Cnf {
+        num_variables: n * k,
+        clauses,
+    }
+}
+
+/// The coloring (with colors from 1) given by `model`, an assignment satisfying the formula of
+/// [`encode`] for a graph with `n` vertices and `k` colors.
+pub fn decode(model: &[bool], n: usize, k: usize) -> Vec<usize> {
+    (0..n)
+        .map(|v| {
+            (0..k)
+                .find(|color| model[variable(v, *color, k) as usize - 1])
+                .unwrap()
+                + 1
+        })
+        .collect()
+}
+
+/// Writes `cnf` in the DIMACS CNF format, with each line of `comment` as a comment line.
+pub fn write_cnf<W: Write>(writer: &mut W, cnf: &Cnf, comment: &str) -> io::Result<()> {
+    for line in comment.lines() {
+        writeln!(writer, "c {line}")?;
+    }
+    writeln!(writer, "p cnf {} {}", cnf.num_variables, cnf.clauses.len())?;
+
+    for clause in cnf.clauses.iter() {
+        for literal in clause {
+            write!(writer, "{literal} ")?;
+        }
+        writeln!(writer, "0")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing;
+
+    #[test]
+    fn test_encode() {
+        let graph = testing::cycle(3);
+        let cnf = encode(&graph, 3);
+
+        assert_eq!(cnf.num_variables, 9);
+        // At least one and at most one color for each vertex, a conflict clause for each edge and
+        // color, and the colors of the triangle
+        assert_eq!(cnf.clauses.len(), 3 + 3 * 3 + 3 * 3 + 3);
+        assert!(cnf
+            .clauses
+            .contains(&vec![-variable(0, 2, 3), -variable(1, 2, 3)]));
+
+        // The triangle doesn't fit in 2 colors
+        assert!(encode(&graph, 2).clauses.contains(&Vec::new()));
+    }
+
+    #[test]
+    fn test_write_cnf() {
+        let mut graph = AdjList::new(2);
+        graph.add_edge(0, 1);
+
+        let mut output = Vec::new();
+        write_cnf(&mut output, &encode(&graph, 2), "2-coloring").unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "c 2-coloring\np cnf 4 8\n1 2 0\n-1 -2 0\n3 4 0\n-3 -4 0\n-1 -3 0\n-2 -4 0\n1 0\n4 0\n"
+        );
+    }
+}
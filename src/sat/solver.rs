@@ -0,0 +1,166 @@
+//! Solves the formulas of [`super::encode`] in-process with varisat, so the exact chromatic number
+//! can be found without leaving the tool.
+//!
+//! Varisat can't be given a budget or interrupted directly, so the solver writes its (DRAT) proof
+//! into a [`Budget`], which fails the solver once it learnt too many clauses or the algorithms were
+//! asked to stop. Each conflict learns a clause, so that bounds the conflicts, although the proof
+//! reaches the budget in buffered chunks, so a few hundred more conflicts may be spent.
+
+use super::Cnf;
+use crate::progress;
+use std::io::{self, Write};
+use varisat::{ExtendFormula, Lit, ProofFormat, Solver};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// The value of each variable, in order, in an assignment satisfying every clause.
+    Satisfiable(Vec<bool>),
+    Unsatisfiable,
+    /// The budget of conflicts ran out, or the algorithms were asked to stop.
+    Unknown,
+}
+
+/// Solves `cnf`, giving up after about `max_conflicts` conflicts (if any).
+pub fn solve(cnf: &Cnf, max_conflicts: Option<usize>) -> Outcome {
+    let mut solver = Solver::new();
+    solver.write_proof(Budget::new(max_conflicts), ProofFormat::Drat);
+
+    for clause in cnf.clauses.iter() {
+        let literals: Vec<Lit> = clause
+            .iter()
+            .map(|literal| Lit::from_dimacs(*literal as isize))
+            .collect();
+        solver.add_clause(&literals);
+    }
+
+    let result = solver.solve();
+
+    // The budget may only run out as the proof is flushed, after a model was found
+    if let Some(model) = solver.model() {
+        let mut values = vec![false; cnf.num_variables];
+        for literal in model.into_iter().filter(|literal| literal.is_positive()) {
+            values[literal.index()] = true;
+        }
+        return Outcome::Satisfiable(values);
+    }
+
+    match result {
+        Ok(false) => Outcome::Unsatisfiable,
+        _ => Outcome::Unknown,
+    }
+}
+
+/// Sink of a DRAT proof counting the clauses it adds, which fails once there are more than
+/// `max_clauses` of them or the algorithms were asked to stop.
+struct Budget {
+    max_clauses: Option<usize>,
+    clauses: usize,
+    /// Whether the next byte starts a line of the proof.
+    line_start: bool,
+    /// Whether the current line of the proof deletes a clause.
+    deletion: bool,
+}
+
+impl Budget {
+    fn new(max_clauses: Option<usize>) -> Self {
+        Budget {
+            max_clauses,
+            clauses: 0,
+            line_start: true,
+            deletion: false,
+        }
+    }
+}
+
+impl Write for Budget {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for byte in buf {
+            if self.line_start {
+                self.deletion = *byte == b'd';
+            }
+            self.line_start = *byte == b'\n';
+            if self.line_start && !self.deletion {
+                self.clauses += 1;
+            }
+        }
+
+        if self
+            .max_clauses
+            .is_some_and(|max_clauses| self.clauses > max_clauses)
+            || progress::stop_requested()
+        {
+            return Err(io::Error::other("the SAT solver ran out of its budget"));
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sat::{decode, encode};
+    use crate::{algorithms::is_coloring_valid, testing};
+
+    #[test]
+    fn test_solve() {
+        let cnf = |clauses: Vec<Vec<i64>>| Cnf {
+            num_variables: 3,
+            clauses,
+        };
+
+        let Outcome::Satisfiable(model) = solve(
+            &cnf(vec![vec![1, 2], vec![-1, 3], vec![-3, -2], vec![-2]]),
+            None,
+        ) else {
+            panic!("the formula is satisfiable");
+        };
+        assert_eq!(model, vec![true, false, true]);
+
+        assert_eq!(
+            solve(
+                &cnf(vec![vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]]),
+                None
+            ),
+            Outcome::Unsatisfiable
+        );
+        assert_eq!(solve(&cnf(vec![vec![]]), None), Outcome::Unsatisfiable);
+    }
+
+    #[test]
+    fn test_solve_coloring() {
+        for fixture in testing::fixtures() {
+            let k = fixture.chromatic_number;
+            let n = fixture.graph.num_vertices();
+
+            let Outcome::Satisfiable(model) = solve(&encode(&fixture.graph, k), None) else {
+                panic!("{} can be colored with {k} colors", fixture.name);
+            };
+            let coloring = decode(&model, n, k);
+            assert!(
+                is_coloring_valid(&fixture.graph, &coloring),
+                "{}",
+                fixture.name
+            );
+
+            assert_eq!(
+                solve(&encode(&fixture.graph, k - 1), None),
+                Outcome::Unsatisfiable,
+                "{}",
+                fixture.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_budget() {
+        // Proving myciel5 can't be colored with 5 colors takes many conflicts
+        let cnf = encode(&testing::myciel5(), 5);
+
+        assert_eq!(solve(&cnf, Some(0)), Outcome::Unknown);
+    }
+}
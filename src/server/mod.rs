@@ -0,0 +1,699 @@
+//! A minimal HTTP/1.1 server that solves instances in the background.
+//!
+//! - `POST /jobs?algorithm=<algorithm>` takes an instance (in the DIMACS format, or as a JSON
+//!   object with `num_vertices` and 1-indexed `edges`) and starts solving it, returning its `id`.
+//!   The solver also takes a `lower_bound` (instead of the size of a greedy clique) and a `seed`.
+//! - `GET /jobs/<id>` returns the status of the job (`running`, `done` or `failed`, if the solver
+//!   panicked), along with its best number of colors so far.
+//! - `GET /jobs/<id>/coloring` returns the coloring of a finished job.
+//! - `GET /metrics` returns the [`progress`](crate::progress) of the algorithms and the number of
+//!   jobs, in the Prometheus text format.
+//!
+//! Each job records its own [`progress`](crate::progress), which the metrics add up.
+
+use crate::algorithms::solve_with_defaults;
+use crate::args::Algorithm;
+use crate::graph::{adj_list::AdjList, clique::greedy_clique};
+use crate::input;
+use crate::progress::{self, Progress, Snapshot, PROGRESS};
+use crate::rng;
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+/// Largest request body accepted, in bytes.
+const MAX_BODY_SIZE: usize = 64 << 20;
+
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub query: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Response {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+struct Job {
+    algorithm: Algorithm,
+    num_vertices: usize,
+    started: Instant,
+    progress: Arc<Progress>,
+    status: Status,
+}
+
+enum Status {
+    Running,
+    /// The number of colors, the coloring and the duration (in milliseconds).
+    Done(usize, Vec<usize>, u128),
+    /// The message of the panic that stopped the solver.
+    Failed(String),
+}
+
+impl Status {
+    fn name(&self) -> &'static str {
+        match self {
+            Status::Running => "running",
+            Status::Done(..) => "done",
+            Status::Failed(_) => "failed",
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Jobs(Arc<Mutex<Vec<Job>>>);
+
+#[derive(Deserialize)]
+struct JsonGraph {
+    num_vertices: usize,
+    edges: Vec<(usize, usize)>,
+}
+
+/// Serves the API on `host`:`port` until the process is stopped, handling each connection on its
+/// own thread.
+///
+/// A connection that fails to be accepted is logged and dropped, so later ones are still served.
+pub fn serve(host: &str, port: u16) -> io::Result<()> {
+    let listener = TcpListener::bind((host, port))?;
+    let jobs = Jobs::default();
+    PROGRESS.reset();
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("Failed to accept a connection: {err}");
+                continue;
+            }
+        };
+        let jobs = jobs.clone();
+
+        thread::spawn(move || {
+            if let Err(err) = handle_connection(stream, &jobs) {
+                eprintln!("Failed to handle a connection: {err}");
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Serves only `GET /metrics` on `listener` until the process is stopped, with the progress of
+/// the run of this process.
+///
+/// A failed connection is logged and dropped, so later scrapes are still served.
+pub fn serve_metrics(listener: TcpListener) {
+    for stream in listener.incoming() {
+        if let Err(err) = stream.and_then(handle_metrics_connection) {
+            eprintln!("Failed to handle a metrics connection: {err}");
+        }
+    }
+}
+
+fn handle_metrics_connection(stream: TcpStream) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+
+    match read_request(BufReader::new(stream)) {
+        Ok(request) if request.method == "GET" && request.path == "/metrics" => {
+            write_metrics(&mut writer, &metrics(&PROGRESS.snapshot(), None))
+        }
+        Ok(request) => write_response(
+            &mut writer,
+            &Response::error(404, format!("there's no endpoint at {}", request.path)),
+        ),
+        Err(err) => write_response(&mut writer, &Response::error(400, err)),
+    }
+}
+
+fn handle_connection(stream: TcpStream, jobs: &Jobs) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    let response = match read_request(BufReader::new(stream)) {
+        // The metrics are plain text, unlike the responses of the other endpoints
+        Ok(request) if request.method == "GET" && request.path == "/metrics" => {
+            return write_metrics(&mut writer, &metrics(&PROGRESS.snapshot(), Some(jobs)));
+        }
+        Ok(request) => handle(&request, jobs),
+        Err(err) => Response::error(400, err),
+    };
+
+    write_response(&mut writer, &response)
+}
+
+/// Reads an HTTP request, with a body of `Content-Length` bytes.
+pub fn read_request(mut reader: impl BufRead) -> Result<Request, String> {
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|err| err.to_string())?;
+
+    let [method, target, _version] = line.split_whitespace().collect::<Vec<_>>()[..] else {
+        return Err(format!("malformed request line: {}", line.trim()));
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect();
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0;
+
+    loop {
+        let mut header = String::new();
+        reader
+            .read_line(&mut header)
+            .map_err(|err| err.to_string())?;
+
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("malformed Content-Length: {}", value.trim()))?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        return Err(format!("the body is larger than {MAX_BODY_SIZE} bytes"));
+    }
+
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|err| err.to_string())?;
+
+    Ok(Request {
+        method,
+        path,
+        query,
+        body,
+    })
+}
+
+pub fn write_response(writer: &mut impl Write, response: &Response) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        _ => "",
+    };
+
+    write_http(
+        writer,
+        response.status,
+        reason,
+        "application/json",
+        &response.body.to_string(),
+    )
+}
+
+fn write_metrics(writer: &mut impl Write, metrics: &str) -> io::Result<()> {
+    write_http(writer, 200, "OK", "text/plain; version=0.0.4", metrics)
+}
+
+fn write_http(
+    writer: &mut impl Write,
+    status: u16,
+    reason: &str,
+    content_type: &str,
+    body: &str,
+) -> io::Result<()> {
+    write!(
+        writer,
+        "HTTP/1.1 {status} {reason}\r\n\
+         Content-Type: {content_type}\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    writer.flush()
+}
+
+/// Renders `snapshot` (and the number of `jobs`, if serving them) in the Prometheus text format.
+///
+/// The best number of colors and the conflicts are those of the runs since the progress was last
+/// reset, so they're the incumbent of a single run, but the best over all the jobs of a server.
+pub fn metrics(snapshot: &Snapshot, jobs: Option<&Jobs>) -> String {
+    let mut output = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, samples: Vec<(String, String)>| {
+        let _ = writeln!(output, "# HELP {name} {help}");
+        let _ = writeln!(output, "# TYPE {name} {kind}");
+        for (labels, value) in samples {
+            let _ = writeln!(output, "{name}{labels} {value}");
+        }
+    };
+    let unlabeled = |value: String| vec![(String::new(), value)];
+
+    metric(
+        "gcp_iterations_total",
+        "counter",
+        "Iterations of the algorithms (GRASP iterations, GA generations and so on).",
+        unlabeled(snapshot.total_iterations().to_string()),
+    );
+    metric(
+        "gcp_thread_iterations_total",
+        "counter",
+        "Iterations of each thread that ran any, where thread 0 is the main one.",
+        snapshot
+            .iterations
+            .iter()
+            .enumerate()
+            .filter(|(_, iterations)| **iterations > 0)
+            .map(|(thread, iterations)| {
+                (format!("{{thread=\"{thread}\"}}"), iterations.to_string())
+            })
+            .collect(),
+    );
+    metric(
+        "gcp_active_threads",
+        "gauge",
+        "Threads that ran any iterations.",
+        unlabeled(
+            snapshot
+                .iterations
+                .iter()
+                .filter(|iterations| **iterations > 0)
+                .count()
+                .to_string(),
+        ),
+    );
+    metric(
+        "gcp_elapsed_seconds",
+        "gauge",
+        "Time since the run started.",
+        unlabeled(snapshot.elapsed.as_secs_f64().to_string()),
+    );
+    if let Some(best_colors) = snapshot.best_colors {
+        metric(
+            "gcp_best_colors",
+            "gauge",
+            "Colors of the best valid coloring found so far.",
+            unlabeled(best_colors.to_string()),
+        );
+    }
+    if let Some(conflicts) = snapshot.conflicts {
+        metric(
+            "gcp_conflicts",
+            "gauge",
+            "Conflicting edges of the current coloring of the algorithms that allow conflicts.",
+            unlabeled(conflicts.to_string()),
+        );
+    }
+
+    if let Some(jobs) = jobs {
+        let jobs = jobs.0.lock().unwrap();
+        let count = |status: &str| {
+            let count = jobs
+                .iter()
+                .filter(|job| job.status.name() == status)
+                .count();
+
+            (format!("{{status=\"{status}\"}}"), count.to_string())
+        };
+
+        metric(
+            "gcp_jobs",
+            "gauge",
+            "Jobs submitted, by status.",
+            vec![count("running"), count("done"), count("failed")],
+        );
+    }
+
+    output
+}
+
+/// Routes `request` to its endpoint.
+pub fn handle(request: &Request, jobs: &Jobs) -> Response {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+
+    match (request.method.as_str(), &segments[..]) {
+        ("POST", ["jobs"]) => submit(request, jobs),
+        ("GET", ["jobs", id]) => job_response(jobs, id, false),
+        ("GET", ["jobs", id, "coloring"]) => job_response(jobs, id, true),
+        (_, ["jobs"]) | (_, ["jobs", _]) | (_, ["jobs", _, "coloring"]) => {
+            Response::error(405, format!("{} isn't allowed here", request.method))
+        }
+        _ => Response::error(404, format!("there's no endpoint at {}", request.path)),
+    }
+}
+
+fn submit(request: &Request, jobs: &Jobs) -> Response {
+    let algorithm = match request.query.get("algorithm") {
+        Some(name) => match Algorithm::from_str(name, true) {
+            Ok(algorithm) => algorithm,
+            Err(_) => return Response::error(400, format!("unknown algorithm {name}")),
+        },
+        None => Algorithm::default(),
+    };
+    let (lower_bound, seed) = match (number(request, "lower_bound"), number(request, "seed")) {
+        (Ok(lower_bound), Ok(seed)) => (lower_bound.map(|bound| bound as usize), seed),
+        (Err(err), _) | (_, Err(err)) => return Response::error(400, err),
+    };
+    let graph = match parse_instance(&request.body) {
+        Ok(graph) => graph,
+        Err(err) => return Response::error(400, err),
+    };
+
+    let progress = Arc::new(Progress::for_job(Some(&PROGRESS)));
+    let id = {
+        let mut jobs = jobs.0.lock().unwrap();
+
+        jobs.push(Job {
+            algorithm: algorithm.clone(),
+            num_vertices: graph.num_vertices(),
+            started: Instant::now(),
+            progress: Arc::clone(&progress),
+            status: Status::Running,
+        });
+        jobs.len() - 1
+    };
+
+    let jobs = jobs.clone();
+    thread::spawn(move || {
+        let start = Instant::now();
+        let lower_bound = lower_bound.unwrap_or_else(|| greedy_clique(&graph).len());
+        let solve = || solve_with_defaults(&graph, &algorithm, Some(lower_bound));
+        // A panicking solver would otherwise leave the job running forever
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            progress::run_job(&progress, || match seed {
+                Some(seed) => rng::with_seed(seed, solve),
+                None => solve(),
+            })
+        }));
+        let duration = start.elapsed().as_millis();
+
+        jobs.0.lock().unwrap()[id].status = match result {
+            Ok((num_colors, coloring)) => Status::Done(num_colors, coloring, duration),
+            Err(payload) => Status::Failed(panic_message(payload.as_ref())),
+        };
+    });
+
+    Response {
+        status: 202,
+        body: json!({ "id": id }),
+    }
+}
+
+/// Parses the query parameter `name` as a number, if it's given.
+fn number(request: &Request, name: &str) -> Result<Option<u64>, String> {
+    request
+        .query
+        .get(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| format!("malformed {name}: {value}"))
+        })
+        .transpose()
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "the solver panicked".to_string(),
+    }
+}
+
+fn job_response(jobs: &Jobs, id: &str, with_coloring: bool) -> Response {
+    let jobs = jobs.0.lock().unwrap();
+    let Some(job) = id.parse::<usize>().ok().and_then(|id| jobs.get(id)) else {
+        return Response::error(404, format!("there's no job {id}"));
+    };
+
+    let body = match (&job.status, with_coloring) {
+        (Status::Running, false) => json!({
+            "status": "running",
+            "algorithm": job.algorithm.to_possible_value().unwrap().get_name(),
+            "num_vertices": job.num_vertices,
+            "num_colors": job.progress.snapshot().best_colors,
+            "elapsed": job.started.elapsed().as_millis(),
+        }),
+        (Status::Running, true) => {
+            return Response::error(409, format!("job {id} is still running"))
+        }
+        (Status::Done(num_colors, _, duration), false) => json!({
+            "status": "done",
+            "algorithm": job.algorithm.to_possible_value().unwrap().get_name(),
+            "num_vertices": job.num_vertices,
+            "num_colors": num_colors,
+            "duration": duration,
+        }),
+        (Status::Done(num_colors, coloring, _), true) => json!({
+            "num_colors": num_colors,
+            "coloring": coloring,
+        }),
+        (Status::Failed(message), false) => json!({
+            "status": "failed",
+            "algorithm": job.algorithm.to_possible_value().unwrap().get_name(),
+            "num_vertices": job.num_vertices,
+            "error": message,
+        }),
+        (Status::Failed(_), true) => return Response::error(409, format!("job {id} failed")),
+    };
+
+    Response { status: 200, body }
+}
+
+/// Reads an instance in the DIMACS format, or as a JSON object with `num_vertices` and 1-indexed
+/// `edges` (as written by the JSON output format).
+pub fn parse_instance(body: &[u8]) -> Result<AdjList, String> {
+    let is_json = body.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{');
+
+    if !is_json {
+        return match input::read_graph(body) {
+            Ok(Some(graph)) => Ok(graph),
+            Ok(None) => Err("the instance has no problem line".to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+    }
+
+    let json: JsonGraph = serde_json::from_slice(body).map_err(|err| err.to_string())?;
+    input::check_num_vertices(json.num_vertices as u64).map_err(|err| err.to_string())?;
+    let mut graph = AdjList::new(json.num_vertices);
+
+    for (u, v) in json.edges {
+        if u == 0 || v == 0 || u > json.num_vertices || v > json.num_vertices || u == v {
+            return Err(format!("invalid edge ({u}, {v})"));
+        }
+
+        graph.add_edge(u - 1, v - 1);
+    }
+
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::is_coloring_valid;
+    use std::time::Duration;
+
+    fn request(method: &str, target: &str, body: &str) -> Request {
+        let raw = format!(
+            "{method} {target} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+
+        read_request(raw.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_read_request() {
+        let request = request("POST", "/jobs?algorithm=genetic", "p edge 2 1\ne 1 2\n");
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/jobs");
+        assert_eq!(request.query["algorithm"], "genetic");
+        assert_eq!(request.body, b"p edge 2 1\ne 1 2\n");
+
+        assert!(read_request("GET\r\n\r\n".as_bytes()).is_err());
+
+        let mut buffer = Vec::new();
+        write_response(&mut buffer, &Response::error(404, "gone")).unwrap();
+        let response = String::from_utf8(buffer).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(response.ends_with("\r\n\r\n{\"error\":\"gone\"}"));
+    }
+
+    #[test]
+    fn test_jobs() {
+        let jobs = Jobs::default();
+        let triangle = r#"{"num_vertices": 3, "edges": [[1, 2], [2, 3], [1, 3]]}"#;
+
+        let response = handle(&request("POST", "/jobs", triangle), &jobs);
+        assert_eq!(
+            response,
+            Response {
+                status: 202,
+                body: json!({ "id": 0 })
+            }
+        );
+
+        // The triangle is colored at once, as the clique bound is met
+        let mut response = handle(&request("GET", "/jobs/0", ""), &jobs);
+        while response.body["status"] == "running" {
+            thread::sleep(Duration::from_millis(10));
+            response = handle(&request("GET", "/jobs/0", ""), &jobs);
+        }
+
+        assert_eq!(response.body["num_colors"], 3);
+
+        let response = handle(&request("GET", "/jobs/0/coloring", ""), &jobs);
+        let coloring: Vec<usize> =
+            serde_json::from_value(response.body["coloring"].clone()).unwrap();
+
+        assert!(is_coloring_valid(&AdjList::complete(3), &coloring));
+
+        let error = |method, target, body| handle(&request(method, target, body), &jobs).status;
+
+        assert_eq!(error("POST", "/jobs?algorithm=magic", triangle), 400);
+        assert_eq!(error("POST", "/jobs?seed=-1", triangle), 400);
+        assert_eq!(error("POST", "/jobs?lower_bound=three", triangle), 400);
+        assert_eq!(error("POST", "/jobs", "e 1 2\n"), 400);
+        assert_eq!(
+            error("POST", "/jobs", r#"{"num_vertices": 2, "edges": [[1, 3]]}"#),
+            400
+        );
+        assert_eq!(error("GET", "/jobs/7", ""), 404);
+        assert_eq!(error("DELETE", "/jobs/0", ""), 405);
+        assert_eq!(error("GET", "/", ""), 404);
+    }
+
+    #[test]
+    fn test_job_params() {
+        let jobs = Jobs::default();
+        let instance = "p edge 4 4\ne 1 2\ne 2 3\ne 3 4\ne 4 1\n";
+        let target = "/jobs?algorithm=tabu&lower_bound=2&seed=7";
+
+        assert_eq!(
+            handle(&request("POST", target, instance), &jobs).status,
+            202
+        );
+
+        let mut response = handle(&request("GET", "/jobs/0", ""), &jobs);
+        while response.body["status"] == "running" {
+            thread::sleep(Duration::from_millis(10));
+            response = handle(&request("GET", "/jobs/0", ""), &jobs);
+        }
+
+        assert_eq!(response.body["algorithm"], "tabu");
+        assert_eq!(response.body["num_colors"], 2);
+    }
+
+    #[test]
+    fn test_failed_job() {
+        let jobs = Jobs::default();
+        jobs.0.lock().unwrap().push(Job {
+            algorithm: Algorithm::Grasp,
+            num_vertices: 3,
+            started: Instant::now(),
+            progress: Arc::new(Progress::for_job(None)),
+            status: Status::Failed(panic_message(&"out of colors")),
+        });
+
+        let response = handle(&request("GET", "/jobs/0", ""), &jobs);
+        assert_eq!(response.body["status"], "failed");
+        assert_eq!(response.body["error"], "out of colors");
+
+        assert_eq!(
+            handle(&request("GET", "/jobs/0/coloring", ""), &jobs).status,
+            409
+        );
+        assert!(
+            metrics(&PROGRESS.snapshot(), Some(&jobs)).contains("gcp_jobs{status=\"failed\"} 1\n")
+        );
+    }
+
+    #[test]
+    fn test_metrics() {
+        let snapshot = Snapshot {
+            elapsed: Duration::from_millis(1500),
+            best_colors: Some(4),
+            conflicts: None,
+            iterations: vec![0, 10, 5, 0],
+            history: Vec::new(),
+            conflict_history: Vec::new(),
+        };
+        let jobs = Jobs::default();
+        jobs.0.lock().unwrap().push(Job {
+            algorithm: Algorithm::Grasp,
+            num_vertices: 3,
+            started: Instant::now(),
+            progress: Arc::new(Progress::for_job(None)),
+            status: Status::Running,
+        });
+
+        let output = metrics(&snapshot, Some(&jobs));
+
+        assert!(output.contains("# TYPE gcp_iterations_total counter\ngcp_iterations_total 15\n"));
+        assert!(output.contains("gcp_thread_iterations_total{thread=\"1\"} 10\n"));
+        assert!(!output.contains("{thread=\"0\"}"));
+        assert!(output.contains("gcp_active_threads 2\n"));
+        assert!(output.contains("gcp_elapsed_seconds 1.5\n"));
+        assert!(output.contains("gcp_best_colors 4\n"));
+        assert!(!output.contains("gcp_conflicts"));
+        assert!(output.contains("gcp_jobs{status=\"running\"} 1\n"));
+
+        assert!(!metrics(&snapshot, None).contains("gcp_jobs"));
+
+        let mut buffer = Vec::new();
+        write_metrics(&mut buffer, &output).unwrap();
+
+        assert!(String::from_utf8(buffer)
+            .unwrap()
+            .contains("Content-Type: text/plain; version=0.0.4\r\n"));
+    }
+
+    #[test]
+    fn test_serve_metrics_after_dropped_connection() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || serve_metrics(listener));
+
+        // Closed without sending a request, as a health probe would
+        drop(TcpStream::connect(address).unwrap());
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        io::Read::read_to_string(&mut stream, &mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    }
+}
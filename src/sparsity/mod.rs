@@ -0,0 +1,240 @@
+use crate::graph::adj_list::AdjList;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::io::BufRead;
+
+/// Which derivative matrix is compressed, which determines the graph that is colored.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum Compression {
+    /// Columns sharing a nonzero row get different colors: a distance-1 coloring of the column
+    /// intersection graph (or, equivalently, a partial distance-2 coloring of the bipartite graph
+    /// of the matrix)
+    #[default]
+    Jacobian,
+    /// Columns that are adjacent, or share a neighbor, in the graph of a symmetric matrix get
+    /// different colors: a distance-2 coloring of that graph
+    Hessian,
+}
+
+/// The positions of the nonzeros of a sparse matrix.
+#[derive(Debug, PartialEq)]
+pub struct Pattern {
+    pub num_rows: usize,
+    pub num_cols: usize,
+    /// Columns of the nonzeros of each row, 0-indexed and in increasing order.
+    pub rows: Vec<Vec<usize>>,
+}
+
+/// Reads the pattern of a matrix in the Matrix Market coordinate format. Values, if any, are
+/// ignored, and the entries of a `symmetric` (or `skew-symmetric`, or `hermitian`) matrix are
+/// mirrored.
+pub fn read_pattern(reader: impl BufRead) -> Result<Pattern, Box<dyn Error>> {
+    let mut lines = reader.lines();
+
+    let header = lines.next().ok_or("the file is empty")??;
+    let header = header.to_lowercase();
+    let header: Vec<&str> = header.split_whitespace().collect();
+
+    let ["%%matrixmarket", "matrix", "coordinate", _, symmetry] = header[..] else {
+        return Err("the header isn't a Matrix Market coordinate matrix header".into());
+    };
+    let is_symmetric = symmetry != "general";
+
+    let mut size: Option<(usize, usize)> = None;
+    let mut rows: Vec<BTreeSet<usize>> = Vec::new();
+
+    for line in lines {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('%') {
+            continue;
+        }
+
+        let numbers: Vec<&str> = line.split_whitespace().collect();
+
+        let Some((num_rows, num_cols)) = size else {
+            let [num_rows, num_cols, _] = numbers[..] else {
+                return Err(format!("malformed size line: {line}").into());
+            };
+            let num_rows = num_rows.parse()?;
+
+            size = Some((num_rows, num_cols.parse()?));
+            rows = vec![BTreeSet::new(); num_rows];
+            continue;
+        };
+
+        let [i, j, ..] = numbers[..] else {
+            return Err(format!("malformed entry: {line}").into());
+        };
+        let (i, j): (usize, usize) = (i.parse()?, j.parse()?);
+
+        if !(1..=num_rows).contains(&i) || !(1..=num_cols).contains(&j) {
+            return Err(
+                format!("entry ({i}, {j}) is outside the {num_rows}x{num_cols} matrix").into(),
+            );
+        }
+
+        rows[i - 1].insert(j - 1);
+        if is_symmetric && j <= num_rows && i <= num_cols {
+            rows[j - 1].insert(i - 1);
+        }
+    }
+
+    let Some((num_rows, num_cols)) = size else {
+        return Err("the file has no size line".into());
+    };
+
+    Ok(Pattern {
+        num_rows,
+        num_cols,
+        rows: rows
+            .into_iter()
+            .map(|row| row.into_iter().collect())
+            .collect(),
+    })
+}
+
+/// Builds the column intersection graph: two columns are adjacent if a row has nonzeros in both.
+pub fn column_intersection_graph(pattern: &Pattern) -> AdjList {
+    let mut edges = BTreeSet::new();
+
+    for row in pattern.rows.iter() {
+        for (i, u) in row.iter().enumerate() {
+            for v in row[i + 1..].iter() {
+                edges.insert((*u, *v));
+            }
+        }
+    }
+
+    let mut graph = AdjList::new(pattern.num_cols);
+    for (u, v) in edges {
+        graph.add_edge(u, v);
+    }
+
+    graph
+}
+
+/// Builds the graph whose colorings are distance-2 colorings of the graph of the (square) matrix,
+/// where the off-diagonal nonzeros are the edges: two columns are adjacent if they're at most two
+/// edges apart.
+pub fn distance_2_graph(pattern: &Pattern) -> Result<AdjList, String> {
+    if pattern.num_rows != pattern.num_cols {
+        return Err(format!(
+            "the matrix must be square, but it's {}x{}",
+            pattern.num_rows, pattern.num_cols
+        ));
+    }
+
+    // Each row, with its diagonal, is the closed neighborhood of its vertex: two columns in the
+    // same row are within two edges of each other (through the vertex of the row)
+    let mut edges = BTreeSet::new();
+
+    for (v, row) in pattern.rows.iter().enumerate() {
+        let neighborhood: BTreeSet<usize> = row.iter().copied().chain([v]).collect();
+        let neighborhood: Vec<usize> = neighborhood.into_iter().collect();
+
+        for (i, u) in neighborhood.iter().enumerate() {
+            for w in neighborhood[i + 1..].iter() {
+                edges.insert((*u, *w));
+            }
+        }
+    }
+
+    let mut graph = AdjList::new(pattern.num_cols);
+    for (u, v) in edges {
+        graph.add_edge(u, v);
+    }
+
+    Ok(graph)
+}
+
+/// The graph whose colorings give a valid compression of the matrix.
+pub fn compression_graph(pattern: &Pattern, compression: Compression) -> Result<AdjList, String> {
+    match compression {
+        Compression::Jacobian => Ok(column_intersection_graph(pattern)),
+        Compression::Hessian => distance_2_graph(pattern),
+    }
+}
+
+/// Most nonzeros in a row: a lower bound on the number of colors of any compression, since they
+/// must all have different colors.
+pub fn max_row_nonzeros(pattern: &Pattern) -> usize {
+    pattern.rows.iter().map(Vec::len).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // An arrowhead matrix: the first row and column are dense, as is the diagonal
+    const ARROWHEAD: &str = "%%MatrixMarket matrix coordinate real symmetric
+% Entries of the lower triangle
+4 4 7
+1 1 4.0
+2 1 1.0
+3 1 1.0
+4 1 1.0
+2 2 4.0
+3 3 4.0
+4 4 4.0
+";
+
+    #[test]
+    fn test_read_pattern() {
+        let pattern = read_pattern(ARROWHEAD.as_bytes()).unwrap();
+
+        assert_eq!(
+            pattern.rows,
+            vec![vec![0, 1, 2, 3], vec![0, 1], vec![0, 2], vec![0, 3]]
+        );
+        assert_eq!(max_row_nonzeros(&pattern), 4);
+
+        let general = "%%MatrixMarket matrix coordinate pattern general\n2 3 2\n1 3\n2 1\n";
+
+        assert_eq!(
+            read_pattern(general.as_bytes()).unwrap(),
+            Pattern {
+                num_rows: 2,
+                num_cols: 3,
+                rows: vec![vec![2], vec![0]],
+            }
+        );
+
+        assert!(read_pattern("%%MatrixMarket matrix array real general\n".as_bytes()).is_err());
+        assert!(read_pattern(
+            "%%MatrixMarket matrix coordinate pattern general\n2 2 1\n3 1\n".as_bytes()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_compression_graphs() {
+        let pattern = read_pattern(ARROWHEAD.as_bytes()).unwrap();
+
+        // The dense first row makes every pair of columns intersect
+        assert_eq!(column_intersection_graph(&pattern).edges().len(), 6);
+
+        // A tridiagonal-like pattern: only consecutive columns share a row
+        let pattern = Pattern {
+            num_rows: 3,
+            num_cols: 4,
+            rows: vec![vec![0, 1], vec![1, 2], vec![2, 3]],
+        };
+
+        assert_eq!(
+            column_intersection_graph(&pattern).edges(),
+            vec![(0, 1), (1, 2), (2, 3)]
+        );
+        assert!(distance_2_graph(&pattern).is_err());
+
+        // The path 1 - 2 - 3 (with the diagonal): 1 and 3 are two edges apart
+        let pattern = Pattern {
+            num_rows: 3,
+            num_cols: 3,
+            rows: vec![vec![0, 1], vec![0, 1, 2], vec![1, 2]],
+        };
+
+        assert_eq!(distance_2_graph(&pattern).unwrap().edges().len(), 3);
+    }
+}
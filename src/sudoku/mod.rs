@@ -0,0 +1,150 @@
+use crate::algorithms::exact::extend_coloring;
+use crate::graph::adj_list::AdjList;
+use crate::regalloc::allocate;
+
+/// Cells of a Sudoku grid, row by row.
+pub const NUM_CELLS: usize = 81;
+
+/// Which solver completed the grid.
+#[derive(Debug, PartialEq)]
+pub enum Solver {
+    Heuristic,
+    Exact,
+}
+
+/// Reads a puzzle as 81 cells, row by row: digits from 1 to 9 are givens, and `0` or `.` are
+/// blanks. Any other character (such as whitespace) is ignored.
+pub fn parse_puzzle(puzzle: &str) -> Result<Vec<Option<usize>>, String> {
+    let cells: Vec<Option<usize>> = puzzle
+        .chars()
+        .filter_map(|c| match c {
+            '0' | '.' => Some(None),
+            '1'..='9' => Some(c.to_digit(10).map(|digit| digit as usize)),
+            _ => None,
+        })
+        .collect();
+
+    if cells.len() != NUM_CELLS {
+        return Err(format!(
+            "the puzzle has {} cells, instead of {NUM_CELLS}",
+            cells.len()
+        ));
+    }
+
+    Ok(cells)
+}
+
+/// The Sudoku graph: each cell is adjacent to the other cells of its row, column and box.
+pub fn sudoku_graph() -> AdjList {
+    let mut graph = AdjList::new(NUM_CELLS);
+
+    for u in 0..NUM_CELLS {
+        for v in u + 1..NUM_CELLS {
+            let (row_u, col_u) = (u / 9, u % 9);
+            let (row_v, col_v) = (v / 9, v % 9);
+            let same_box = (row_u / 3, col_u / 3) == (row_v / 3, col_v / 3);
+
+            if row_u == row_v || col_u == col_v || same_box {
+                graph.add_edge(u, v);
+            }
+        }
+    }
+
+    graph
+}
+
+/// Solves the puzzle as a 9-coloring of the Sudoku graph, precolored with the givens.
+///
+/// The register allocation heuristic is tried first, since it solves easy puzzles at once;
+/// the exact search completes the grid otherwise. Returns `None` if the puzzle has no solution.
+pub fn solve(cells: &[Option<usize>]) -> Option<(Vec<usize>, Solver)> {
+    let graph = sudoku_graph();
+    // The allocation uses 0-indexed registers
+    let registers: Vec<Option<usize>> = cells.iter().map(|cell| cell.map(|d| d - 1)).collect();
+
+    match allocate(&graph, &registers, 9) {
+        Ok(allocation) if allocation.spilled.is_empty() => {
+            let grid = allocation.registers.into_iter().flatten().collect();
+            Some((grid, Solver::Heuristic))
+        }
+        Ok(_) => extend_coloring(&graph, cells, 9).map(|grid| (grid, Solver::Exact)),
+        // Two givens clash
+        Err(_) => None,
+    }
+}
+
+/// Formats the grid as 9 lines of digits, with the boxes separated.
+pub fn format_grid(grid: &[usize]) -> String {
+    let mut output = String::new();
+
+    for (row, cells) in grid.chunks(9).enumerate() {
+        if row > 0 && row % 3 == 0 {
+            output.push_str("------+-------+------\n");
+        }
+
+        let boxes: Vec<String> = cells
+            .chunks(3)
+            .map(|cells| {
+                let digits: Vec<String> = cells.iter().map(|d| d.to_string()).collect();
+                digits.join(" ")
+            })
+            .collect();
+
+        output.push_str(&boxes.join(" | "));
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::is_coloring_valid;
+
+    // "Platinum Blonde", one of the hardest known puzzles for backtracking solvers
+    const HARD: &str = "
+        .......12 ........3 ..23..4..
+        ..1..9..5 .4....... 7........
+        ..6..5..8 .9....6.. ..4..2...";
+
+    #[test]
+    fn test_sudoku_graph() {
+        let graph = sudoku_graph();
+
+        // 8 in the row, 8 in the column and 4 more in the box
+        assert!(graph
+            .adj_list()
+            .iter()
+            .all(|neighbors| neighbors.len() == 20));
+    }
+
+    #[test]
+    fn test_solve() {
+        let cells = parse_puzzle(HARD).unwrap();
+        let (grid, _) = solve(&cells).unwrap();
+
+        assert!(is_coloring_valid(&sudoku_graph(), &grid));
+        assert!(cells
+            .iter()
+            .zip(grid.iter())
+            .all(|(cell, digit)| cell.is_none_or(|given| given == *digit)));
+
+        // Two 1s in the first row
+        let mut cells = cells;
+        cells[0] = Some(1);
+        cells[1] = Some(1);
+
+        assert!(solve(&cells).is_none());
+        assert!(parse_puzzle("123").is_err());
+    }
+
+    #[test]
+    fn test_format_grid() {
+        let grid: Vec<usize> = (0..NUM_CELLS).map(|cell| cell % 9 + 1).collect();
+        let output = format_grid(&grid);
+
+        assert_eq!(output.lines().count(), 11);
+        assert_eq!(output.lines().next(), Some("1 2 3 | 4 5 6 | 7 8 9"));
+    }
+}
@@ -0,0 +1,173 @@
+//! Graphs built in code for the tests, so they don't depend on the instances of the `data`
+//! directory, and strategies of arbitrary graphs and colorings for the property-based tests.
+
+use crate::algorithms::{count_colors, is_coloring_valid, Solution};
+use crate::generate::families::Family;
+use crate::graph::adj_list::AdjList;
+use crate::rng;
+
+/// Seed of the generator while a fixture is solved, so the checks of the stochastic algorithms
+/// don't depend on luck.
+const SEED: u64 = 0;
+
+/// A graph whose chromatic number is known.
+pub struct Fixture {
+    pub name: &'static str,
+    pub graph: AdjList,
+    pub chromatic_number: usize,
+}
+
+/// The `myciel3` instance of the DIMACS benchmarks (11 vertices, chromatic number 4), with the
+/// same vertex numbering.
+pub fn myciel3() -> AdjList {
+    mycielski(4)
+}
+
+/// The `myciel4` instance of the DIMACS benchmarks (23 vertices, chromatic number 5).
+pub fn myciel4() -> AdjList {
+    mycielski(5)
+}
+
+/// The `myciel5` instance of the DIMACS benchmarks (47 vertices, chromatic number 6).
+pub fn myciel5() -> AdjList {
+    mycielski(6)
+}
+
+/// The cycle with `n` vertices, in order.
+pub fn cycle(n: usize) -> AdjList {
+    let mut graph = AdjList::new(n);
+
+    for v in 0..n {
+        graph.add_edge(v, (v + 1) % n);
+    }
+
+    graph
+}
+
+/// The Petersen graph: an outer 5-cycle (`0..5`) joined to an inner pentagram (`5..10`).
+pub fn petersen() -> AdjList {
+    let mut graph = AdjList::new(10);
+
+    for v in 0..5 {
+        graph.add_edge(v, (v + 1) % 5);
+        graph.add_edge(v, v + 5);
+        graph.add_edge(v + 5, (v + 2) % 5 + 5);
+    }
+
+    graph
+}
+
+/// Small graphs with known chromatic numbers.
+pub fn fixtures() -> Vec<Fixture> {
+    vec![
+        Fixture {
+            name: "K5",
+            graph: AdjList::complete(5),
+            chromatic_number: 5,
+        },
+        Fixture {
+            name: "C6",
+            graph: cycle(6),
+            chromatic_number: 2,
+        },
+        Fixture {
+            name: "C7",
+            graph: cycle(7),
+            chromatic_number: 3,
+        },
+        Fixture {
+            name: "petersen",
+            graph: petersen(),
+            chromatic_number: 3,
+        },
+        Fixture {
+            name: "queen5_5",
+            graph: Family::Queen { n: 5 }.generate().unwrap(),
+            chromatic_number: 5,
+        },
+        Fixture {
+            name: "myciel3",
+            graph: myciel3(),
+            chromatic_number: 4,
+        },
+        Fixture {
+            name: "myciel4",
+            graph: myciel4(),
+            chromatic_number: 5,
+        },
+    ]
+}
+
+/// Colors each fixture with `solve`, checking that its coloring is valid and has as many colors
+/// as reported. Returns each fixture along with its number of colors, for the checks specific to
+/// each algorithm.
+///
+/// The generator is seeded before each fixture is solved, so the results are reproducible (save
+/// for the algorithms running on several threads).
+pub fn solve_fixtures(mut solve: impl FnMut(&Fixture) -> Solution) -> Vec<(Fixture, usize)> {
+    fixtures()
+        .into_iter()
+        .map(|fixture| {
+            let (num_colors, coloring) = rng::with_seed(SEED, || solve(&fixture));
+
+            assert!(
+                is_coloring_valid(&fixture.graph, &coloring),
+                "{}",
+                fixture.name
+            );
+            assert_eq!(count_colors(&coloring), num_colors, "{}", fixture.name);
+
+            (fixture, num_colors)
+        })
+        .collect()
+}
+
+/// Like [`solve_fixtures`], also checking that each fixture is colored with its chromatic number.
+pub fn solve_fixtures_optimally(solve: impl FnMut(&Fixture) -> Solution) {
+    for (fixture, num_colors) in solve_fixtures(solve) {
+        assert_eq!(num_colors, fixture.chromatic_number, "{}", fixture.name);
+    }
+}
+
+/// Number of cases checked by each property-based test.
+pub const NUM_CASES: u32 = 50;
+
+/// Strategy of arbitrary small graphs for property-based tests, with up to 30 vertices and any
+/// density. Failing cases shrink towards fewer vertices and edges.
+pub fn arbitrary_graph() -> impl proptest::strategy::Strategy<Value = AdjList> {
+    use proptest::prelude::*;
+
+    (1..=30usize, 0.0..=1.0f64).prop_flat_map(|(num_vertices, probability)| {
+        let num_pairs = num_vertices * (num_vertices - 1) / 2;
+
+        proptest::collection::vec(proptest::bool::weighted(probability), num_pairs).prop_map(
+            move |edges| {
+                let mut graph = AdjList::new(num_vertices);
+                let pairs =
+                    (0..num_vertices).flat_map(|i| (i + 1..num_vertices).map(move |j| (i, j)));
+
+                for ((i, j), edge) in pairs.zip(edges) {
+                    if edge {
+                        graph.add_edge(i, j);
+                    }
+                }
+
+                graph
+            },
+        )
+    })
+}
+
+/// Strategy of arbitrary (not necessarily proper) colorings of `num_vertices` vertices, with up to
+/// `num_vertices` colors.
+pub fn arbitrary_coloring(
+    num_vertices: usize,
+) -> impl proptest::strategy::Strategy<Value = Vec<usize>> {
+    use proptest::prelude::*;
+
+    (1..=num_vertices.max(1)).prop_flat_map(move |k| proptest::collection::vec(1..=k, num_vertices))
+}
+
+fn mycielski(k: usize) -> AdjList {
+    Family::Mycielski { k }.generate().unwrap()
+}
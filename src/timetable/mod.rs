@@ -0,0 +1,200 @@
+use crate::graph::{adj_list::AdjList, adj_matrix::AdjMatrix};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+/// The exams each student is enrolled in.
+#[derive(Debug)]
+pub struct Enrollments {
+    /// Names of the exams, in order of first appearance. Exams are referred to by their index.
+    pub exams: Vec<String>,
+    /// Exams of each student, without repetitions.
+    pub students: Vec<Vec<usize>>,
+}
+
+/// Statistics of a timetable.
+#[derive(Debug, PartialEq)]
+pub struct Statistics {
+    pub num_exams: usize,
+    pub num_students: usize,
+    pub num_enrollments: usize,
+    /// Pairs of exams that share at least one student.
+    pub num_conflicts: usize,
+    pub num_timeslots: usize,
+    /// Times a student has two exams in the same timeslot.
+    pub clashes: usize,
+    /// Times a student has exams in consecutive timeslots.
+    pub back_to_back: usize,
+}
+
+/// Reads enrollments from a CSV file with one `student,exam` pair per line.
+///
+/// Blank lines, lines starting with `#` and a `student,exam` header are skipped.
+pub fn read_enrollments(reader: impl BufRead) -> Result<Enrollments, Box<dyn Error>> {
+    let mut exams = Vec::new();
+    let mut exam_indices: HashMap<String, usize> = HashMap::new();
+    let mut student_indices: HashMap<String, usize> = HashMap::new();
+    let mut students: Vec<Vec<usize>> = Vec::new();
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') || line.eq_ignore_ascii_case("student,exam") {
+            continue;
+        }
+
+        let Some((student, exam)) = line.split_once(',') else {
+            return Err(format!("line {} isn't a student,exam pair", number + 1).into());
+        };
+        let (student, exam) = (student.trim(), exam.trim());
+
+        if student.is_empty() || exam.is_empty() || exam.contains(',') {
+            return Err(format!("line {} isn't a student,exam pair", number + 1).into());
+        }
+
+        let exam = *exam_indices.entry(exam.to_string()).or_insert_with(|| {
+            exams.push(exam.to_string());
+            exams.len() - 1
+        });
+        let student = *student_indices
+            .entry(student.to_string())
+            .or_insert_with(|| {
+                students.push(Vec::new());
+                students.len() - 1
+            });
+
+        if !students[student].contains(&exam) {
+            students[student].push(exam);
+        }
+    }
+
+    Ok(Enrollments { exams, students })
+}
+
+/// Builds the conflict graph of the exams: two exams are adjacent if a student is enrolled in both.
+pub fn conflict_graph(enrollments: &Enrollments) -> AdjList {
+    // Pairs of exams are shared by many students, so the conflicts are merged in a matrix first
+    let mut conflicts = AdjMatrix::new(enrollments.exams.len());
+
+    for exams in enrollments.students.iter() {
+        for (i, u) in exams.iter().enumerate() {
+            for v in exams[i + 1..].iter() {
+                conflicts.add_edge(*u, *v);
+            }
+        }
+    }
+
+    conflicts.to_adj_list()
+}
+
+/// Computes the statistics of the timetable that assigns each exam to a (1-indexed) timeslot.
+pub fn statistics(enrollments: &Enrollments, timeslots: &[usize]) -> Statistics {
+    let mut clashes = 0;
+    let mut back_to_back = 0;
+
+    for exams in enrollments.students.iter() {
+        let mut slots: Vec<usize> = exams.iter().map(|exam| timeslots[*exam]).collect();
+        slots.sort();
+
+        for pair in slots.windows(2) {
+            match pair[1] - pair[0] {
+                0 => clashes += 1,
+                1 => back_to_back += 1,
+                _ => {}
+            }
+        }
+    }
+
+    Statistics {
+        num_exams: enrollments.exams.len(),
+        num_students: enrollments.students.len(),
+        num_enrollments: enrollments.students.iter().map(Vec::len).sum(),
+        num_conflicts: conflict_graph(enrollments).edges().len(),
+        num_timeslots: timeslots.iter().collect::<HashSet<_>>().len(),
+        clashes,
+        back_to_back,
+    }
+}
+
+/// Writes the timeslot of each exam as `exam,timeslot` lines, with a header.
+pub fn write_timetable(
+    writer: &mut impl Write,
+    enrollments: &Enrollments,
+    timeslots: &[usize],
+) -> io::Result<()> {
+    writeln!(writer, "exam,timeslot")?;
+
+    for (exam, timeslot) in enrollments.exams.iter().zip(timeslots) {
+        writeln!(writer, "{exam},{timeslot}")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "student,exam
+# Alice takes three exams
+alice,math
+alice,physics
+alice,chemistry
+bob,math
+bob,history
+carol,history
+carol,math
+";
+
+    #[test]
+    fn test_read_enrollments() {
+        let enrollments = read_enrollments(CSV.as_bytes()).unwrap();
+
+        assert_eq!(
+            enrollments.exams,
+            vec!["math", "physics", "chemistry", "history"]
+        );
+        assert_eq!(
+            enrollments.students,
+            vec![vec![0, 1, 2], vec![0, 3], vec![3, 0]]
+        );
+
+        let graph = conflict_graph(&enrollments);
+
+        // History only conflicts with math, even though two students take both
+        assert_eq!(graph.edges(), vec![(0, 1), (0, 2), (0, 3), (1, 2)]);
+
+        assert!(read_enrollments("alice\n".as_bytes()).is_err());
+        assert!(read_enrollments("alice,math,physics\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_statistics() {
+        let enrollments = read_enrollments(CSV.as_bytes()).unwrap();
+
+        assert_eq!(
+            statistics(&enrollments, &[1, 2, 3, 2]),
+            Statistics {
+                num_exams: 4,
+                num_students: 3,
+                num_enrollments: 7,
+                num_conflicts: 4,
+                num_timeslots: 3,
+                clashes: 0,
+                back_to_back: 4,
+            }
+        );
+
+        // Physics and chemistry clash for alice
+        assert_eq!(statistics(&enrollments, &[1, 2, 2, 3]).clashes, 1);
+
+        let mut buffer = Vec::new();
+        write_timetable(&mut buffer, &enrollments, &[1, 2, 3, 2]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "exam,timeslot\nmath,1\nphysics,2\nchemistry,3\nhistory,2\n"
+        );
+    }
+}
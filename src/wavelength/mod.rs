@@ -0,0 +1,167 @@
+use crate::graph::adj_list::AdjList;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+
+/// An undirected network link, with its endpoints in order.
+pub type Link = (String, String);
+
+/// A demand routed over a path of the network.
+#[derive(Debug, PartialEq)]
+pub struct Demand {
+    pub name: String,
+    /// Links of the path, without repetitions.
+    pub links: Vec<Link>,
+}
+
+/// Reads the routed demands, given as `<demand> <node>-<node> ...` lines with the links of each
+/// path. Links are undirected, so `a-b` and `b-a` are the same link.
+///
+/// Blank lines and lines starting with `#` are skipped.
+pub fn read_demands(reader: impl BufRead) -> Result<Vec<Demand>, Box<dyn Error>> {
+    let mut demands = Vec::new();
+
+    for (number, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut splits = line.split_whitespace();
+        let name = splits.next().unwrap().to_string();
+        let mut links = Vec::new();
+
+        for link in splits {
+            let Some((u, v)) = link
+                .split_once('-')
+                .filter(|(u, v)| !u.is_empty() && !v.is_empty())
+            else {
+                return Err(
+                    format!("line {}: {link} isn't a <node>-<node> link", number + 1).into(),
+                );
+            };
+
+            let link = (u.min(v).to_string(), u.max(v).to_string());
+            if !links.contains(&link) {
+                links.push(link);
+            }
+        }
+
+        if links.is_empty() {
+            return Err(format!("demand {name} has no links").into());
+        }
+
+        demands.push(Demand { name, links });
+    }
+
+    Ok(demands)
+}
+
+/// Builds the path conflict graph: two demands are adjacent if their paths share a link.
+pub fn conflict_graph(demands: &[Demand]) -> AdjList {
+    let mut graph = AdjList::new(demands.len());
+    let mut edges = BTreeSet::new();
+
+    for demands in link_demands(demands).values() {
+        for (i, u) in demands.iter().enumerate() {
+            for v in demands[i + 1..].iter() {
+                if edges.insert((*u, *v)) {
+                    graph.add_edge(*u, *v);
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+/// The most demands routed over a single link: a lower bound on the number of wavelengths, since
+/// they all need different ones.
+pub fn max_link_load(demands: &[Demand]) -> usize {
+    link_demands(demands)
+        .values()
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Demands routed over each link, in increasing order.
+fn link_demands(demands: &[Demand]) -> BTreeMap<&Link, Vec<usize>> {
+    let mut link_demands: BTreeMap<&Link, Vec<usize>> = BTreeMap::new();
+
+    for (i, demand) in demands.iter().enumerate() {
+        for link in demand.links.iter() {
+            link_demands.entry(link).or_default().push(i);
+        }
+    }
+
+    link_demands
+}
+
+/// Writes the wavelength of each demand as `demand,wavelength` lines, with a header.
+pub fn write_wavelengths(
+    writer: &mut impl Write,
+    demands: &[Demand],
+    wavelengths: &[usize],
+) -> io::Result<()> {
+    writeln!(writer, "demand,wavelength")?;
+
+    for (demand, wavelength) in demands.iter().zip(wavelengths) {
+        writeln!(writer, "{},{wavelength}", demand.name)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A ring of four nodes, a - b - c - d - a
+    const PATHS: &str = "# Demands over the ring
+ab a-b
+ac a-b b-c
+bd c-b d-c
+da a-d
+";
+
+    #[test]
+    fn test_read_demands() {
+        let demands = read_demands(PATHS.as_bytes()).unwrap();
+
+        assert_eq!(demands.len(), 4);
+        assert_eq!(
+            demands[2],
+            Demand {
+                name: "bd".to_string(),
+                links: vec![
+                    ("b".to_string(), "c".to_string()),
+                    ("c".to_string(), "d".to_string())
+                ],
+            }
+        );
+
+        assert!(read_demands("ab\n".as_bytes()).is_err());
+        assert!(read_demands("ab a-\n".as_bytes()).is_err());
+        assert!(read_demands("ab a\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_conflict_graph() {
+        let demands = read_demands(PATHS.as_bytes()).unwrap();
+
+        // ab and ac share a-b, ac and bd share b-c
+        assert_eq!(conflict_graph(&demands).edges(), vec![(0, 1), (1, 2)]);
+        assert_eq!(max_link_load(&demands), 2);
+
+        let mut buffer = Vec::new();
+        write_wavelengths(&mut buffer, &demands[..2], &[1, 2]).unwrap();
+
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "demand,wavelength\nab,1\nac,2\n"
+        );
+    }
+}